@@ -2,10 +2,20 @@
 //! Issue: tauri-explorer-0xr, tauri-explorer-kez
 
 use crate::error::AppError;
+use crate::files::{metadata_to_entry, FileEntry};
+use crate::task_registry::TaskRegistry;
+use chrono::{DateTime, Local};
+use flate2::write::GzEncoder;
+use flate2::Compression;
 use log;
+use serde::{Deserialize, Serialize};
+use std::cell::Cell;
 use std::fs;
 use std::io::{Read, Write};
-use std::path::{Path, PathBuf};
+use std::path::{Component, Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter};
 use zip::write::FileOptions;
 
 /// Compress files/directories into a ZIP archive.
@@ -63,70 +73,835 @@ pub fn compress_to_zip(paths: Vec<String>) -> Result<String, AppError> {
     Ok(zip_path.to_string_lossy().to_string())
 }
 
-/// Extract a ZIP archive to a directory.
+/// Extract a ZIP or tar archive (`.tar`, `.tar.gz`, `.tar.bz2`, `.tar.xz`).
 ///
-/// If `dest_dir` is None, extracts to a folder named after the archive
-/// in the same directory as the archive.
-/// If `extract_here` is true, extracts directly into the archive's parent directory.
+/// Extracts into `dest_dir` when given. Otherwise, if `extract_here` is
+/// true, into the archive's parent directory, else into a new folder named
+/// after the archive next to it. Existing files are overwritten, as they
+/// always were, unless `overwrite` is `false`; then they are skipped and
+/// listed in `skipped`. Symlinks come back as links, and nothing is
+/// written outside the destination. Streams `extract-progress` events and
+/// can be cancelled with `cancel_archive`, which removes the files and
+/// folders extracted so far; files it already overwrote keep their new
+/// contents.
 #[tauri::command]
-pub fn extract_archive(
+pub async fn extract_archive(
+    app: AppHandle,
     archive_path: String,
-    extract_here: bool,
-) -> Result<String, AppError> {
+    extract_here: Option<bool>,
+    dest_dir: Option<String>,
+    overwrite: Option<bool>,
+) -> Result<ExtractResult, AppError> {
     let archive = PathBuf::from(&archive_path);
     if !archive.exists() {
         return Err(AppError::NotFound(archive_path));
     }
 
+    let (archive_id, cancelled) = ARCHIVE_TASKS.start();
+    let result = tokio::task::spawn_blocking(move || {
+        let dest = resolve_extract_destination(&archive, extract_here.unwrap_or(false), dest_dir)?;
+        log::info!("Extracting {:?} into {:?}", archive, dest);
+        extract_archive_sync(
+            &archive,
+            &dest,
+            overwrite.unwrap_or(true),
+            &cancelled,
+            &mut |files_extracted, total_files, bytes_extracted| {
+                let _ = app.emit(
+                    "extract-progress",
+                    ExtractProgress {
+                        archive_id,
+                        files_extracted,
+                        total_files,
+                        bytes_extracted,
+                    },
+                );
+            },
+        )
+    })
+    .await
+    .map_err(|e| AppError::Other(format!("Task join error: {}", e)));
+    ARCHIVE_TASKS.cleanup(archive_id);
+    result?
+}
+
+fn resolve_extract_destination(
+    archive: &Path,
+    extract_here: bool,
+    dest_dir: Option<String>,
+) -> Result<PathBuf, AppError> {
+    if let Some(dest) = dest_dir.filter(|d| !d.is_empty()) {
+        return Ok(PathBuf::from(dest));
+    }
+
     let parent_dir = archive
         .parent()
         .ok_or(AppError::InvalidPath("Cannot determine parent directory".into()))?;
 
-    let dest = if extract_here {
-        parent_dir.to_path_buf()
+    if extract_here {
+        Ok(parent_dir.to_path_buf())
     } else {
-        let folder_name = archive
-            .file_stem()
-            .map(|s| s.to_string_lossy().to_string())
-            .unwrap_or_else(|| "extracted".to_string());
-        let dest = find_unique_path(parent_dir, &folder_name, "");
-        fs::create_dir_all(&dest)?;
+        Ok(find_unique_path(parent_dir, &archive_stem(archive), ""))
+    }
+}
+
+/// Archive file name without its (possibly double) extension:
+/// `photos.tar.gz` → `photos`.
+fn archive_stem(archive: &Path) -> String {
+    let name = archive
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let lower = name.to_lowercase();
+    let stem = [".tar.gz", ".tar.bz2", ".tar.xz", ".tgz", ".tbz2", ".tbz", ".txz", ".tar", ".zip"]
+        .iter()
+        .find(|ext| lower.ends_with(*ext))
+        .map(|ext| &name[..name.len() - ext.len()])
+        .unwrap_or(&name);
+    if stem.is_empty() {
+        "extracted".to_string()
+    } else {
+        stem.to_string()
+    }
+}
+
+// ── Archive creation with progress ──────────────────────────────────────────
+
+static ARCHIVE_TASKS: TaskRegistry = TaskRegistry::new();
+
+/// Minimum interval between progress events.
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Archive formats recognised from the file name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArchiveFormat {
+    Zip,
+    Tar,
+    TarGz,
+    TarBz2,
+    TarXz,
+}
+
+impl ArchiveFormat {
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let name = path.file_name()?.to_string_lossy().to_lowercase();
+        if name.ends_with(".zip") {
+            Some(Self::Zip)
+        } else if name.ends_with(".tar.gz") || name.ends_with(".tgz") {
+            Some(Self::TarGz)
+        } else if name.ends_with(".tar.bz2") || name.ends_with(".tbz2") || name.ends_with(".tbz") {
+            Some(Self::TarBz2)
+        } else if name.ends_with(".tar.xz") || name.ends_with(".txz") {
+            Some(Self::TarXz)
+        } else if name.ends_with(".tar") {
+            Some(Self::Tar)
+        } else {
+            None
+        }
+    }
+}
+
+/// Event payload for `archive-progress`.
+#[derive(Debug, Clone, Serialize)]
+pub struct ArchiveProgress {
+    #[serde(rename = "archiveId")]
+    pub archive_id: u64,
+    #[serde(rename = "filesAdded")]
+    pub files_added: u64,
+    #[serde(rename = "totalFiles")]
+    pub total_files: u64,
+    #[serde(rename = "bytesProcessed")]
+    pub bytes_processed: u64,
+}
+
+/// A file or directory to write into the archive under `name`.
+struct ArchiveSource {
+    path: PathBuf,
+    name: String,
+    is_dir: bool,
+}
+
+/// Create a `.zip` or `.tar.gz` archive at `output_path` from `entries`,
+/// keeping each entry's directory structure below its own name. Symlinks
+/// are stored as links, not followed.
+///
+/// Returns the final archive size in bytes. Streams `archive-progress`
+/// events and can be cancelled with `cancel_archive`, which removes the
+/// partial archive.
+#[tauri::command]
+pub async fn create_archive(
+    app: AppHandle,
+    output_path: String,
+    entries: Vec<String>,
+    compression_level: Option<u32>,
+) -> Result<u64, AppError> {
+    let (archive_id, cancelled) = ARCHIVE_TASKS.start();
+    let result = tokio::task::spawn_blocking(move || {
+        let entries: Vec<PathBuf> = entries.iter().map(PathBuf::from).collect();
+        create_archive_sync(
+            Path::new(&output_path),
+            &entries,
+            compression_level,
+            &cancelled,
+            &mut |files_added, total_files, bytes_processed| {
+                let _ = app.emit(
+                    "archive-progress",
+                    ArchiveProgress {
+                        archive_id,
+                        files_added,
+                        total_files: total_files.unwrap_or(0),
+                        bytes_processed,
+                    },
+                );
+            },
+        )
+    })
+    .await
+    .map_err(|e| AppError::Other(format!("Task join error: {}", e)));
+    ARCHIVE_TASKS.cleanup(archive_id);
+    result?
+}
+
+/// Cancel a running `create_archive`, `extract_archive`, `compress_file` or `decompress_file`. What the
+/// operation wrote so far is removed.
+#[tauri::command]
+pub fn cancel_archive(archive_id: u64) -> Result<(), AppError> {
+    ARCHIVE_TASKS.cancel(archive_id);
+    Ok(())
+}
+
+pub(crate) fn create_archive_sync(
+    output: &Path,
+    entries: &[PathBuf],
+    compression_level: Option<u32>,
+    cancelled: &AtomicBool,
+    progress: &mut dyn FnMut(u64, Option<u64>, u64),
+) -> Result<u64, AppError> {
+    if entries.is_empty() {
+        return Err(AppError::Other("No paths provided".into()));
+    }
+    if output.exists() {
+        return Err(AppError::AlreadyExists(output.to_string_lossy().to_string()));
+    }
+    let format = ArchiveFormat::from_path(output)
+        .filter(|f| matches!(f, ArchiveFormat::Zip | ArchiveFormat::TarGz))
+        .ok_or_else(|| {
+            AppError::InvalidPath(format!(
+                "Unsupported archive type: {} (use .zip or .tar.gz)",
+                output.display()
+            ))
+        })?;
+    if compression_level.is_some_and(|l| l > 9) {
+        return Err(AppError::Other("Compression level must be between 0 and 9".into()));
+    }
+
+    let mut sources = Vec::new();
+    for entry in entries {
+        if entry.symlink_metadata().is_err() {
+            return Err(AppError::NotFound(entry.to_string_lossy().to_string()));
+        }
+        let name = entry
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .ok_or_else(|| AppError::InvalidPath(entry.to_string_lossy().to_string()))?;
+        collect_archive_sources(entry, name, &mut sources)?;
+    }
+
+    let file = fs::OpenOptions::new().write(true).create_new(true).open(output)?;
+    let result = match format {
+        ArchiveFormat::Zip => write_zip(file, &sources, compression_level, cancelled, progress),
+        _ => write_tar_gz(file, &sources, compression_level, cancelled, progress),
+    };
+    if let Err(e) = result {
+        let _ = fs::remove_file(output);
+        return Err(e);
+    }
+
+    let size = fs::metadata(output)?.len();
+    log::info!("Created {:?} archive {:?} ({} bytes)", format, output, size);
+    Ok(size)
+}
+
+/// Flatten `path` into archive sources. Symlinked directories are stored
+/// as plain entries rather than followed, so cycles can't occur.
+fn collect_archive_sources(path: &Path, name: String, out: &mut Vec<ArchiveSource>) -> Result<(), AppError> {
+    let is_dir = fs::symlink_metadata(path)?.is_dir();
+    out.push(ArchiveSource {
+        path: path.to_path_buf(),
+        name: name.clone(),
+        is_dir,
+    });
+    if is_dir {
+        let mut children: Vec<_> = fs::read_dir(path)?.filter_map(|e| e.ok()).collect();
+        children.sort_by_key(|e| e.file_name());
+        for child in children {
+            let child_name = format!("{}/{}", name, child.file_name().to_string_lossy());
+            collect_archive_sources(&child.path(), child_name, out)?;
+        }
+    }
+    Ok(())
+}
+
+fn count_files(sources: &[ArchiveSource]) -> u64 {
+    sources.iter().filter(|s| !s.is_dir).count() as u64
+}
+
+/// Drives progress reporting and cancellation checks while creating or
+/// extracting archives.
+struct ArchiveTracker<'a> {
+    total_files: Option<u64>,
+    files_added: u64,
+    bytes_processed: u64,
+    last_emit: Instant,
+    cancelled: &'a AtomicBool,
+    progress: &'a mut dyn FnMut(u64, Option<u64>, u64),
+}
+
+impl<'a> ArchiveTracker<'a> {
+    fn new(
+        total_files: Option<u64>,
+        cancelled: &'a AtomicBool,
+        progress: &'a mut dyn FnMut(u64, Option<u64>, u64),
+    ) -> Self {
+        Self {
+            total_files,
+            files_added: 0,
+            bytes_processed: 0,
+            last_emit: Instant::now(),
+            cancelled,
+            progress,
+        }
+    }
+
+    fn check_cancelled(&self) -> Result<(), AppError> {
+        if self.cancelled.load(Ordering::Relaxed) {
+            return Err(AppError::Other("Archive operation cancelled".into()));
+        }
+        Ok(())
+    }
+
+    fn file_done(&mut self, bytes: u64) {
+        self.files_added += 1;
+        self.bytes_processed += bytes;
+        if self.last_emit.elapsed() >= PROGRESS_INTERVAL {
+            self.emit();
+        }
+    }
+
+    fn emit(&mut self) {
+        self.last_emit = Instant::now();
+        (self.progress)(self.files_added, self.total_files, self.bytes_processed);
+    }
+}
+
+fn write_zip(
+    file: fs::File,
+    sources: &[ArchiveSource],
+    compression_level: Option<u32>,
+    cancelled: &AtomicBool,
+    progress: &mut dyn FnMut(u64, Option<u64>, u64),
+) -> Result<(), AppError> {
+    let zip_err = |e: zip::result::ZipError| AppError::Other(format!("Failed to write ZIP: {}", e));
+    let options = match compression_level {
+        Some(0) => FileOptions::<()>::default().compression_method(zip::CompressionMethod::Stored),
+        level => FileOptions::<()>::default()
+            .compression_method(zip::CompressionMethod::Deflated)
+            .compression_level(level.map(i64::from)),
+    }
+    .large_file(true);
+
+    let mut tracker = ArchiveTracker::new(Some(count_files(sources)), cancelled, progress);
+    let mut zip = zip::ZipWriter::new(file);
+    for source in sources {
+        tracker.check_cancelled()?;
+        if source.is_dir {
+            zip.add_directory(format!("{}/", source.name), options).map_err(zip_err)?;
+            continue;
+        }
+        if fs::symlink_metadata(&source.path)?.is_symlink() {
+            let target = fs::read_link(&source.path)?;
+            zip.add_symlink(source.name.as_str(), target.to_string_lossy(), options)
+                .map_err(zip_err)?;
+            tracker.file_done(0);
+            continue;
+        }
+        zip.start_file(source.name.as_str(), options).map_err(zip_err)?;
+        let bytes = std::io::copy(&mut fs::File::open(&source.path)?, &mut zip)?;
+        tracker.file_done(bytes);
+    }
+    zip.finish().map_err(zip_err)?;
+    tracker.emit();
+    Ok(())
+}
+
+fn write_tar_gz(
+    file: fs::File,
+    sources: &[ArchiveSource],
+    compression_level: Option<u32>,
+    cancelled: &AtomicBool,
+    progress: &mut dyn FnMut(u64, Option<u64>, u64),
+) -> Result<(), AppError> {
+    let level = compression_level.map_or_else(Compression::default, Compression::new);
+    let mut tracker = ArchiveTracker::new(Some(count_files(sources)), cancelled, progress);
+    let mut tar = tar::Builder::new(GzEncoder::new(file, level));
+    tar.follow_symlinks(false);
+    for source in sources {
+        tracker.check_cancelled()?;
+        tar.append_path_with_name(&source.path, &source.name)?;
+        if !source.is_dir {
+            tracker.file_done(fs::symlink_metadata(&source.path).map(|m| m.len()).unwrap_or(0));
+        }
+    }
+    tar.into_inner()?.finish()?;
+    tracker.emit();
+    Ok(())
+}
+
+// ── Extraction ──────────────────────────────────────────────────────────────
+
+/// Summary returned by `extract_archive`.
+#[derive(Debug, Clone, Serialize)]
+pub struct ExtractResult {
+    /// Directory the archive was extracted into.
+    pub destination: String,
+    #[serde(rename = "filesExtracted")]
+    pub files_extracted: usize,
+    #[serde(rename = "bytesExtracted")]
+    pub bytes_extracted: u64,
+    /// Archive paths left alone because the file already existed.
+    pub skipped: Vec<String>,
+}
+
+/// Event payload for `extract-progress`. `totalFiles` is null for tar
+/// archives, which can't be counted without a full decompression pass.
+#[derive(Debug, Clone, Serialize)]
+pub struct ExtractProgress {
+    #[serde(rename = "archiveId")]
+    pub archive_id: u64,
+    #[serde(rename = "filesExtracted")]
+    pub files_extracted: u64,
+    #[serde(rename = "totalFiles")]
+    pub total_files: Option<u64>,
+    #[serde(rename = "bytesExtracted")]
+    pub bytes_extracted: u64,
+}
+
+pub(crate) fn extract_archive_sync(
+    archive: &Path,
+    dest: &Path,
+    overwrite: bool,
+    cancelled: &AtomicBool,
+    progress: &mut dyn FnMut(u64, Option<u64>, u64),
+) -> Result<ExtractResult, AppError> {
+    let format = ArchiveFormat::from_path(archive).ok_or_else(|| {
+        AppError::InvalidPath(format!("Unsupported archive type: {}", archive.display()))
+    })?;
+    let file = fs::File::open(archive)?;
+    // Everything new this extraction made, parents first, removed on cancel
+    let mut created = Vec::new();
+    create_dir_tracked(dest, &mut created)?;
+    let mut result = ExtractResult {
+        destination: dest.to_string_lossy().to_string(),
+        files_extracted: 0,
+        bytes_extracted: 0,
+        skipped: Vec::new(),
+    };
+
+    let extracted = match format {
+        ArchiveFormat::Zip => extract_zip(file, dest, overwrite, cancelled, progress, &mut result, &mut created),
+        _ => extract_tar(open_tar(format, file), dest, overwrite, cancelled, progress, &mut result, &mut created),
+    };
+    if let Err(e) = extracted {
+        if cancelled.load(Ordering::Relaxed) {
+            remove_created(&created);
+        }
+        return Err(e);
+    }
+
+    log::info!(
+        "Extracted {} files ({} bytes, {} skipped) into {:?}",
+        result.files_extracted,
+        result.bytes_extracted,
+        result.skipped.len(),
         dest
+    );
+    Ok(result)
+}
+
+/// Ancestors of `path`, itself included, that don't exist yet, outermost first.
+fn missing_dirs(path: &Path) -> Vec<PathBuf> {
+    let mut missing: Vec<PathBuf> = path
+        .ancestors()
+        .take_while(|dir| !dir.as_os_str().is_empty() && fs::symlink_metadata(dir).is_err())
+        .map(Path::to_path_buf)
+        .collect();
+    missing.reverse();
+    missing
+}
+
+/// `fs::create_dir_all`, noting the directories it created in `created`.
+fn create_dir_tracked(dir: &Path, created: &mut Vec<PathBuf>) -> Result<(), AppError> {
+    let missing = missing_dirs(dir);
+    fs::create_dir_all(dir)?;
+    created.extend(missing);
+    Ok(())
+}
+
+/// Undo a cancelled extraction: remove `created`, deepest first. Directories
+/// are only removed once empty, so files that were already there survive.
+fn remove_created(created: &[PathBuf]) {
+    for path in created.iter().rev() {
+        let removed = match fs::symlink_metadata(path) {
+            Ok(metadata) if metadata.is_dir() => fs::remove_dir(path),
+            Ok(_) => fs::remove_file(path),
+            Err(_) => continue,
+        };
+        if let Err(e) = removed {
+            log::warn!("Failed to remove {} after cancelling: {}", path.display(), e);
+        }
+    }
+}
+
+/// Open a tar archive, decompressing according to `format`.
+fn open_tar(format: ArchiveFormat, file: fs::File) -> tar::Archive<Box<dyn Read>> {
+    let reader = std::io::BufReader::new(file);
+    let reader: Box<dyn Read> = match format {
+        ArchiveFormat::TarGz => Box::new(flate2::read::GzDecoder::new(reader)),
+        ArchiveFormat::TarBz2 => Box::new(bzip2::read::BzDecoder::new(reader)),
+        ArchiveFormat::TarXz => Box::new(xz2::read::XzDecoder::new(reader)),
+        _ => Box::new(reader),
     };
+    tar::Archive::new(reader)
+}
 
-    let file = fs::File::open(&archive)?;
-    let mut zip = zip::ZipArchive::new(file)
-        .map_err(|e| AppError::Other(format!("Failed to read ZIP archive: {}", e)))?;
+/// Relative path of an archive entry, or an error if it is absolute or
+/// climbs out of the destination with `..`.
+fn safe_entry_path(name: &Path) -> Result<PathBuf, AppError> {
+    let mut out = PathBuf::new();
+    for component in name.components() {
+        match component {
+            Component::Normal(part) => out.push(part),
+            Component::CurDir => {}
+            _ => {
+                return Err(AppError::InvalidPath(format!(
+                    "Archive entry escapes the destination: {}",
+                    name.display()
+                )))
+            }
+        }
+    }
+    Ok(out)
+}
 
-    log::info!("Extracting archive ({} entries, extract_here={})", zip.len(), extract_here);
+/// Refuse a link entry at `relative` whose `target` is absolute or climbs
+/// out of the destination, resolved lexically from the link's directory.
+fn check_link_target(relative: &Path, target: &Path) -> Result<(), AppError> {
+    let mut depth = relative.components().count().saturating_sub(1);
+    for component in target.components() {
+        match component {
+            Component::Normal(_) => depth += 1,
+            Component::CurDir => {}
+            Component::ParentDir if depth > 0 => depth -= 1,
+            _ => {
+                return Err(AppError::InvalidPath(format!(
+                    "Archive link escapes the destination: {} -> {}",
+                    relative.display(),
+                    target.display()
+                )))
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Create a symlink at `link` pointing to `target`.
+fn create_symlink(target: &Path, link: &Path) -> Result<(), AppError> {
+    #[cfg(unix)]
+    {
+        std::os::unix::fs::symlink(target, link)?;
+    }
+    #[cfg(windows)]
+    {
+        std::os::windows::fs::symlink_file(target, link)?;
+    }
+    Ok(())
+}
+
+fn extract_zip(
+    file: fs::File,
+    dest: &Path,
+    overwrite: bool,
+    cancelled: &AtomicBool,
+    progress: &mut dyn FnMut(u64, Option<u64>, u64),
+    result: &mut ExtractResult,
+    created: &mut Vec<PathBuf>,
+) -> Result<(), AppError> {
+    let zip_err = |e: zip::result::ZipError| AppError::Other(format!("Failed to read ZIP archive: {}", e));
+    let mut zip = zip::ZipArchive::new(file).map_err(zip_err)?;
+    let root = fs::canonicalize(dest)?;
+
+    // The central directory is cheap to scan, so reject traversal before writing anything
+    let mut names = Vec::with_capacity(zip.len());
     for i in 0..zip.len() {
-        let mut entry = zip
-            .by_index(i)
-            .map_err(|e| AppError::Other(format!("Failed to read ZIP entry: {}", e)))?;
-
-        let entry_path = dest.join(
-            entry
-                .enclosed_name()
-                .ok_or_else(|| AppError::InvalidPath("Invalid entry name in archive".into()))?,
-        );
+        let entry = zip.by_index_raw(i).map_err(zip_err)?;
+        names.push(safe_entry_path(Path::new(entry.name()))?);
+    }
 
-        // Security: ensure we don't extract outside dest
-        if !entry_path.starts_with(&dest) {
-            return Err(AppError::InvalidPath("ZIP entry contains path traversal".into()));
-        }
+    let total_files = (0..zip.len())
+        .filter(|&i| zip.by_index_raw(i).is_ok_and(|e| !e.is_dir()))
+        .count() as u64;
+    let mut tracker = ArchiveTracker::new(Some(total_files), cancelled, progress);
+    for (i, relative) in names.into_iter().enumerate() {
+        tracker.check_cancelled()?;
+        let mut entry = zip.by_index(i).map_err(zip_err)?;
+        let target = dest.join(&relative);
 
         if entry.is_dir() {
-            fs::create_dir_all(&entry_path)?;
-        } else {
-            if let Some(parent) = entry_path.parent() {
-                fs::create_dir_all(parent)?;
+            create_dir_tracked(&target, created)?;
+            continue;
+        }
+        let existing = fs::symlink_metadata(&target).ok();
+        if !overwrite && existing.is_some() {
+            result.skipped.push(relative.to_string_lossy().to_string());
+            continue;
+        }
+        if let Some(parent) = target.parent() {
+            create_dir_tracked(parent, created)?;
+            // A link already in `dest`, or one extracted earlier, could lead elsewhere
+            if !fs::canonicalize(parent)?.starts_with(&root) {
+                return Err(AppError::InvalidPath(format!(
+                    "Archive entry escapes the destination: {}",
+                    relative.display()
+                )));
             }
-            let mut outfile = fs::File::create(&entry_path)?;
-            std::io::copy(&mut entry, &mut outfile)?;
         }
+
+        if entry.is_symlink() {
+            let mut link = String::new();
+            entry.read_to_string(&mut link)?;
+            check_link_target(&relative, Path::new(&link))?;
+            if existing.is_some() {
+                fs::remove_file(&target)?;
+            } else {
+                created.push(target.clone());
+            }
+            create_symlink(Path::new(&link), &target)?;
+            tracker.file_done(link.len() as u64);
+            continue;
+        }
+        match existing {
+            // Opening it would write wherever the link points
+            Some(metadata) if metadata.is_symlink() => {
+                return Err(AppError::InvalidPath(format!(
+                    "Not writing through the symlink at {}",
+                    target.display()
+                )));
+            }
+            Some(_) => {}
+            None => created.push(target.clone()),
+        }
+        let bytes = std::io::copy(&mut entry, &mut fs::File::create(&target)?)?;
+        tracker.file_done(bytes);
+    }
+    tracker.emit();
+
+    result.files_extracted = tracker.files_added as usize;
+    result.bytes_extracted = tracker.bytes_processed;
+    Ok(())
+}
+
+fn extract_tar(
+    mut archive: tar::Archive<Box<dyn Read>>,
+    dest: &Path,
+    overwrite: bool,
+    cancelled: &AtomicBool,
+    progress: &mut dyn FnMut(u64, Option<u64>, u64),
+    result: &mut ExtractResult,
+    created: &mut Vec<PathBuf>,
+) -> Result<(), AppError> {
+    let tar_err = |e: std::io::Error| AppError::Other(format!("Failed to read tar archive: {}", e));
+    archive.set_overwrite(true);
+    let mut tracker = ArchiveTracker::new(None, cancelled, progress);
+
+    for entry in archive.entries().map_err(tar_err)? {
+        tracker.check_cancelled()?;
+        let mut entry = entry.map_err(tar_err)?;
+        let relative = safe_entry_path(&entry.path().map_err(tar_err)?)?;
+        let is_dir = entry.header().entry_type().is_dir();
+        let target = dest.join(&relative);
+
+        if !is_dir && !overwrite && fs::symlink_metadata(&target).is_ok() {
+            result.skipped.push(relative.to_string_lossy().to_string());
+            continue;
+        }
+        // unpack_in creates the parents itself; they're noted (not made
+        // here) because it also refuses to write through symlinks planted
+        // by earlier entries
+        let missing = missing_dirs(&target);
+        if !entry.unpack_in(dest).map_err(tar_err)? {
+            return Err(AppError::InvalidPath(format!(
+                "Archive entry escapes the destination: {}",
+                relative.display()
+            )));
+        }
+        created.extend(missing);
+        if !is_dir {
+            tracker.file_done(entry.size());
+        }
+    }
+    tracker.emit();
+
+    result.files_extracted = tracker.files_added as usize;
+    result.bytes_extracted = tracker.bytes_processed;
+    Ok(())
+}
+
+// ── Listing ─────────────────────────────────────────────────────────────────
+
+/// One entry inside an archive, as shown before extracting.
+#[derive(Debug, Clone, Serialize)]
+pub struct ArchiveEntry {
+    pub name: String,
+    /// Path within the archive, `/`-separated, without a trailing slash.
+    pub path: String,
+    /// Stored size; null for tar entries, which are compressed as one stream.
+    #[serde(rename = "compressedSize")]
+    pub compressed_size: Option<u64>,
+    #[serde(rename = "uncompressedSize")]
+    pub uncompressed_size: u64,
+    /// ISO 8601, empty if the archive doesn't record it.
+    pub modified: String,
+    #[serde(rename = "isDirectory")]
+    pub is_directory: bool,
+    #[serde(rename = "compressionMethod")]
+    pub compression_method: String,
+}
+
+/// List the entries of a ZIP or tar archive without extracting it, sorted
+/// by path.
+#[tauri::command]
+pub async fn list_archive_contents(path: String) -> Result<Vec<ArchiveEntry>, AppError> {
+    let archive = PathBuf::from(&path);
+    if !archive.exists() {
+        return Err(AppError::NotFound(path));
+    }
+    tokio::task::spawn_blocking(move || list_archive_sync(&archive))
+        .await
+        .map_err(|e| AppError::Other(format!("Task join error: {}", e)))?
+}
+
+pub(crate) fn list_archive_sync(archive: &Path) -> Result<Vec<ArchiveEntry>, AppError> {
+    let format = ArchiveFormat::from_path(archive).ok_or_else(|| {
+        AppError::InvalidPath(format!(
+            "Unsupported archive type: {} (supported: .zip, .tar, .tar.gz, .tar.bz2, .tar.xz)",
+            archive.display()
+        ))
+    })?;
+    let file = fs::File::open(archive)?;
+    let mut entries = match format {
+        ArchiveFormat::Zip => list_zip(file)?,
+        _ => list_tar(format, file)?,
+    };
+    entries.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(entries)
+}
+
+/// Reads only the central directory, so large ZIPs list quickly.
+fn list_zip(file: fs::File) -> Result<Vec<ArchiveEntry>, AppError> {
+    let corrupt = |e: zip::result::ZipError| {
+        AppError::Other(format!("Failed to read ZIP archive (it may be corrupt or incomplete): {}", e))
+    };
+    let mut zip = zip::ZipArchive::new(std::io::BufReader::new(file)).map_err(corrupt)?;
+
+    let mut entries = Vec::with_capacity(zip.len());
+    for i in 0..zip.len() {
+        let entry = zip.by_index_raw(i).map_err(corrupt)?;
+        let modified = entry
+            .last_modified()
+            .map(|t| {
+                format!(
+                    "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}",
+                    t.year(),
+                    t.month(),
+                    t.day(),
+                    t.hour(),
+                    t.minute(),
+                    t.second()
+                )
+            })
+            .unwrap_or_default();
+        let compression_method = match entry.compression() {
+            zip::CompressionMethod::Stored => "stored".to_string(),
+            zip::CompressionMethod::Deflated => "deflate".to_string(),
+            other => format!("{:?}", other).to_lowercase(),
+        };
+        entries.push(archive_entry(
+            entry.name(),
+            entry.is_dir(),
+            Some(entry.compressed_size()),
+            entry.size(),
+            modified,
+            compression_method,
+        ));
+    }
+    Ok(entries)
+}
+
+/// Walks the headers; file data is skipped, though compressed tars still
+/// have to be decompressed to find each header.
+fn list_tar(format: ArchiveFormat, file: fs::File) -> Result<Vec<ArchiveEntry>, AppError> {
+    let corrupt =
+        |e: std::io::Error| AppError::Other(format!("Failed to read tar archive (it may be corrupt or incomplete): {}", e));
+    let compression_method = match format {
+        ArchiveFormat::TarGz => "gzip",
+        ArchiveFormat::TarBz2 => "bzip2",
+        ArchiveFormat::TarXz => "xz",
+        _ => "none",
+    };
+
+    let mut archive = open_tar(format, file);
+    let mut entries = Vec::new();
+    for entry in archive.entries().map_err(corrupt)? {
+        let entry = entry.map_err(corrupt)?;
+        let header = entry.header();
+        let modified = header
+            .mtime()
+            .ok()
+            .and_then(|secs| DateTime::from_timestamp(secs as i64, 0))
+            .map(|t| t.with_timezone(&Local).format("%Y-%m-%dT%H:%M:%S").to_string())
+            .unwrap_or_default();
+        let path = entry.path().map_err(corrupt)?.to_string_lossy().to_string();
+        entries.push(archive_entry(
+            &path,
+            header.entry_type().is_dir(),
+            None,
+            entry.size(),
+            modified,
+            compression_method.to_string(),
+        ));
     }
+    Ok(entries)
+}
 
-    Ok(dest.to_string_lossy().to_string())
+fn archive_entry(
+    raw_path: &str,
+    is_directory: bool,
+    compressed_size: Option<u64>,
+    uncompressed_size: u64,
+    modified: String,
+    compression_method: String,
+) -> ArchiveEntry {
+    let path = raw_path.trim_start_matches("./").trim_end_matches('/').to_string();
+    let name = path.rsplit('/').next().unwrap_or(&path).to_string();
+    ArchiveEntry {
+        name,
+        path,
+        compressed_size,
+        uncompressed_size: if is_directory { 0 } else { uncompressed_size },
+        modified,
+        is_directory,
+        compression_method,
+    }
 }
 
 fn add_file_to_zip(
@@ -200,6 +975,259 @@ fn find_unique_path(dir: &Path, base_name: &str, extension: &str) -> PathBuf {
     make_path(" (overflow)")
 }
 
+// ── Single-file compression ─────────────────────────────────────────────────
+
+/// Stream compressors for a single file (no archive container).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CompressionAlgorithm {
+    Gzip,
+    Bzip2,
+}
+
+impl CompressionAlgorithm {
+    fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_string_lossy().to_lowercase().as_str() {
+            "gz" => Some(Self::Gzip),
+            "bz2" => Some(Self::Bzip2),
+            _ => None,
+        }
+    }
+
+    fn extension(self) -> &'static str {
+        match self {
+            Self::Gzip => "gz",
+            Self::Bzip2 => "bz2",
+        }
+    }
+}
+
+/// Event payload for `compress-progress` and `decompress-progress`.
+/// Both count bytes read from the source file.
+#[derive(Debug, Clone, Serialize)]
+pub struct FileCompressionProgress {
+    #[serde(rename = "archiveId")]
+    pub archive_id: u64,
+    #[serde(rename = "bytesProcessed")]
+    pub bytes_processed: u64,
+    #[serde(rename = "totalBytes")]
+    pub total_bytes: u64,
+}
+
+/// Compress `source` to `{source}.gz` or `{source}.bz2` next to it. The
+/// source is left in place.
+///
+/// `level` is 0-9 for gzip and 1-9 for bzip2. Streams `compress-progress`
+/// events and can be cancelled with `cancel_archive`, which removes the
+/// partial output.
+#[tauri::command]
+pub async fn compress_file(
+    app: AppHandle,
+    source: String,
+    algorithm: CompressionAlgorithm,
+    level: Option<u32>,
+) -> Result<FileEntry, AppError> {
+    let (archive_id, cancelled) = ARCHIVE_TASKS.start();
+    let result = tokio::task::spawn_blocking(move || {
+        let output = compress_file_sync(
+            Path::new(&source),
+            algorithm,
+            level,
+            &cancelled,
+            &mut |bytes_processed, total_bytes| {
+                let _ = app.emit(
+                    "compress-progress",
+                    FileCompressionProgress { archive_id, bytes_processed, total_bytes },
+                );
+            },
+        )?;
+        Ok(metadata_to_entry(&output, &fs::metadata(&output)?))
+    })
+    .await
+    .map_err(|e| AppError::Other(format!("Task join error: {}", e)));
+    ARCHIVE_TASKS.cleanup(archive_id);
+    result?
+}
+
+/// Decompress a `.gz` or `.bz2` file into `dest_dir` (default: next to it),
+/// named without the extension. Fails with `AlreadyExists` rather than
+/// overwriting. Streams `decompress-progress` events and can be cancelled
+/// with `cancel_archive`.
+#[tauri::command]
+pub async fn decompress_file(
+    app: AppHandle,
+    source: String,
+    dest_dir: Option<String>,
+) -> Result<FileEntry, AppError> {
+    let (archive_id, cancelled) = ARCHIVE_TASKS.start();
+    let result = tokio::task::spawn_blocking(move || {
+        let output = decompress_file_sync(
+            Path::new(&source),
+            dest_dir.as_deref().map(Path::new),
+            &cancelled,
+            &mut |bytes_processed, total_bytes| {
+                let _ = app.emit(
+                    "decompress-progress",
+                    FileCompressionProgress { archive_id, bytes_processed, total_bytes },
+                );
+            },
+        )?;
+        Ok(metadata_to_entry(&output, &fs::metadata(&output)?))
+    })
+    .await
+    .map_err(|e| AppError::Other(format!("Task join error: {}", e)));
+    ARCHIVE_TASKS.cleanup(archive_id);
+    result?
+}
+
+pub(crate) fn compress_file_sync(
+    source: &Path,
+    algorithm: CompressionAlgorithm,
+    level: Option<u32>,
+    cancelled: &AtomicBool,
+    progress: &mut dyn FnMut(u64, u64),
+) -> Result<PathBuf, AppError> {
+    let metadata = fs::metadata(source).map_err(|_| AppError::NotFound(source.to_string_lossy().to_string()))?;
+    if metadata.is_dir() {
+        return Err(AppError::InvalidPath(format!(
+            "{} is a directory; use an archive instead",
+            source.display()
+        )));
+    }
+    let min_level = match algorithm {
+        CompressionAlgorithm::Gzip => 0,
+        CompressionAlgorithm::Bzip2 => 1,
+    };
+    if level.is_some_and(|l| !(min_level..=9).contains(&l)) {
+        return Err(AppError::Other(format!("Compression level must be between {} and 9", min_level)));
+    }
+
+    let mut output = source.as_os_str().to_owned();
+    output.push(".");
+    output.push(algorithm.extension());
+    let output = PathBuf::from(output);
+    let file = create_output(&output)?;
+
+    let read = Cell::new(0);
+    let mut reader = CountingReader { inner: fs::File::open(source)?, count: &read };
+    let mut copy = |writer: &mut dyn Write| {
+        copy_with_progress(&mut reader, writer, &read, metadata.len(), cancelled, progress)
+    };
+    let result = match algorithm {
+        CompressionAlgorithm::Gzip => {
+            let mut encoder = GzEncoder::new(file, level.map_or_else(Compression::default, Compression::new));
+            copy(&mut encoder).and_then(|_| Ok(encoder.finish().map(drop)?))
+        }
+        CompressionAlgorithm::Bzip2 => {
+            let level = level.map_or_else(bzip2::Compression::default, bzip2::Compression::new);
+            let mut encoder = bzip2::write::BzEncoder::new(file, level);
+            copy(&mut encoder).and_then(|_| Ok(encoder.finish().map(drop)?))
+        }
+    };
+    finish_output(&output, result)?;
+    log::info!("Compressed {:?} with {:?}", source, algorithm);
+    Ok(output)
+}
+
+pub(crate) fn decompress_file_sync(
+    source: &Path,
+    dest_dir: Option<&Path>,
+    cancelled: &AtomicBool,
+    progress: &mut dyn FnMut(u64, u64),
+) -> Result<PathBuf, AppError> {
+    let metadata = fs::metadata(source).map_err(|_| AppError::NotFound(source.to_string_lossy().to_string()))?;
+    let algorithm = CompressionAlgorithm::from_path(source)
+        .filter(|_| metadata.is_file())
+        .ok_or_else(|| {
+            AppError::InvalidPath(format!("Not a .gz or .bz2 file: {}", source.display()))
+        })?;
+    let name = source
+        .file_stem()
+        .filter(|stem| !stem.is_empty())
+        .ok_or_else(|| AppError::InvalidPath(source.to_string_lossy().to_string()))?;
+    let dest_dir = match dest_dir {
+        Some(dir) if !dir.is_dir() => return Err(AppError::NotFound(dir.to_string_lossy().to_string())),
+        Some(dir) => dir,
+        None => source.parent().unwrap_or(Path::new(".")),
+    };
+    let output = dest_dir.join(name);
+    let mut file = create_output(&output)?;
+
+    let read = Cell::new(0);
+    let reader = CountingReader { inner: fs::File::open(source)?, count: &read };
+    // The multi-stream decoders also read files made by concatenating
+    // several compressed streams (`cat a.gz b.gz`)
+    let mut decoder: Box<dyn Read> = match algorithm {
+        CompressionAlgorithm::Gzip => Box::new(flate2::read::MultiGzDecoder::new(reader)),
+        CompressionAlgorithm::Bzip2 => Box::new(bzip2::read::MultiBzDecoder::new(reader)),
+    };
+    let result = copy_with_progress(&mut decoder, &mut file, &read, metadata.len(), cancelled, progress);
+    finish_output(&output, result)?;
+    log::info!("Decompressed {:?} into {:?}", source, output);
+    Ok(output)
+}
+
+fn create_output(output: &Path) -> Result<fs::File, AppError> {
+    fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(output)
+        .map_err(|e| match e.kind() {
+            std::io::ErrorKind::AlreadyExists => AppError::AlreadyExists(output.to_string_lossy().to_string()),
+            _ => AppError::Io(e),
+        })
+}
+
+/// Remove the partial output if writing it failed or was cancelled.
+fn finish_output(output: &Path, result: Result<(), AppError>) -> Result<(), AppError> {
+    if result.is_err() {
+        let _ = fs::remove_file(output);
+    }
+    result
+}
+
+/// Counts the bytes read through it, for progress against the source size.
+struct CountingReader<'a, R> {
+    inner: R,
+    count: &'a Cell<u64>,
+}
+
+impl<R: Read> Read for CountingReader<'_, R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.count.set(self.count.get() + n as u64);
+        Ok(n)
+    }
+}
+
+fn copy_with_progress(
+    reader: &mut dyn Read,
+    writer: &mut dyn Write,
+    read: &Cell<u64>,
+    total: u64,
+    cancelled: &AtomicBool,
+    progress: &mut dyn FnMut(u64, u64),
+) -> Result<(), AppError> {
+    let mut buffer = vec![0u8; 64 * 1024];
+    let mut last_emit = Instant::now();
+    loop {
+        if cancelled.load(Ordering::Relaxed) {
+            return Err(AppError::Other("Archive operation cancelled".into()));
+        }
+        let n = reader.read(&mut buffer)?;
+        if n == 0 {
+            break;
+        }
+        writer.write_all(&buffer[..n])?;
+        if last_emit.elapsed() >= PROGRESS_INTERVAL {
+            last_emit = Instant::now();
+            progress(read.get(), total);
+        }
+    }
+    progress(read.get(), total);
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -219,12 +1247,462 @@ mod tests {
         assert!(PathBuf::from(&zip_path).exists());
 
         // Extract
-        let dest = extract_archive(zip_path, false).unwrap();
-        let dest_path = PathBuf::from(&dest);
+        let archive = PathBuf::from(&zip_path);
+        let dest_path = resolve_extract_destination(&archive, false, None).unwrap();
+        let result =
+            extract_archive_sync(&archive, &dest_path, false, &AtomicBool::new(false), &mut |_, _, _| {}).unwrap();
+        assert_eq!(result.files_extracted, 2);
+        assert_eq!(result.bytes_extracted, 25);
         assert!(dest_path.join("source/hello.txt").exists());
         assert!(dest_path.join("source/sub/nested.txt").exists());
 
         let content = fs::read_to_string(dest_path.join("source/hello.txt")).unwrap();
         assert_eq!(content, "hello world");
     }
+
+    // ── create_archive ──────────────────────────────────────────────────────
+
+    fn sample_tree(root: &Path) -> PathBuf {
+        let src = root.join("project");
+        fs::create_dir_all(src.join("src/nested")).unwrap();
+        fs::write(src.join("README.md"), "readme").unwrap();
+        fs::write(src.join("src/main.rs"), "fn main() {}").unwrap();
+        fs::write(src.join("src/nested/data.bin"), vec![7u8; 4096]).unwrap();
+        src
+    }
+
+    #[test]
+    fn test_create_zip_archive_preserves_relative_paths() {
+        let dir = tempdir().unwrap();
+        let src = sample_tree(dir.path());
+        let single = dir.path().join("single.txt");
+        fs::write(&single, "solo").unwrap();
+        let output = dir.path().join("out.zip");
+
+        let mut events = Vec::new();
+        let size = create_archive_sync(
+            &output,
+            &[src, single],
+            Some(9),
+            &AtomicBool::new(false),
+            &mut |added, total, bytes| events.push((added, total, bytes)),
+        )
+        .unwrap();
+        assert_eq!(size, fs::metadata(&output).unwrap().len());
+        assert_eq!(events.last(), Some(&(4, Some(4), 6 + 12 + 4096 + 4)));
+
+        let mut zip = zip::ZipArchive::new(fs::File::open(&output).unwrap()).unwrap();
+        let mut names: Vec<String> = zip.file_names().map(String::from).collect();
+        names.sort();
+        assert_eq!(
+            names,
+            vec![
+                "project/",
+                "project/README.md",
+                "project/src/",
+                "project/src/main.rs",
+                "project/src/nested/",
+                "project/src/nested/data.bin",
+                "single.txt",
+            ]
+        );
+        let mut content = String::new();
+        zip.by_name("project/src/main.rs").unwrap().read_to_string(&mut content).unwrap();
+        assert_eq!(content, "fn main() {}");
+    }
+
+    #[test]
+    fn test_create_tar_gz_archive() {
+        let dir = tempdir().unwrap();
+        let src = sample_tree(dir.path());
+        let output = dir.path().join("out.tar.gz");
+
+        create_archive_sync(&output, &[src], None, &AtomicBool::new(false), &mut |_, _, _| {}).unwrap();
+
+        let gz = flate2::read::GzDecoder::new(fs::File::open(&output).unwrap());
+        let mut archive = tar::Archive::new(gz);
+        let mut names: Vec<String> = archive
+            .entries()
+            .unwrap()
+            .map(|e| e.unwrap().path().unwrap().to_string_lossy().trim_end_matches('/').to_string())
+            .collect();
+        names.sort();
+        assert_eq!(
+            names,
+            vec![
+                "project",
+                "project/README.md",
+                "project/src",
+                "project/src/main.rs",
+                "project/src/nested",
+                "project/src/nested/data.bin",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_create_archive_rejects_existing_output_and_bad_input() {
+        let dir = tempdir().unwrap();
+        let sources = [sample_tree(dir.path())];
+        let existing = dir.path().join("exists.zip");
+        fs::write(&existing, "x").unwrap();
+        let noop = &mut |_: u64, _: Option<u64>, _: u64| {};
+        let flag = AtomicBool::new(false);
+
+        assert!(matches!(
+            create_archive_sync(&existing, &sources, None, &flag, noop),
+            Err(AppError::AlreadyExists(_))
+        ));
+        assert!(matches!(
+            create_archive_sync(&dir.path().join("out.rar"), &sources, None, &flag, noop),
+            Err(AppError::InvalidPath(_))
+        ));
+        assert!(create_archive_sync(&dir.path().join("out.zip"), &sources, Some(12), &flag, noop).is_err());
+        assert!(!dir.path().join("out.zip").exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_create_archive_stores_symlinks_as_links() {
+        let dir = tempdir().unwrap();
+        let src = sample_tree(dir.path());
+        std::os::unix::fs::symlink("README.md", src.join("readme-link")).unwrap();
+        std::os::unix::fs::symlink("missing.txt", src.join("dangling")).unwrap();
+        let noop = &mut |_: u64, _: Option<u64>, _: u64| {};
+
+        let zip_path = dir.path().join("out.zip");
+        create_archive_sync(&zip_path, std::slice::from_ref(&src), None, &AtomicBool::new(false), noop).unwrap();
+        let mut zip = zip::ZipArchive::new(fs::File::open(&zip_path).unwrap()).unwrap();
+        let mut target = String::new();
+        let mut link = zip.by_name("project/dangling").unwrap();
+        assert!(link.is_symlink());
+        link.read_to_string(&mut target).unwrap();
+        assert_eq!(target, "missing.txt");
+
+        let tgz = dir.path().join("out.tgz");
+        create_archive_sync(&tgz, &[src.join("dangling")], None, &AtomicBool::new(false), noop).unwrap();
+    }
+
+    #[test]
+    fn test_create_archive_cancelled_removes_output() {
+        let dir = tempdir().unwrap();
+        let src = sample_tree(dir.path());
+        let output = dir.path().join("out.tgz");
+
+        let result = create_archive_sync(&output, &[src], None, &AtomicBool::new(true), &mut |_, _, _| {});
+        assert!(result.is_err());
+        assert!(!output.exists());
+    }
+
+    // ── extract_archive ─────────────────────────────────────────────────────
+
+    fn extract(archive: &Path, dest: &Path, overwrite: bool) -> Result<ExtractResult, AppError> {
+        extract_archive_sync(archive, dest, overwrite, &AtomicBool::new(false), &mut |_, _, _| {})
+    }
+
+    #[test]
+    fn test_extract_tar_variants_roundtrip() {
+        let dir = tempdir().unwrap();
+        let src = sample_tree(dir.path());
+        let tgz = dir.path().join("out.tar.gz");
+        create_archive_sync(&tgz, &[src], None, &AtomicBool::new(false), &mut |_, _, _| {}).unwrap();
+
+        // Recompress the tar stream as plain, bzip2 and xz
+        let mut tar_bytes = Vec::new();
+        flate2::read::GzDecoder::new(fs::File::open(&tgz).unwrap())
+            .read_to_end(&mut tar_bytes)
+            .unwrap();
+        let plain = dir.path().join("out.tar");
+        fs::write(&plain, &tar_bytes).unwrap();
+        let bz2 = dir.path().join("out.tar.bz2");
+        let mut enc = bzip2::write::BzEncoder::new(fs::File::create(&bz2).unwrap(), bzip2::Compression::default());
+        enc.write_all(&tar_bytes).unwrap();
+        enc.finish().unwrap();
+        let xz = dir.path().join("out.txz");
+        let mut enc = xz2::write::XzEncoder::new(fs::File::create(&xz).unwrap(), 6);
+        enc.write_all(&tar_bytes).unwrap();
+        enc.finish().unwrap();
+
+        for (i, archive) in [&tgz, &plain, &bz2, &xz].into_iter().enumerate() {
+            let dest = dir.path().join(format!("dest{}", i));
+            let result = extract(archive, &dest, false).unwrap();
+            assert_eq!(result.files_extracted, 3, "{:?}", archive);
+            assert_eq!(result.bytes_extracted, 6 + 12 + 4096);
+            assert_eq!(fs::read_to_string(dest.join("project/src/main.rs")).unwrap(), "fn main() {}");
+        }
+    }
+
+    #[test]
+    fn test_extract_skips_existing_unless_overwrite() {
+        let dir = tempdir().unwrap();
+        let sources = [sample_tree(dir.path())];
+        let dest = dir.path().join("dest");
+
+        for name in ["out.zip", "out.tar.gz"] {
+            let _ = fs::remove_dir_all(&dest);
+            fs::create_dir_all(dest.join("project")).unwrap();
+            fs::write(dest.join("project/README.md"), "mine").unwrap();
+            let archive = dir.path().join(name);
+            create_archive_sync(&archive, &sources, None, &AtomicBool::new(false), &mut |_, _, _| {}).unwrap();
+
+            let result = extract(&archive, &dest, false).unwrap();
+            assert_eq!(result.skipped, vec!["project/README.md"], "{}", name);
+            assert_eq!(result.files_extracted, 2);
+            assert_eq!(fs::read_to_string(dest.join("project/README.md")).unwrap(), "mine");
+        }
+
+        let result = extract(&dir.path().join("out.zip"), &dest, true).unwrap();
+        assert!(result.skipped.is_empty());
+        assert_eq!(fs::read_to_string(dest.join("project/README.md")).unwrap(), "readme");
+    }
+
+    #[test]
+    fn test_extract_cancelled_removes_what_it_created() {
+        let dir = tempdir().unwrap();
+        let sources = [sample_tree(dir.path())];
+
+        for name in ["out.zip", "out.tar.gz"] {
+            let archive = dir.path().join(name);
+            create_archive_sync(&archive, &sources, None, &AtomicBool::new(false), &mut |_, _, _| {}).unwrap();
+
+            // Cancelled before the first entry: the new folder is removed again
+            let fresh = dir.path().join("fresh");
+            assert!(extract_archive_sync(&archive, &fresh, true, &AtomicBool::new(true), &mut |_, _, _| {}).is_err());
+            assert!(!fresh.exists(), "{}", name);
+
+            // Everything a full run notes as created goes; what was there stays
+            let dest = dir.path().join("dest");
+            let _ = fs::remove_dir_all(&dest);
+            fs::create_dir_all(dest.join("project")).unwrap();
+            fs::write(dest.join("project/notes.txt"), "mine").unwrap();
+            let mut result = ExtractResult {
+                destination: String::new(),
+                files_extracted: 0,
+                bytes_extracted: 0,
+                skipped: Vec::new(),
+            };
+            let mut created = Vec::new();
+            let (file, cancelled) = (fs::File::open(&archive).unwrap(), AtomicBool::new(false));
+            let progress = &mut |_: u64, _: Option<u64>, _: u64| {};
+            match ArchiveFormat::from_path(&archive).unwrap() {
+                ArchiveFormat::Zip => extract_zip(file, &dest, true, &cancelled, progress, &mut result, &mut created),
+                format => {
+                    let tar = open_tar(format, file);
+                    extract_tar(tar, &dest, true, &cancelled, progress, &mut result, &mut created)
+                }
+            }
+            .unwrap();
+            assert!(dest.join("project/src/nested/data.bin").exists());
+
+            remove_created(&created);
+            let left: Vec<String> = fs::read_dir(dest.join("project"))
+                .unwrap()
+                .map(|e| e.unwrap().file_name().to_string_lossy().to_string())
+                .collect();
+            assert_eq!(left, vec!["notes.txt"], "{}", name);
+        }
+    }
+
+    #[test]
+    fn test_extract_rejects_path_traversal() {
+        let dir = tempdir().unwrap();
+        let dest = dir.path().join("dest");
+        fs::create_dir(&dest).unwrap();
+
+        let zip_path = dir.path().join("evil.zip");
+        let mut zip = zip::ZipWriter::new(fs::File::create(&zip_path).unwrap());
+        zip.start_file("ok.txt", FileOptions::<()>::default()).unwrap();
+        zip.write_all(b"fine").unwrap();
+        zip.start_file("../escaped.txt", FileOptions::<()>::default()).unwrap();
+        zip.write_all(b"evil").unwrap();
+        zip.finish().unwrap();
+
+        assert!(matches!(extract(&zip_path, &dest, true), Err(AppError::InvalidPath(_))));
+        assert!(!dest.join("ok.txt").exists(), "nothing is written when any entry is unsafe");
+        assert!(!dir.path().join("escaped.txt").exists());
+
+        let tar_path = dir.path().join("evil.tar");
+        let mut builder = tar::Builder::new(fs::File::create(&tar_path).unwrap());
+        let mut header = tar::Header::new_gnu();
+        header.as_gnu_mut().unwrap().name[..14].copy_from_slice(b"../escaped.txt");
+        header.set_size(4);
+        header.set_mode(0o644);
+        header.set_cksum();
+        builder.append(&header, &b"evil"[..]).unwrap();
+        builder.finish().unwrap();
+
+        assert!(matches!(extract(&tar_path, &dest, true), Err(AppError::InvalidPath(_))));
+        assert!(!dir.path().join("escaped.txt").exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_extract_zip_recreates_symlinks() {
+        let dir = tempdir().unwrap();
+        let src = sample_tree(dir.path());
+        std::os::unix::fs::symlink("README.md", src.join("readme-link")).unwrap();
+        std::os::unix::fs::symlink("../README.md", src.join("src/up-link")).unwrap();
+        let zip_path = dir.path().join("out.zip");
+        create_archive_sync(&zip_path, &[src], None, &AtomicBool::new(false), &mut |_, _, _| {}).unwrap();
+
+        let dest = dir.path().join("dest");
+        extract(&zip_path, &dest, true).unwrap();
+        assert_eq!(fs::read_link(dest.join("project/readme-link")).unwrap(), Path::new("README.md"));
+        assert_eq!(fs::read_to_string(dest.join("project/src/up-link")).unwrap(), "readme");
+
+        // Extracting again replaces the links rather than failing on them
+        extract(&zip_path, &dest, true).unwrap();
+        assert!(fs::symlink_metadata(dest.join("project/readme-link")).unwrap().is_symlink());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_extract_zip_refuses_escaping_links() {
+        let dir = tempdir().unwrap();
+        let dest = dir.path().join("dest");
+        fs::write(dir.path().join("outside.txt"), "keep").unwrap();
+
+        let zip_path = dir.path().join("evil.zip");
+        let mut zip = zip::ZipWriter::new(fs::File::create(&zip_path).unwrap());
+        zip.add_symlink("sub/link", "../../outside.txt", FileOptions::<()>::default()).unwrap();
+        zip.finish().unwrap();
+        assert!(matches!(extract(&zip_path, &dest, true), Err(AppError::InvalidPath(_))));
+        assert!(fs::symlink_metadata(dest.join("sub/link")).is_err());
+
+        // A link already at the destination isn't written through
+        let zip_path = dir.path().join("plain.zip");
+        let mut zip = zip::ZipWriter::new(fs::File::create(&zip_path).unwrap());
+        zip.start_file("a.txt", FileOptions::<()>::default()).unwrap();
+        zip.write_all(b"evil").unwrap();
+        zip.finish().unwrap();
+        std::os::unix::fs::symlink(dir.path().join("outside.txt"), dest.join("a.txt")).unwrap();
+        assert!(matches!(extract(&zip_path, &dest, true), Err(AppError::InvalidPath(_))));
+        assert_eq!(fs::read_to_string(dir.path().join("outside.txt")).unwrap(), "keep");
+    }
+
+    #[test]
+    fn test_archive_stem() {
+        assert_eq!(archive_stem(Path::new("/x/photos.tar.gz")), "photos");
+        assert_eq!(archive_stem(Path::new("/x/Backup.TBZ2")), "Backup");
+        assert_eq!(archive_stem(Path::new("/x/docs.zip")), "docs");
+        assert_eq!(archive_stem(Path::new("/x/.zip")), "extracted");
+    }
+
+    // ── list_archive_contents ───────────────────────────────────────────────
+
+    #[test]
+    fn test_list_archive_contents_zip_and_tar() {
+        let dir = tempdir().unwrap();
+        let sources = [sample_tree(dir.path())];
+        for name in ["out.zip", "out.tar.gz"] {
+            let archive = dir.path().join(name);
+            create_archive_sync(&archive, &sources, None, &AtomicBool::new(false), &mut |_, _, _| {}).unwrap();
+
+            let entries = list_archive_sync(&archive).unwrap();
+            let paths: Vec<&str> = entries.iter().map(|e| e.path.as_str()).collect();
+            assert_eq!(
+                paths,
+                vec![
+                    "project",
+                    "project/README.md",
+                    "project/src",
+                    "project/src/main.rs",
+                    "project/src/nested",
+                    "project/src/nested/data.bin",
+                ],
+                "{}",
+                name
+            );
+
+            let data = entries.iter().find(|e| e.name == "data.bin").unwrap();
+            assert_eq!(data.uncompressed_size, 4096);
+            assert!(!data.is_directory);
+            assert!(!data.modified.is_empty());
+            assert!(entries.iter().find(|e| e.path == "project/src").unwrap().is_directory);
+
+            if name.ends_with(".zip") {
+                assert_eq!(data.compression_method, "deflate");
+                assert!(data.compressed_size.unwrap() < 4096);
+            } else {
+                assert_eq!(data.compression_method, "gzip");
+                assert_eq!(data.compressed_size, None);
+            }
+        }
+    }
+
+    #[test]
+    fn test_list_archive_contents_errors() {
+        let dir = tempdir().unwrap();
+        let unknown = dir.path().join("file.rar");
+        fs::write(&unknown, "x").unwrap();
+        assert!(matches!(list_archive_sync(&unknown), Err(AppError::InvalidPath(_))));
+
+        let corrupt = dir.path().join("broken.zip");
+        fs::write(&corrupt, "definitely not a zip").unwrap();
+        let err = list_archive_sync(&corrupt).unwrap_err();
+        assert!(err.to_string().contains("corrupt"), "{}", err);
+    }
+
+    // ── compress_file / decompress_file ─────────────────────────────────────
+
+    #[test]
+    fn test_compress_and_decompress_file_roundtrip() {
+        let dir = tempdir().unwrap();
+        let source = dir.path().join("app.log");
+        let content: Vec<u8> = (0..200_000u32).flat_map(|i| (i % 251).to_le_bytes()).collect();
+        fs::write(&source, &content).unwrap();
+        let out = dir.path().join("out");
+        fs::create_dir(&out).unwrap();
+
+        let cases = [(CompressionAlgorithm::Gzip, "app.log.gz"), (CompressionAlgorithm::Bzip2, "app.log.bz2")];
+        for (algorithm, name) in cases {
+            let mut events = Vec::new();
+            let mut progress = |read, total| events.push((read, total));
+            let compressed =
+                compress_file_sync(&source, algorithm, Some(6), &AtomicBool::new(false), &mut progress).unwrap();
+            assert_eq!(compressed, dir.path().join(name));
+            assert!(source.exists());
+            let size = content.len() as u64;
+            assert_eq!(events.last(), Some(&(size, size)));
+
+            let restored =
+                decompress_file_sync(&compressed, Some(&out), &AtomicBool::new(false), &mut |_, _| {}).unwrap();
+            assert_eq!(restored, out.join("app.log"));
+            assert_eq!(fs::read(&restored).unwrap(), content);
+            fs::remove_file(&restored).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_compress_file_errors() {
+        let dir = tempdir().unwrap();
+        let source = dir.path().join("a.txt");
+        fs::write(&source, "abc").unwrap();
+        let none = AtomicBool::new(false);
+
+        let bad_level = compress_file_sync(&source, CompressionAlgorithm::Bzip2, Some(0), &none, &mut |_, _| {});
+        assert!(matches!(bad_level, Err(AppError::Other(_))));
+        let is_dir = compress_file_sync(dir.path(), CompressionAlgorithm::Gzip, None, &none, &mut |_, _| {});
+        assert!(matches!(is_dir, Err(AppError::InvalidPath(_))));
+
+        compress_file_sync(&source, CompressionAlgorithm::Gzip, None, &none, &mut |_, _| {}).unwrap();
+        let again = compress_file_sync(&source, CompressionAlgorithm::Gzip, None, &none, &mut |_, _| {});
+        assert!(matches!(again, Err(AppError::AlreadyExists(_))));
+
+        // Decompressing next to the source would overwrite a.txt
+        let gz = dir.path().join("a.txt.gz");
+        assert!(matches!(
+            decompress_file_sync(&gz, None, &none, &mut |_, _| {}),
+            Err(AppError::AlreadyExists(_))
+        ));
+        assert!(matches!(
+            decompress_file_sync(&source, None, &none, &mut |_, _| {}),
+            Err(AppError::InvalidPath(_))
+        ));
+
+        let stop = AtomicBool::new(true);
+        let cancelled = compress_file_sync(&source, CompressionAlgorithm::Bzip2, None, &stop, &mut |_, _| {});
+        assert!(cancelled.is_err());
+        assert!(!dir.path().join("a.txt.bz2").exists());
+    }
 }
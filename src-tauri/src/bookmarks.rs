@@ -0,0 +1,201 @@
+//! Bookmarked (pinned) folders and files.
+//!
+//! Stored as a JSON array in the app's local data directory
+//! (~/.local/share/tauri-explorer/bookmarks.json on Linux).
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter};
+
+use crate::error::AppError;
+
+/// Serializes read-modify-write cycles on the bookmarks file.
+static BOOKMARKS_LOCK: Mutex<()> = Mutex::new(());
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Bookmark {
+    pub path: String,
+    pub label: String,
+    /// ISO 8601, local time.
+    #[serde(rename = "createdAt")]
+    pub created_at: String,
+    #[serde(rename = "iconPath", default)]
+    pub icon_path: Option<String>,
+}
+
+/// Path of the bookmarks file.
+fn bookmarks_file() -> Result<PathBuf, AppError> {
+    let base = dirs::data_local_dir()
+        .ok_or_else(|| AppError::Other("Could not determine data directory".into()))?;
+    Ok(base.join("tauri-explorer").join("bookmarks.json"))
+}
+
+/// Create an empty bookmarks file if none exists yet. Called on startup.
+pub fn ensure_bookmarks_file() -> Result<(), AppError> {
+    ensure_file(&bookmarks_file()?)
+}
+
+fn ensure_file(file: &Path) -> Result<(), AppError> {
+    if file.exists() {
+        return Ok(());
+    }
+    if let Some(parent) = file.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(file, "[]")?;
+    Ok(())
+}
+
+fn load(file: &Path) -> Result<Vec<Bookmark>, AppError> {
+    if !file.exists() {
+        return Ok(Vec::new());
+    }
+    let data = fs::read_to_string(file)?;
+    if data.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+    serde_json::from_str(&data).map_err(|e| AppError::Other(format!("Corrupt bookmarks file: {}", e)))
+}
+
+fn save(file: &Path, bookmarks: &[Bookmark]) -> Result<(), AppError> {
+    ensure_file(file)?;
+    let data = serde_json::to_string_pretty(bookmarks)
+        .map_err(|e| AppError::Other(format!("Failed to serialize bookmarks: {}", e)))?;
+    // Write beside the file and rename so a crash never leaves it half-written
+    let tmp = file.with_extension("json.tmp");
+    fs::write(&tmp, data)?;
+    fs::rename(&tmp, file)?;
+    Ok(())
+}
+
+fn add_bookmark_in(file: &Path, path: &str, label: Option<String>) -> Result<Bookmark, AppError> {
+    let target = Path::new(path);
+    if !target.exists() {
+        return Err(AppError::NotFound(path.to_string()));
+    }
+
+    let _guard = BOOKMARKS_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut bookmarks = load(file)?;
+    if bookmarks.iter().any(|b| b.path == path) {
+        return Err(AppError::AlreadyExists(path.to_string()));
+    }
+
+    let label = label
+        .map(|l| l.trim().to_string())
+        .filter(|l| !l.is_empty())
+        .unwrap_or_else(|| {
+            target
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_else(|| path.to_string())
+        });
+    let bookmark = Bookmark {
+        path: path.to_string(),
+        label,
+        created_at: Local::now().format("%Y-%m-%dT%H:%M:%S").to_string(),
+        icon_path: None,
+    };
+    bookmarks.push(bookmark.clone());
+    save(file, &bookmarks)?;
+    Ok(bookmark)
+}
+
+fn remove_bookmark_in(file: &Path, path: &str) -> Result<(), AppError> {
+    let _guard = BOOKMARKS_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut bookmarks = load(file)?;
+    let before = bookmarks.len();
+    bookmarks.retain(|b| b.path != path);
+    if bookmarks.len() == before {
+        return Err(AppError::NotFound(path.to_string()));
+    }
+    save(file, &bookmarks)
+}
+
+/// Bookmark `path`. `label` defaults to the file name.
+/// Emits `bookmarks-changed` with the updated list.
+#[tauri::command]
+pub fn add_bookmark(app: AppHandle, path: String, label: Option<String>) -> Result<Bookmark, AppError> {
+    let file = bookmarks_file()?;
+    let bookmark = add_bookmark_in(&file, &path, label)?;
+    log::info!("Added bookmark: {}", path);
+    notify_changed(&app, &file);
+    Ok(bookmark)
+}
+
+/// Remove the bookmark for `path`. Emits `bookmarks-changed`.
+#[tauri::command]
+pub fn remove_bookmark(app: AppHandle, path: String) -> Result<(), AppError> {
+    let file = bookmarks_file()?;
+    remove_bookmark_in(&file, &path)?;
+    log::info!("Removed bookmark: {}", path);
+    notify_changed(&app, &file);
+    Ok(())
+}
+
+/// All bookmarks, in the order they were added.
+#[tauri::command]
+pub fn list_bookmarks() -> Result<Vec<Bookmark>, AppError> {
+    load(&bookmarks_file()?)
+}
+
+fn notify_changed(app: &AppHandle, file: &Path) {
+    match load(file) {
+        Ok(bookmarks) => {
+            let _ = app.emit("bookmarks-changed", bookmarks);
+        }
+        Err(e) => log::warn!("Failed to reload bookmarks: {}", e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_add_list_remove_bookmark() {
+        let dir = tempdir().unwrap();
+        let file = dir.path().join("data").join("bookmarks.json");
+        let target = dir.path().join("Projects");
+        fs::create_dir(&target).unwrap();
+        let target = target.to_string_lossy().to_string();
+
+        let added = add_bookmark_in(&file, &target, None).unwrap();
+        assert_eq!(added.label, "Projects");
+        assert!(matches!(
+            add_bookmark_in(&file, &target, Some("Again".into())),
+            Err(AppError::AlreadyExists(_))
+        ));
+        assert_eq!(load(&file).unwrap(), vec![added]);
+
+        remove_bookmark_in(&file, &target).unwrap();
+        assert!(load(&file).unwrap().is_empty());
+        assert!(matches!(remove_bookmark_in(&file, &target), Err(AppError::NotFound(_))));
+    }
+
+    #[test]
+    fn test_add_bookmark_custom_label_and_missing_path() {
+        let dir = tempdir().unwrap();
+        let file = dir.path().join("bookmarks.json");
+        let target = dir.path().to_string_lossy().to_string();
+
+        let added = add_bookmark_in(&file, &target, Some("  Work  ".into())).unwrap();
+        assert_eq!(added.label, "Work");
+
+        let missing = dir.path().join("missing").to_string_lossy().to_string();
+        assert!(matches!(add_bookmark_in(&file, &missing, None), Err(AppError::NotFound(_))));
+    }
+
+    #[test]
+    fn test_ensure_file_creates_empty_list() {
+        let dir = tempdir().unwrap();
+        let file = dir.path().join("nested").join("bookmarks.json");
+        ensure_file(&file).unwrap();
+        assert_eq!(fs::read_to_string(&file).unwrap(), "[]");
+        assert!(load(&file).unwrap().is_empty());
+    }
+}
@@ -6,9 +6,15 @@
 //! reliably (its `clipboard-rs` backend is X11-only, broken on Wayland).
 //! This module shells out to `wl-paste`/`wl-copy` (Wayland) or
 //! `xclip` (X11) to read and write file URIs directly.
+//! Plain text (copied paths) goes through `arboard` on every platform.
 
 use log;
+use serde::Deserialize;
 use std::process::Command;
+#[cfg(all(unix, not(target_os = "macos")))]
+use std::sync::Mutex;
+
+use crate::error::AppError;
 
 /// Detect whether the session is Wayland or X11.
 fn is_wayland() -> bool {
@@ -115,8 +121,8 @@ fn percent_encode_path(path: &str) -> String {
     let mut result = String::with_capacity(path.len() * 2);
     for b in path.bytes() {
         match b {
-            // Unreserved characters (RFC 3986) + '/' (path separator)
-            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' | b'/' => {
+            // Unreserved characters (RFC 3986) + '/' (path separator) + ':' (drive letters)
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' | b'/' | b':' => {
                 result.push(b as char);
             }
             _ => {
@@ -185,6 +191,76 @@ fn write_clipboard_file_paths(paths: &[String]) -> bool {
     write_mime("x-special/gnome-copied-files", gnome_data.as_bytes())
 }
 
+/// Put plain text on the clipboard.
+fn write_clipboard_text(text: &str) -> Result<(), AppError> {
+    let unavailable = |e: arboard::Error| AppError::Other(format!("Could not open the clipboard: {}", e));
+
+    // X11 and Wayland serve the clipboard from the process that set it, so
+    // the handle is kept for the app's lifetime there
+    #[cfg(all(unix, not(target_os = "macos")))]
+    {
+        static CLIPBOARD: Mutex<Option<arboard::Clipboard>> = Mutex::new(None);
+        let mut clipboard = CLIPBOARD.lock().unwrap_or_else(|e| e.into_inner());
+        if clipboard.is_none() {
+            *clipboard = Some(arboard::Clipboard::new().map_err(unavailable)?);
+        }
+        clipboard.as_mut().expect("opened above").set_text(text).map_err(unavailable)
+    }
+
+    #[cfg(not(all(unix, not(target_os = "macos"))))]
+    {
+        arboard::Clipboard::new()
+            .and_then(|mut clipboard| clipboard.set_text(text))
+            .map_err(unavailable)
+    }
+}
+
+/// How `copy_path_to_clipboard` renders a path.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PathFormat {
+    /// Absolute path with the platform's separators.
+    Absolute,
+    /// Forward slashes.
+    PosixStyle,
+    /// Backslashes, also on Unix for pasting into Windows tools.
+    WindowsStyle,
+    /// `file://` URI (`file:///C:/...` for drive paths).
+    Uri,
+}
+
+/// Render an absolute path in `format`.
+fn format_path(absolute: &str, format: PathFormat) -> String {
+    match format {
+        PathFormat::Absolute => absolute.to_string(),
+        PathFormat::PosixStyle => absolute.replace('\\', "/"),
+        PathFormat::WindowsStyle => absolute.replace('/', "\\"),
+        PathFormat::Uri => {
+            let posix = absolute.replace('\\', "/");
+            let encoded = percent_encode_path(&posix);
+            if posix.starts_with("//") {
+                // UNC path: \\server\share -> file://server/share
+                format!("file:{}", encoded)
+            } else if posix.starts_with('/') {
+                format!("file://{}", encoded)
+            } else {
+                format!("file:///{}", encoded)
+            }
+        }
+    }
+}
+
+/// Copy `path`, made absolute and rendered in `format`, to the clipboard as
+/// text. Fails when no clipboard is reachable (e.g. a headless session).
+#[tauri::command]
+pub fn copy_path_to_clipboard(path: String, format: PathFormat) -> Result<(), AppError> {
+    let absolute = std::path::absolute(&path).map_err(|_| AppError::InvalidPath(path.clone()))?;
+    let text = format_path(&absolute.to_string_lossy(), format);
+    write_clipboard_text(&text)?;
+    log::debug!("Copied path to clipboard ({:?})", format);
+    Ok(())
+}
+
 /// Read raw image data (PNG) from the OS clipboard.
 /// Returns the raw bytes or None if no image is available.
 fn read_clipboard_image() -> Option<Vec<u8>> {
@@ -356,6 +432,17 @@ mod tests {
         assert_eq!(decoded, original);
     }
 
+    #[test]
+    fn percent_encode_keeps_colons_roundtrip() {
+        // ':' is allowed in URI path segments, and keeps `file:///C:/...` readable
+        let original = "/home/user/notes:draft.txt";
+        let encoded = percent_encode_path(original);
+        assert_eq!(encoded, original);
+        assert_eq!(percent_decode(&encoded), original);
+        let uris = paths_to_uris(&[original.to_string()]);
+        assert_eq!(parse_file_uris(&uris.join("\n")), vec![original]);
+    }
+
     #[test]
     fn paths_to_uris_basic() {
         let paths = vec![
@@ -368,4 +455,17 @@ mod tests {
             "file:///tmp/test%20file.txt",
         ]);
     }
+
+    #[test]
+    fn format_path_styles() {
+        let unix = "/home/user/My Docs/a.txt";
+        assert_eq!(format_path(unix, PathFormat::Absolute), unix);
+        assert_eq!(format_path(unix, PathFormat::WindowsStyle), "\\home\\user\\My Docs\\a.txt");
+        assert_eq!(format_path(unix, PathFormat::Uri), "file:///home/user/My%20Docs/a.txt");
+
+        let windows = "C:\\Users\\me\\a b.txt";
+        assert_eq!(format_path(windows, PathFormat::PosixStyle), "C:/Users/me/a b.txt");
+        assert_eq!(format_path(windows, PathFormat::Uri), "file:///C:/Users/me/a%20b.txt");
+        assert_eq!(format_path("\\\\server\\share\\x", PathFormat::Uri), "file://server/share/x");
+    }
 }
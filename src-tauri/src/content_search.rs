@@ -2,15 +2,24 @@
 //! Issue: tauri-explorer-3a1q, tauri-explorer-5w06, tauri-pkc4, tauri-dbiw
 
 use crate::error::AppError;
+use crate::export::{write_export, ExportFormat};
+use crate::files::text_file::{guess_encoding, ENCODING_SAMPLE_SIZE};
 use grep_matcher::Matcher;
 use grep_regex::RegexMatcherBuilder;
-use grep_searcher::sinks::UTF8;
-use grep_searcher::{BinaryDetection, MmapChoice, SearcherBuilder};
+use globset::{GlobBuilder, GlobSet, GlobSetBuilder};
+use grep_searcher::{
+    BinaryDetection, MmapChoice, Searcher, SearcherBuilder, Sink, SinkContext, SinkContextKind,
+    SinkMatch,
+};
 use ignore::{WalkBuilder, WalkState};
-use serde::Serialize;
-use std::path::PathBuf;
-use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
-use std::sync::{mpsc, Arc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{mpsc, Arc, Condvar, Mutex, OnceLock};
+use std::time::{Duration, Instant, UNIX_EPOCH};
 use tauri::{AppHandle, Emitter};
 
 /// Maximum matches to collect per file to prevent runaway processing
@@ -19,22 +28,50 @@ const MAX_MATCHES_PER_FILE: usize = 50;
 /// Maximum characters to include in line_content before truncation
 const MAX_LINE_LENGTH: usize = 300;
 
+/// Heap a multiline search may use for one file. Files are memory-mapped
+/// where possible, so this only bounds the read-into-memory fallback; files
+/// that would need more are skipped.
+const MULTILINE_HEAP_LIMIT: usize = 64 * 1024 * 1024;
+
+/// With `auto_detect_encoding`, files are only decoded when the encoding
+/// guess is at least this confident; otherwise their raw bytes are searched.
+const MIN_ENCODING_CONFIDENCE: f32 = 0.7;
+
+/// Files over this size are searched as raw bytes even with
+/// `auto_detect_encoding`, since decoding holds the whole file in memory.
+const MAX_TRANSCODE_SIZE: u64 = 64 * 1024 * 1024;
+
 /// A single match within a file.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ContentMatch {
+    /// Same as `line_start`, kept for callers that predate multiline search.
     #[serde(rename = "lineNumber")]
     pub line_number: u64,
+    /// First and last line the match spans (equal outside multiline mode).
+    #[serde(rename = "lineStart", default)]
+    pub line_start: u64,
+    #[serde(rename = "lineEnd", default)]
+    pub line_end: u64,
+    /// Column of the match start within `line_start`.
     pub column: u64,
+    /// The matched line, or all the lines a multiline match spans, joined
+    /// with `\n`. `match_start`/`match_end` are byte offsets into it.
     #[serde(rename = "lineContent")]
     pub line_content: String,
     #[serde(rename = "matchStart")]
     pub match_start: usize,
     #[serde(rename = "matchEnd")]
     pub match_end: usize,
+    /// Lines preceding the match (empty unless context was requested).
+    #[serde(rename = "beforeContext", default)]
+    pub before_context: Vec<String>,
+    /// Lines following the match (empty unless context was requested).
+    #[serde(rename = "afterContext", default)]
+    pub after_context: Vec<String>,
 }
 
 /// Search result for a single file containing matches.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ContentSearchResult {
     pub path: String,
     #[serde(rename = "relativePath")]
@@ -55,11 +92,334 @@ pub struct ContentSearchEvent {
     pub total_matches: usize,
 }
 
-/// Registry for active content searches
-static CONTENT_SEARCHES: crate::task_registry::TaskRegistry = crate::task_registry::TaskRegistry::new();
+/// Matching files of one directory, for `group_by_directory`.
+#[derive(Debug, Clone, Serialize)]
+pub struct DirectoryGroup {
+    /// Directory relative to the search root; empty for the root itself.
+    pub directory: String,
+    pub results: Vec<ContentSearchResult>,
+    #[serde(rename = "matchCount")]
+    pub match_count: usize,
+}
+
+/// `ContentSearchEvent` with its results grouped by directory, emitted
+/// instead of it when `group_by_directory` is set.
+#[derive(Debug, Clone, Serialize)]
+pub struct GroupedContentSearchEvent {
+    #[serde(rename = "searchId")]
+    pub search_id: u64,
+    pub groups: Vec<DirectoryGroup>,
+    pub done: bool,
+    #[serde(rename = "filesSearched")]
+    pub files_searched: usize,
+    #[serde(rename = "totalMatches")]
+    pub total_matches: usize,
+}
+
+impl From<ContentSearchEvent> for GroupedContentSearchEvent {
+    fn from(event: ContentSearchEvent) -> Self {
+        GroupedContentSearchEvent {
+            search_id: event.search_id,
+            groups: group_by_directory(event.results),
+            done: event.done,
+            files_searched: event.files_searched,
+            total_matches: event.total_matches,
+        }
+    }
+}
+
+/// Group results by their parent directory, most matches first (ties by
+/// directory).
+fn group_by_directory(results: Vec<ContentSearchResult>) -> Vec<DirectoryGroup> {
+    let mut groups: Vec<DirectoryGroup> = Vec::new();
+    let mut index: HashMap<String, usize> = HashMap::new();
+    for result in results {
+        let directory = Path::new(&result.relative_path)
+            .parent()
+            .map(|p| p.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let i = *index.entry(directory.clone()).or_insert_with(|| {
+            groups.push(DirectoryGroup {
+                directory,
+                results: Vec::new(),
+                match_count: 0,
+            });
+            groups.len() - 1
+        });
+        groups[i].match_count += result.matches.len();
+        groups[i].results.push(result);
+    }
+    groups.sort_by(|a, b| b.match_count.cmp(&a.match_count).then_with(|| a.directory.cmp(&b.directory)));
+    groups
+}
+
+/// Optional file filters for content search.
+/// Every field defaults to searching all text files, so callers can omit it.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ContentSearchOptions {
+    /// Only search files with these extensions (case-insensitive, leading dot optional).
+    pub file_extensions: Vec<String>,
+    /// Skip files larger than this.
+    pub max_file_size_bytes: Option<u64>,
+    /// Skip files smaller than this.
+    pub min_file_size_bytes: Option<u64>,
+    /// Only search files matching at least one of these `.gitignore`-style
+    /// globs (relative to the root). Empty means all files.
+    pub include_patterns: Vec<String>,
+    /// Skip files matching any of these `.gitignore`-style globs.
+    pub exclude_patterns: Vec<String>,
+    /// Decode files that aren't UTF-8 (e.g. Latin-1, windows-1252, UTF-16)
+    /// before searching, when their encoding can be told from the first 8 KB.
+    pub auto_detect_encoding: bool,
+    /// Emit `GroupedContentSearchEvent`s, each batch's results grouped by
+    /// directory, instead of flat results.
+    pub group_by_directory: bool,
+}
+
+impl ContentSearchOptions {
+    /// Lowercase extensions and strip leading dots so matching is a plain compare.
+    fn normalized(mut self) -> Self {
+        self.file_extensions = self
+            .file_extensions
+            .iter()
+            .map(|e| e.trim().trim_start_matches('.').to_lowercase())
+            .filter(|e| !e.is_empty())
+            .collect();
+        self
+    }
+
+    fn matches_extension(&self, path: &std::path::Path) -> bool {
+        self.file_extensions.is_empty()
+            || path
+                .extension()
+                .and_then(|e| e.to_str())
+                .is_some_and(|e| self.file_extensions.iter().any(|x| x.eq_ignore_ascii_case(e)))
+    }
+
+    fn path_filter(&self) -> Result<PathFilter, AppError> {
+        Ok(PathFilter {
+            include: compile_patterns(&self.include_patterns)?,
+            exclude: compile_patterns(&self.exclude_patterns)?,
+        })
+    }
+
+    fn has_size_bounds(&self) -> bool {
+        self.max_file_size_bytes.is_some() || self.min_file_size_bytes.is_some()
+    }
+
+    fn matches_size(&self, size: u64) -> bool {
+        self.max_file_size_bytes.is_none_or(|max| size <= max)
+            && self.min_file_size_bytes.is_none_or(|min| size >= min)
+    }
+}
+
+/// Compiled include/exclude patterns of `ContentSearchOptions`.
+#[derive(Debug, Clone, Default)]
+struct PathFilter {
+    include: Option<GlobSet>,
+    exclude: Option<GlobSet>,
+}
+
+impl PathFilter {
+    /// Whether a file at `relative_path` (relative to the search root) is searched.
+    fn matches(&self, relative_path: &Path) -> bool {
+        self.include.as_ref().is_none_or(|set| set.is_match(relative_path))
+            && !self.exclude.as_ref().is_some_and(|set| set.is_match(relative_path))
+    }
+}
+
+/// Compile `.gitignore`-style patterns: a pattern without a slash matches at
+/// any depth, a leading slash (or one in the middle) anchors it to the root,
+/// and a pattern naming a directory covers everything under it. `*` doesn't
+/// cross `/`; `**` does. `None` when there are no patterns.
+fn compile_patterns(patterns: &[String]) -> Result<Option<GlobSet>, AppError> {
+    let mut builder = GlobSetBuilder::new();
+    let mut any = false;
+    for pattern in patterns.iter().map(|p| p.trim()).filter(|p| !p.is_empty()) {
+        let trimmed = pattern.trim_end_matches('/');
+        let anchored = trimmed.contains('/');
+        let trimmed = trimmed.trim_start_matches('/');
+        let base = if anchored {
+            trimmed.to_string()
+        } else {
+            format!("**/{}", trimmed)
+        };
+        for glob in [base.clone(), format!("{}/**", base)] {
+            let glob = GlobBuilder::new(&glob)
+                .literal_separator(true)
+                .build()
+                .map_err(|e| AppError::Other(format!("Invalid pattern {:?}: {}", pattern, e)))?;
+            builder.add(glob);
+        }
+        any = true;
+    }
+    if !any {
+        return Ok(None);
+    }
+    builder
+        .build()
+        .map(Some)
+        .map_err(|e| AppError::Other(format!("Invalid patterns: {}", e)))
+}
+
+/// Registry for running and recently finished content searches
+static CONTENT_SEARCHES: ContentSearches = ContentSearches::new();
+
+/// Number of finished searches kept around for replace previews and stats
+const MAX_STORED_SEARCHES: usize = 16;
+
+/// Live state of a content search, shared between its threads and
+/// `get_content_search_stats`.
+struct ContentSearchState {
+    cancelled: Arc<AtomicBool>,
+    files_searched: Arc<AtomicUsize>,
+    total_matches: Arc<AtomicUsize>,
+    /// While set, walker workers block on `resumed` before the next file.
+    paused: Arc<Mutex<bool>>,
+    resumed: Arc<Condvar>,
+    started: Instant,
+    /// Set to the total run time when the search finishes.
+    finished: OnceLock<Duration>,
+    /// What the search matched, set if it ran to completion. Replace needs
+    /// the pattern and the matched files' mtimes to detect later edits.
+    record: OnceLock<SearchRecord>,
+}
+
+impl ContentSearchState {
+    fn new(cancelled: Arc<AtomicBool>) -> Self {
+        ContentSearchState {
+            cancelled,
+            files_searched: Arc::new(AtomicUsize::new(0)),
+            total_matches: Arc::new(AtomicUsize::new(0)),
+            paused: Arc::new(Mutex::new(false)),
+            resumed: Arc::new(Condvar::new()),
+            started: Instant::now(),
+            finished: OnceLock::new(),
+            record: OnceLock::new(),
+        }
+    }
+}
+
+/// Content searches by ID: the running ones plus the last
+/// `MAX_STORED_SEARCHES` finished ones, oldest first.
+struct ContentSearches {
+    next_id: AtomicU64,
+    searches: Mutex<Vec<(u64, Arc<ContentSearchState>)>>,
+}
+
+impl ContentSearches {
+    const fn new() -> Self {
+        ContentSearches {
+            next_id: AtomicU64::new(1),
+            searches: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Register a new search that stops when `cancelled` is set, dropping
+    /// the oldest finished ones beyond `MAX_STORED_SEARCHES`.
+    fn start(&self, cancelled: Arc<AtomicBool>) -> (u64, Arc<ContentSearchState>) {
+        let search_id = self.next_id.fetch_add(1, Ordering::SeqCst);
+        let state = Arc::new(ContentSearchState::new(cancelled));
+
+        let mut searches = self.searches.lock().unwrap_or_else(|e| e.into_inner());
+        let mut finished = searches.iter().filter(|(_, s)| s.finished.get().is_some()).count();
+        searches.retain(|(_, s)| {
+            if finished > MAX_STORED_SEARCHES && s.finished.get().is_some() {
+                finished -= 1;
+                return false;
+            }
+            true
+        });
+        searches.push((search_id, state.clone()));
+        (search_id, state)
+    }
+
+    fn get(&self, search_id: u64) -> Option<Arc<ContentSearchState>> {
+        self.searches
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .iter()
+            .find(|(id, _)| *id == search_id)
+            .map(|(_, state)| state.clone())
+    }
+
+    /// Cancel a search by ID. No-op if it doesn't exist or already finished.
+    fn cancel(&self, search_id: u64) {
+        if let Some(state) = self.get(search_id) {
+            state.cancelled.store(true, Ordering::Relaxed);
+        }
+    }
+
+    /// Whether a search is still running.
+    #[cfg(test)]
+    fn is_active(&self, search_id: u64) -> bool {
+        self.get(search_id).is_some_and(|state| state.finished.get().is_none())
+    }
+}
+
+/// How often a paused worker rechecks for cancellation.
+const PAUSE_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Block while the search is paused. Cancellation doesn't touch the
+/// condvar, so the wait times out periodically to notice it.
+fn wait_while_paused(paused: &Mutex<bool>, resumed: &Condvar, cancelled: &AtomicBool) {
+    let mut is_paused = paused.lock().unwrap_or_else(|e| e.into_inner());
+    while *is_paused && !cancelled.load(Ordering::Relaxed) {
+        is_paused = resumed
+            .wait_timeout(is_paused, PAUSE_POLL_INTERVAL)
+            .unwrap_or_else(|e| e.into_inner())
+            .0;
+    }
+}
+
+/// Progress of a content search, from `get_content_search_stats`.
+#[derive(Debug, Clone, Serialize)]
+pub struct ContentSearchStats {
+    #[serde(rename = "filesSearched")]
+    pub files_searched: usize,
+    #[serde(rename = "totalMatches")]
+    pub total_matches: usize,
+    #[serde(rename = "elapsedMs")]
+    pub elapsed_ms: u64,
+    #[serde(rename = "isComplete")]
+    pub is_complete: bool,
+    #[serde(rename = "isCancelled")]
+    pub is_cancelled: bool,
+    #[serde(rename = "isPaused")]
+    pub is_paused: bool,
+}
+
+/// What a completed search matched, as needed to replace its matches later.
+#[derive(Debug, Clone)]
+struct SearchRecord {
+    /// Final regex pattern (already escaped in literal mode).
+    pattern: String,
+    case_sensitive: bool,
+    regex_mode: bool,
+    /// Replace works line by line, so multiline searches can't use it.
+    multiline: bool,
+    /// Matched files with their modification time (ms since epoch) at search time.
+    files: Vec<(PathBuf, u64)>,
+}
+
+/// A file that matched, plus its mtime, as sent from the walker threads.
+struct FileHit {
+    result: ContentSearchResult,
+    modified_ms: u64,
+}
+
+/// Maximum context lines on either side of a match
+const MAX_CONTEXT_LINES: usize = 10;
 
 /// Start a streaming content search using ripgrep.
 /// Returns search ID immediately, emits results via 'content-search-results' events.
+/// `context_lines` requests up to that many lines before and after each match.
+/// `whole_word` only matches the query between word boundaries.
+/// `options` restricts which files are searched by extension and size.
+/// `multiline` lets matches span lines, with `.` matching newlines (`(?s)`).
+/// With `options.group_by_directory` the events carry `groups` instead of
+/// `results`; each batch is grouped on its own.
 #[tauri::command]
 pub fn start_content_search(
     app: AppHandle,
@@ -68,6 +428,10 @@ pub fn start_content_search(
     case_sensitive: bool,
     regex_mode: bool,
     max_results: usize,
+    context_lines: Option<usize>,
+    whole_word: Option<bool>,
+    options: Option<ContentSearchOptions>,
+    multiline: Option<bool>,
 ) -> Result<u64, AppError> {
     let root_path = PathBuf::from(&root);
 
@@ -85,75 +449,130 @@ pub fn start_content_search(
         return Err(AppError::Other("Search query cannot be empty".into()));
     }
 
-    let (search_id, cancelled) = CONTENT_SEARCHES.start();
-    let max_results = max_results.min(5000).max(1);
+    let filters = options.unwrap_or_default().normalized();
+    let grouped = filters.group_by_directory;
+    let params = ContentQuery {
+        query,
+        case_sensitive,
+        regex_mode,
+        whole_word: whole_word.unwrap_or(false),
+        multiline: multiline.unwrap_or(false),
+        max_results: max_results.clamp(1, 5000),
+        context_lines: context_lines.unwrap_or(0).min(MAX_CONTEXT_LINES),
+        paths: filters.path_filter()?,
+        filters,
+    };
 
-    // Spawn search in background thread
-    std::thread::spawn(move || {
-        let result = perform_content_search(
-            &app,
-            search_id,
-            &query,
-            &root_path,
-            case_sensitive,
-            regex_mode,
-            max_results,
-            &cancelled,
-        );
+    Ok(spawn_content_search(params, root_path, move |event| {
+        let _ = if grouped {
+            app.emit("content-search-results", GroupedContentSearchEvent::from(event))
+        } else {
+            app.emit("content-search-results", event)
+        };
+    }))
+}
 
-        CONTENT_SEARCHES.cleanup(search_id);
-
-        if let Err(e) = result {
-            // Emit error event
-            let _ = app.emit(
-                "content-search-results",
-                ContentSearchEvent {
-                    search_id,
-                    results: vec![],
-                    done: true,
-                    files_searched: 0,
-                    total_matches: 0,
-                },
-            );
-            #[cfg(debug_assertions)]
-            eprintln!("Content search error: {}", e);
-        }
-    });
+/// Parameters of a single content search, after validation.
+struct ContentQuery {
+    query: String,
+    case_sensitive: bool,
+    regex_mode: bool,
+    whole_word: bool,
+    multiline: bool,
+    max_results: usize,
+    context_lines: usize,
+    filters: ContentSearchOptions,
+    paths: PathFilter,
+}
+
+/// Register a search and run it on a background thread, passing each event to `emit`.
+fn spawn_content_search<E>(params: ContentQuery, root_path: PathBuf, emit: E) -> u64
+where
+    E: Fn(ContentSearchEvent) + Send + 'static,
+{
+    let (search_id, state) = CONTENT_SEARCHES.start(Arc::new(AtomicBool::new(false)));
+    std::thread::spawn(move || run_content_search(search_id, &params, &root_path, &state, &emit));
+    search_id
+}
 
-    Ok(search_id)
+/// Run a content search with default options on the calling thread, as
+/// part of a search that combines it with others. It stops when `cancelled`
+/// is set rather than through `cancel_content_search`.
+pub(crate) fn run_linked_content_search(
+    query: String,
+    root_path: &Path,
+    regex_mode: bool,
+    max_results: usize,
+    cancelled: Arc<AtomicBool>,
+    emit: &dyn Fn(ContentSearchEvent),
+) -> Result<(), AppError> {
+    let filters = ContentSearchOptions::default().normalized();
+    let params = ContentQuery {
+        query,
+        case_sensitive: false,
+        regex_mode,
+        whole_word: false,
+        multiline: false,
+        max_results: max_results.clamp(1, 5000),
+        context_lines: 0,
+        paths: filters.path_filter()?,
+        filters,
+    };
+    // The ID only keys this search's stats and replace record
+    let (search_id, state) = CONTENT_SEARCHES.start(cancelled);
+    run_content_search(search_id, &params, root_path, &state, emit);
+    Ok(())
+}
+
+fn run_content_search(
+    search_id: u64,
+    params: &ContentQuery,
+    root_path: &Path,
+    state: &ContentSearchState,
+    emit: &dyn Fn(ContentSearchEvent),
+) {
+    let result = perform_content_search(search_id, params, root_path, state, emit);
+
+    let _ = state.finished.set(state.started.elapsed());
+
+    if let Err(e) = result {
+        // Emit error event
+        emit(ContentSearchEvent {
+            search_id,
+            results: vec![],
+            done: true,
+            files_searched: 0,
+            total_matches: 0,
+        });
+        #[cfg(debug_assertions)]
+        eprintln!("Content search error: {}", e);
+    }
 }
 
 fn perform_content_search(
-    app: &AppHandle,
     search_id: u64,
-    query: &str,
+    params: &ContentQuery,
     root_path: &std::path::Path,
-    case_sensitive: bool,
-    regex_mode: bool,
-    max_results: usize,
-    cancelled: &Arc<AtomicBool>,
+    state: &ContentSearchState,
+    emit: &dyn Fn(ContentSearchEvent),
 ) -> Result<(), AppError> {
+    let cancelled = &state.cancelled;
+    let ContentQuery { case_sensitive, regex_mode, whole_word, multiline, max_results, context_lines, .. } =
+        *params;
+
     // Build the regex matcher
-    let pattern = if regex_mode {
-        query.to_string()
-    } else {
-        regex::escape(query)
-    };
+    let pattern = build_pattern(&params.query, regex_mode, whole_word);
 
-    let matcher = RegexMatcherBuilder::new()
-        .case_insensitive(!case_sensitive)
-        .line_terminator(Some(b'\n'))
-        .build(&pattern)
-        .map_err(|e| AppError::Other(format!("Invalid search pattern: {}", e)))?;
+    let matcher = build_matcher(&pattern, case_sensitive, multiline)?;
 
     let matcher = Arc::new(matcher);
 
-    // Shared counters for parallel access
-    let files_searched = Arc::new(AtomicUsize::new(0));
-    let total_matches = Arc::new(AtomicUsize::new(0));
+    // Shared counters for parallel access, also read by get_content_search_stats
+    let files_searched = state.files_searched.clone();
+    let total_matches = state.total_matches.clone();
 
     // Channel for collecting results from parallel workers
-    let (tx, rx) = mpsc::channel::<ContentSearchResult>();
+    let (tx, rx) = mpsc::channel::<FileHit>();
 
     // Use parallel walker for multi-core file processing
     let walker = WalkBuilder::new(root_path)
@@ -165,13 +584,17 @@ fn perform_content_search(
         .build_parallel();
 
     let root_path = root_path.to_path_buf();
+    let filters = Arc::new(params.filters.clone());
+    let paths = Arc::new(params.paths.clone());
 
     // Spawn parallel workers
     let cancelled_clone = cancelled.clone();
     let files_searched_clone = files_searched.clone();
     let total_matches_clone = total_matches.clone();
+    let paused_clone = state.paused.clone();
+    let resumed_clone = state.resumed.clone();
 
-    std::thread::spawn(move || {
+    let walker_thread = std::thread::spawn(move || {
         walker.run(|| {
             let matcher = matcher.clone();
             let cancelled = cancelled_clone.clone();
@@ -179,15 +602,18 @@ fn perform_content_search(
             let root_path = root_path.clone();
             let files_searched = files_searched_clone.clone();
             let total_matches = total_matches_clone.clone();
+            let filters = filters.clone();
+            let paths = paths.clone();
+            let paused = paused_clone.clone();
+            let resumed = resumed_clone.clone();
 
             // Create searcher once per worker thread: avoids buffer re-allocation per file.
             // mmap avoids read syscalls; binary_detection::quit stops on first NUL byte.
-            let mut searcher = SearcherBuilder::new()
-                .binary_detection(BinaryDetection::quit(b'\x00'))
-                .memory_map(unsafe { MmapChoice::auto() })
-                .build();
+            let mut searcher = build_searcher(context_lines, multiline);
 
             Box::new(move |entry| {
+                wait_while_paused(&paused, &resumed, &cancelled);
+
                 // Check for cancellation
                 if cancelled.load(Ordering::Relaxed) {
                     return WalkState::Quit;
@@ -210,7 +636,23 @@ fn perform_content_search(
                     return WalkState::Continue;
                 }
 
-                // Fast pre-filter: skip known-binary extensions before opening the file
+                if !paths.matches(path.strip_prefix(&root_path).unwrap_or(path)) {
+                    return WalkState::Continue;
+                }
+
+                if !filters.matches_extension(path) {
+                    return WalkState::Continue;
+                }
+
+                // Only stat when a size bound is set
+                if filters.has_size_bounds()
+                    && !entry.metadata().is_ok_and(|m| filters.matches_size(m.len()))
+                {
+                    return WalkState::Continue;
+                }
+
+                // Skip files whose magic bytes mark them as binary; last, as
+                // it opens the file
                 if is_binary_file(path) {
                     return WalkState::Continue;
                 }
@@ -218,58 +660,13 @@ fn perform_content_search(
                 files_searched.fetch_add(1, Ordering::Relaxed);
 
                 // Search the file with per-file match limit
-                let mut file_matches: Vec<ContentMatch> = Vec::new();
-
-                let _ = searcher.search_path(
-                    matcher.as_ref(),
-                    path,
-                    UTF8(|line_num, line| {
-                        // Check per-file limit
-                        if file_matches.len() >= MAX_MATCHES_PER_FILE {
-                            return Ok(false);
-                        }
-
-                        let mut byte_offset = 0;
-                        while let Ok(Some(m)) = matcher.find(&line.as_bytes()[byte_offset..]) {
-                            if file_matches.len() >= MAX_MATCHES_PER_FILE {
-                                return Ok(false);
-                            }
-
-                            let match_start = byte_offset + m.start();
-                            let match_end = byte_offset + m.end();
-
-                            // Truncate long lines before IPC serialization
-                            let trimmed = line.trim_end();
-                            let (line_content, clamped_start, clamped_end) =
-                                if trimmed.len() > MAX_LINE_LENGTH {
-                                    let end = trimmed.floor_char_boundary(MAX_LINE_LENGTH);
-                                    (
-                                        format!("{}...", &trimmed[..end]),
-                                        match_start.min(end),
-                                        match_end.min(end),
-                                    )
-                                } else {
-                                    (trimmed.to_string(), match_start, match_end)
-                                };
-
-                            file_matches.push(ContentMatch {
-                                line_number: line_num,
-                                column: (clamped_start + 1) as u64,
-                                line_content,
-                                match_start: clamped_start,
-                                match_end: clamped_end,
-                            });
-
-                            byte_offset = match_end;
-                            if byte_offset >= line.len() {
-                                break;
-                            }
-                        }
-                        Ok(true)
-                    }),
-                );
-
-                if !file_matches.is_empty() {
+                let transcoded = filters.auto_detect_encoding.then(|| transcoded_text(path)).flatten();
+                let file_matches = match transcoded {
+                    Some(text) => search_text(&mut searcher, matcher.as_ref(), &text),
+                    None => search_file(&mut searcher, matcher.as_ref(), path),
+                };
+
+                if !file_matches.is_empty() && !cancelled.load(Ordering::Relaxed) {
                     let relative_path = path
                         .strip_prefix(&root_path)
                         .map(|p| p.to_string_lossy().to_string())
@@ -277,10 +674,14 @@ fn perform_content_search(
 
                     total_matches.fetch_add(file_matches.len(), Ordering::Relaxed);
 
-                    let _ = tx.send(ContentSearchResult {
-                        path: path.to_string_lossy().to_string(),
-                        relative_path,
-                        matches: file_matches,
+                    let modified_ms = entry.metadata().map(|m| mtime_ms(&m)).unwrap_or(0);
+                    let _ = tx.send(FileHit {
+                        result: ContentSearchResult {
+                            path: path.to_string_lossy().to_string(),
+                            relative_path,
+                            matches: file_matches,
+                        },
+                        modified_ms,
                     });
                 }
 
@@ -296,68 +697,282 @@ fn perform_content_search(
     let mut batch_interval = std::time::Duration::from_millis(16);
     let steady_interval = std::time::Duration::from_millis(100);
     let mut last_emit = std::time::Instant::now();
+    let mut matched_files: Vec<(PathBuf, u64)> = Vec::new();
 
     loop {
+        if cancelled.load(Ordering::Relaxed) {
+            break;
+        }
+
         let should_flush = match rx.recv_timeout(batch_interval) {
-            Ok(result) => {
-                pending_results.push(result);
+            Ok(hit) => {
+                matched_files.push((PathBuf::from(&hit.result.path), hit.modified_ms));
+                pending_results.push(hit.result);
                 last_emit.elapsed() >= batch_interval
             }
             Err(mpsc::RecvTimeoutError::Timeout) => true,
             Err(mpsc::RecvTimeoutError::Disconnected) => break,
         };
 
-        if should_flush && !pending_results.is_empty() {
-            let _ = app.emit(
-                "content-search-results",
-                ContentSearchEvent {
-                    search_id,
-                    results: std::mem::take(&mut pending_results),
-                    done: false,
-                    files_searched: files_searched.load(Ordering::Relaxed),
-                    total_matches: total_matches.load(Ordering::Relaxed),
-                },
-            );
+        if should_flush && !pending_results.is_empty() && !cancelled.load(Ordering::Relaxed) {
+            emit(ContentSearchEvent {
+                search_id,
+                results: std::mem::take(&mut pending_results),
+                done: false,
+                files_searched: files_searched.load(Ordering::Relaxed),
+                total_matches: total_matches.load(Ordering::Relaxed),
+            });
             last_emit = std::time::Instant::now();
             batch_interval = steady_interval;
         }
-
-        if cancelled.load(Ordering::Relaxed) {
-            break;
-        }
     }
 
+    // Close the channel so late worker sends are discarded; workers quit on
+    // their next entry once cancelled. Join so the walker is fully gone
+    // before the search is marked finished.
+    drop(rx);
+    let _ = walker_thread.join();
+
     // Emit final results
     if !cancelled.load(Ordering::Relaxed) {
-        let _ = app.emit(
-            "content-search-results",
-            ContentSearchEvent {
-                search_id,
-                results: pending_results,
-                done: true,
-                files_searched: files_searched.load(Ordering::Relaxed),
-                total_matches: total_matches.load(Ordering::Relaxed),
-            },
-        );
+        let _ = state.record.set(SearchRecord {
+            pattern,
+            case_sensitive,
+            regex_mode,
+            multiline,
+            files: matched_files,
+        });
+        emit(ContentSearchEvent {
+            search_id,
+            results: pending_results,
+            done: true,
+            files_searched: files_searched.load(Ordering::Relaxed),
+            total_matches: total_matches.load(Ordering::Relaxed),
+        });
     }
 
     Ok(())
 }
 
-/// Simple heuristic to detect binary files by extension.
-fn is_binary_file(path: &std::path::Path) -> bool {
-    let binary_extensions = [
-        "exe", "dll", "so", "dylib", "bin", "o", "a", "lib", "obj", "class", "jar", "war", "ear",
-        "zip", "tar", "gz", "bz2", "xz", "7z", "rar", "iso", "dmg", "img", "pdf", "doc", "docx",
-        "xls", "xlsx", "ppt", "pptx", "odt", "ods", "odp", "png", "jpg", "jpeg", "gif", "bmp",
-        "ico", "svg", "webp", "mp3", "mp4", "avi", "mkv", "mov", "wmv", "flv", "wav", "flac",
-        "ogg", "woff", "woff2", "ttf", "otf", "eot", "pyc", "pyo", "wasm", "node",
-    ];
+/// Line-oriented unless `multiline`, where matches may span lines, `.`
+/// matches newlines and `^`/`$` anchor at line boundaries.
+fn build_matcher(pattern: &str, case_sensitive: bool, multiline: bool) -> Result<grep_regex::RegexMatcher, AppError> {
+    let mut builder = RegexMatcherBuilder::new();
+    builder.case_insensitive(!case_sensitive);
+    if multiline {
+        builder.multi_line(true).dot_matches_new_line(true);
+    } else {
+        builder.line_terminator(Some(b'\n'));
+    }
+    builder
+        .build(pattern)
+        .map_err(|e| AppError::Other(format!("Invalid search pattern: {}", e)))
+}
+
+/// A multiline searcher sees the whole file at once: memory-mapped where
+/// possible, otherwise read into a buffer of at most `MULTILINE_HEAP_LIMIT`.
+fn build_searcher(context_lines: usize, multiline: bool) -> Searcher {
+    let mut builder = SearcherBuilder::new();
+    builder
+        .binary_detection(BinaryDetection::quit(b'\x00'))
+        .memory_map(unsafe { MmapChoice::auto() })
+        .before_context(context_lines)
+        .after_context(context_lines);
+    if multiline {
+        builder.multi_line(true).heap_limit(Some(MULTILINE_HEAP_LIMIT));
+    }
+    builder.build()
+}
+
+/// Turn the user's query into a regex pattern.
+/// Whole-word mode anchors it with `\b`; a regex query is grouped first so
+/// alternations like `a|b` stay bounded on both sides.
+fn build_pattern(query: &str, regex_mode: bool, whole_word: bool) -> String {
+    match (regex_mode, whole_word) {
+        (true, true) => format!(r"(?:\b)(?:{})(?:\b)", query),
+        (true, false) => query.to_string(),
+        (false, true) => format!(r"\b{}\b", regex::escape(query)),
+        (false, false) => regex::escape(query),
+    }
+}
 
-    path.extension()
-        .and_then(|e| e.to_str())
-        .map(|e| binary_extensions.contains(&e.to_lowercase().as_str()))
-        .unwrap_or(false)
+/// Modification time in milliseconds since the epoch (0 if unavailable).
+fn mtime_ms(meta: &fs::Metadata) -> u64 {
+    meta.modified()
+        .ok()
+        .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+/// Search a single file, collecting up to `MAX_MATCHES_PER_FILE` matches.
+fn search_file<M: Matcher>(
+    searcher: &mut Searcher,
+    matcher: &M,
+    path: &std::path::Path,
+) -> Vec<ContentMatch> {
+    let mut sink = MatchSink {
+        matcher,
+        matches: Vec::new(),
+        pending_before: Vec::new(),
+    };
+    let _ = searcher.search_path(matcher, path, &mut sink);
+    sink.matches
+}
+
+/// Search already-decoded file contents, like `search_file`.
+fn search_text<M: Matcher>(searcher: &mut Searcher, matcher: &M, text: &str) -> Vec<ContentMatch> {
+    let mut sink = MatchSink {
+        matcher,
+        matches: Vec::new(),
+        pending_before: Vec::new(),
+    };
+    let _ = searcher.search_slice(matcher, text.as_bytes(), &mut sink);
+    sink.matches
+}
+
+/// The contents of `path` decoded to UTF-8, when its first 8 KB show it
+/// isn't UTF-8 and the encoding guess scores at least
+/// `MIN_ENCODING_CONFIDENCE`. `None` means search the raw bytes.
+fn transcoded_text(path: &Path) -> Option<String> {
+    use std::io::Read;
+
+    let mut file = fs::File::open(path).ok()?;
+    let len = file.metadata().ok()?.len();
+    if len > MAX_TRANSCODE_SIZE {
+        return None;
+    }
+    let mut bytes = Vec::new();
+    (&mut file).take(ENCODING_SAMPLE_SIZE).read_to_end(&mut bytes).ok()?;
+    let (encoding, bom_len, confidence) = guess_encoding(&bytes, len > bytes.len() as u64);
+    if encoding == encoding_rs::UTF_8 || confidence < MIN_ENCODING_CONFIDENCE {
+        return None;
+    }
+    file.read_to_end(&mut bytes).ok()?;
+    let (text, _) = encoding.decode_without_bom_handling(&bytes[bom_len..]);
+    Some(text.into_owned())
+}
+
+/// Truncate long lines before IPC serialization.
+/// Returns the (possibly truncated) line and the byte length kept.
+fn truncate_line(line: &str) -> (String, usize) {
+    let trimmed = line.trim_end();
+    if trimmed.len() > MAX_LINE_LENGTH {
+        let end = trimmed.floor_char_boundary(MAX_LINE_LENGTH);
+        (format!("{}...", &trimmed[..end]), end)
+    } else {
+        (trimmed.to_string(), trimmed.len())
+    }
+}
+
+/// The whole lines a match touches within a block of lines.
+struct LineSpan {
+    /// Byte range of those lines in the block, without the final newline.
+    start: usize,
+    end: usize,
+    /// Line offsets from the block's first line.
+    first_line: u64,
+    last_line: u64,
+}
+
+/// Lines of `block` covered by the match `match_start..match_end`. A match
+/// ending on a newline ends on that newline's line.
+fn lines_spanned(block: &str, match_start: usize, match_end: usize) -> LineSpan {
+    let newlines_before =
+        |offset: usize| block.as_bytes()[..offset].iter().filter(|&&b| b == b'\n').count() as u64;
+    let last_byte = match_end.max(match_start + 1).min(block.len()).saturating_sub(1).max(match_start);
+    let start = block[..match_start].rfind('\n').map_or(0, |i| i + 1);
+    let end = block[last_byte..].find('\n').map_or(block.len(), |i| last_byte + i);
+    LineSpan {
+        start,
+        end: end.max(start),
+        first_line: newlines_before(match_start),
+        last_line: newlines_before(last_byte.min(block.len())),
+    }
+}
+
+/// Sink that turns searcher output into `ContentMatch`es.
+///
+/// The searcher reports each line once, either as a match or as context, so
+/// context never overlaps between neighbouring matches: when two matches are
+/// fewer than `2 * context_lines` apart, the lines between them are split
+/// into the first match's after-context and the second's before-context.
+struct MatchSink<'m, M: Matcher> {
+    matcher: &'m M,
+    matches: Vec<ContentMatch>,
+    /// Before-context lines waiting for the next match.
+    pending_before: Vec<String>,
+}
+
+impl<M: Matcher> Sink for MatchSink<'_, M> {
+    type Error = std::io::Error;
+
+    fn matched(&mut self, _searcher: &Searcher, mat: &SinkMatch<'_>) -> Result<bool, Self::Error> {
+        // One line, or in multiline mode every line the match(es) touch
+        let line = String::from_utf8_lossy(mat.bytes());
+        let line_num = mat.line_number().unwrap_or(0);
+        let first_new = self.matches.len();
+
+        let mut byte_offset = 0;
+        while let Ok(Some(m)) = self.matcher.find(&line.as_bytes()[byte_offset..]) {
+            if self.matches.len() >= MAX_MATCHES_PER_FILE {
+                break;
+            }
+
+            let match_start = byte_offset + m.start();
+            let match_end = byte_offset + m.end();
+            let span = lines_spanned(&line, match_start, match_end);
+            let (line_content, kept) = truncate_line(&line[span.start..span.end]);
+            let clamped_start = (match_start - span.start).min(kept);
+            let clamped_end = (match_end - span.start).min(kept);
+            let line_start = line_num + span.first_line;
+
+            self.matches.push(ContentMatch {
+                line_number: line_start,
+                line_start,
+                line_end: line_num + span.last_line,
+                column: (clamped_start + 1) as u64,
+                line_content,
+                match_start: clamped_start,
+                match_end: clamped_end,
+                before_context: Vec::new(),
+                after_context: Vec::new(),
+            });
+
+            // Guard against empty matches looping forever
+            byte_offset = match_end.max(byte_offset + 1);
+            if byte_offset >= line.len() {
+                break;
+            }
+        }
+
+        if let Some(first) = self.matches.get_mut(first_new) {
+            first.before_context = std::mem::take(&mut self.pending_before);
+        } else {
+            self.pending_before.clear();
+        }
+
+        Ok(self.matches.len() < MAX_MATCHES_PER_FILE)
+    }
+
+    fn context(&mut self, _searcher: &Searcher, ctx: &SinkContext<'_>) -> Result<bool, Self::Error> {
+        let (line, _) = truncate_line(&String::from_utf8_lossy(ctx.bytes()));
+        match ctx.kind() {
+            SinkContextKind::Before => self.pending_before.push(line),
+            SinkContextKind::After => {
+                if let Some(last) = self.matches.last_mut() {
+                    last.after_context.push(line);
+                }
+            }
+            SinkContextKind::Other => {}
+        }
+        Ok(true)
+    }
+}
+
+/// Detect binary files from their leading bytes, whatever the extension.
+fn is_binary_file(path: &std::path::Path) -> bool {
+    crate::files::file_type::is_binary_content(path)
 }
 
 /// Cancel an active content search.
@@ -367,36 +982,426 @@ pub fn cancel_content_search(search_id: u64) -> Result<(), AppError> {
     Ok(())
 }
 
+/// Save content search results to `output_path`. JSON keeps the per-file
+/// grouping; CSV and TSV have one row per match with the columns
+/// `path,line_number,column,line_content,match_start,match_end`. Returns the
+/// number of matches written.
+#[tauri::command]
+pub fn export_content_search_results(
+    results: Vec<ContentSearchResult>,
+    output_path: String,
+    format: ExportFormat,
+) -> Result<usize, AppError> {
+    let rows: Vec<Vec<String>> = results
+        .iter()
+        .flat_map(|r| {
+            r.matches.iter().map(|m| {
+                vec![
+                    r.path.clone(),
+                    m.line_number.to_string(),
+                    m.column.to_string(),
+                    m.line_content.clone(),
+                    m.match_start.to_string(),
+                    m.match_end.to_string(),
+                ]
+            })
+        })
+        .collect();
+    write_export(
+        &output_path,
+        format,
+        &results,
+        &["path", "line_number", "column", "line_content", "match_start", "match_end"],
+        &rows,
+    )
+}
+
+/// Progress of a running or recently finished content search, for a
+/// progress bar that doesn't have to count events.
+#[tauri::command]
+pub fn get_content_search_stats(search_id: u64) -> Result<ContentSearchStats, AppError> {
+    let state = search_state(search_id)?;
+    let is_paused = *state.paused.lock().unwrap_or_else(|e| e.into_inner());
+    let elapsed = state.finished.get().copied().unwrap_or_else(|| state.started.elapsed());
+    Ok(ContentSearchStats {
+        files_searched: state.files_searched.load(Ordering::Relaxed),
+        total_matches: state.total_matches.load(Ordering::Relaxed),
+        elapsed_ms: elapsed.as_millis() as u64,
+        is_complete: state.finished.get().is_some(),
+        is_cancelled: state.cancelled.load(Ordering::Relaxed),
+        is_paused,
+    })
+}
+
+/// Pause a running content search: workers stop before their next file
+/// until `resume_search`. Files already being searched finish first.
+#[tauri::command]
+pub fn pause_search(search_id: u64) -> Result<(), AppError> {
+    set_paused(search_id, true)
+}
+
+/// Continue a search paused with `pause_search`.
+#[tauri::command]
+pub fn resume_search(search_id: u64) -> Result<(), AppError> {
+    set_paused(search_id, false)
+}
+
+fn set_paused(search_id: u64, paused: bool) -> Result<(), AppError> {
+    let state = search_state(search_id)?;
+    *state.paused.lock().unwrap_or_else(|e| e.into_inner()) = paused;
+    if !paused {
+        state.resumed.notify_all();
+    }
+    log::debug!("Content search {} paused={}", search_id, paused);
+    Ok(())
+}
+
+fn search_state(search_id: u64) -> Result<Arc<ContentSearchState>, AppError> {
+    CONTENT_SEARCHES
+        .get(search_id)
+        .ok_or_else(|| AppError::NotFound(format!("Content search {}", search_id)))
+}
+
+// ── Replace ─────────────────────────────────────────────────────────────────
+
+/// One line as it is now and as it would be after replacement.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LineChange {
+    #[serde(rename = "lineNumber")]
+    pub line_number: u64,
+    #[serde(rename = "oldLine")]
+    pub old_line: String,
+    #[serde(rename = "newLine")]
+    pub new_line: String,
+}
+
+/// Proposed replacement for one file. Sent back unchanged to `apply_replace`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReplacePreview {
+    pub path: String,
+    /// File mtime (ms since epoch) recorded by the search.
+    #[serde(rename = "modifiedMs")]
+    pub modified_ms: u64,
+    pub changes: Vec<LineChange>,
+    /// Why the file can't be replaced in (e.g. it isn't UTF-8 text); `changes`
+    /// is then empty and `apply_replace` reports it as skipped.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub skipped: Option<String>,
+}
+
+/// A matched file that `apply_replace` left alone.
+#[derive(Debug, Clone, Serialize)]
+pub struct SkippedReplace {
+    pub path: String,
+    pub reason: String,
+}
+
+/// Outcome of `apply_replace`.
+#[derive(Debug, Clone, Serialize)]
+pub struct ReplaceResult {
+    #[serde(rename = "filesChanged")]
+    pub files_changed: usize,
+    #[serde(rename = "linesChanged")]
+    pub lines_changed: usize,
+    /// Paths of `.bak` copies written, if backups were requested.
+    pub backups: Vec<String>,
+    pub skipped: Vec<SkippedReplace>,
+}
+
+/// Preview replacing every match of a completed search with `replacement`.
+/// In regex mode `replacement` may use capture groups (`$1`, `${name}`);
+/// otherwise it is inserted literally. Fails if any matched file changed
+/// since the search, or if the search was multiline.
+#[tauri::command]
+pub fn preview_replace(search_id: u64, replacement: String) -> Result<Vec<ReplacePreview>, AppError> {
+    let record = CONTENT_SEARCHES
+        .get(search_id)
+        .and_then(|state| state.record.get().cloned())
+        .ok_or_else(|| AppError::NotFound(format!("No completed content search with id {}", search_id)))?;
+    if record.multiline {
+        return Err(AppError::Other("Replace is not supported for multiline searches".into()));
+    }
+
+    let re = regex::RegexBuilder::new(&record.pattern)
+        .case_insensitive(!record.case_sensitive)
+        .build()
+        .map_err(|e| AppError::Other(format!("Invalid search pattern: {}", e)))?;
+
+    let stale: Vec<String> = record
+        .files
+        .iter()
+        .filter(|(path, modified_ms)| !is_unchanged(path, *modified_ms))
+        .map(|(path, _)| path.to_string_lossy().to_string())
+        .collect();
+    if !stale.is_empty() {
+        return Err(stale_error(&stale));
+    }
+
+    let mut previews = Vec::new();
+    for (path, modified_ms) in &record.files {
+        let content = match fs::read_to_string(path) {
+            Ok(c) => c,
+            Err(e) => {
+                log::debug!("preview_replace: skipping {}: {}", path.display(), e);
+                let reason = match e.kind() {
+                    std::io::ErrorKind::InvalidData => "Not UTF-8 text".to_string(),
+                    _ => e.to_string(),
+                };
+                previews.push(ReplacePreview {
+                    path: path.to_string_lossy().to_string(),
+                    modified_ms: *modified_ms,
+                    changes: Vec::new(),
+                    skipped: Some(reason),
+                });
+                continue;
+            }
+        };
+
+        let mut changes = Vec::new();
+        for (idx, raw) in content.split_inclusive('\n').enumerate() {
+            let line = strip_line_ending(raw);
+            if !re.is_match(line) {
+                continue;
+            }
+            let new_line = if record.regex_mode {
+                re.replace_all(line, replacement.as_str())
+            } else {
+                re.replace_all(line, regex::NoExpand(&replacement))
+            };
+            if new_line != line {
+                changes.push(LineChange {
+                    line_number: idx as u64 + 1,
+                    old_line: line.to_string(),
+                    new_line: new_line.into_owned(),
+                });
+            }
+        }
+
+        if !changes.is_empty() {
+            previews.push(ReplacePreview {
+                path: path.to_string_lossy().to_string(),
+                modified_ms: *modified_ms,
+                changes,
+                skipped: None,
+            });
+        }
+    }
+
+    Ok(previews)
+}
+
+/// Apply previews from `preview_replace`, all files or none. Every file is
+/// validated first (mtime and line contents); if any is stale nothing is
+/// written. The new contents are then staged in temp files and renamed over
+/// the originals; if a rename fails, the files already replaced are
+/// restored. Skipped previews are passed through to `skipped`.
+#[tauri::command]
+pub fn apply_replace(previews: Vec<ReplacePreview>, create_backups: bool) -> Result<ReplaceResult, AppError> {
+    // (path, original content, new content)
+    let mut updated: Vec<(PathBuf, String, String)> = Vec::with_capacity(previews.len());
+    let mut skipped = Vec::new();
+    let mut stale = Vec::new();
+
+    for preview in &previews {
+        if let Some(reason) = &preview.skipped {
+            skipped.push(SkippedReplace { path: preview.path.clone(), reason: reason.clone() });
+            continue;
+        }
+        let path = PathBuf::from(&preview.path);
+        if !is_unchanged(&path, preview.modified_ms) {
+            stale.push(preview.path.clone());
+            continue;
+        }
+        let original = fs::read_to_string(&path)?;
+        match apply_changes(&original, &preview.changes) {
+            Some(content) => updated.push((path, original, content)),
+            None => stale.push(preview.path.clone()),
+        }
+    }
+
+    if !stale.is_empty() {
+        return Err(stale_error(&stale));
+    }
+
+    // Temp files not yet renamed are deleted when dropped, on any early return
+    let staged = updated
+        .iter()
+        .map(|(path, _, content)| stage_temp(path, content))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut backups = Vec::new();
+    if create_backups {
+        for (path, _, _) in &updated {
+            let backup = write_backup(path)?;
+            backups.push(backup.to_string_lossy().to_string());
+        }
+    }
+
+    for (i, tmp) in staged.into_iter().enumerate() {
+        let path = &updated[i].0;
+        if let Err(e) = tmp.persist(path) {
+            for (path, original, _) in &updated[..i] {
+                if let Err(e) = write_atomic(path, original) {
+                    log::error!("apply_replace: failed to restore {}: {}", path.display(), e);
+                }
+            }
+            return Err(AppError::Other(format!(
+                "Failed to write {}: {}; no files were changed",
+                path.display(),
+                e.error
+            )));
+        }
+    }
+
+    log::info!("apply_replace: {} files updated", updated.len());
+
+    Ok(ReplaceResult {
+        files_changed: updated.len(),
+        lines_changed: previews.iter().map(|p| p.changes.len()).sum(),
+        backups,
+        skipped,
+    })
+}
+
+/// Whether the file still has the mtime recorded at search time.
+fn is_unchanged(path: &Path, modified_ms: u64) -> bool {
+    fs::metadata(path).is_ok_and(|m| mtime_ms(&m) == modified_ms)
+}
+
+fn stale_error(paths: &[String]) -> AppError {
+    AppError::Other(format!("Files changed since search: {}", paths.join(", ")))
+}
+
+fn strip_line_ending(line: &str) -> &str {
+    line.trim_end_matches(['\n', '\r'])
+}
+
+/// Rewrite `content` with `changes`, keeping each line's original ending.
+/// Returns None if a changed line no longer holds the expected text.
+fn apply_changes(content: &str, changes: &[LineChange]) -> Option<String> {
+    let mut lines: Vec<String> = content.split_inclusive('\n').map(String::from).collect();
+    for change in changes {
+        let line = lines.get_mut((change.line_number as usize).checked_sub(1)?)?;
+        let body = strip_line_ending(line);
+        if body != change.old_line {
+            return None;
+        }
+        let ending = line[body.len()..].to_string();
+        *line = format!("{}{}", change.new_line, ending);
+    }
+    Some(lines.concat())
+}
+
+/// Copy `path` to `name.bak` beside it, or to `name.1.bak`, `name.2.bak`...
+/// when that is taken. Existing files are never overwritten.
+fn write_backup(path: &Path) -> Result<PathBuf, AppError> {
+    let name = path.file_name().unwrap_or_default();
+    let mut n = 0u32;
+    loop {
+        let mut backup_name = name.to_os_string();
+        if n > 0 {
+            backup_name.push(format!(".{}", n));
+        }
+        backup_name.push(".bak");
+        let backup = path.with_file_name(backup_name);
+        match fs::OpenOptions::new().write(true).create_new(true).open(&backup) {
+            Ok(mut file) => {
+                let copied = fs::File::open(path).and_then(|mut original| std::io::copy(&mut original, &mut file));
+                if let Err(e) = copied {
+                    drop(file);
+                    let _ = fs::remove_file(&backup);
+                    return Err(e.into());
+                }
+                return Ok(backup);
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => n += 1,
+            Err(e) => return Err(e.into()),
+        }
+    }
+}
+
+/// Write `content` to a uniquely named sibling temp file with `path`'s
+/// permissions, ready to be persisted over it. The temp file is deleted if
+/// it is dropped instead.
+fn stage_temp(path: &Path, content: &str) -> Result<tempfile::NamedTempFile, AppError> {
+    let parent = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    let mut tmp = tempfile::NamedTempFile::new_in(parent)?;
+    tmp.write_all(content.as_bytes())?;
+    if let Ok(meta) = fs::metadata(path) {
+        let _ = tmp.as_file().set_permissions(meta.permissions());
+    }
+    Ok(tmp)
+}
+
+/// Write via a sibling temp file and rename, so readers never see a partial file.
+fn write_atomic(path: &Path, content: &str) -> Result<(), AppError> {
+    stage_temp(path, content)?.persist(path).map_err(|e| e.error)?;
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use grep_matcher::Matcher;
+    use grep_searcher::sinks::UTF8;
     use std::fs::File;
     use std::io::Write;
     use tempfile::tempdir;
 
+    /// A case-sensitive literal search for `pattern` with default options;
+    /// tests override the fields they exercise.
+    fn query(pattern: &str) -> ContentQuery {
+        ContentQuery {
+            query: pattern.to_string(),
+            case_sensitive: true,
+            regex_mode: false,
+            whole_word: false,
+            multiline: false,
+            max_results: 100,
+            context_lines: 0,
+            filters: ContentSearchOptions::default(),
+            paths: PathFilter::default(),
+        }
+    }
+
     #[test]
     fn test_is_binary_file() {
-        assert!(is_binary_file(std::path::Path::new("test.png")));
-        assert!(is_binary_file(std::path::Path::new("test.exe")));
-        assert!(!is_binary_file(std::path::Path::new("test.rs")));
-        assert!(!is_binary_file(std::path::Path::new("test.txt")));
-        assert!(!is_binary_file(std::path::Path::new("test.js")));
+        let dir = tempdir().unwrap();
+        let renamed_png = dir.path().join("image.txt");
+        fs::write(&renamed_png, b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR").unwrap();
+        let elf = dir.path().join("tool");
+        fs::write(&elf, b"\x7fELF\x02\x01\x01\0\0\0\0\0\0\0\0\0\x02\0\x3e\0").unwrap();
+        let source = dir.path().join("test.png");
+        fs::write(&source, "fn main() {}").unwrap();
+
+        assert!(is_binary_file(&renamed_png));
+        assert!(is_binary_file(&elf));
+        assert!(!is_binary_file(&source));
+        assert!(!is_binary_file(&dir.path().join("missing.exe")));
     }
 
     #[test]
     fn test_content_match_serialization() {
         let m = ContentMatch {
             line_number: 10,
+            line_start: 10,
+            line_end: 10,
             column: 5,
             line_content: "hello world".to_string(),
             match_start: 0,
             match_end: 5,
+            before_context: vec!["before".to_string()],
+            after_context: vec![],
         };
 
         let json = serde_json::to_string(&m).unwrap();
         assert!(json.contains("\"lineNumber\":10"));
         assert!(json.contains("\"lineContent\":\"hello world\""));
+        assert!(json.contains("\"beforeContext\":[\"before\"]"));
+        assert!(json.contains("\"afterContext\":[]"));
     }
 
     #[test]
@@ -497,4 +1502,531 @@ mod tests {
         let m = matcher.find(hay).unwrap();
         assert!(m.is_some());
     }
+
+    /// Helper: search `content` in a temp file with the given context size.
+    fn search_with_context(content: &str, pattern: &str, context_lines: usize) -> Vec<ContentMatch> {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("test.txt");
+        std::fs::write(&path, content).unwrap();
+
+        let matcher = RegexMatcherBuilder::new()
+            .line_terminator(Some(b'\n'))
+            .build(pattern)
+            .unwrap();
+        let mut searcher = SearcherBuilder::new()
+            .before_context(context_lines)
+            .after_context(context_lines)
+            .build();
+        search_file(&mut searcher, &matcher, &path)
+    }
+
+    #[test]
+    fn test_context_lines_surround_match() {
+        let matches = search_with_context("one\ntwo\nthree\nMATCH\nfour\nfive\nsix\n", "MATCH", 2);
+
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].line_number, 4);
+        assert_eq!(matches[0].before_context, vec!["two", "three"]);
+        assert_eq!(matches[0].after_context, vec!["four", "five"]);
+    }
+
+    #[test]
+    fn test_context_lines_do_not_overlap() {
+        // Matches 3 lines apart with 2 context lines: the two lines between
+        // them must be shared out, not duplicated.
+        let matches = search_with_context("a\nMATCH 1\nb\nc\nMATCH 2\nd\n", "MATCH", 2);
+
+        assert_eq!(matches.len(), 2);
+        assert_eq!(matches[0].before_context, vec!["a"]);
+        assert_eq!(matches[0].after_context, vec!["b", "c"]);
+        assert!(matches[1].before_context.is_empty());
+        assert_eq!(matches[1].after_context, vec!["d"]);
+    }
+
+    #[test]
+    fn test_multiline_match_spans_lines() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("sig.rs");
+        std::fs::write(&path, "// header\n\nfn parse(\n    input: &str,\n) -> Result {\n}\nfn other() {}\n").unwrap();
+
+        let mut searcher = build_searcher(0, true);
+        let matcher = build_matcher(r"fn parse\(.*?\) ->", true, true).unwrap();
+        let matches = search_file(&mut searcher, &matcher, &path);
+        assert_eq!(matches.len(), 1);
+        let m = &matches[0];
+        assert_eq!((m.line_start, m.line_end, m.line_number), (3, 5, 3));
+        assert_eq!(m.column, 1);
+        assert_eq!(m.line_content, "fn parse(\n    input: &str,\n) -> Result {");
+        assert_eq!(&m.line_content[m.match_start..m.match_end], "fn parse(\n    input: &str,\n) ->");
+
+        // Line by line, the same pattern can't cross the newlines
+        let mut searcher = build_searcher(0, false);
+        let matcher = build_matcher(r"fn parse\(.*?\) ->", true, false).unwrap();
+        assert!(search_file(&mut searcher, &matcher, &path).is_empty());
+
+        // Single-line matches in multiline mode keep their own line
+        let mut searcher = build_searcher(0, true);
+        let matcher = build_matcher("other", true, true).unwrap();
+        let matches = search_file(&mut searcher, &matcher, &path);
+        assert_eq!((matches[0].line_start, matches[0].line_end), (7, 7));
+        assert_eq!(matches[0].line_content, "fn other() {}");
+        assert_eq!(matches[0].column, 4);
+    }
+
+    #[test]
+    fn test_no_context_by_default() {
+        let matches = search_with_context("x\nhello hello\ny\n", "hello", 0);
+
+        assert_eq!(matches.len(), 2);
+        assert_eq!(matches[1].column, 7);
+        assert!(matches.iter().all(|m| m.before_context.is_empty() && m.after_context.is_empty()));
+    }
+
+    // ── File filters ────────────────────────────────────────────────────────
+
+    #[test]
+    fn test_content_search_options_extensions() {
+        let options = ContentSearchOptions {
+            file_extensions: vec![".PY".to_string(), "js".to_string(), " ".to_string()],
+            ..Default::default()
+        }
+        .normalized();
+
+        assert_eq!(options.file_extensions, vec!["py", "js"]);
+        assert!(options.matches_extension(Path::new("src/app.py")));
+        assert!(options.matches_extension(Path::new("src/App.JS")));
+        assert!(!options.matches_extension(Path::new("src/app.rs")));
+        assert!(!options.matches_extension(Path::new("Makefile")));
+        assert!(ContentSearchOptions::default().matches_extension(Path::new("Makefile")));
+    }
+
+    #[test]
+    fn test_content_search_options_size_bounds() {
+        let options = ContentSearchOptions {
+            min_file_size_bytes: Some(10),
+            max_file_size_bytes: Some(100),
+            ..Default::default()
+        };
+
+        assert!(options.has_size_bounds());
+        assert!(!options.matches_size(9));
+        assert!(options.matches_size(10));
+        assert!(options.matches_size(100));
+        assert!(!options.matches_size(101));
+        assert!(!ContentSearchOptions::default().has_size_bounds());
+    }
+
+    #[test]
+    fn test_content_search_options_deserialize() {
+        let options: ContentSearchOptions =
+            serde_json::from_str(r#"{"fileExtensions":["rs"],"maxFileSizeBytes":5242880}"#).unwrap();
+
+        assert_eq!(options.file_extensions, vec!["rs"]);
+        assert_eq!(options.max_file_size_bytes, Some(5 * 1024 * 1024));
+        assert_eq!(options.min_file_size_bytes, None);
+    }
+
+    #[test]
+    fn test_path_filter_gitignore_style_patterns() {
+        let filter = ContentSearchOptions {
+            include_patterns: vec!["*.rs".into(), "/docs/".into()],
+            exclude_patterns: vec!["tests/".into(), "src/gen/*.rs".into()],
+            ..Default::default()
+        }
+        .path_filter()
+        .unwrap();
+
+        assert!(filter.matches(Path::new("main.rs")));
+        assert!(filter.matches(Path::new("src/deep/lib.rs")));
+        assert!(filter.matches(Path::new("docs/guide/intro.md")));
+        assert!(!filter.matches(Path::new("README.md")));
+        assert!(!filter.matches(Path::new("sub/docs/intro.md")), "leading slash anchors to the root");
+        assert!(!filter.matches(Path::new("tests/it.rs")));
+        assert!(!filter.matches(Path::new("crate/tests/fixtures/it.rs")));
+        assert!(!filter.matches(Path::new("src/gen/out.rs")));
+        assert!(filter.matches(Path::new("src/gen/nested/out.rs")), "`*` stays within one level");
+
+        assert!(PathFilter::default().matches(Path::new("anything")));
+        let invalid = ContentSearchOptions {
+            exclude_patterns: vec!["a[".into()],
+            ..Default::default()
+        };
+        assert!(invalid.path_filter().is_err());
+    }
+
+    #[test]
+    fn test_filtered_content_search_skips_other_files() {
+        let dir = tempdir().unwrap();
+        std::fs::write(dir.path().join("a.py"), "needle\n").unwrap();
+        std::fs::write(dir.path().join("b.rs"), "needle\n").unwrap();
+        std::fs::write(dir.path().join("big.py"), format!("needle\n{}", "x".repeat(1000))).unwrap();
+
+        let params = ContentQuery {
+            filters: ContentSearchOptions {
+                file_extensions: vec!["py".to_string()],
+                max_file_size_bytes: Some(100),
+                ..Default::default()
+            },
+            ..query("needle")
+        };
+        let (tx, rx) = mpsc::channel();
+        spawn_content_search(params, dir.path().to_path_buf(), move |event| {
+            let _ = tx.send(event);
+        });
+
+        let mut found = Vec::new();
+        for event in rx.iter() {
+            found.extend(event.results.into_iter().map(|r| r.relative_path));
+            if event.done {
+                break;
+            }
+        }
+        assert_eq!(found, vec!["a.py"]);
+    }
+
+    // ── Whole word ──────────────────────────────────────────────────────────
+
+    fn whole_word_matches(content: &str, query: &str, regex_mode: bool) -> Vec<ContentMatch> {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("words.txt");
+        std::fs::write(&path, content).unwrap();
+
+        let matcher = RegexMatcherBuilder::new()
+            .line_terminator(Some(b'\n'))
+            .build(&build_pattern(query, regex_mode, true))
+            .unwrap();
+        search_file(&mut SearcherBuilder::new().build(), &matcher, &path)
+    }
+
+    #[test]
+    fn test_whole_word_literal() {
+        let matches = whole_word_matches("set reset setter\n", "set", false);
+
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].column, 1);
+        assert_eq!((matches[0].match_start, matches[0].match_end), (0, 3));
+    }
+
+    #[test]
+    fn test_whole_word_regex_alternation() {
+        let matches = whole_word_matches("reset setter get set\n", "set|get", true);
+
+        let columns: Vec<u64> = matches.iter().map(|m| m.column).collect();
+        assert_eq!(columns, vec![14, 18]);
+    }
+
+    #[test]
+    fn test_whole_word_escapes_literal() {
+        assert_eq!(build_pattern("a.b", false, true), r"\ba\.b\b");
+        assert_eq!(build_pattern("a.b", false, false), r"a\.b");
+    }
+
+    // ── Encoding ────────────────────────────────────────────────────────────
+
+    #[test]
+    fn test_auto_detect_encoding() {
+        let dir = tempdir().unwrap();
+        let matcher = RegexMatcherBuilder::new().line_terminator(Some(b'\n')).build("crème").unwrap();
+        let mut searcher = build_searcher(0, false);
+
+        let latin = dir.path().join("latin.txt");
+        std::fs::write(&latin, b"caf\xE9\ncr\xE8me br\xFBl\xE9e\n").unwrap();
+        assert!(search_file(&mut searcher, &matcher, &latin).is_empty());
+        let text = transcoded_text(&latin).unwrap();
+        let matches = search_text(&mut searcher, &matcher, &text);
+        assert_eq!(matches.len(), 1);
+        assert_eq!((matches[0].line_number, matches[0].line_content.as_str()), (2, "crème brûlée"));
+
+        let utf8 = dir.path().join("utf8.txt");
+        std::fs::write(&utf8, "crème").unwrap();
+        assert!(transcoded_text(&utf8).is_none(), "UTF-8 is searched as is");
+        let unsure = dir.path().join("unsure.txt");
+        std::fs::write(&unsure, b"\x81\x8D\x8F\x90 cr\xE8me").unwrap();
+        assert!(transcoded_text(&unsure).is_none(), "low confidence falls back to raw bytes");
+
+        let options: ContentSearchOptions = serde_json::from_str(r#"{"autoDetectEncoding":true}"#).unwrap();
+        assert!(options.auto_detect_encoding);
+    }
+
+    // ── Replace ─────────────────────────────────────────────────────────────
+
+    /// Helper: write files and register a completed search over them.
+    fn record_search(dir: &Path, files: &[(&str, &str)], query: &str, regex_mode: bool) -> u64 {
+        let (search_id, state) = CONTENT_SEARCHES.start(Arc::new(AtomicBool::new(false)));
+        let files = files
+            .iter()
+            .map(|(name, content)| {
+                let path = dir.join(name);
+                std::fs::write(&path, content).unwrap();
+                let modified_ms = mtime_ms(&std::fs::metadata(&path).unwrap());
+                (path, modified_ms)
+            })
+            .collect();
+        state
+            .record
+            .set(SearchRecord {
+                pattern: build_pattern(query, regex_mode, false),
+                case_sensitive: true,
+                regex_mode,
+                multiline: false,
+                files,
+            })
+            .unwrap();
+        state.finished.set(Duration::ZERO).unwrap();
+        search_id
+    }
+
+    #[test]
+    fn test_preview_replace_lists_changed_lines() {
+        let dir = tempdir().unwrap();
+        let id = record_search(dir.path(), &[("a.txt", "foo one\nbar\nfoo foo\n")], "foo", false);
+
+        let previews = preview_replace(id, "$1".to_string()).unwrap();
+
+        assert_eq!(previews.len(), 1);
+        let changes = &previews[0].changes;
+        assert_eq!(changes.len(), 2);
+        assert_eq!(changes[0].line_number, 1);
+        // Literal mode does not expand capture references
+        assert_eq!(changes[0].new_line, "$1 one");
+        assert_eq!(changes[1].line_number, 3);
+        assert_eq!(changes[1].new_line, "$1 $1");
+    }
+
+    #[test]
+    fn test_preview_replace_regex_captures() {
+        let dir = tempdir().unwrap();
+        let id = record_search(dir.path(), &[("a.txt", "let x = 1;\n")], r"let (\w+)", true);
+
+        let previews = preview_replace(id, "const $1".to_string()).unwrap();
+
+        assert_eq!(previews[0].changes[0].new_line, "const x = 1;");
+    }
+
+    #[test]
+    fn test_preview_replace_unknown_search() {
+        assert!(matches!(preview_replace(u64::MAX, "x".to_string()), Err(AppError::NotFound(_))));
+    }
+
+    #[test]
+    fn test_preview_replace_rejects_stale_file() {
+        let dir = tempdir().unwrap();
+        let id = record_search(dir.path(), &[("a.txt", "foo\n")], "foo", false);
+        let file = std::fs::File::options().write(true).open(dir.path().join("a.txt")).unwrap();
+        file.set_modified(UNIX_EPOCH).unwrap();
+
+        let err = preview_replace(id, "bar".to_string()).unwrap_err();
+        assert!(err.to_string().contains("changed since search"));
+    }
+
+    #[test]
+    fn test_apply_replace_writes_files_and_backups() {
+        let dir = tempdir().unwrap();
+        let id = record_search(
+            dir.path(),
+            &[("a.txt", "foo\r\nkeep\r\n"), ("b.txt", "x foo")],
+            "foo",
+            false,
+        );
+        let previews = preview_replace(id, "bar".to_string()).unwrap();
+
+        let result = apply_replace(previews, true).unwrap();
+
+        assert_eq!(result.files_changed, 2);
+        assert_eq!(result.lines_changed, 2);
+        assert_eq!(result.backups.len(), 2);
+        assert_eq!(std::fs::read_to_string(dir.path().join("a.txt")).unwrap(), "bar\r\nkeep\r\n");
+        assert_eq!(std::fs::read_to_string(dir.path().join("b.txt")).unwrap(), "x bar");
+        assert_eq!(std::fs::read_to_string(dir.path().join("a.txt.bak")).unwrap(), "foo\r\nkeep\r\n");
+    }
+
+    #[test]
+    fn test_apply_replace_backup_keeps_existing_bak() {
+        let dir = tempdir().unwrap();
+        let id = record_search(dir.path(), &[("a.txt", "foo\n")], "foo", false);
+        std::fs::write(dir.path().join("a.txt.bak"), "older backup").unwrap();
+        let previews = preview_replace(id, "bar".to_string()).unwrap();
+
+        let result = apply_replace(previews, true).unwrap();
+
+        let backup = dir.path().join("a.txt.1.bak");
+        assert_eq!(result.backups, vec![backup.to_string_lossy().to_string()]);
+        assert_eq!(std::fs::read_to_string(&backup).unwrap(), "foo\n");
+        assert_eq!(std::fs::read_to_string(dir.path().join("a.txt.bak")).unwrap(), "older backup");
+    }
+
+    #[test]
+    fn test_apply_replace_is_all_or_nothing() {
+        let dir = tempdir().unwrap();
+        let id = record_search(dir.path(), &[("a.txt", "foo\n"), ("b.txt", "foo\n")], "foo", false);
+        let previews = preview_replace(id, "bar".to_string()).unwrap();
+
+        // Edit b.txt after the preview, keeping its recorded mtime
+        let b = dir.path().join("b.txt");
+        let mtime = std::fs::metadata(&b).unwrap().modified().unwrap();
+        std::fs::write(&b, "changed\n").unwrap();
+        std::fs::File::options().write(true).open(&b).unwrap().set_modified(mtime).unwrap();
+
+        assert!(apply_replace(previews, false).is_err());
+        assert_eq!(std::fs::read_to_string(dir.path().join("a.txt")).unwrap(), "foo\n");
+        assert_eq!(std::fs::read_to_string(&b).unwrap(), "changed\n");
+    }
+
+    #[test]
+    fn test_apply_replace_reports_non_utf8_files() {
+        let dir = tempdir().unwrap();
+        let id = record_search(dir.path(), &[("a.txt", "foo\n"), ("b.txt", "foo\n")], "foo", false);
+        let b = dir.path().join("b.txt");
+        let mtime = std::fs::metadata(&b).unwrap().modified().unwrap();
+        std::fs::write(&b, b"foo \xff\n").unwrap();
+        std::fs::File::options().write(true).open(&b).unwrap().set_modified(mtime).unwrap();
+
+        let previews = preview_replace(id, "bar".to_string()).unwrap();
+        let result = apply_replace(previews, false).unwrap();
+
+        assert_eq!(result.files_changed, 1);
+        assert_eq!(result.skipped.len(), 1);
+        assert_eq!(result.skipped[0].path, b.to_string_lossy());
+        assert_eq!(std::fs::read(&b).unwrap(), b"foo \xff\n");
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 2, "no temp files left behind");
+    }
+
+    // ── Export ──────────────────────────────────────────────────────────────
+
+    #[test]
+    fn test_export_content_search_results_one_row_per_match() {
+        let dir = tempdir().unwrap();
+        let results: Vec<ContentSearchResult> = serde_json::from_str(
+            r#"[{ "path": "/p/a.rs", "relativePath": "a.rs", "matches": [
+                { "lineNumber": 3, "column": 5, "lineContent": "let x, y", "matchStart": 4, "matchEnd": 5 },
+                { "lineNumber": 9, "column": 1, "lineContent": "x", "matchStart": 0, "matchEnd": 1 }
+            ] }]"#,
+        )
+        .unwrap();
+        let out = dir.path().join("matches.csv");
+
+        let count =
+            export_content_search_results(results, out.to_string_lossy().into(), ExportFormat::Csv).unwrap();
+        assert_eq!(count, 2);
+        assert_eq!(
+            fs::read_to_string(&out).unwrap(),
+            "path,line_number,column,line_content,match_start,match_end\n\
+             /p/a.rs,3,5,\"let x, y\",4,5\n\
+             /p/a.rs,9,1,x,0,1\n"
+        );
+    }
+
+    // ── Grouping ────────────────────────────────────────────────────────────
+
+    #[test]
+    fn test_group_by_directory() {
+        let result = |relative_path: &str, matches: usize| ContentSearchResult {
+            path: format!("/p/{}", relative_path),
+            relative_path: relative_path.into(),
+            matches: search_with_context(&"x\n".repeat(matches), "x", 0),
+        };
+        let sep = std::path::MAIN_SEPARATOR;
+        let groups = group_by_directory(vec![
+            result("a.rs", 1),
+            result(&format!("src{}lib.rs", sep), 2),
+            result(&format!("src{}main.rs", sep), 1),
+            result(&format!("docs{}guide.md", sep), 1),
+        ]);
+
+        let summary: Vec<(&str, usize, usize)> =
+            groups.iter().map(|g| (g.directory.as_str(), g.results.len(), g.match_count)).collect();
+        assert_eq!(summary, [("src", 2, 3), ("", 1, 1), ("docs", 1, 1)]);
+    }
+
+    // ── Cancellation ────────────────────────────────────────────────────────
+
+    #[test]
+    fn test_cancel_content_search_stops_background_thread() {
+        let dir = tempdir().unwrap();
+        for i in 0..3000 {
+            std::fs::write(dir.path().join(format!("f{i}.txt")), "needle\n".repeat(20)).unwrap();
+        }
+
+        let params = ContentQuery { max_results: 5000, ..query("needle") };
+        let search_id = spawn_content_search(params, dir.path().to_path_buf(), |_| {});
+
+        std::thread::sleep(std::time::Duration::from_millis(10));
+        cancel_content_search(search_id).unwrap();
+
+        let deadline = std::time::Instant::now() + std::time::Duration::from_millis(500);
+        while CONTENT_SEARCHES.is_active(search_id) {
+            assert!(std::time::Instant::now() < deadline, "search thread still running after cancel");
+            std::thread::sleep(std::time::Duration::from_millis(5));
+        }
+
+        let stats = get_content_search_stats(search_id).unwrap();
+        assert!(stats.is_cancelled && stats.is_complete);
+    }
+
+    // ── Stats ───────────────────────────────────────────────────────────────
+
+    #[test]
+    fn test_content_search_stats_after_completion() {
+        let dir = tempdir().unwrap();
+        std::fs::write(dir.path().join("a.txt"), "needle\nneedle\n").unwrap();
+        std::fs::write(dir.path().join("b.txt"), "hay\n").unwrap();
+
+        let params = query("needle");
+        let (tx, rx) = mpsc::channel();
+        let search_id = spawn_content_search(params, dir.path().to_path_buf(), move |event| {
+            let _ = tx.send(event.done);
+        });
+        assert!(rx.iter().any(|done| done));
+
+        // `finished` is set just after the final event is emitted
+        let deadline = std::time::Instant::now() + std::time::Duration::from_millis(500);
+        let stats = loop {
+            let stats = get_content_search_stats(search_id).unwrap();
+            if stats.is_complete || std::time::Instant::now() > deadline {
+                break stats;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(5));
+        };
+        assert!(stats.is_complete && !stats.is_cancelled);
+        assert_eq!((stats.files_searched, stats.total_matches), (2, 2));
+
+        assert!(matches!(get_content_search_stats(u64::MAX), Err(AppError::NotFound(_))));
+    }
+
+    // ── Pause ───────────────────────────────────────────────────────────────
+
+    #[test]
+    fn test_pause_and_resume_search() {
+        let dir = tempdir().unwrap();
+        for i in 0..2000 {
+            std::fs::write(dir.path().join(format!("f{i}.txt")), "needle\n").unwrap();
+        }
+
+        let params = ContentQuery { max_results: 5000, ..query("needle") };
+        let (tx, rx) = mpsc::channel();
+        let search_id = spawn_content_search(params, dir.path().to_path_buf(), move |event| {
+            let _ = tx.send(event);
+        });
+        pause_search(search_id).unwrap();
+        assert!(get_content_search_stats(search_id).unwrap().is_paused);
+
+        // Let files that were already in flight drain out
+        std::thread::sleep(std::time::Duration::from_millis(150));
+        while rx.try_recv().is_ok() {}
+        assert!(
+            rx.recv_timeout(std::time::Duration::from_millis(200)).is_err(),
+            "progress event while paused"
+        );
+
+        resume_search(search_id).unwrap();
+        let mut files_searched = 0;
+        for event in rx.iter() {
+            files_searched = event.files_searched;
+            if event.done {
+                break;
+            }
+        }
+        assert_eq!(files_searched, 2000);
+        assert!(matches!(pause_search(u64::MAX), Err(AppError::NotFound(_))));
+    }
 }
@@ -29,13 +29,65 @@ pub enum AppError {
     Other(String),
 }
 
+/// Stable, language-independent error codes sent to the frontend, so it
+/// can pick a translated message without parsing English text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorCode {
+    NotFound,
+    PermissionDenied,
+    AlreadyExists,
+    InvalidPath,
+    Io,
+    Other,
+}
+
+impl ErrorCode {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            ErrorCode::NotFound => "NOT_FOUND",
+            ErrorCode::PermissionDenied => "PERMISSION_DENIED",
+            ErrorCode::AlreadyExists => "ALREADY_EXISTS",
+            ErrorCode::InvalidPath => "INVALID_PATH",
+            ErrorCode::Io => "IO",
+            ErrorCode::Other => "OTHER",
+        }
+    }
+}
+
+impl AppError {
+    pub const fn code(&self) -> ErrorCode {
+        match self {
+            AppError::NotFound(_) => ErrorCode::NotFound,
+            AppError::PermissionDenied(_) => ErrorCode::PermissionDenied,
+            AppError::AlreadyExists(_) => ErrorCode::AlreadyExists,
+            AppError::InvalidPath(_) => ErrorCode::InvalidPath,
+            AppError::Io(_) => ErrorCode::Io,
+            AppError::Other(_) => ErrorCode::Other,
+        }
+    }
+
+    /// The path the error is about, for variants that carry one.
+    /// `InvalidPath` usually carries a description, so it has none.
+    pub fn path(&self) -> Option<&str> {
+        match self {
+            AppError::NotFound(path) | AppError::PermissionDenied(path) | AppError::AlreadyExists(path) => {
+                Some(path)
+            }
+            AppError::InvalidPath(_) | AppError::Io(_) | AppError::Other(_) => None,
+        }
+    }
+}
+
+/// Serialized as `{ code, kind, message, path }`. `kind` is the older
+/// lowercase form of `code`, kept for existing callers; `path` is null when
+/// the variant has none.
 impl Serialize for AppError {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: serde::Serializer,
     {
-        use serde::ser::SerializeMap;
-        let mut map = serializer.serialize_map(Some(2))?;
+        use serde::ser::SerializeStruct;
+        let mut state = serializer.serialize_struct("AppError", 4)?;
         let kind = match self {
             AppError::NotFound(_) => "not_found",
             AppError::PermissionDenied(_) => "permission_denied",
@@ -44,9 +96,11 @@ impl Serialize for AppError {
             AppError::Io(_) => "io",
             AppError::Other(_) => "other",
         };
-        map.serialize_entry("kind", kind)?;
-        map.serialize_entry("message", &self.to_string())?;
-        map.end()
+        state.serialize_field("code", self.code().as_str())?;
+        state.serialize_field("kind", kind)?;
+        state.serialize_field("message", &self.to_string())?;
+        state.serialize_field("path", &self.path())?;
+        state.end()
     }
 }
 
@@ -61,3 +115,42 @@ impl From<&str> for AppError {
         AppError::Other(s.to_string())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_serialized_shape_of_each_variant() {
+        let cases = [
+            (
+                AppError::NotFound("/a".into()),
+                json!({ "code": "NOT_FOUND", "kind": "not_found", "message": "Path not found: /a", "path": "/a" }),
+            ),
+            (
+                AppError::PermissionDenied("/b".into()),
+                json!({ "code": "PERMISSION_DENIED", "kind": "permission_denied", "message": "Permission denied: /b", "path": "/b" }),
+            ),
+            (
+                AppError::AlreadyExists("/c".into()),
+                json!({ "code": "ALREADY_EXISTS", "kind": "already_exists", "message": "Path already exists: /c", "path": "/c" }),
+            ),
+            (
+                AppError::InvalidPath("bad name".into()),
+                json!({ "code": "INVALID_PATH", "kind": "invalid_path", "message": "Invalid path: bad name", "path": null }),
+            ),
+            (
+                AppError::Io(std::io::Error::other("disk")),
+                json!({ "code": "IO", "kind": "io", "message": "IO error: disk", "path": null }),
+            ),
+            (
+                AppError::Other("oops".into()),
+                json!({ "code": "OTHER", "kind": "other", "message": "oops", "path": null }),
+            ),
+        ];
+        for (error, expected) in cases {
+            assert_eq!(serde_json::to_value(&error).unwrap(), expected);
+        }
+    }
+}
@@ -0,0 +1,126 @@
+//! Saving search results to disk as JSON, CSV or TSV.
+
+use std::fs;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::AppError;
+
+/// Output format for exported results.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ExportFormat {
+    Json,
+    Csv,
+    Tsv,
+}
+
+/// Quote a CSV field when it contains a delimiter, quote or line break.
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// TSV has no quoting, so tabs and line breaks become spaces.
+fn tsv_field(value: &str) -> String {
+    value.replace(['\t', '\n', '\r'], " ")
+}
+
+/// Write `json` (for `Json`) or `header` plus `rows` (for `Csv`/`Tsv`) to
+/// `output_path` via a sibling temp file and rename. Returns the number of
+/// rows, excluding the header.
+pub(crate) fn write_export<T: Serialize + ?Sized>(
+    output_path: &str,
+    format: ExportFormat,
+    json: &T,
+    header: &[&str],
+    rows: &[Vec<String>],
+) -> Result<usize, AppError> {
+    let path = Path::new(output_path);
+    let name = path
+        .file_name()
+        .ok_or_else(|| AppError::InvalidPath(output_path.to_string()))?;
+    if path.is_dir() {
+        return Err(AppError::InvalidPath(format!("{} is a directory", output_path)));
+    }
+    let tmp = path.with_file_name(format!(".{}.export-tmp", name.to_string_lossy()));
+
+    let written = (|| -> Result<(), AppError> {
+        let mut out = BufWriter::new(fs::File::create(&tmp)?);
+        match format {
+            ExportFormat::Json => {
+                serde_json::to_writer_pretty(&mut out, json)
+                    .map_err(|e| AppError::Other(format!("Failed to serialize results: {}", e)))?;
+                writeln!(out)?;
+            }
+            ExportFormat::Csv | ExportFormat::Tsv => {
+                let (field, separator): (fn(&str) -> String, &str) = match format {
+                    ExportFormat::Csv => (csv_field, ","),
+                    _ => (tsv_field, "\t"),
+                };
+                let header: Vec<String> = header.iter().map(|h| field(h)).collect();
+                writeln!(out, "{}", header.join(separator))?;
+                for row in rows {
+                    let row: Vec<String> = row.iter().map(|v| field(v)).collect();
+                    writeln!(out, "{}", row.join(separator))?;
+                }
+            }
+        }
+        out.into_inner().map_err(|e| e.into_error())?.sync_all()?;
+        fs::rename(&tmp, path)?;
+        Ok(())
+    })();
+    if let Err(e) = written {
+        let _ = fs::remove_file(&tmp);
+        return Err(e);
+    }
+
+    log::debug!("Exported {} rows to {:?} as {:?}", rows.len(), path, format);
+    Ok(rows.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_write_export_formats() {
+        let dir = tempdir().unwrap();
+        let rows = vec![
+            vec!["a,b".to_string(), "say \"hi\"".to_string()],
+            vec!["tab\there".to_string(), "2".to_string()],
+        ];
+        let json = serde_json::json!([{ "name": "a,b" }]);
+
+        let csv = dir.path().join("out.csv");
+        let count =
+            write_export(&csv.to_string_lossy(), ExportFormat::Csv, &json, &["name", "value"], &rows)
+                .unwrap();
+        assert_eq!(count, 2);
+        assert_eq!(
+            fs::read_to_string(&csv).unwrap(),
+            "name,value\n\"a,b\",\"say \"\"hi\"\"\"\ntab\there,2\n"
+        );
+
+        let tsv = dir.path().join("out.tsv");
+        write_export(&tsv.to_string_lossy(), ExportFormat::Tsv, &json, &["name", "value"], &rows)
+            .unwrap();
+        assert_eq!(
+            fs::read_to_string(&tsv).unwrap(),
+            "name\tvalue\na,b\tsay \"hi\"\ntab here\t2\n"
+        );
+
+        let out = dir.path().join("out.json");
+        write_export(&out.to_string_lossy(), ExportFormat::Json, &json, &[], &rows).unwrap();
+        let parsed: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&out).unwrap()).unwrap();
+        assert_eq!(parsed, json);
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 3, "temp files left behind");
+    }
+}
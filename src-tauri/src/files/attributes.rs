@@ -0,0 +1,174 @@
+//! Windows file attribute flags (hidden, read-only, system, archive).
+//!
+//! Off Windows there are no such flags; the commands map them onto what
+//! Unix has (a dot-prefixed name, the write permission bits) so the
+//! frontend can call them unconditionally.
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::AppError;
+
+/// The attribute flags the explorer lets users toggle.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WindowsAttributes {
+    pub hidden: bool,
+    pub read_only: bool,
+    pub system: bool,
+    pub archive: bool,
+}
+
+#[cfg(windows)]
+mod win {
+    use std::os::windows::ffi::OsStrExt;
+
+    #[link(name = "kernel32")]
+    extern "system" {
+        fn GetFileAttributesW(file_name: *const u16) -> u32;
+        fn SetFileAttributesW(file_name: *const u16, attributes: u32) -> i32;
+    }
+    const INVALID_FILE_ATTRIBUTES: u32 = u32::MAX;
+    pub const FILE_ATTRIBUTE_READONLY: u32 = 0x1;
+    pub const FILE_ATTRIBUTE_HIDDEN: u32 = 0x2;
+    pub const FILE_ATTRIBUTE_SYSTEM: u32 = 0x4;
+    pub const FILE_ATTRIBUTE_ARCHIVE: u32 = 0x20;
+    pub const FILE_ATTRIBUTE_NORMAL: u32 = 0x80;
+
+    fn wide(path: &str) -> Vec<u16> {
+        std::ffi::OsStr::new(path).encode_wide().chain(std::iter::once(0)).collect()
+    }
+
+    pub fn get(path: &str) -> std::io::Result<u32> {
+        match unsafe { GetFileAttributesW(wide(path).as_ptr()) } {
+            INVALID_FILE_ATTRIBUTES => Err(std::io::Error::last_os_error()),
+            attributes => Ok(attributes),
+        }
+    }
+
+    pub fn set(path: &str, attributes: u32) -> std::io::Result<()> {
+        if unsafe { SetFileAttributesW(wide(path).as_ptr(), attributes) } == 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(windows)]
+fn attribute_error(path: String, e: std::io::Error) -> AppError {
+    match e.kind() {
+        std::io::ErrorKind::NotFound => AppError::NotFound(path),
+        std::io::ErrorKind::PermissionDenied => AppError::PermissionDenied(path),
+        _ => AppError::Io(e),
+    }
+}
+
+/// The hidden, read-only, system and archive flags of `path`.
+#[cfg(windows)]
+#[tauri::command]
+pub fn get_windows_file_attributes(path: String) -> Result<WindowsAttributes, AppError> {
+    use win::*;
+    let attributes = get(&path).map_err(|e| attribute_error(path, e))?;
+    Ok(WindowsAttributes {
+        hidden: attributes & FILE_ATTRIBUTE_HIDDEN != 0,
+        read_only: attributes & FILE_ATTRIBUTE_READONLY != 0,
+        system: attributes & FILE_ATTRIBUTE_SYSTEM != 0,
+        archive: attributes & FILE_ATTRIBUTE_ARCHIVE != 0,
+    })
+}
+
+/// Set the four flags of `path` to `attrs`, keeping its other attributes.
+#[cfg(windows)]
+#[tauri::command]
+pub fn set_windows_file_attributes(path: String, attrs: WindowsAttributes) -> Result<(), AppError> {
+    use win::*;
+    let current = get(&path).map_err(|e| attribute_error(path.clone(), e))?;
+    let flags = [
+        (attrs.hidden, FILE_ATTRIBUTE_HIDDEN),
+        (attrs.read_only, FILE_ATTRIBUTE_READONLY),
+        (attrs.system, FILE_ATTRIBUTE_SYSTEM),
+        (attrs.archive, FILE_ATTRIBUTE_ARCHIVE),
+    ];
+    let mut attributes = current & !FILE_ATTRIBUTE_NORMAL;
+    for (on, bit) in flags {
+        attributes = if on { attributes | bit } else { attributes & !bit };
+    }
+    // NORMAL is only valid on its own, meaning "no other flags"
+    if attributes == 0 {
+        attributes = FILE_ATTRIBUTE_NORMAL;
+    }
+    set(&path, attributes).map_err(|e| attribute_error(path.clone(), e))?;
+    log::info!("Set attributes {:?} on {}", attrs, path);
+    Ok(())
+}
+
+/// Hidden means a dot-prefixed name and read-only means no write bits;
+/// system and archive are always false.
+#[cfg(not(windows))]
+#[tauri::command]
+pub fn get_windows_file_attributes(path: String) -> Result<WindowsAttributes, AppError> {
+    let file_path = std::path::Path::new(&path);
+    let metadata = std::fs::metadata(file_path).map_err(|_| AppError::NotFound(path.clone()))?;
+    Ok(WindowsAttributes {
+        hidden: file_path.file_name().is_some_and(|n| n.to_string_lossy().starts_with('.')),
+        read_only: metadata.permissions().readonly(),
+        system: false,
+        archive: false,
+    })
+}
+
+/// Only `read_only` has an effect: it clears every write bit, and clearing
+/// it gives the owner write access back. Renaming is how Unix hides files.
+#[cfg(unix)]
+#[tauri::command]
+pub fn set_windows_file_attributes(path: String, attrs: WindowsAttributes) -> Result<(), AppError> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let metadata = std::fs::metadata(&path).map_err(|_| AppError::NotFound(path.clone()))?;
+    let mode = metadata.permissions().mode();
+    let new_mode = if attrs.read_only { mode & !0o222 } else { mode | 0o200 };
+    if new_mode != mode {
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(new_mode)).map_err(|e| {
+            match e.kind() {
+                std::io::ErrorKind::PermissionDenied => AppError::PermissionDenied(path.clone()),
+                _ => AppError::Io(e),
+            }
+        })?;
+    }
+    Ok(())
+}
+
+#[cfg(not(any(unix, windows)))]
+#[tauri::command]
+pub fn set_windows_file_attributes(_path: String, _attrs: WindowsAttributes) -> Result<(), AppError> {
+    Err(AppError::Other("File attributes are not supported on this platform".into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_only_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join(".hidden-file");
+        std::fs::write(&file, "").unwrap();
+        let path = file.to_string_lossy().to_string();
+
+        let attrs = get_windows_file_attributes(path.clone()).unwrap();
+        assert!(!attrs.read_only);
+        #[cfg(unix)]
+        assert!(attrs.hidden);
+
+        set_windows_file_attributes(path.clone(), WindowsAttributes { read_only: true, ..attrs }).unwrap();
+        assert!(get_windows_file_attributes(path.clone()).unwrap().read_only);
+        set_windows_file_attributes(path.clone(), WindowsAttributes { read_only: false, ..attrs }).unwrap();
+        assert!(!get_windows_file_attributes(path.clone()).unwrap().read_only);
+
+        let json = serde_json::to_value(attrs).unwrap();
+        assert!(json.get("readOnly").is_some());
+        assert!(matches!(
+            get_windows_file_attributes(dir.path().join("missing").to_string_lossy().to_string()),
+            Err(AppError::NotFound(_))
+        ));
+    }
+}
@@ -0,0 +1,181 @@
+//! Timestamped backup copies of single files, taken before an edit
+//! overwrites them, and restoring from those copies.
+//!
+//! A backup of `notes.txt` is named `notes.txt.20250101_120000` and kept in
+//! `.backups/` next to the file unless another directory is given.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use chrono::{Local, NaiveDateTime};
+use serde::Serialize;
+
+use super::{metadata_to_entry, FileEntry};
+use crate::error::AppError;
+
+/// Default backup directory, created next to the file.
+const BACKUP_DIR_NAME: &str = ".backups";
+
+/// Timestamp appended to backup names.
+const TIMESTAMP_FORMAT: &str = "%Y%m%d_%H%M%S";
+
+/// One backup of a file, as listed by `list_file_backups`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct BackupEntry {
+    pub path: String,
+    /// When the backup was taken (ISO 8601, local time), from its name.
+    pub created: String,
+    pub size: u64,
+}
+
+fn backup_dir_for(file: &Path, backup_dir: Option<String>) -> PathBuf {
+    match backup_dir {
+        Some(dir) => PathBuf::from(dir),
+        None => file.parent().unwrap_or(Path::new(".")).join(BACKUP_DIR_NAME),
+    }
+}
+
+fn file_name(path: &Path) -> Result<String, AppError> {
+    path.file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .ok_or_else(|| AppError::InvalidPath(path.to_string_lossy().to_string()))
+}
+
+/// Copy `path` to `{backup_dir}/{filename}.{YYYYMMDD_HHMMSS}` (default
+/// `backup_dir`: `.backups` next to the file, created if needed) and return
+/// the backup's path. A second backup in the same second gets `-2`, `-3`...
+#[tauri::command]
+pub fn backup_file(path: String, backup_dir: Option<String>) -> Result<String, AppError> {
+    let file = PathBuf::from(&path);
+    let metadata = fs::metadata(&file).map_err(|_| AppError::NotFound(path.clone()))?;
+    if metadata.is_dir() {
+        return Err(AppError::InvalidPath(format!("{} is a directory", path)));
+    }
+    let dir = backup_dir_for(&file, backup_dir);
+    fs::create_dir_all(&dir)?;
+
+    let base = format!("{}.{}", file_name(&file)?, Local::now().format(TIMESTAMP_FORMAT));
+    let mut backup = dir.join(&base);
+    let mut counter = 2;
+    while fs::symlink_metadata(&backup).is_ok() {
+        backup = dir.join(format!("{}-{}", base, counter));
+        counter += 1;
+    }
+    fs::copy(&file, &backup)?;
+    log::info!("Backed up {:?} to {:?}", file, backup);
+    Ok(backup.to_string_lossy().to_string())
+}
+
+/// Backups of `path` in `backup_dir` (default `.backups` next to it),
+/// newest first. Empty when there are none.
+#[tauri::command]
+pub fn list_file_backups(path: String, backup_dir: Option<String>) -> Result<Vec<BackupEntry>, AppError> {
+    let file = PathBuf::from(&path);
+    let prefix = format!("{}.", file_name(&file)?);
+    let Ok(entries) = fs::read_dir(backup_dir_for(&file, backup_dir)) else {
+        return Ok(Vec::new());
+    };
+
+    let mut backups: Vec<(NaiveDateTime, u32, BackupEntry)> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            let (created, counter) = parse_backup_suffix(name.strip_prefix(&prefix)?)?;
+            let metadata = entry.metadata().ok().filter(|m| m.is_file())?;
+            let backup = BackupEntry {
+                path: entry.path().to_string_lossy().to_string(),
+                created: created.format("%Y-%m-%dT%H:%M:%S").to_string(),
+                size: metadata.len(),
+            };
+            Some((created, counter, backup))
+        })
+        .collect();
+    backups.sort_by_key(|(created, counter, _)| std::cmp::Reverse((*created, *counter)));
+    Ok(backups.into_iter().map(|(_, _, backup)| backup).collect())
+}
+
+/// `20250101_120000` or `20250101_120000-3` → timestamp and counter (1 for
+/// the first backup of that second).
+fn parse_backup_suffix(suffix: &str) -> Option<(NaiveDateTime, u32)> {
+    let (timestamp, counter) = match suffix.split_once('-') {
+        Some((timestamp, counter)) => (timestamp, counter.parse().ok()?),
+        None => (suffix, 1),
+    };
+    Some((NaiveDateTime::parse_from_str(timestamp, TIMESTAMP_FORMAT).ok()?, counter))
+}
+
+/// Replace `original_path` with the contents of `backup_path` (temp file +
+/// rename, so a failed restore leaves the original intact). The backup is
+/// kept.
+#[tauri::command]
+pub fn restore_file_backup(backup_path: String, original_path: String) -> Result<FileEntry, AppError> {
+    let backup = PathBuf::from(&backup_path);
+    if !backup.is_file() {
+        return Err(AppError::NotFound(backup_path));
+    }
+    let original = PathBuf::from(&original_path);
+    if original.is_dir() {
+        return Err(AppError::InvalidPath(format!("{} is a directory", original_path)));
+    }
+
+    let tmp = original.with_file_name(format!(".{}.restore-tmp", file_name(&original)?));
+    fs::copy(&backup, &tmp)?;
+    if let Err(e) = fs::rename(&tmp, &original) {
+        let _ = fs::remove_file(&tmp);
+        return Err(e.into());
+    }
+    log::info!("Restored {:?} from {:?}", original, backup);
+    Ok(metadata_to_entry(&original, &fs::metadata(&original)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_backup_list_and_restore() {
+        let dir = tempdir().unwrap();
+        let file = dir.path().join("notes.txt");
+        let path = file.to_string_lossy().to_string();
+        fs::write(&file, "first").unwrap();
+
+        let first = backup_file(path.clone(), None).unwrap();
+        fs::write(&file, "second").unwrap();
+        let second = backup_file(path.clone(), None).unwrap();
+        assert_ne!(first, second);
+        assert!(Path::new(&first).starts_with(dir.path().join(".backups")));
+        fs::write(dir.path().join(".backups/notes.txt.bak"), "not a backup").unwrap();
+        fs::write(dir.path().join(".backups/other.txt.20250101_120000"), "other file").unwrap();
+
+        let backups = list_file_backups(path.clone(), None).unwrap();
+        let paths: Vec<&str> = backups.iter().map(|b| b.path.as_str()).collect();
+        assert_eq!(paths, [second.as_str(), first.as_str()]);
+        assert_eq!(backups[1].size, 5);
+
+        fs::write(&file, "edited").unwrap();
+        let restored = restore_file_backup(first, path.clone()).unwrap();
+        assert_eq!(restored.size, 5);
+        assert_eq!(fs::read_to_string(&file).unwrap(), "first");
+
+        let custom = dir.path().join("elsewhere");
+        let backup = backup_file(path.clone(), Some(custom.to_string_lossy().to_string())).unwrap();
+        assert!(Path::new(&backup).starts_with(&custom));
+        assert_eq!(list_file_backups(path, Some(custom.to_string_lossy().to_string())).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_backup_errors() {
+        let dir = tempdir().unwrap();
+        let missing = dir.path().join("missing.txt").to_string_lossy().to_string();
+        assert!(matches!(backup_file(missing.clone(), None), Err(AppError::NotFound(_))));
+        assert!(matches!(
+            backup_file(dir.path().to_string_lossy().to_string(), None),
+            Err(AppError::InvalidPath(_))
+        ));
+        assert!(list_file_backups(missing.clone(), None).unwrap().is_empty());
+        assert!(matches!(restore_file_backup(missing.clone(), missing), Err(AppError::NotFound(_))));
+        assert_eq!(parse_backup_suffix("20250101_120000-3").unwrap().1, 3);
+        assert!(parse_backup_suffix("bak").is_none());
+    }
+}
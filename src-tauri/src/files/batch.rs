@@ -0,0 +1,940 @@
+//! Batch copy and move of many entries in one background operation, with
+//! progress events, cancellation and interactive conflict resolution.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fs;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter};
+
+use super::file_ops::{estimate_path_size, generate_copy_name_with, replace_existing};
+use crate::error::AppError;
+use crate::task_registry::TaskRegistry;
+
+static BATCH_OPERATIONS: TaskRegistry = TaskRegistry::new();
+
+/// Worker threads copying files in parallel.
+const BATCH_WORKERS: usize = 4;
+
+/// Minimum interval between progress events while items are in flight.
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Progress events the throughput behind `eta_ms` is averaged over.
+const THROUGHPUT_WINDOW: usize = 5;
+
+/// Event payload for `batch-copy-progress` and `batch-move-progress`.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct BatchProgress {
+    #[serde(rename = "operationId")]
+    pub operation_id: u64,
+    /// Top-level sources finished (copied or failed).
+    pub completed: usize,
+    pub total: usize,
+    #[serde(rename = "currentFile")]
+    pub current_file: String,
+    #[serde(rename = "bytesTotal")]
+    pub bytes_total: u64,
+    #[serde(rename = "bytesCopied")]
+    pub bytes_copied: u64,
+    /// `bytes_copied` as a share of `bytes_total`, 0.0 to 100.0.
+    #[serde(rename = "percentComplete")]
+    pub percent_complete: f32,
+    #[serde(rename = "elapsedMs")]
+    pub elapsed_ms: u64,
+    /// Time left at the throughput of the last few events; `None` until
+    /// bytes have been copied across at least two of them.
+    #[serde(rename = "etaMs")]
+    pub eta_ms: Option<u64>,
+    /// `"<source>: <error>"` for every source that failed.
+    pub errors: Vec<String>,
+    /// Set on the final event, also after cancellation.
+    pub done: bool,
+}
+
+/// Shared progress, updated by the workers and throttled into events.
+struct BatchTracker<'a> {
+    state: Mutex<TrackerState>,
+    started: Instant,
+    cancelled: &'a AtomicBool,
+    emit: &'a (dyn Fn(BatchProgress) + Sync),
+}
+
+struct TrackerState {
+    progress: BatchProgress,
+    last_emit: Instant,
+    /// `(time, bytes_copied)` at the last `THROUGHPUT_WINDOW` events.
+    samples: VecDeque<(Instant, u64)>,
+}
+
+impl TrackerState {
+    /// Fill in the timing fields for an event sent at `now`.
+    fn stamp(&mut self, started: Instant, now: Instant) {
+        let progress = &mut self.progress;
+        progress.elapsed_ms = now.duration_since(started).as_millis() as u64;
+        progress.percent_complete = percent_complete(progress.bytes_copied, progress.bytes_total);
+        if self.samples.len() == THROUGHPUT_WINDOW {
+            self.samples.pop_front();
+        }
+        self.samples.push_back((now, progress.bytes_copied));
+        progress.eta_ms = eta_ms(&self.samples, progress.bytes_total.saturating_sub(progress.bytes_copied));
+    }
+}
+
+fn percent_complete(bytes_copied: u64, bytes_total: u64) -> f32 {
+    if bytes_total == 0 {
+        return 0.0;
+    }
+    (bytes_copied as f64 / bytes_total as f64 * 100.0).clamp(0.0, 100.0) as f32
+}
+
+/// `remaining` bytes divided by the throughput between the oldest and
+/// newest sample. `None` while that throughput is zero.
+fn eta_ms(samples: &VecDeque<(Instant, u64)>, remaining: u64) -> Option<u64> {
+    let (&(first_at, first_bytes), &(last_at, last_bytes)) = (samples.front()?, samples.back()?);
+    let ms = last_at.duration_since(first_at).as_secs_f64() * 1000.0;
+    let bytes = last_bytes.saturating_sub(first_bytes) as f64;
+    if ms <= 0.0 || bytes <= 0.0 {
+        return None;
+    }
+    Some((remaining as f64 / (bytes / ms)).round() as u64)
+}
+
+impl<'a> BatchTracker<'a> {
+    fn new(
+        operation_id: u64,
+        total: usize,
+        bytes_total: u64,
+        cancelled: &'a AtomicBool,
+        emit: &'a (dyn Fn(BatchProgress) + Sync),
+    ) -> Self {
+        let progress = BatchProgress {
+            operation_id,
+            total,
+            bytes_total,
+            ..Default::default()
+        };
+        let now = Instant::now();
+        Self {
+            state: Mutex::new(TrackerState {
+                progress,
+                last_emit: now,
+                samples: VecDeque::with_capacity(THROUGHPUT_WINDOW),
+            }),
+            started: now,
+            cancelled,
+            emit,
+        }
+    }
+
+    fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+
+    fn update(&self, force: bool, f: impl FnOnce(&mut BatchProgress)) {
+        let snapshot = {
+            let mut state = self.state.lock().unwrap();
+            f(&mut state.progress);
+            let now = Instant::now();
+            if !force && now.duration_since(state.last_emit) < PROGRESS_INTERVAL {
+                return;
+            }
+            state.last_emit = now;
+            state.stamp(self.started, now);
+            state.progress.clone()
+        };
+        (self.emit)(snapshot);
+    }
+
+    fn add_bytes(&self, path: &Path, bytes: u64) {
+        self.update(false, |p| {
+            p.bytes_copied += bytes;
+            p.current_file = path.to_string_lossy().to_string();
+        });
+    }
+
+    fn item_done(&self, source: &Path, result: Result<(), AppError>) {
+        self.update(true, |p| {
+            p.completed += 1;
+            if let Err(e) = result {
+                p.errors.push(format!("{}: {}", source.display(), e));
+            }
+        });
+    }
+
+    fn finish(self) -> BatchProgress {
+        let mut state = self.state.into_inner().unwrap();
+        state.stamp(self.started, Instant::now());
+        let mut progress = state.progress;
+        progress.done = true;
+        progress.current_file.clear();
+        (self.emit)(progress.clone());
+        progress
+    }
+}
+
+fn worker_pool() -> Option<&'static rayon::ThreadPool> {
+    static POOL: OnceLock<Option<rayon::ThreadPool>> = OnceLock::new();
+    POOL.get_or_init(|| {
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(BATCH_WORKERS)
+            .thread_name(|i| format!("batch-copy-{}", i))
+            .build()
+            .map_err(|e| log::warn!("Failed to start batch copy pool: {}", e))
+            .ok()
+    })
+    .as_ref()
+}
+
+/// Copy every entry in `sources` into `dest_dir` in the background.
+///
+/// Returns an operation ID immediately; progress arrives as
+/// `batch-copy-progress` events, the last one with `done: true`. Name
+/// clashes get a "name - Copy" suffix like `copy_entry`. Files are copied
+/// by 4 workers in parallel, directories one at a time.
+#[tauri::command]
+pub fn copy_multiple_entries(app: AppHandle, sources: Vec<String>, dest_dir: String) -> Result<u64, AppError> {
+    let dest = PathBuf::from(&dest_dir);
+    if !dest.is_dir() {
+        return Err(AppError::NotFound(format!(
+            "Destination directory does not exist: {}",
+            dest_dir
+        )));
+    }
+    if sources.is_empty() {
+        return Err(AppError::Other("No paths provided".into()));
+    }
+
+    let (operation_id, cancelled) = BATCH_OPERATIONS.start();
+    std::thread::spawn(move || {
+        let sources: Vec<PathBuf> = sources.iter().map(PathBuf::from).collect();
+        let result = copy_batch(operation_id, &sources, &dest, &cancelled, &|progress| {
+            let _ = app.emit("batch-copy-progress", progress);
+        });
+        log::info!(
+            "Batch copy {} finished: {}/{} items, {} errors",
+            operation_id,
+            result.completed,
+            result.total,
+            result.errors.len()
+        );
+        BATCH_OPERATIONS.cleanup(operation_id);
+    });
+    Ok(operation_id)
+}
+
+/// Cancel a running batch operation. Items already copied are kept.
+#[tauri::command]
+pub fn cancel_batch_operation(operation_id: u64) -> Result<(), AppError> {
+    BATCH_OPERATIONS.cancel(operation_id);
+    Ok(())
+}
+
+pub(crate) fn copy_batch(
+    operation_id: u64,
+    sources: &[PathBuf],
+    dest: &Path,
+    cancelled: &AtomicBool,
+    emit: &(dyn Fn(BatchProgress) + Sync),
+) -> BatchProgress {
+    let (mut file_count, mut bytes_total) = (0, 0);
+    for source in sources {
+        estimate_path_size(source, &mut file_count, &mut bytes_total);
+    }
+    let tracker = BatchTracker::new(operation_id, sources.len(), bytes_total, cancelled, emit);
+
+    // Pick targets up front so parallel workers never race for the same name
+    let mut planned: HashSet<PathBuf> = HashSet::new();
+    let mut files = Vec::new();
+    let mut dirs = Vec::new();
+    for source in sources {
+        match plan_target(source, dest, &planned) {
+            Ok(target) => {
+                planned.insert(target.clone());
+                if source.is_dir() {
+                    dirs.push((source.as_path(), target));
+                } else {
+                    files.push((source.as_path(), target));
+                }
+            }
+            Err(e) => tracker.item_done(source, Err(e)),
+        }
+    }
+
+    let copy_one = |(source, target): &(&Path, PathBuf)| {
+        if tracker.is_cancelled() {
+            return;
+        }
+        let result = copy_file_tracked(source, target, &tracker);
+        tracker.item_done(source, result);
+    };
+    match worker_pool() {
+        Some(pool) => pool.install(|| files.par_iter().for_each(copy_one)),
+        None => files.iter().for_each(copy_one),
+    }
+
+    for (source, target) in &dirs {
+        if tracker.is_cancelled() {
+            break;
+        }
+        let result = copy_tree_tracked(source, target, &tracker);
+        tracker.item_done(source, result);
+    }
+
+    tracker.finish()
+}
+
+/// Target path for `source` inside `dest`, adding a copy suffix when the
+/// name is taken on disk or by an earlier item of the batch.
+fn plan_target(source: &Path, dest: &Path, planned: &HashSet<PathBuf>) -> Result<PathBuf, AppError> {
+    if !source.exists() {
+        return Err(AppError::NotFound(source.to_string_lossy().to_string()));
+    }
+    let name = source
+        .file_name()
+        .ok_or_else(|| AppError::InvalidPath("Invalid source path".to_string()))?
+        .to_string_lossy()
+        .to_string();
+    if source.is_dir() && is_inside(dest, source) {
+        return Err(AppError::InvalidPath(format!(
+            "Cannot copy a folder into itself: {}",
+            source.display()
+        )));
+    }
+
+    let taken = |p: &Path| p.exists() || planned.contains(p);
+    let target = dest.join(&name);
+    if !taken(&target) {
+        return Ok(target);
+    }
+    Ok(generate_copy_name_with(dest, &name, source.is_dir(), taken))
+}
+
+/// Whether `dest` is the folder `source` or lies inside it. Both paths are
+/// canonicalized when they exist, so `..` or a symlinked parent can't hide it.
+fn is_inside(dest: &Path, source: &Path) -> bool {
+    match (fs::canonicalize(dest), fs::canonicalize(source)) {
+        (Ok(dest), Ok(source)) => dest.starts_with(source),
+        _ => dest.starts_with(source),
+    }
+}
+
+/// Copy one file in chunks so progress and cancellation stay responsive
+/// for large files. A copy that is cancelled or fails part way removes its
+/// partial target.
+fn copy_file_tracked(source: &Path, target: &Path, tracker: &BatchTracker) -> Result<(), AppError> {
+    let meta = fs::symlink_metadata(source)?;
+    if meta.file_type().is_symlink() {
+        return copy_symlink(source, target);
+    }
+
+    let mut reader = fs::File::open(source)?;
+    let mut writer = fs::File::create(target)?;
+    let mut copy = || -> Result<(), AppError> {
+        let mut buf = vec![0u8; 256 * 1024];
+        loop {
+            if tracker.is_cancelled() {
+                return Err(AppError::Other("Copy cancelled".into()));
+            }
+            let n = reader.read(&mut buf)?;
+            if n == 0 {
+                break;
+            }
+            writer.write_all(&buf[..n])?;
+            tracker.add_bytes(source, n as u64);
+        }
+        writer.set_permissions(meta.permissions())?;
+        Ok(())
+    };
+    let result = copy();
+    if result.is_err() {
+        drop(writer);
+        if let Err(e) = fs::remove_file(target) {
+            log::warn!("Failed to remove partial copy {}: {}", target.display(), e);
+        }
+    }
+    result
+}
+
+/// Copy a directory tree. A cancelled copy removes the partial tree when
+/// `target` didn't exist before.
+fn copy_tree_tracked(source: &Path, target: &Path, tracker: &BatchTracker) -> Result<(), AppError> {
+    let fresh = fs::symlink_metadata(target).is_err();
+    let result = copy_tree_contents(source, target, tracker);
+    if result.is_err() && fresh && tracker.is_cancelled() {
+        if let Err(e) = fs::remove_dir_all(target) {
+            log::warn!("Failed to remove partial copy {}: {}", target.display(), e);
+        }
+    }
+    result
+}
+
+fn copy_tree_contents(source: &Path, target: &Path, tracker: &BatchTracker) -> Result<(), AppError> {
+    fs::create_dir_all(target)?;
+    for entry in fs::read_dir(source)? {
+        if tracker.is_cancelled() {
+            return Err(AppError::Other("Copy cancelled".into()));
+        }
+        let entry = entry?;
+        let path = entry.path();
+        let dest = target.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            copy_tree_contents(&path, &dest, tracker)?;
+        } else {
+            copy_file_tracked(&path, &dest, tracker)?;
+        }
+    }
+    Ok(())
+}
+
+/// Recreate a symlink rather than copying what it points to.
+pub(crate) fn copy_symlink(source: &Path, target: &Path) -> Result<(), AppError> {
+    #[cfg(unix)]
+    {
+        std::os::unix::fs::symlink(fs::read_link(source)?, target)?;
+    }
+    #[cfg(not(unix))]
+    {
+        fs::copy(source, target)?;
+    }
+    Ok(())
+}
+
+// ── Batch move ──────────────────────────────────────────────────────────────
+
+/// A resolution plus whether it applies to the rest of the batch.
+type ConflictAnswer = (ConflictResolution, bool);
+
+/// Senders for move operations currently blocked on a name conflict.
+static PENDING_CONFLICTS: OnceLock<Mutex<HashMap<u64, mpsc::Sender<ConflictAnswer>>>> = OnceLock::new();
+
+/// How long a blocked move waits between cancellation checks.
+const CONFLICT_POLL: Duration = Duration::from_millis(200);
+
+fn pending_conflicts() -> &'static Mutex<HashMap<u64, mpsc::Sender<ConflictAnswer>>> {
+    PENDING_CONFLICTS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Answer to a `batch-move-conflict` event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ConflictResolution {
+    /// Merge a folder into the existing one; clashing files inside stay in
+    /// the source and are reported. Same as `Skip` for files.
+    Continue,
+    /// Replace the existing entry. It's only deleted once the move has
+    /// succeeded.
+    Overwrite,
+    /// Leave the source where it is.
+    Skip,
+    /// Move under a "name - Copy" name instead.
+    Rename,
+}
+
+/// Event payload for `batch-move-conflict`.
+#[derive(Debug, Clone, Serialize)]
+pub struct MoveConflict {
+    #[serde(rename = "operationId")]
+    pub operation_id: u64,
+    pub source: String,
+    pub target: String,
+    #[serde(rename = "isDirectory")]
+    pub is_directory: bool,
+}
+
+/// Move every entry in `sources` into `dest_dir` in the background.
+///
+/// Returns an operation ID; progress arrives as `batch-move-progress`
+/// events (same shape as `batch-copy-progress`). When a name is already
+/// taken a `batch-move-conflict` event is emitted and the move waits for
+/// `resolve_move_conflict`. Same-filesystem moves are renames; across
+/// filesystems the sources are copied and only deleted once the whole
+/// batch has been copied and verified.
+#[tauri::command]
+pub fn move_multiple_entries(app: AppHandle, sources: Vec<String>, dest_dir: String) -> Result<u64, AppError> {
+    let dest = PathBuf::from(&dest_dir);
+    if !dest.is_dir() {
+        return Err(AppError::NotFound(format!(
+            "Destination directory does not exist: {}",
+            dest_dir
+        )));
+    }
+    if sources.is_empty() {
+        return Err(AppError::Other("No paths provided".into()));
+    }
+
+    let (operation_id, cancelled) = BATCH_OPERATIONS.start();
+    let (tx, rx) = mpsc::channel();
+    pending_conflicts().lock().unwrap().insert(operation_id, tx);
+
+    std::thread::spawn(move || {
+        let sources: Vec<PathBuf> = sources.iter().map(PathBuf::from).collect();
+        let progress_app = app.clone();
+        let result = move_batch(
+            operation_id,
+            &sources,
+            &dest,
+            &cancelled,
+            &|progress| {
+                let _ = progress_app.emit("batch-move-progress", progress);
+            },
+            &mut |conflict| {
+                let _ = app.emit("batch-move-conflict", conflict);
+                loop {
+                    match rx.recv_timeout(CONFLICT_POLL) {
+                        Ok(answer) => return Some(answer),
+                        Err(mpsc::RecvTimeoutError::Timeout) if !cancelled.load(Ordering::Relaxed) => {}
+                        Err(_) => return None,
+                    }
+                }
+            },
+        );
+        log::info!(
+            "Batch move {} finished: {}/{} items, {} errors",
+            operation_id,
+            result.completed,
+            result.total,
+            result.errors.len()
+        );
+        pending_conflicts().lock().unwrap().remove(&operation_id);
+        BATCH_OPERATIONS.cleanup(operation_id);
+    });
+    Ok(operation_id)
+}
+
+/// Answer the pending `batch-move-conflict` of a move operation. With
+/// `apply_to_all`, later conflicts in the batch are resolved the same way
+/// without asking.
+#[tauri::command]
+pub fn resolve_move_conflict(
+    operation_id: u64,
+    resolution: ConflictResolution,
+    apply_to_all: Option<bool>,
+) -> Result<(), AppError> {
+    let pending = pending_conflicts().lock().unwrap();
+    let sender = pending
+        .get(&operation_id)
+        .ok_or_else(|| AppError::Other(format!("No move operation {} is running", operation_id)))?;
+    sender
+        .send((resolution, apply_to_all.unwrap_or(false)))
+        .map_err(|_| AppError::Other(format!("Move operation {} has finished", operation_id)))
+}
+
+/// A cross-filesystem item copied to its target, waiting for the source
+/// to be deleted.
+struct PendingDelete {
+    source: PathBuf,
+    target: PathBuf,
+    file_count: u64,
+    bytes: u64,
+}
+
+pub(crate) fn move_batch(
+    operation_id: u64,
+    sources: &[PathBuf],
+    dest: &Path,
+    cancelled: &AtomicBool,
+    emit: &(dyn Fn(BatchProgress) + Sync),
+    resolve: &mut dyn FnMut(MoveConflict) -> Option<ConflictAnswer>,
+) -> BatchProgress {
+    let (mut file_count, mut bytes_total) = (0, 0);
+    for source in sources {
+        estimate_path_size(source, &mut file_count, &mut bytes_total);
+    }
+    let tracker = BatchTracker::new(operation_id, sources.len(), bytes_total, cancelled, emit);
+    let mut remembered: Option<ConflictResolution> = None;
+    let mut pending: Vec<PendingDelete> = Vec::new();
+
+    for source in sources {
+        if tracker.is_cancelled() {
+            break;
+        }
+        let result = move_one(source, dest, &tracker, &mut pending, &mut |target| {
+            if let Some(choice) = remembered {
+                return Some(choice);
+            }
+            let (choice, apply_to_all) = resolve(MoveConflict {
+                operation_id,
+                source: source.to_string_lossy().to_string(),
+                target: target.to_string_lossy().to_string(),
+                is_directory: source.is_dir(),
+            })?;
+            if apply_to_all {
+                remembered = Some(choice);
+            }
+            Some(choice)
+        });
+        tracker.item_done(source, result);
+    }
+
+    // Cross-filesystem sources go only once everything has landed intact
+    if !tracker.is_cancelled() {
+        for item in pending {
+            let (mut count, mut bytes) = (0, 0);
+            estimate_path_size(&item.target, &mut count, &mut bytes);
+            let removed = if (count, bytes) != (item.file_count, item.bytes) {
+                Err(AppError::Other("copy could not be verified; source kept".into()))
+            } else if item.source.is_dir() {
+                fs::remove_dir_all(&item.source).map_err(AppError::from)
+            } else {
+                fs::remove_file(&item.source).map_err(AppError::from)
+            };
+            if let Err(e) = removed {
+                tracker.update(true, |p| p.errors.push(format!("{}: {}", item.source.display(), e)));
+            }
+        }
+    }
+
+    tracker.finish()
+}
+
+/// Move a single top-level source, asking `resolve` when its name is taken.
+fn move_one(
+    source: &Path,
+    dest: &Path,
+    tracker: &BatchTracker,
+    pending: &mut Vec<PendingDelete>,
+    resolve: &mut dyn FnMut(&Path) -> Option<ConflictResolution>,
+) -> Result<(), AppError> {
+    if fs::symlink_metadata(source).is_err() {
+        return Err(AppError::NotFound(source.to_string_lossy().to_string()));
+    }
+    let name = source
+        .file_name()
+        .ok_or_else(|| AppError::InvalidPath("Invalid source path".to_string()))?
+        .to_string_lossy()
+        .to_string();
+    let is_dir = source.is_dir();
+    if is_dir && is_inside(dest, source) {
+        return Err(AppError::InvalidPath(format!(
+            "Cannot move a folder into itself: {}",
+            source.display()
+        )));
+    }
+
+    let mut target = dest.join(&name);
+    if target == source {
+        return Ok(());
+    }
+    if fs::symlink_metadata(&target).is_ok() {
+        match resolve(&target).ok_or_else(|| AppError::Other("Move cancelled".into()))? {
+            ConflictResolution::Skip => return Ok(()),
+            ConflictResolution::Continue if is_dir && target.is_dir() => {
+                return merge_into(source, &target, tracker, pending);
+            }
+            ConflictResolution::Continue => return Ok(()),
+            ConflictResolution::Rename => {
+                target = generate_copy_name_with(dest, &name, is_dir, |p| fs::symlink_metadata(p).is_ok());
+            }
+            ConflictResolution::Overwrite => {
+                return replace_existing(source, &target, || move_path(source, &target, tracker, pending));
+            }
+        }
+    }
+    move_path(source, &target, tracker, pending)
+}
+
+/// Rename `source` to `target`, or copy it and queue the source for
+/// deletion when they are on different filesystems.
+fn move_path(
+    source: &Path,
+    target: &Path,
+    tracker: &BatchTracker,
+    pending: &mut Vec<PendingDelete>,
+) -> Result<(), AppError> {
+    let (mut file_count, mut bytes) = (0, 0);
+    estimate_path_size(source, &mut file_count, &mut bytes);
+
+    match fs::rename(source, target) {
+        Ok(()) => {
+            tracker.add_bytes(source, bytes);
+            Ok(())
+        }
+        // EXDEV on Unix, ERROR_NOT_SAME_DEVICE on Windows
+        Err(e) if e.kind() == std::io::ErrorKind::CrossesDevices => {
+            log::info!("Cross-device move of {:?}, copying", source);
+            let copied = if source.is_dir() {
+                copy_tree_tracked(source, target, tracker)
+            } else {
+                copy_file_tracked(source, target, tracker)
+            };
+            copied?;
+            pending.push(PendingDelete {
+                source: source.to_path_buf(),
+                target: target.to_path_buf(),
+                file_count,
+                bytes,
+            });
+            Ok(())
+        }
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Move the children of `source` into the existing directory `target`,
+/// recursing into folders present on both sides. Clashing files are left
+/// in place and reported; `source` is removed if it ends up empty.
+fn merge_into(
+    source: &Path,
+    target: &Path,
+    tracker: &BatchTracker,
+    pending: &mut Vec<PendingDelete>,
+) -> Result<(), AppError> {
+    let mut clashes = Vec::new();
+    for entry in fs::read_dir(source)? {
+        let entry = entry?;
+        let child = entry.path();
+        let child_target = target.join(entry.file_name());
+        if fs::symlink_metadata(&child_target).is_err() {
+            move_path(&child, &child_target, tracker, pending)?;
+        } else if entry.file_type()?.is_dir() && child_target.is_dir() {
+            if let Err(e) = merge_into(&child, &child_target, tracker, pending) {
+                clashes.push(e.to_string());
+            }
+        } else {
+            clashes.push(child_target.to_string_lossy().to_string());
+        }
+    }
+    let _ = fs::remove_dir(source);
+    if clashes.is_empty() {
+        Ok(())
+    } else {
+        Err(AppError::AlreadyExists(clashes.join(", ")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn collect(events: &Mutex<Vec<BatchProgress>>) -> impl Fn(BatchProgress) + Sync + '_ {
+        move |p| events.lock().unwrap().push(p)
+    }
+
+    #[test]
+    fn test_copy_batch_files_and_dirs() {
+        let dir = tempdir().unwrap();
+        let src = dir.path().join("src");
+        let dest = dir.path().join("dest");
+        fs::create_dir_all(src.join("folder/sub")).unwrap();
+        fs::create_dir(&dest).unwrap();
+        for i in 0..10 {
+            fs::write(src.join(format!("file{}.txt", i)), format!("content {}", i)).unwrap();
+        }
+        fs::write(src.join("folder/sub/deep.txt"), "deep").unwrap();
+
+        let mut sources: Vec<PathBuf> = (0..10).map(|i| src.join(format!("file{}.txt", i))).collect();
+        sources.push(src.join("folder"));
+
+        let events = Mutex::new(Vec::new());
+        let result = copy_batch(7, &sources, &dest, &AtomicBool::new(false), &collect(&events));
+
+        assert!(result.done);
+        assert_eq!((result.completed, result.total), (11, 11));
+        assert!(result.errors.is_empty(), "{:?}", result.errors);
+        assert_eq!(result.bytes_copied, result.bytes_total);
+        assert_eq!(result.percent_complete, 100.0);
+        assert_eq!(fs::read_to_string(dest.join("file3.txt")).unwrap(), "content 3");
+        assert_eq!(fs::read_to_string(dest.join("folder/sub/deep.txt")).unwrap(), "deep");
+        assert!(events.lock().unwrap().iter().all(|e| e.operation_id == 7));
+    }
+
+    #[test]
+    fn test_progress_percent_and_eta() {
+        assert_eq!(percent_complete(0, 0), 0.0);
+        assert_eq!(percent_complete(25, 100), 25.0);
+        assert_eq!(percent_complete(150, 100), 100.0);
+
+        let start = Instant::now();
+        let mut samples = VecDeque::from([(start, 0)]);
+        assert_eq!(eta_ms(&samples, 1000), None);
+        samples.push_back((start + Duration::from_millis(100), 0));
+        assert_eq!(eta_ms(&samples, 1000), None);
+        // 500 bytes per 200ms
+        samples.push_back((start + Duration::from_millis(200), 500));
+        assert_eq!(eta_ms(&samples, 1000), Some(400));
+    }
+
+    #[test]
+    fn test_copy_batch_name_clashes_and_errors() {
+        let dir = tempdir().unwrap();
+        let (a, b, dest) = (dir.path().join("a"), dir.path().join("b"), dir.path().join("dest"));
+        for d in [&a, &b, &dest] {
+            fs::create_dir(d).unwrap();
+        }
+        fs::write(a.join("same.txt"), "from a").unwrap();
+        fs::write(b.join("same.txt"), "from b").unwrap();
+        fs::write(dest.join("same.txt"), "existing").unwrap();
+
+        let sources = vec![a.join("same.txt"), b.join("same.txt"), a.join("missing.txt"), dir.path().to_path_buf()];
+        let result = copy_batch(1, &sources, &dest, &AtomicBool::new(false), &|_| {});
+
+        assert_eq!(result.completed, 4);
+        assert_eq!(result.errors.len(), 2, "{:?}", result.errors);
+        assert_eq!(fs::read_to_string(dest.join("same.txt")).unwrap(), "existing");
+        let mut copies: Vec<String> = vec![
+            fs::read_to_string(dest.join("same - Copy.txt")).unwrap(),
+            fs::read_to_string(dest.join("same - Copy (2).txt")).unwrap(),
+        ];
+        copies.sort();
+        assert_eq!(copies, vec!["from a", "from b"]);
+    }
+
+    #[test]
+    fn test_copy_batch_cancelled() {
+        let dir = tempdir().unwrap();
+        let dest = dir.path().join("dest");
+        fs::create_dir(&dest).unwrap();
+        let source = dir.path().join("big.bin");
+        fs::write(&source, vec![0u8; 1024 * 1024]).unwrap();
+
+        let result = copy_batch(1, &[source], &dest, &AtomicBool::new(true), &|_| {});
+        assert!(result.done);
+        assert_eq!(result.completed, 0);
+        assert!(!dest.join("big.bin").exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_copy_file_failure_removes_partial_target() {
+        let dir = tempdir().unwrap();
+        let target = dir.path().join("copy");
+        let (cancelled, emit) = (AtomicBool::new(false), |_: BatchProgress| {});
+        let tracker = BatchTracker::new(1, 1, 1, &cancelled, &emit);
+
+        // A directory opens as a file but fails on the first read
+        assert!(copy_file_tracked(dir.path(), &target, &tracker).is_err());
+        assert!(!target.exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_copy_batch_refuses_folder_into_itself_through_link() {
+        let dir = tempdir().unwrap();
+        let folder = dir.path().join("folder");
+        fs::create_dir_all(folder.join("inner")).unwrap();
+        fs::create_dir(dir.path().join("other")).unwrap();
+        std::os::unix::fs::symlink(&folder, dir.path().join("link")).unwrap();
+
+        for dest in [dir.path().join("link/inner"), dir.path().join("other/../folder/inner")] {
+            let result = copy_batch(1, std::slice::from_ref(&folder), &dest, &AtomicBool::new(false), &|_| {});
+            assert_eq!(result.errors.len(), 1, "{:?}", result.errors);
+        }
+        assert_eq!(fs::read_dir(folder.join("inner")).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn test_copy_tree_cancelled_removes_partial_tree() {
+        let dir = tempdir().unwrap();
+        let (source, target) = (dir.path().join("src"), dir.path().join("copy"));
+        fs::create_dir_all(source.join("sub")).unwrap();
+        fs::write(source.join("sub/a.txt"), "a").unwrap();
+
+        let (cancelled, emit) = (AtomicBool::new(true), |_: BatchProgress| {});
+        let tracker = BatchTracker::new(1, 1, 1, &cancelled, &emit);
+        assert!(copy_tree_tracked(&source, &target, &tracker).is_err());
+        assert!(!target.exists());
+
+        // An existing target is left alone
+        fs::create_dir(&target).unwrap();
+        fs::write(target.join("keep.txt"), "keep").unwrap();
+        assert!(copy_tree_tracked(&source, &target, &tracker).is_err());
+        assert!(target.join("keep.txt").exists());
+    }
+
+    // ── move_multiple_entries ───────────────────────────────────────────────
+
+    fn move_with(
+        sources: &[PathBuf],
+        dest: &Path,
+        answer: Option<ConflictAnswer>,
+    ) -> (BatchProgress, Vec<MoveConflict>) {
+        let mut conflicts = Vec::new();
+        let result = move_batch(1, sources, dest, &AtomicBool::new(false), &|_| {}, &mut |c| {
+            conflicts.push(c);
+            answer
+        });
+        (result, conflicts)
+    }
+
+    #[test]
+    fn test_move_batch_renames() {
+        let dir = tempdir().unwrap();
+        let (src, dest) = (dir.path().join("src"), dir.path().join("dest"));
+        fs::create_dir_all(src.join("folder")).unwrap();
+        fs::create_dir(&dest).unwrap();
+        fs::write(src.join("a.txt"), "a").unwrap();
+        fs::write(src.join("folder/b.txt"), "bb").unwrap();
+
+        let (result, conflicts) = move_with(&[src.join("a.txt"), src.join("folder")], &dest, None);
+        assert!(conflicts.is_empty());
+        assert!(result.errors.is_empty(), "{:?}", result.errors);
+        assert_eq!((result.completed, result.bytes_copied), (2, 3));
+        assert!(!src.join("a.txt").exists() && !src.join("folder").exists());
+        assert_eq!(fs::read_to_string(dest.join("folder/b.txt")).unwrap(), "bb");
+    }
+
+    #[test]
+    fn test_move_batch_conflict_resolutions() {
+        let dir = tempdir().unwrap();
+        let (src, dest) = (dir.path().join("src"), dir.path().join("dest"));
+        fs::create_dir_all(&src).unwrap();
+        fs::create_dir_all(&dest).unwrap();
+        let setup = |name: &str| {
+            fs::write(src.join(name), "new").unwrap();
+            fs::write(dest.join(name), "old").unwrap();
+            vec![src.join(name)]
+        };
+
+        let (_, conflicts) = move_with(&setup("skip.txt"), &dest, Some((ConflictResolution::Skip, false)));
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].target, dest.join("skip.txt").to_string_lossy());
+        assert!(src.join("skip.txt").exists());
+        assert_eq!(fs::read_to_string(dest.join("skip.txt")).unwrap(), "old");
+
+        move_with(&setup("over.txt"), &dest, Some((ConflictResolution::Overwrite, false)));
+        assert!(!src.join("over.txt").exists());
+        assert_eq!(fs::read_to_string(dest.join("over.txt")).unwrap(), "new");
+        assert_eq!(fs::read_dir(&dest).unwrap().count(), 2, "replaced entry left behind");
+
+        move_with(&setup("both.txt"), &dest, Some((ConflictResolution::Rename, false)));
+        assert_eq!(fs::read_to_string(dest.join("both.txt")).unwrap(), "old");
+        assert_eq!(fs::read_to_string(dest.join("both - Copy.txt")).unwrap(), "new");
+
+        // No answer (cancelled while waiting) fails just that item
+        let (result, _) = move_with(&setup("wait.txt"), &dest, None);
+        assert_eq!(result.errors.len(), 1);
+        assert!(src.join("wait.txt").exists());
+    }
+
+    #[test]
+    fn test_move_batch_apply_to_all_and_merge() {
+        let dir = tempdir().unwrap();
+        let (src, dest) = (dir.path().join("src"), dir.path().join("dest"));
+        fs::create_dir_all(src.join("docs/inner")).unwrap();
+        fs::create_dir_all(dest.join("docs/inner")).unwrap();
+        fs::write(src.join("docs/new.txt"), "new").unwrap();
+        fs::write(src.join("docs/inner/clash.txt"), "mine").unwrap();
+        fs::write(dest.join("docs/inner/clash.txt"), "theirs").unwrap();
+        fs::write(src.join("x.txt"), "x").unwrap();
+        fs::write(dest.join("x.txt"), "old x").unwrap();
+
+        let (result, conflicts) = move_with(
+            &[src.join("docs"), src.join("x.txt")],
+            &dest,
+            Some((ConflictResolution::Continue, true)),
+        );
+        assert_eq!(conflicts.len(), 1, "apply_to_all answers the second conflict");
+        assert_eq!(fs::read_to_string(dest.join("docs/new.txt")).unwrap(), "new");
+        assert_eq!(fs::read_to_string(dest.join("docs/inner/clash.txt")).unwrap(), "theirs");
+        assert!(src.join("docs/inner/clash.txt").exists());
+        assert_eq!(result.errors.len(), 1, "{:?}", result.errors);
+        assert!(src.join("x.txt").exists(), "continue skips clashing files");
+    }
+}
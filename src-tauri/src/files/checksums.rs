@@ -0,0 +1,610 @@
+//! Content hashes for every file in a tree, and verifying a tree against
+//! them, for checking that a copy (e.g. on a backup drive) arrived intact.
+//!
+//! Sidecar files use the GNU `sha256sum` format: `<hex hash>  <path>`, with
+//! paths relative to the root and `/`-separated.
+
+use std::collections::HashSet;
+use std::fs;
+use std::io::{BufReader, Write};
+use std::path::{Component, Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+use jwalk::WalkDir;
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256, Sha512};
+use tauri::{AppHandle, Emitter};
+
+use crate::error::AppError;
+use crate::task_registry::TaskRegistry;
+
+/// Running `batch_checksum` operations.
+static CHECKSUM_OPERATIONS: TaskRegistry = TaskRegistry::new();
+
+/// Hash used by `batch_checksum`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChecksumAlgorithm {
+    Sha256,
+    Sha512,
+    Blake3,
+}
+
+impl ChecksumAlgorithm {
+    /// Name of the sidecar file written into the root, as the matching
+    /// coreutils/b3sum tools would name it.
+    fn sidecar_name(self) -> &'static str {
+        match self {
+            ChecksumAlgorithm::Sha256 => ".sha256sums",
+            ChecksumAlgorithm::Sha512 => ".sha512sums",
+            ChecksumAlgorithm::Blake3 => ".b3sums",
+        }
+    }
+
+    /// The algorithm behind a checksum file: from its name when that says
+    /// (`.b3sums`, `SHA512SUMS`), otherwise from the digest length.
+    fn for_checksum_file(path: &Path, hash_len: usize) -> Option<Self> {
+        let name = path.file_name()?.to_string_lossy().to_lowercase();
+        if name.contains("b3") || name.contains("blake3") {
+            Some(ChecksumAlgorithm::Blake3)
+        } else if name.contains("sha512") || hash_len == 128 {
+            Some(ChecksumAlgorithm::Sha512)
+        } else if hash_len == 64 {
+            Some(ChecksumAlgorithm::Sha256)
+        } else {
+            None
+        }
+    }
+
+    /// Lowercase hex digest of the file's contents.
+    fn hash_file(self, path: &Path) -> Result<String, AppError> {
+        let mut file = BufReader::with_capacity(64 * 1024, fs::File::open(path)?);
+        Ok(match self {
+            ChecksumAlgorithm::Sha256 => {
+                let mut hasher = Sha256::new();
+                std::io::copy(&mut file, &mut hasher)?;
+                hex::encode(hasher.finalize())
+            }
+            ChecksumAlgorithm::Sha512 => {
+                let mut hasher = Sha512::new();
+                std::io::copy(&mut file, &mut hasher)?;
+                hex::encode(hasher.finalize())
+            }
+            ChecksumAlgorithm::Blake3 => {
+                let mut hasher = blake3::Hasher::new();
+                std::io::copy(&mut file, &mut hasher)?;
+                hasher.finalize().to_hex().to_string()
+            }
+        })
+    }
+}
+
+/// Hash of one file. `path` is relative to the root and `/`-separated.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FileChecksum {
+    pub path: String,
+    pub hash: String,
+}
+
+/// Event payload for `checksum-progress`, one per file. Files that couldn't
+/// be read have `error` set and no hash, and are left out of the results.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChecksumProgress {
+    pub operation_id: u64,
+    pub path: String,
+    pub hash: Option<String>,
+    pub error: Option<String>,
+    pub completed: usize,
+    pub total: usize,
+}
+
+/// Event payload for `checksum-complete`, sent once per operation unless it
+/// was cancelled.
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChecksumComplete {
+    pub operation_id: u64,
+    /// Sorted by path.
+    pub checksums: Vec<FileChecksum>,
+    /// Path of the written sidecar file, with `write_sidecar`.
+    pub sidecar: Option<String>,
+    /// Set when the walk or writing the sidecar failed.
+    pub error: Option<String>,
+}
+
+/// Hash every regular file under `root` in parallel, in a background
+/// thread. Returns an operation ID for `cancel_checksum`. Emits
+/// `checksum-progress` per file and `checksum-complete` at the end; with
+/// `write_sidecar` the hashes are also written to e.g. `root/.sha256sums`.
+#[tauri::command]
+pub fn batch_checksum(
+    app: AppHandle,
+    root: String,
+    algorithm: ChecksumAlgorithm,
+    include_hidden: bool,
+    write_sidecar: Option<bool>,
+) -> Result<u64, AppError> {
+    let root = PathBuf::from(root);
+    if !root.is_dir() {
+        return Err(AppError::NotFound(root.to_string_lossy().to_string()));
+    }
+
+    let (operation_id, cancelled) = CHECKSUM_OPERATIONS.start();
+    std::thread::spawn(move || {
+        let progress_app = app.clone();
+        let result = batch_checksum_sync(operation_id, &root, algorithm, include_hidden, &cancelled, &|progress| {
+            let _ = progress_app.emit("checksum-progress", progress);
+        });
+        let mut complete = ChecksumComplete { operation_id, ..Default::default() };
+        match result {
+            Ok(checksums) => {
+                if write_sidecar.unwrap_or(false) {
+                    match write_sidecar_file(&root, algorithm, &checksums) {
+                        Ok(path) => complete.sidecar = Some(path.to_string_lossy().to_string()),
+                        Err(e) => complete.error = Some(e.to_string()),
+                    }
+                }
+                complete.checksums = checksums;
+            }
+            Err(e) => complete.error = Some(e.to_string()),
+        }
+        if !cancelled.load(Ordering::Relaxed) {
+            log::info!("Checksum {} finished: {} files", operation_id, complete.checksums.len());
+            let _ = app.emit("checksum-complete", complete);
+        }
+        CHECKSUM_OPERATIONS.cleanup(operation_id);
+    });
+    Ok(operation_id)
+}
+
+/// Cancel a running `batch_checksum`. No `checksum-complete` is sent.
+#[tauri::command]
+pub fn cancel_checksum(operation_id: u64) -> Result<(), AppError> {
+    CHECKSUM_OPERATIONS.cancel(operation_id);
+    Ok(())
+}
+
+/// Hashes of the regular files under `root`, sorted by path. Stops early
+/// (returning what it has) when `cancelled` is set.
+fn batch_checksum_sync(
+    operation_id: u64,
+    root: &Path,
+    algorithm: ChecksumAlgorithm,
+    include_hidden: bool,
+    cancelled: &AtomicBool,
+    progress: &(dyn Fn(ChecksumProgress) + Sync),
+) -> Result<Vec<FileChecksum>, AppError> {
+    let (files, unreadable) = collect_files(root, include_hidden)?;
+    let total = files.len() + unreadable.len();
+    let completed = AtomicUsize::new(0);
+
+    // Folders that couldn't be read are reported like unreadable files
+    for (relative, error) in unreadable {
+        log::warn!("Skipping unreadable {}: {}", root.join(&relative).display(), error);
+        progress(ChecksumProgress {
+            operation_id,
+            path: slash_path(&relative),
+            hash: None,
+            error: Some(error),
+            completed: completed.fetch_add(1, Ordering::Relaxed) + 1,
+            total,
+        });
+    }
+
+    let mut checksums: Vec<FileChecksum> = files
+        .par_iter()
+        .filter_map(|relative| {
+            if cancelled.load(Ordering::Relaxed) {
+                return None;
+            }
+            let result = algorithm.hash_file(&root.join(relative));
+            let (hash, error) = match result {
+                Ok(hash) => (Some(hash), None),
+                Err(e) => (None, Some(e.to_string())),
+            };
+            let path = slash_path(relative);
+            progress(ChecksumProgress {
+                operation_id,
+                path: path.clone(),
+                hash: hash.clone(),
+                error,
+                completed: completed.fetch_add(1, Ordering::Relaxed) + 1,
+                total,
+            });
+            hash.map(|hash| FileChecksum { path, hash })
+        })
+        .collect();
+    checksums.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(checksums)
+}
+
+/// Root-relative paths of the regular files under `root`, leaving out
+/// sidecar files in the root itself, and of the entries the walk couldn't
+/// read with their errors. Only an unreadable `root` fails.
+fn collect_files(
+    root: &Path,
+    include_hidden: bool,
+) -> Result<(Vec<PathBuf>, Vec<(PathBuf, String)>), AppError> {
+    let sidecars = [ChecksumAlgorithm::Sha256, ChecksumAlgorithm::Sha512, ChecksumAlgorithm::Blake3]
+        .map(|algorithm| PathBuf::from(algorithm.sidecar_name()));
+    let (mut files, mut unreadable) = (Vec::new(), Vec::new());
+    for entry in WalkDir::new(root).skip_hidden(!include_hidden) {
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err(e) => {
+                let relative = e.path().and_then(|path| path.strip_prefix(root).ok()).unwrap_or(Path::new(""));
+                if relative.as_os_str().is_empty() {
+                    return Err(AppError::Other(format!("Failed to walk {}: {}", root.display(), e)));
+                }
+                unreadable.push((relative.to_path_buf(), e.to_string()));
+                continue;
+            }
+        };
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let path = entry.path();
+        let relative = path.strip_prefix(root).unwrap_or(&path).to_path_buf();
+        if !sidecars.contains(&relative) {
+            files.push(relative);
+        }
+    }
+    Ok((files, unreadable))
+}
+
+/// Write `checksums` to `root`'s sidecar file for `algorithm`, replacing it.
+fn write_sidecar_file(
+    root: &Path,
+    algorithm: ChecksumAlgorithm,
+    checksums: &[FileChecksum],
+) -> Result<PathBuf, AppError> {
+    let path = root.join(algorithm.sidecar_name());
+    let mut out = std::io::BufWriter::new(fs::File::create(&path)?);
+    for checksum in checksums {
+        writeln!(out, "{}", format_checksum_line(checksum))?;
+    }
+    out.flush()?;
+    Ok(path)
+}
+
+/// `<hash>  <path>`. Like coreutils, names containing a newline or backslash
+/// are escaped and the line gets a leading `\`.
+fn format_checksum_line(checksum: &FileChecksum) -> String {
+    if checksum.path.contains(['\n', '\\']) {
+        let escaped = checksum.path.replace('\\', "\\\\").replace('\n', "\\n");
+        format!("\\{}  {}", checksum.hash, escaped)
+    } else {
+        format!("{}  {}", checksum.hash, checksum.path)
+    }
+}
+
+/// Outcome of `verify_directory_checksums`. Paths are as listed in the
+/// checksum file (relative, `/`-separated), sorted.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct VerifyResult {
+    pub ok: Vec<String>,
+    /// Present but with a different hash, or unreadable.
+    pub corrupted: Vec<String>,
+    pub missing: Vec<String>,
+    /// Present under `root` but not in the checksum file.
+    pub extra: Vec<String>,
+}
+
+/// Event payload for `verify-progress`, one per listed file.
+#[derive(Debug, Clone, Serialize)]
+pub struct VerifyProgress {
+    pub root: String,
+    pub path: String,
+    pub completed: usize,
+    pub total: usize,
+}
+
+/// Check the files under `root` against `checksum_file` (written by
+/// `batch_checksum` or GNU `sha256sum`/`sha512sum`/`b3sum`; a relative path
+/// is taken from `root`). Hashes in parallel and emits `verify-progress`.
+#[tauri::command]
+pub async fn verify_directory_checksums(
+    app: AppHandle,
+    root: String,
+    checksum_file: String,
+) -> Result<VerifyResult, AppError> {
+    tokio::task::spawn_blocking(move || {
+        verify_directory_checksums_sync(Path::new(&root), Path::new(&checksum_file), &|progress| {
+            let _ = app.emit("verify-progress", progress);
+        })
+    })
+    .await
+    .map_err(|e| AppError::Other(format!("Task join error: {}", e)))?
+}
+
+fn verify_directory_checksums_sync(
+    root: &Path,
+    checksum_file: &Path,
+    progress: &(dyn Fn(VerifyProgress) + Sync),
+) -> Result<VerifyResult, AppError> {
+    if !root.is_dir() {
+        return Err(AppError::NotFound(root.to_string_lossy().to_string()));
+    }
+    let checksum_file = root.join(checksum_file);
+    let text = fs::read_to_string(&checksum_file)
+        .map_err(|_| AppError::NotFound(checksum_file.to_string_lossy().to_string()))?;
+    let listed = parse_checksum_file(&text)?;
+    let hash_len = listed.first().map_or(0, |c| c.hash.len());
+    let algorithm = ChecksumAlgorithm::for_checksum_file(&checksum_file, hash_len).ok_or_else(|| {
+        AppError::Other(format!("Unknown checksum algorithm in {}", checksum_file.display()))
+    })?;
+
+    let total = listed.len();
+    let completed = AtomicUsize::new(0);
+    let root_name = root.to_string_lossy().to_string();
+    // (path, Some(matched)) for present files, (path, None) for missing ones
+    let checked: Vec<(&str, Option<bool>)> = listed
+        .par_iter()
+        .map(|checksum| {
+            let path = root.join(&checksum.path);
+            let status = path.is_file().then(|| match algorithm.hash_file(&path) {
+                Ok(hash) => hash.eq_ignore_ascii_case(&checksum.hash),
+                Err(e) => {
+                    log::warn!("Failed to hash {}: {}", path.display(), e);
+                    false
+                }
+            });
+            progress(VerifyProgress {
+                root: root_name.clone(),
+                path: checksum.path.clone(),
+                completed: completed.fetch_add(1, Ordering::Relaxed) + 1,
+                total,
+            });
+            (checksum.path.as_str(), status)
+        })
+        .collect();
+
+    let mut result = VerifyResult::default();
+    for (path, status) in checked {
+        match status {
+            Some(true) => result.ok.push(path.to_string()),
+            Some(false) => result.corrupted.push(path.to_string()),
+            None => result.missing.push(path.to_string()),
+        }
+    }
+
+    // Hidden files only count as extra when the checksum file covers some
+    let include_hidden = listed.iter().any(|c| c.path.split('/').any(|part| part.starts_with('.')));
+    let listed_paths: HashSet<&str> = listed.iter().map(|c| c.path.as_str()).collect();
+    let own_path = fs::canonicalize(root)
+        .ok()
+        .zip(fs::canonicalize(&checksum_file).ok())
+        .and_then(|(root, file)| file.strip_prefix(root).ok().map(slash_path));
+    result.extra = collect_files(root, include_hidden)?
+        .0
+        .iter()
+        .map(|relative| slash_path(relative))
+        .filter(|path| !listed_paths.contains(path.as_str()) && own_path.as_deref() != Some(path))
+        .collect();
+
+    for paths in [&mut result.ok, &mut result.corrupted, &mut result.missing, &mut result.extra] {
+        paths.sort();
+    }
+    log::info!(
+        "Verified {}: {} ok, {} corrupted, {} missing, {} extra",
+        root.display(),
+        result.ok.len(),
+        result.corrupted.len(),
+        result.missing.len(),
+        result.extra.len()
+    );
+    Ok(result)
+}
+
+/// Entries of a `sha256sum`-format file: `<hash>  <path>` or, for binary
+/// mode, `<hash> *<path>`. Blank and `#` lines are skipped; a leading `./`
+/// is dropped from paths. Absolute paths and paths with `..` are refused,
+/// so verifying never reads outside the root.
+fn parse_checksum_file(text: &str) -> Result<Vec<FileChecksum>, AppError> {
+    let mut checksums = Vec::new();
+    for (number, line) in text.lines().enumerate() {
+        if line.trim().is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let (escaped, line) = match line.strip_prefix('\\') {
+            Some(rest) => (true, rest),
+            None => (false, line),
+        };
+        let entry = line.split_once(' ').and_then(|(hash, rest)| {
+            let path = rest.strip_prefix(' ').or_else(|| rest.strip_prefix('*'))?;
+            let valid = !hash.is_empty() && hash.bytes().all(|b| b.is_ascii_hexdigit()) && !path.is_empty();
+            valid.then_some((hash, path))
+        });
+        let Some((hash, path)) = entry else {
+            return Err(AppError::Other(format!("Invalid checksum line {}: {}", number + 1, line)));
+        };
+        let path = if escaped { unescape_checksum_path(path) } else { path.to_string() };
+        let path = path.strip_prefix("./").map(str::to_string).unwrap_or(path);
+        if !Path::new(&path).components().all(|c| matches!(c, Component::Normal(_) | Component::CurDir)) {
+            return Err(AppError::InvalidPath(format!(
+                "Checksum line {} points outside the root: {}",
+                number + 1,
+                path
+            )));
+        }
+        checksums.push(FileChecksum { path, hash: hash.to_lowercase() });
+    }
+    Ok(checksums)
+}
+
+/// Undo the `\\` and `\n` escapes of `format_checksum_line`.
+fn unescape_checksum_path(path: &str) -> String {
+    let mut unescaped = String::with_capacity(path.len());
+    let mut chars = path.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => unescaped.push('\n'),
+            Some(other) => unescaped.push(other),
+            None => unescaped.push('\\'),
+        }
+    }
+    unescaped
+}
+
+fn slash_path(relative: &Path) -> String {
+    relative
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+    use tempfile::tempdir;
+
+    fn build_tree(root: &Path) {
+        fs::create_dir_all(root.join("photos/2024")).unwrap();
+        fs::write(root.join("notes.txt"), "hello").unwrap();
+        fs::write(root.join("photos/2024/a.jpg"), vec![7u8; 200_000]).unwrap();
+        fs::write(root.join(".hidden"), "secret").unwrap();
+    }
+
+    #[test]
+    fn test_batch_checksum_is_stable() {
+        let dir = tempdir().unwrap();
+        build_tree(dir.path());
+        let run = |algorithm, include_hidden| {
+            let events = Mutex::new(Vec::new());
+            let never = AtomicBool::new(false);
+            let result = batch_checksum_sync(1, dir.path(), algorithm, include_hidden, &never, &|p| {
+                events.lock().unwrap().push(p.completed)
+            })
+            .unwrap();
+            let mut events = events.into_inner().unwrap();
+            events.sort();
+            assert_eq!(events, (1..=result.len()).collect::<Vec<_>>());
+            result
+        };
+
+        let first = run(ChecksumAlgorithm::Sha256, false);
+        assert_eq!(first, run(ChecksumAlgorithm::Sha256, false));
+        let paths: Vec<&str> = first.iter().map(|c| c.path.as_str()).collect();
+        assert_eq!(paths, ["notes.txt", "photos/2024/a.jpg"]);
+        assert_eq!(first[0].hash, "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824");
+
+        assert_eq!(run(ChecksumAlgorithm::Blake3, true).len(), 3);
+        assert_eq!(run(ChecksumAlgorithm::Sha512, false)[0].hash.len(), 128);
+    }
+
+    #[test]
+    fn test_sidecar_file() {
+        let dir = tempdir().unwrap();
+        build_tree(dir.path());
+        let never = AtomicBool::new(false);
+        let checksums =
+            batch_checksum_sync(1, dir.path(), ChecksumAlgorithm::Sha256, true, &never, &|_| {}).unwrap();
+        let sidecar = write_sidecar_file(dir.path(), ChecksumAlgorithm::Sha256, &checksums).unwrap();
+        let text = fs::read_to_string(&sidecar).unwrap();
+        assert_eq!(text.lines().count(), 3);
+        assert!(text.contains("  photos/2024/a.jpg\n"));
+
+        // The sidecar isn't hashed on the next run
+        let again = batch_checksum_sync(1, dir.path(), ChecksumAlgorithm::Sha256, true, &never, &|_| {}).unwrap();
+        assert_eq!(again, checksums);
+
+        let escaped = FileChecksum { path: "a\\b".into(), hash: "00".into() };
+        assert_eq!(format_checksum_line(&escaped), "\\00  a\\\\b");
+        assert_eq!(parse_checksum_file(&format_checksum_line(&escaped)).unwrap(), [escaped]);
+    }
+
+    #[test]
+    fn test_verify_directory_checksums() {
+        let dir = tempdir().unwrap();
+        let root = dir.path().join("backup");
+        build_tree(&root);
+        fs::write(root.join("gone.txt"), "bye").unwrap();
+        let never = AtomicBool::new(false);
+        let checksums = batch_checksum_sync(1, &root, ChecksumAlgorithm::Blake3, false, &never, &|_| {}).unwrap();
+        write_sidecar_file(&root, ChecksumAlgorithm::Blake3, &checksums).unwrap();
+
+        fs::remove_file(root.join("gone.txt")).unwrap();
+        fs::write(root.join("notes.txt"), "hellO").unwrap();
+        fs::write(root.join("photos/new.jpg"), "new").unwrap();
+        let events = AtomicUsize::new(0);
+        let result = verify_directory_checksums_sync(&root, Path::new(".b3sums"), &|_| {
+            events.fetch_add(1, Ordering::Relaxed);
+        })
+        .unwrap();
+        assert_eq!(
+            result,
+            VerifyResult {
+                ok: vec!["photos/2024/a.jpg".into()],
+                corrupted: vec!["notes.txt".into()],
+                missing: vec!["gone.txt".into()],
+                extra: vec!["photos/new.jpg".into()],
+            }
+        );
+        assert_eq!(events.into_inner(), 3);
+    }
+
+    #[test]
+    fn test_verify_gnu_sha256sum_file() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join("notes.txt"), "hello").unwrap();
+        let sums = dir.path().join("SHA256SUMS");
+        let hash = "2CF24DBA5FB0A30E26E83B2AC5B9E29E1B161E5C1FA7425E73043362938B9824";
+        fs::write(&sums, format!("# made by sha256sum\n{} *./notes.txt\n", hash)).unwrap();
+        let result = verify_directory_checksums_sync(dir.path(), &sums, &|_| {}).unwrap();
+        assert_eq!(result.ok, ["notes.txt"]);
+        assert!(result.extra.is_empty(), "the checksum file itself isn't extra: {:?}", result.extra);
+
+        fs::write(&sums, "not a checksum line\n").unwrap();
+        assert!(verify_directory_checksums_sync(dir.path(), &sums, &|_| {}).is_err());
+    }
+
+    #[test]
+    fn test_verify_refuses_paths_outside_root() {
+        let dir = tempdir().unwrap();
+        let root = dir.path().join("root");
+        fs::create_dir(&root).unwrap();
+        fs::write(dir.path().join("outside.txt"), "hello").unwrap();
+        let sums = root.join("SHA256SUMS");
+        let hash = "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824";
+
+        for listed in ["../outside.txt", "sub/../../outside.txt", "/etc/passwd"] {
+            fs::write(&sums, format!("{}  {}\n", hash, listed)).unwrap();
+            let result = verify_directory_checksums_sync(&root, &sums, &|_| {});
+            assert!(matches!(result, Err(AppError::InvalidPath(_))), "{}", listed);
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_batch_checksum_skips_unreadable_folders() {
+        use std::os::unix::fs::PermissionsExt;
+        let dir = tempdir().unwrap();
+        build_tree(dir.path());
+        let locked = dir.path().join("photos/2024");
+        fs::set_permissions(&locked, fs::Permissions::from_mode(0o000)).unwrap();
+
+        let errors = Mutex::new(Vec::new());
+        let never = AtomicBool::new(false);
+        let result = batch_checksum_sync(1, dir.path(), ChecksumAlgorithm::Sha256, false, &never, &|p| {
+            if p.error.is_some() {
+                errors.lock().unwrap().push(p.path);
+            }
+        });
+        fs::set_permissions(&locked, fs::Permissions::from_mode(0o755)).unwrap();
+        // root ignores permission bits
+        if unsafe { libc::geteuid() } == 0 {
+            return;
+        }
+        let paths: Vec<String> = result.unwrap().into_iter().map(|c| c.path).collect();
+        assert_eq!(paths, ["notes.txt"]);
+        assert_eq!(errors.into_inner().unwrap(), ["photos/2024"]);
+    }
+}
@@ -0,0 +1,686 @@
+//! Directory tree comparison: which relative paths exist on one side only,
+//! and which files differ. Also one-way sync, which acts on the same diff.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant, UNIX_EPOCH};
+
+use jwalk::WalkDir;
+use rayon::prelude::*;
+use serde::Serialize;
+use tauri::{AppHandle, Emitter};
+
+use super::batch::copy_symlink;
+use super::file_ops::replace_existing;
+use crate::error::AppError;
+use crate::task_registry::TaskRegistry;
+
+/// Maximum entries walked per side; larger trees are rejected up front
+/// rather than holding both listings in memory.
+const MAX_COMPARE_ENTRIES: usize = 50_000;
+
+/// Files hashed between `compare-progress` events.
+const HASH_CHUNK: usize = 64;
+
+/// Result of `compare_directories`. Paths are relative and `/`-separated.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct DirectoryDiff {
+    #[serde(rename = "onlyInLeft")]
+    pub only_in_left: Vec<String>,
+    #[serde(rename = "onlyInRight")]
+    pub only_in_right: Vec<String>,
+    #[serde(rename = "inBothSame")]
+    pub in_both_same: Vec<String>,
+    #[serde(rename = "inBothDifferent")]
+    pub in_both_different: Vec<String>,
+}
+
+/// Event payload for `compare-progress`.
+#[derive(Debug, Clone, Serialize)]
+pub struct CompareProgress {
+    /// Entries walked across both trees.
+    pub scanned: usize,
+    /// Files present on both sides compared so far.
+    pub compared: usize,
+    /// Files present on both sides (0 while still walking).
+    pub total: usize,
+}
+
+#[derive(Debug, Clone)]
+struct TreeEntry {
+    is_dir: bool,
+    size: u64,
+    modified_ms: u128,
+    /// Where a symlink points; links are compared and synced by this, never
+    /// followed.
+    link: Option<PathBuf>,
+}
+
+/// Compare the trees under `left` and `right`.
+///
+/// Files in both trees count as the same when size and modification time
+/// match (`shallow`) or when their BLAKE3 content hashes match. Directories
+/// are reported when they exist on one side only; a path that is a file on
+/// one side and a directory on the other is "different". Symlinks match only
+/// symlinks with the same target path. Emits
+/// `compare-progress` events.
+#[tauri::command]
+pub async fn compare_directories(
+    app: AppHandle,
+    left: String,
+    right: String,
+    shallow: bool,
+) -> Result<DirectoryDiff, AppError> {
+    tokio::task::spawn_blocking(move || {
+        compare_directories_sync(Path::new(&left), Path::new(&right), shallow, &|progress| {
+            let _ = app.emit("compare-progress", progress);
+        })
+    })
+    .await
+    .map_err(|e| AppError::Other(format!("Task join error: {}", e)))?
+}
+
+pub(crate) fn compare_directories_sync(
+    left: &Path,
+    right: &Path,
+    shallow: bool,
+    progress: &(dyn Fn(CompareProgress) + Sync),
+) -> Result<DirectoryDiff, AppError> {
+    for root in [left, right] {
+        if !root.is_dir() {
+            return Err(AppError::NotFound(root.to_string_lossy().to_string()));
+        }
+    }
+
+    let left_tree = walk_tree(left)?;
+    let right_tree = walk_tree(right)?;
+    let scanned = left_tree.len() + right_tree.len();
+    progress(CompareProgress {
+        scanned,
+        compared: 0,
+        total: 0,
+    });
+
+    let mut diff = DirectoryDiff::default();
+    let mut common_files = Vec::new();
+    for (path, l) in &left_tree {
+        match right_tree.get(path) {
+            None => diff.only_in_left.push(path.clone()),
+            Some(r) if l.is_dir != r.is_dir => diff.in_both_different.push(path.clone()),
+            Some(_) if l.is_dir => {}
+            Some(r) if l.link.is_some() || r.link.is_some() => {
+                if l.link == r.link {
+                    diff.in_both_same.push(path.clone());
+                } else {
+                    diff.in_both_different.push(path.clone());
+                }
+            }
+            Some(r) if l.size != r.size => diff.in_both_different.push(path.clone()),
+            Some(r) if shallow => {
+                if l.modified_ms == r.modified_ms {
+                    diff.in_both_same.push(path.clone());
+                } else {
+                    diff.in_both_different.push(path.clone());
+                }
+            }
+            Some(_) => common_files.push(path.clone()),
+        }
+    }
+    diff.only_in_right = right_tree
+        .keys()
+        .filter(|path| !left_tree.contains_key(*path))
+        .cloned()
+        .collect();
+
+    // Same-size files on both sides: compare content hashes in parallel
+    let total = common_files.len();
+    let mut compared = 0;
+    for chunk in common_files.chunks(HASH_CHUNK) {
+        let results: Vec<(String, bool)> = chunk
+            .par_iter()
+            .map(|path| {
+                let same = match (hash_file(&left.join(path)), hash_file(&right.join(path))) {
+                    (Ok(a), Ok(b)) => a == b,
+                    _ => false,
+                };
+                (path.clone(), same)
+            })
+            .collect();
+        for (path, same) in results {
+            if same {
+                diff.in_both_same.push(path);
+            } else {
+                diff.in_both_different.push(path);
+            }
+        }
+        compared += chunk.len();
+        progress(CompareProgress {
+            scanned,
+            compared,
+            total,
+        });
+    }
+
+    diff.in_both_same.sort();
+    diff.in_both_different.sort();
+    log::info!(
+        "Compared {:?} and {:?}: {} left only, {} right only, {} same, {} different",
+        left,
+        right,
+        diff.only_in_left.len(),
+        diff.only_in_right.len(),
+        diff.in_both_same.len(),
+        diff.in_both_different.len()
+    );
+    Ok(diff)
+}
+
+/// Relative path → entry for everything under `root`, hidden files included.
+/// Symlinks are not followed.
+fn walk_tree(root: &Path) -> Result<BTreeMap<String, TreeEntry>, AppError> {
+    let mut tree = BTreeMap::new();
+    for entry in WalkDir::new(root).skip_hidden(false).follow_links(false) {
+        let Ok(entry) = entry else { continue };
+        let path: PathBuf = entry.path();
+        let relative = match path.strip_prefix(root) {
+            Ok(rel) if !rel.as_os_str().is_empty() => rel,
+            _ => continue,
+        };
+        if tree.len() >= MAX_COMPARE_ENTRIES {
+            return Err(AppError::Other(format!(
+                "{} has more than {} entries; compare smaller folders",
+                root.display(),
+                MAX_COMPARE_ENTRIES
+            )));
+        }
+
+        let Ok(meta) = fs::symlink_metadata(&path) else { continue };
+        let modified_ms = meta
+            .modified()
+            .ok()
+            .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+            .map(|d| d.as_millis())
+            .unwrap_or(0);
+        let link = if meta.is_symlink() {
+            let Ok(target) = fs::read_link(&path) else { continue };
+            Some(target)
+        } else {
+            None
+        };
+        let key = rel_key(relative);
+        tree.insert(
+            key,
+            TreeEntry {
+                is_dir: meta.is_dir(),
+                size: if meta.is_dir() { 0 } else { meta.len() },
+                modified_ms,
+                link,
+            },
+        );
+    }
+    Ok(tree)
+}
+
+// ── One-way sync ────────────────────────────────────────────────────────────
+
+static SYNC_OPERATIONS: TaskRegistry = TaskRegistry::new();
+
+/// Minimum interval between `sync-progress` events for unchanged files.
+const SYNC_PROGRESS_INTERVAL: Duration = Duration::from_millis(100);
+
+/// What happened to one path during a sync.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SyncStatus {
+    /// New in the destination.
+    Copied,
+    /// Replaced because it differed.
+    Updated,
+    Unchanged,
+    /// Removed from the destination (`deleteExtra`).
+    Deleted,
+    Failed,
+}
+
+/// Event payload for `sync-progress`: one per copied, updated, deleted or
+/// failed path (unchanged files are throttled), then a final `done` event.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct SyncProgress {
+    #[serde(rename = "syncId")]
+    pub sync_id: u64,
+    /// Relative, `/`-separated; empty on the final event.
+    pub path: String,
+    pub status: Option<SyncStatus>,
+    pub error: Option<String>,
+    pub processed: usize,
+    pub total: usize,
+    /// Paths copied or updated so far; like the counts below, directories
+    /// count once each.
+    pub copied: u64,
+    pub deleted: u64,
+    pub failed: u64,
+    /// Set on the final event, also after cancellation.
+    pub done: bool,
+}
+
+/// Make `destination` a mirror of `source` in the background: copy files
+/// that are new or changed and, with `delete_extra`, remove what `source`
+/// doesn't have. Files count as changed when size or mtime differ, or with
+/// `hash_verify` when size or BLAKE3 hash differ. Copies get the source's
+/// mtime so the next sync sees them as unchanged. Symlinks are recreated
+/// with the same target rather than followed. Replaced files are staged, so
+/// a failed copy keeps the old one.
+///
+/// Returns a sync ID at once; progress arrives as `sync-progress` events
+/// and `cancel_sync` stops the sync between files.
+#[tauri::command]
+pub fn sync_directories(
+    app: AppHandle,
+    source: String,
+    destination: String,
+    delete_extra: bool,
+    hash_verify: Option<bool>,
+) -> Result<u64, AppError> {
+    let (source, destination) = (PathBuf::from(source), PathBuf::from(destination));
+    check_sync_roots(&source, &destination)?;
+
+    let (sync_id, cancelled) = SYNC_OPERATIONS.start();
+    std::thread::spawn(move || {
+        let result = sync_directories_sync(
+            sync_id,
+            &source,
+            &destination,
+            delete_extra,
+            hash_verify.unwrap_or(false),
+            &cancelled,
+            &|progress| {
+                let _ = app.emit("sync-progress", progress);
+            },
+        );
+        match result {
+            Ok(summary) => log::info!(
+                "Sync {} finished: {} copied, {} deleted, {} failed",
+                sync_id,
+                summary.copied,
+                summary.deleted,
+                summary.failed
+            ),
+            Err(e) => {
+                log::warn!("Sync {} failed: {}", sync_id, e);
+                let _ = app.emit(
+                    "sync-progress",
+                    SyncProgress { sync_id, error: Some(e.to_string()), done: true, ..Default::default() },
+                );
+            }
+        }
+        SYNC_OPERATIONS.cleanup(sync_id);
+    });
+    Ok(sync_id)
+}
+
+/// Cancel a running `sync_directories`. Files already copied are kept.
+#[tauri::command]
+pub fn cancel_sync(sync_id: u64) -> Result<(), AppError> {
+    SYNC_OPERATIONS.cancel(sync_id);
+    Ok(())
+}
+
+/// `source` must be a directory and neither root may contain the other:
+/// the sync would copy into its own input, or delete its source.
+fn check_sync_roots(source: &Path, destination: &Path) -> Result<(), AppError> {
+    if !source.is_dir() {
+        return Err(AppError::NotFound(source.to_string_lossy().to_string()));
+    }
+    if destination.exists() && !destination.is_dir() {
+        return Err(AppError::InvalidPath(format!("{} is not a directory", destination.display())));
+    }
+    let source = fs::canonicalize(source)?;
+    // A destination that doesn't exist yet is checked through its parent
+    let destination = match fs::canonicalize(destination) {
+        Ok(path) => path,
+        Err(_) => match (destination.parent(), destination.file_name()) {
+            (Some(parent), Some(name)) => fs::canonicalize(parent)?.join(name),
+            _ => destination.to_path_buf(),
+        },
+    };
+    if destination.starts_with(&source) || source.starts_with(&destination) {
+        return Err(AppError::InvalidPath(format!(
+            "Can't sync {} and {}: one contains the other",
+            source.display(),
+            destination.display()
+        )));
+    }
+    Ok(())
+}
+
+pub(crate) fn sync_directories_sync(
+    sync_id: u64,
+    source: &Path,
+    destination: &Path,
+    delete_extra: bool,
+    hash_verify: bool,
+    cancelled: &AtomicBool,
+    emit: &dyn Fn(SyncProgress),
+) -> Result<SyncProgress, AppError> {
+    check_sync_roots(source, destination)?;
+    fs::create_dir_all(destination)?;
+    let source_tree = walk_tree(source)?;
+    let dest_tree = walk_tree(destination)?;
+
+    // Extra entries inside an extra directory go with it
+    let extra: Vec<&String> = if delete_extra {
+        dest_tree
+            .keys()
+            .filter(|path| !source_tree.contains_key(*path))
+            .filter(|path| path.rsplit_once('/').is_none_or(|(parent, _)| source_tree.contains_key(parent)))
+            .collect()
+    } else {
+        Vec::new()
+    };
+
+    let mut progress = SyncProgress { sync_id, total: source_tree.len() + extra.len(), ..Default::default() };
+    let mut last_emit = Instant::now();
+    let mut report = |progress: &mut SyncProgress, path: &str, outcome: Result<SyncStatus, AppError>| {
+        progress.processed += 1;
+        progress.path = path.to_string();
+        progress.error = None;
+        let status = match outcome {
+            Ok(status) => status,
+            Err(e) => {
+                progress.error = Some(e.to_string());
+                SyncStatus::Failed
+            }
+        };
+        match status {
+            SyncStatus::Copied | SyncStatus::Updated => progress.copied += 1,
+            SyncStatus::Deleted => progress.deleted += 1,
+            SyncStatus::Failed => progress.failed += 1,
+            SyncStatus::Unchanged => {}
+        }
+        progress.status = Some(status);
+        if status != SyncStatus::Unchanged || last_emit.elapsed() >= SYNC_PROGRESS_INTERVAL {
+            last_emit = Instant::now();
+            emit(progress.clone());
+        }
+    };
+
+    // BTreeMap order puts every directory before its contents
+    for (path, entry) in &source_tree {
+        if cancelled.load(Ordering::Relaxed) {
+            break;
+        }
+        let (from, to) = (source.join(path), destination.join(path));
+        let outcome = sync_entry(&from, &to, entry, dest_tree.get(path), hash_verify);
+        report(&mut progress, path, outcome);
+    }
+    for path in extra {
+        if cancelled.load(Ordering::Relaxed) {
+            break;
+        }
+        let target = destination.join(path);
+        let outcome = if dest_tree[path].is_dir { fs::remove_dir_all(&target) } else { fs::remove_file(&target) };
+        report(&mut progress, path, outcome.map(|_| SyncStatus::Deleted).map_err(AppError::from));
+    }
+
+    progress.path.clear();
+    progress.status = None;
+    progress.error = None;
+    progress.done = true;
+    emit(progress.clone());
+    Ok(progress)
+}
+
+/// Bring `target` in line with `source`. Directories report `Unchanged`
+/// unless they had to be created.
+fn sync_entry(
+    source: &Path,
+    target: &Path,
+    entry: &TreeEntry,
+    existing: Option<&TreeEntry>,
+    hash_verify: bool,
+) -> Result<SyncStatus, AppError> {
+    // A file where a directory belongs, or the other way round
+    if let Some(existing) = existing.filter(|e| e.is_dir != entry.is_dir) {
+        if existing.is_dir {
+            fs::remove_dir_all(target)?;
+        } else {
+            fs::remove_file(target)?;
+        }
+    }
+    let existing = existing.filter(|e| e.is_dir == entry.is_dir);
+
+    if entry.is_dir {
+        if existing.is_some() {
+            return Ok(SyncStatus::Unchanged);
+        }
+        fs::create_dir_all(target)?;
+        return Ok(SyncStatus::Copied);
+    }
+
+    let changed = match existing {
+        None => true,
+        Some(e) if e.link.is_some() || entry.link.is_some() => e.link != entry.link,
+        Some(e) if e.size != entry.size => true,
+        Some(_) if hash_verify => hash_file(source)? != hash_file(target)?,
+        Some(e) => e.modified_ms != entry.modified_ms,
+    };
+    if !changed {
+        return Ok(SyncStatus::Unchanged);
+    }
+    let place = || {
+        if entry.link.is_some() {
+            copy_symlink(source, target)
+        } else {
+            copy_with_mtime(source, target)
+        }
+    };
+    if existing.is_some() {
+        // Renaming the old one aside also replaces read-only targets
+        replace_existing(source, target, place)?;
+        Ok(SyncStatus::Updated)
+    } else {
+        place()?;
+        Ok(SyncStatus::Copied)
+    }
+}
+
+/// Copy a file and give the copy `source`'s mtime.
+fn copy_with_mtime(source: &Path, target: &Path) -> Result<(), AppError> {
+    fs::copy(source, target)?;
+    let modified = fs::metadata(source)?.modified()?;
+    fs::OpenOptions::new()
+        .write(true)
+        .open(target)
+        .or_else(|_| fs::File::open(target))?
+        .set_modified(modified)?;
+    Ok(())
+}
+
+fn rel_key(relative: &Path) -> String {
+    relative
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+fn hash_file(path: &Path) -> Result<blake3::Hash, AppError> {
+    let mut hasher = blake3::Hasher::new();
+    let mut file = fs::File::open(path)?;
+    let mut buf = vec![0u8; 64 * 1024];
+    loop {
+        let n = file.read(&mut buf)?;
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buf[..n]);
+    }
+    Ok(hasher.finalize())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+    use tempfile::tempdir;
+
+    fn write(root: &Path, rel: &str, content: &str) {
+        let path = root.join(rel);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+
+    #[test]
+    fn test_compare_directories_deep() {
+        let dir = tempdir().unwrap();
+        let (left, right) = (dir.path().join("left"), dir.path().join("right"));
+        write(&left, "same.txt", "identical");
+        write(&right, "same.txt", "identical");
+        write(&left, "src/changed.rs", "fn a() {}");
+        write(&right, "src/changed.rs", "fn b() {}");
+        write(&left, "src/resized.rs", "short");
+        write(&right, "src/resized.rs", "much longer");
+        write(&left, "old/gone.txt", "bye");
+        write(&right, "new.txt", "hi");
+        write(&left, "kind", "a file");
+        write(&right, "kind/inner.txt", "now a dir");
+
+        let events = Mutex::new(Vec::new());
+        let diff = compare_directories_sync(&left, &right, false, &|p| events.lock().unwrap().push(p)).unwrap();
+
+        assert_eq!(diff.only_in_left, vec!["old", "old/gone.txt"]);
+        assert_eq!(diff.only_in_right, vec!["kind/inner.txt", "new.txt"]);
+        assert_eq!(diff.in_both_same, vec!["same.txt"]);
+        assert_eq!(diff.in_both_different, vec!["kind", "src/changed.rs", "src/resized.rs"]);
+
+        let events = events.into_inner().unwrap();
+        let last = events.last().unwrap();
+        assert_eq!((last.compared, last.total), (2, 2));
+    }
+
+    #[test]
+    fn test_compare_directories_shallow_uses_mtime() {
+        let dir = tempdir().unwrap();
+        let (left, right) = (dir.path().join("left"), dir.path().join("right"));
+        write(&left, "a.txt", "same bytes");
+        write(&right, "a.txt", "same bytes");
+        let old = std::time::SystemTime::now() - std::time::Duration::from_secs(3600);
+        fs::File::options()
+            .write(true)
+            .open(right.join("a.txt"))
+            .unwrap()
+            .set_modified(old)
+            .unwrap();
+
+        let shallow = compare_directories_sync(&left, &right, true, &|_| {}).unwrap();
+        assert_eq!(shallow.in_both_different, vec!["a.txt"]);
+
+        let deep = compare_directories_sync(&left, &right, false, &|_| {}).unwrap();
+        assert_eq!(deep.in_both_same, vec!["a.txt"]);
+    }
+
+    #[test]
+    fn test_compare_directories_missing_root() {
+        let dir = tempdir().unwrap();
+        let result = compare_directories_sync(dir.path(), &dir.path().join("missing"), true, &|_| {});
+        assert!(matches!(result, Err(AppError::NotFound(_))));
+    }
+
+    #[test]
+    fn test_sync_directories_mirrors_source() {
+        let dir = tempdir().unwrap();
+        let (source, dest) = (dir.path().join("source"), dir.path().join("dest"));
+        write(&source, "a.txt", "alpha");
+        write(&source, "docs/b.txt", "beta");
+        write(&source, "docs/deep/c.txt", "gamma");
+        write(&dest, "a.txt", "out of date");
+        write(&dest, "extra/old.txt", "remove me");
+        write(&dest, "docs/extra.txt", "remove me too");
+
+        let events = Mutex::new(Vec::new());
+        let emit = |p: SyncProgress| events.lock().unwrap().push(p);
+        let summary = sync_directories_sync(7, &source, &dest, true, false, &AtomicBool::new(false), &emit).unwrap();
+        assert_eq!((summary.copied, summary.deleted, summary.failed), (4, 2, 0), "a.txt, b.txt, deep/, c.txt");
+        assert!(summary.done);
+
+        let events = events.into_inner().unwrap();
+        assert!(events.iter().all(|e| e.sync_id == 7));
+        let updated = events.iter().find(|e| e.path == "a.txt").unwrap();
+        assert_eq!(updated.status, Some(SyncStatus::Updated));
+
+        let diff = compare_directories_sync(&source, &dest, false, &|_| {}).unwrap();
+        assert!(diff.only_in_left.is_empty() && diff.only_in_right.is_empty() && diff.in_both_different.is_empty());
+
+        // Copies carry the source mtime, so a second pass changes nothing
+        let again = sync_directories_sync(8, &source, &dest, true, false, &AtomicBool::new(false), &|_| {}).unwrap();
+        assert_eq!((again.copied, again.deleted), (0, 0));
+    }
+
+    #[test]
+    fn test_sync_directories_hash_verify_and_keep_extra() {
+        let dir = tempdir().unwrap();
+        let (source, dest) = (dir.path().join("source"), dir.path().join("dest"));
+        write(&source, "same-size.txt", "aaaa");
+        write(&dest, "same-size.txt", "bbbb");
+        write(&dest, "keep.txt", "mine");
+        let mtime = fs::metadata(source.join("same-size.txt")).unwrap().modified().unwrap();
+        fs::File::options().write(true).open(dest.join("same-size.txt")).unwrap().set_modified(mtime).unwrap();
+
+        let none = AtomicBool::new(false);
+        let shallow = sync_directories_sync(1, &source, &dest, false, false, &none, &|_| {}).unwrap();
+        assert_eq!(shallow.copied, 0, "same size and mtime looks unchanged");
+        let exact = sync_directories_sync(2, &source, &dest, false, true, &none, &|_| {}).unwrap();
+        assert_eq!(exact.copied, 1);
+        assert_eq!(fs::read_to_string(dest.join("same-size.txt")).unwrap(), "aaaa");
+        assert!(dest.join("keep.txt").exists());
+
+        let nested = sync_directories_sync(3, &source, &source.join("inner"), true, false, &none, &|_| {});
+        assert!(matches!(nested, Err(AppError::InvalidPath(_))));
+        let stop = AtomicBool::new(true);
+        let cancelled = sync_directories_sync(4, &source, &dir.path().join("new"), false, false, &stop, &|_| {}).unwrap();
+        assert_eq!((cancelled.processed, cancelled.done), (0, true));
+    }
+
+    #[test]
+    fn test_sync_entry_failed_copy_keeps_existing() {
+        let dir = tempdir().unwrap();
+        write(dir.path(), "dest/a.txt", "keep");
+        let entry = TreeEntry { is_dir: false, size: 3, modified_ms: 0, link: None };
+        let existing = TreeEntry { is_dir: false, size: 4, modified_ms: 0, link: None };
+        let target = dir.path().join("dest/a.txt");
+
+        // The source vanished between the walk and the copy
+        let result = sync_entry(&dir.path().join("gone.txt"), &target, &entry, Some(&existing), false);
+        assert!(result.is_err());
+        assert_eq!(fs::read_to_string(&target).unwrap(), "keep");
+        assert_eq!(fs::read_dir(dir.path().join("dest")).unwrap().count(), 1, "no parked entry left behind");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_sync_directories_recreates_symlinks() {
+        let dir = tempdir().unwrap();
+        let (source, dest) = (dir.path().join("source"), dir.path().join("dest"));
+        write(&source, "real.txt", "content");
+        write(&source, "docs/b.txt", "beta");
+        std::os::unix::fs::symlink("real.txt", source.join("file-link")).unwrap();
+        std::os::unix::fs::symlink("docs", source.join("dir-link")).unwrap();
+        write(&dest, "file-link", "a stale copy of the target");
+
+        let none = AtomicBool::new(false);
+        let summary = sync_directories_sync(1, &source, &dest, true, false, &none, &|_| {}).unwrap();
+        assert_eq!(summary.failed, 0);
+        assert_eq!(fs::read_link(dest.join("file-link")).unwrap(), Path::new("real.txt"));
+        assert_eq!(fs::read_link(dest.join("dir-link")).unwrap(), Path::new("docs"));
+        assert_eq!(fs::read_to_string(dest.join("dir-link/b.txt")).unwrap(), "beta");
+
+        let diff = compare_directories_sync(&source, &dest, false, &|_| {}).unwrap();
+        assert!(diff.in_both_different.is_empty());
+        assert!(diff.in_both_same.contains(&"file-link".to_string()));
+        let again = sync_directories_sync(2, &source, &dest, true, false, &none, &|_| {}).unwrap();
+        assert_eq!((again.copied, again.failed), (0, 0));
+    }
+}
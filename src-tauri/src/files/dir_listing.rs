@@ -1,13 +1,16 @@
 //! Directory listing with caching and streaming support.
 //! Issue: tauri-explorer-jag7, tauri-explorer-3b5s
 
-use serde::Serialize;
+use globset::{GlobBuilder, GlobMatcher};
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use notify::event::{EventKind, ModifyKind};
+use notify::{RecursiveMode, Watcher};
 use std::fs;
-use std::path::PathBuf;
-use std::sync::atomic::Ordering;
-use std::sync::{Mutex, OnceLock};
-use std::time::Instant;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
 use tauri::{AppHandle, Emitter};
 
 use crate::error::AppError;
@@ -39,11 +42,120 @@ pub fn invalidate_dir_cache(path: String) -> Result<(), AppError> {
     Ok(())
 }
 
+/// Field to sort a listing by.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortField {
+    #[default]
+    Name,
+    Size,
+    Modified,
+    Extension,
+    Kind,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortOrder {
+    #[default]
+    #[serde(alias = "asc")]
+    Ascending,
+    #[serde(alias = "desc")]
+    Descending,
+}
+
+/// Optional filters and sorting for `list_directory`. The defaults return
+/// every entry, directories first, by name.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ListDirectoryOptions {
+    /// Include dotfiles. Defaults to true.
+    pub include_hidden: bool,
+    /// Only return entries of these kinds.
+    pub kinds: Option<Vec<FileKind>>,
+    /// Case-insensitive glob matched against entry names, e.g. `*.rs`.
+    pub name_pattern: Option<String>,
+    pub sort_by: SortField,
+    pub sort_order: SortOrder,
+    /// Keep directories above files regardless of the sort. Defaults to true.
+    pub dirs_first: bool,
+}
+
+impl Default for ListDirectoryOptions {
+    fn default() -> Self {
+        Self {
+            include_hidden: true,
+            kinds: None,
+            name_pattern: None,
+            sort_by: SortField::Name,
+            sort_order: SortOrder::Ascending,
+            dirs_first: true,
+        }
+    }
+}
+
+impl ListDirectoryOptions {
+    fn is_default_sort(&self) -> bool {
+        self.sort_by == SortField::Name && self.sort_order == SortOrder::Ascending && self.dirs_first
+    }
+}
+
+/// `ListDirectoryOptions` compiled for use inside the read loop.
+struct EntryFilter {
+    include_hidden: bool,
+    kinds: Option<Vec<FileKind>>,
+    name_glob: Option<GlobMatcher>,
+}
+
+impl EntryFilter {
+    /// `None` when the options don't filter anything.
+    fn new(options: &ListDirectoryOptions) -> Result<Option<Self>, AppError> {
+        let name_glob = match options.name_pattern.as_deref().map(str::trim) {
+            Some(pattern) if !pattern.is_empty() => Some(
+                GlobBuilder::new(pattern)
+                    .case_insensitive(true)
+                    .literal_separator(true)
+                    .build()
+                    .map_err(|e| AppError::Other(format!("Invalid name pattern: {}", e)))?
+                    .compile_matcher(),
+            ),
+            _ => None,
+        };
+        if options.include_hidden && options.kinds.is_none() && name_glob.is_none() {
+            return Ok(None);
+        }
+        Ok(Some(Self {
+            include_hidden: options.include_hidden,
+            kinds: options.kinds.clone(),
+            name_glob,
+        }))
+    }
+
+    /// Name-only checks, done before the entry is stat'ed.
+    fn accepts_name(&self, name: &str) -> bool {
+        (self.include_hidden || !name.starts_with('.'))
+            && self.name_glob.as_ref().is_none_or(|glob| glob.is_match(name))
+    }
+
+    fn accepts(&self, entry: &FileEntry) -> bool {
+        self.accepts_name(&entry.name) && self.kinds.as_ref().is_none_or(|kinds| kinds.contains(&entry.kind))
+    }
+}
+
 /// List directory contents.
-/// Directories are sorted before files, and items are sorted case-insensitively by name.
+/// By default directories are sorted before files, and items are sorted
+/// case-insensitively by name; `options` can filter and re-sort.
+/// Only unfiltered listings are cached; filtered requests reuse a cached
+/// listing when there is one.
 #[tauri::command]
-pub async fn list_directory(path: String) -> Result<DirectoryListing, AppError> {
+pub async fn list_directory(
+    path: String,
+    options: Option<ListDirectoryOptions>,
+) -> Result<DirectoryListing, AppError> {
     let t_start = std::time::Instant::now();
+    let options = options.unwrap_or_default();
+    let filter = EntryFilter::new(&options)?;
+    crate::recent::record(&path);
 
     // Check cache first
     {
@@ -51,11 +163,16 @@ pub async fn list_directory(path: String) -> Result<DirectoryListing, AppError>
         if let Some(cached) = cache.get(&path) {
             if cached.cached_at.elapsed().as_secs() < CACHE_TTL_SECS {
                 log::debug!("list_directory: cache hit ({} entries)", cached.entries.len());
-                return Ok(DirectoryListing {
-                    path: path.clone(),
-                    entries: cached.entries.clone(),
-                    listing_id: None,
-                });
+                let mut entries: Vec<FileEntry> = match &filter {
+                    Some(filter) => cached.entries.iter().filter(|e| filter.accepts(e)).cloned().collect(),
+                    None => cached.entries.clone(),
+                };
+                if !options.is_default_sort() {
+                    sort_entries_by(&mut entries, options.sort_by, options.sort_order, options.dirs_first);
+                }
+                let mut listing = DirectoryListing::new(path.clone(), entries, None);
+                listing.has_hidden_entries = cached.entries.iter().any(|e| e.name.starts_with('.'));
+                return Ok(listing);
             }
         }
     }
@@ -71,6 +188,7 @@ pub async fn list_directory(path: String) -> Result<DirectoryListing, AppError>
     }
 
     let mut entries = Vec::new();
+    let mut has_hidden_entries = false;
 
     let read_dir = fs::read_dir(&dir_path).map_err(|e| {
         if e.kind() == std::io::ErrorKind::PermissionDenied {
@@ -88,6 +206,15 @@ pub async fn list_directory(path: String) -> Result<DirectoryListing, AppError>
             Err(_) => continue,
         };
 
+        let name = entry.file_name();
+        let name = name.to_string_lossy();
+        has_hidden_entries |= name.starts_with('.');
+        if let Some(filter) = &filter {
+            if !filter.accepts_name(&name) {
+                continue;
+            }
+        }
+
         let metadata = match fs::metadata(entry.path()) {
             Ok(m) => m,
             Err(_) => match entry.metadata() {
@@ -96,10 +223,13 @@ pub async fn list_directory(path: String) -> Result<DirectoryListing, AppError>
             },
         };
 
-        entries.push(metadata_to_entry(&entry.path(), &metadata));
+        let file_entry = metadata_to_entry(&entry.path(), &metadata);
+        if filter.as_ref().is_none_or(|f| f.accepts(&file_entry)) {
+            entries.push(file_entry);
+        }
     }
 
-    sort_entries(&mut entries);
+    sort_default(&mut entries);
 
     let elapsed = t_start.elapsed();
     if elapsed.as_millis() > 100 {
@@ -109,7 +239,7 @@ pub async fn list_directory(path: String) -> Result<DirectoryListing, AppError>
     }
 
     // Update cache
-    {
+    if filter.is_none() {
         let mut cache = get_dir_cache().lock().unwrap();
         if cache.len() >= MAX_CACHE_ENTRIES {
             cache.retain(|_, v| v.cached_at.elapsed().as_secs() < CACHE_TTL_SECS);
@@ -123,15 +253,66 @@ pub async fn list_directory(path: String) -> Result<DirectoryListing, AppError>
         );
     }
 
-    Ok(DirectoryListing {
-        path,
-        entries,
-        listing_id: None,
-    })
+    // The cache holds the default order; other orders are applied per request
+    if !options.is_default_sort() {
+        sort_entries_by(&mut entries, options.sort_by, options.sort_order, options.dirs_first);
+    }
+
+    let mut listing = DirectoryListing::new(path, entries, None);
+    listing.has_hidden_entries |= has_hidden_entries;
+    Ok(listing)
+}
+
+/// Sort by `field` in `order`, ties broken by name. With `dirs_first`,
+/// directories stay above files in either order.
+pub(crate) fn sort_entries_by(entries: &mut [FileEntry], field: SortField, order: SortOrder, dirs_first: bool) {
+    let extension = |e: &FileEntry| match e.kind {
+        FileKind::Directory => String::new(),
+        FileKind::File => std::path::Path::new(&e.name)
+            .extension()
+            .map(|ext| ext.to_string_lossy().to_lowercase())
+            .unwrap_or_default(),
+    };
+    let is_file = |e: &FileEntry| e.kind == FileKind::File;
+
+    entries.sort_by(|a, b| {
+        let group = if dirs_first {
+            is_file(a).cmp(&is_file(b))
+        } else {
+            std::cmp::Ordering::Equal
+        };
+        let by_field = match field {
+            SortField::Name => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
+            SortField::Size => a.size.cmp(&b.size),
+            SortField::Modified => a.modified.cmp(&b.modified),
+            SortField::Extension => extension(a).cmp(&extension(b)),
+            SortField::Kind => is_file(a).cmp(&is_file(b)),
+        };
+        let by_field = match order {
+            SortOrder::Ascending => by_field,
+            SortOrder::Descending => by_field.reverse(),
+        };
+        group
+            .then(by_field)
+            .then_with(|| a.name.to_lowercase().cmp(&b.name.to_lowercase()))
+    });
+}
+
+/// Re-sort entries already on the frontend, e.g. after the user changes the
+/// sort column, with the same ordering as `list_directory` options.
+#[tauri::command]
+pub fn sort_entries(
+    mut entries: Vec<FileEntry>,
+    sort_by: SortField,
+    order: SortOrder,
+    dirs_first: bool,
+) -> Result<Vec<FileEntry>, AppError> {
+    sort_entries_by(&mut entries, sort_by, order, dirs_first);
+    Ok(entries)
 }
 
 /// Sort entries: directories first, then by name case-insensitively.
-fn sort_entries(entries: &mut [FileEntry]) {
+fn sort_default(entries: &mut [FileEntry]) {
     entries.sort_by(|a, b| {
         let a_is_dir = matches!(a.kind, FileKind::Directory);
         let b_is_dir = matches!(b.kind, FileKind::Directory);
@@ -158,6 +339,54 @@ pub struct DirectoryEntriesEvent {
     pub done: bool,
     #[serde(rename = "totalCount")]
     pub total_count: usize,
+    /// Set on the final batch of a recursive listing that hit its entry cap.
+    pub truncated: bool,
+    /// False for `start_streaming_directory_unsorted`, whose entries arrive
+    /// in filesystem order for the frontend to sort.
+    pub sorted: bool,
+}
+
+/// What happened to a path in a watched directory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangeKind {
+    Create,
+    Modify,
+    Remove,
+    Rename,
+}
+
+/// Event payload for `directory-changed`.
+#[derive(Debug, Clone, Serialize)]
+pub struct DirectoryChangedEvent {
+    #[serde(rename = "listingId")]
+    pub listing_id: u64,
+    pub path: String,
+    pub kind: ChangeKind,
+    /// The entry as it is now; `None` once the path is gone (removals and
+    /// the old side of a rename).
+    pub entry: Option<FileEntry>,
+}
+
+/// How often a watching listing checks for cancellation.
+const WATCH_POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Entry for a `read_dir` item, following symlinks for the metadata but
+/// falling back to the link itself when its target is unreadable.
+fn dir_entry_to_file_entry(entry: &fs::DirEntry) -> Option<FileEntry> {
+    let path = entry.path();
+    let metadata = fs::metadata(&path).or_else(|_| entry.metadata()).ok()?;
+    Some(metadata_to_entry(&path, &metadata))
+}
+
+/// Up to `batch_size` more entries from `read_dir`, in the order the OS
+/// returns them. Fewer means the directory is exhausted.
+fn read_unsorted_batch(read_dir: &mut fs::ReadDir, batch_size: usize) -> Vec<FileEntry> {
+    read_dir
+        .flatten()
+        .filter_map(|entry| dir_entry_to_file_entry(&entry))
+        .take(batch_size)
+        .collect()
 }
 
 /// Registry for active directory listings
@@ -165,11 +394,16 @@ static LISTINGS: crate::task_registry::TaskRegistry = crate::task_registry::Task
 
 /// Start streaming directory listing.
 /// Returns first batch immediately and emits remaining entries via events.
+/// With `auto_watch`, the listing then stays open and emits
+/// `directory-changed` for every change in the directory until
+/// `cancel_directory_listing` is called.
 #[tauri::command]
 pub async fn start_streaming_directory(
     app: AppHandle,
     path: String,
+    auto_watch: Option<bool>,
 ) -> Result<DirectoryListing, AppError> {
+    let auto_watch = auto_watch.unwrap_or(false);
     let dir_path = PathBuf::from(&path);
     let batch_size = 100;
 
@@ -192,25 +426,14 @@ pub async fn start_streaming_directory(
     let t_scan_start = std::time::Instant::now();
     let mut all_entries: Vec<FileEntry> = Vec::new();
 
-    for entry_result in read_dir {
-        let entry = match entry_result {
-            Ok(e) => e,
-            Err(_) => continue,
-        };
-
-        let metadata = match fs::metadata(entry.path()) {
-            Ok(m) => m,
-            Err(_) => match entry.metadata() {
-                Ok(m) => m,
-                Err(_) => continue,
-            },
-        };
-
-        all_entries.push(metadata_to_entry(&entry.path(), &metadata));
+    for entry in read_dir.flatten() {
+        if let Some(entry) = dir_entry_to_file_entry(&entry) {
+            all_entries.push(entry);
+        }
     }
     let t_scan_end = std::time::Instant::now();
 
-    sort_entries(&mut all_entries);
+    sort_default(&mut all_entries);
 
     let t_sort_end = std::time::Instant::now();
     let total_count = all_entries.len();
@@ -223,20 +446,16 @@ pub async fn start_streaming_directory(
         t_sort_end - t_scan_end,
     );
 
-    if total_count <= batch_size {
-        return Ok(DirectoryListing {
-            path,
-            entries: all_entries,
-            listing_id: None,
-        });
+    if total_count <= batch_size && !auto_watch {
+        return Ok(DirectoryListing::new(path, all_entries, None));
     }
 
-    let first_batch: Vec<FileEntry> = all_entries.drain(..batch_size).collect();
-    let remaining = all_entries;
-
+    // Totals cover the whole directory; only the first batch is returned
     let (listing_id, cancelled) = LISTINGS.start();
+    let mut listing = DirectoryListing::new(path.clone(), all_entries, Some(listing_id));
+    let remaining = listing.entries.split_off(batch_size.min(total_count));
 
-    let path_clone = path.clone();
+    let path_clone = path;
     std::thread::spawn(move || {
         let mut offset = batch_size;
 
@@ -253,6 +472,8 @@ pub async fn start_streaming_directory(
                     entries: chunk.to_vec(),
                     done: offset + chunk.len() >= total_count,
                     total_count,
+                    truncated: false,
+                    sorted: true,
                 },
             );
 
@@ -260,14 +481,161 @@ pub async fn start_streaming_directory(
             std::thread::sleep(std::time::Duration::from_millis(5));
         }
 
+        // A watched listing that fit in the first batch still reports `done`
+        if remaining.is_empty() {
+            let _ = app.emit(
+                "directory-entries",
+                DirectoryEntriesEvent {
+                    listing_id,
+                    path: path_clone.clone(),
+                    entries: Vec::new(),
+                    done: true,
+                    total_count,
+                    truncated: false,
+                    sorted: true,
+                },
+            );
+        }
+
+        if auto_watch && !cancelled.load(Ordering::Relaxed) {
+            watch_directory(&dir_path, &cancelled, |path, kind, entry| {
+                let _ = app.emit(
+                    "directory-changed",
+                    DirectoryChangedEvent {
+                        listing_id,
+                        path,
+                        kind,
+                        entry,
+                    },
+                );
+            });
+        }
+
         LISTINGS.cleanup(listing_id);
     });
 
-    Ok(DirectoryListing {
-        path,
-        entries: first_batch,
-        listing_id: Some(listing_id),
-    })
+    Ok(listing)
+}
+
+/// Like `start_streaming_directory`, but entries are emitted as they are
+/// read instead of after the whole directory has been scanned and sorted,
+/// so huge directories show their first entries right away. The first
+/// batch is returned directly; the rest arrive as `directory-entries`
+/// events with `sorted: false`, and `totalCount` is the number read so far.
+/// The listing's totals only cover the first batch.
+#[tauri::command]
+pub fn start_streaming_directory_unsorted(app: AppHandle, path: String) -> Result<DirectoryListing, AppError> {
+    let dir_path = PathBuf::from(&path);
+    let batch_size = 100;
+    check_listable(&dir_path, &path)?;
+
+    let mut read_dir = fs::read_dir(&dir_path).map_err(|e| {
+        if e.kind() == std::io::ErrorKind::PermissionDenied {
+            AppError::PermissionDenied(path.clone())
+        } else {
+            AppError::Io(e)
+        }
+    })?;
+
+    let first_batch = read_unsorted_batch(&mut read_dir, batch_size);
+    if first_batch.len() < batch_size {
+        return Ok(DirectoryListing::new(path, first_batch, None));
+    }
+
+    let (listing_id, cancelled) = LISTINGS.start();
+
+    let path_clone = path.clone();
+    std::thread::spawn(move || {
+        let mut total_count = batch_size;
+
+        loop {
+            if cancelled.load(Ordering::Relaxed) {
+                break;
+            }
+
+            let batch = read_unsorted_batch(&mut read_dir, batch_size);
+            let done = batch.len() < batch_size;
+            total_count += batch.len();
+            let _ = app.emit(
+                "directory-entries",
+                DirectoryEntriesEvent {
+                    listing_id,
+                    path: path_clone.clone(),
+                    entries: batch,
+                    done,
+                    total_count,
+                    truncated: false,
+                    sorted: false,
+                },
+            );
+            if done {
+                break;
+            }
+        }
+
+        LISTINGS.cleanup(listing_id);
+    });
+
+    Ok(DirectoryListing::new(path, first_batch, Some(listing_id)))
+}
+
+/// Watch `dir` (not its subdirectories) until `cancelled` is set, calling
+/// `on_change` with each changed path. Returns early if the watcher can't
+/// be installed.
+fn watch_directory(
+    dir: &Path,
+    cancelled: &AtomicBool,
+    mut on_change: impl FnMut(String, ChangeKind, Option<FileEntry>),
+) {
+    let (tx, rx) = mpsc::channel();
+    let mut watcher = match notify::recommended_watcher(tx) {
+        Ok(watcher) => watcher,
+        Err(e) => {
+            log::warn!("Could not create watcher for {:?}: {}", dir, e);
+            return;
+        }
+    };
+    if let Err(e) = watcher.watch(dir, RecursiveMode::NonRecursive) {
+        log::warn!("Could not watch {:?}: {}", dir, e);
+        return;
+    }
+    log::debug!("Watching {:?}", dir);
+
+    while !cancelled.load(Ordering::Relaxed) {
+        match rx.recv_timeout(WATCH_POLL_INTERVAL) {
+            Ok(Ok(event)) => {
+                let Some(kind) = change_kind(&event.kind) else {
+                    continue;
+                };
+                for path in &event.paths {
+                    let entry = match kind {
+                        ChangeKind::Remove => None,
+                        _ => fs::metadata(path)
+                            .or_else(|_| fs::symlink_metadata(path))
+                            .ok()
+                            .map(|metadata| metadata_to_entry(path, &metadata)),
+                    };
+                    on_change(path.to_string_lossy().to_string(), kind, entry);
+                }
+            }
+            Ok(Err(e)) => log::debug!("Watch error on {:?}: {}", dir, e),
+            Err(mpsc::RecvTimeoutError::Timeout) => {}
+            Err(mpsc::RecvTimeoutError::Disconnected) => break,
+        }
+    }
+    log::debug!("Stopped watching {:?}", dir);
+}
+
+/// Map a notify event to the change reported to the frontend. Access
+/// events and events notify can't classify are dropped.
+fn change_kind(kind: &EventKind) -> Option<ChangeKind> {
+    match kind {
+        EventKind::Create(_) => Some(ChangeKind::Create),
+        EventKind::Modify(ModifyKind::Name(_)) => Some(ChangeKind::Rename),
+        EventKind::Modify(_) => Some(ChangeKind::Modify),
+        EventKind::Remove(_) => Some(ChangeKind::Remove),
+        EventKind::Access(_) | EventKind::Any | EventKind::Other => None,
+    }
 }
 
 /// Cancel an active directory listing.
@@ -277,6 +645,205 @@ pub fn cancel_directory_listing(listing_id: u64) -> Result<(), AppError> {
     Ok(())
 }
 
+// ===================
+// Breadcrumbs
+// ===================
+
+/// Every ancestor of `path` plus the path itself, root first, for
+/// breadcrumb navigation. The root keeps its platform form: `/`, the drive
+/// (`C:`) on Windows, or `\\server\share` for UNC paths.
+#[tauri::command]
+pub fn get_parent_chain(path: String) -> Result<Vec<FileEntry>, AppError> {
+    let target = PathBuf::from(&path);
+    if !target.is_absolute() {
+        return Err(AppError::InvalidPath(format!("Not an absolute path: {}", path)));
+    }
+    if !target.exists() {
+        return Err(AppError::NotFound(path));
+    }
+
+    let mut chain: Vec<FileEntry> = target
+        .ancestors()
+        .map(|ancestor| match fs::metadata(ancestor) {
+            Ok(metadata) => metadata_to_entry(ancestor, &metadata),
+            // Unreadable ancestors (e.g. a share root) still belong in the trail
+            Err(_) => FileEntry {
+                name: String::new(),
+                path: ancestor.to_string_lossy().to_string(),
+                kind: FileKind::Directory,
+                size: 0,
+                modified: String::new(),
+                is_symlink: false,
+                symlink_target: None,
+                depth: None,
+            },
+        })
+        .collect();
+    chain.reverse();
+
+    if let Some(root) = chain.first_mut() {
+        let trimmed = root.path.trim_end_matches(['/', '\\']);
+        root.name = if trimmed.is_empty() { root.path.clone() } else { trimmed.to_string() };
+    }
+    Ok(chain)
+}
+
+// ===================
+// Recursive Listing
+// ===================
+
+/// Default cap on entries returned by recursive listings.
+const MAX_RECURSIVE_ENTRIES: usize = 100_000;
+
+/// Entries per `directory-entries` event for streamed recursive listings.
+const RECURSIVE_BATCH_SIZE: usize = 500;
+
+/// Response of `list_directory_recursive`.
+#[derive(Debug, Serialize)]
+pub struct RecursiveListing {
+    pub path: String,
+    /// Depth-first, each directory's children sorted by name; every entry
+    /// carries its `depth` (1 for direct children).
+    pub entries: Vec<FileEntry>,
+    /// True when `max_entries` cut the walk short.
+    pub truncated: bool,
+}
+
+fn check_listable(dir_path: &std::path::Path, path: &str) -> Result<(), AppError> {
+    if !dir_path.exists() {
+        return Err(AppError::NotFound(path.to_string()));
+    }
+    if !dir_path.is_dir() {
+        return Err(AppError::InvalidPath(format!("Not a directory: {}", path)));
+    }
+    Ok(())
+}
+
+/// Walk `root` with jwalk, calling `on_entry` for each entry below it until
+/// `max_entries` have been produced or `on_entry` returns false. Returns
+/// whether the walk was truncated by the cap.
+fn walk_recursive(
+    root: &std::path::Path,
+    max_depth: Option<u32>,
+    include_hidden: bool,
+    max_entries: usize,
+    mut on_entry: impl FnMut(FileEntry) -> bool,
+) -> bool {
+    let mut walker = jwalk::WalkDir::new(root)
+        .skip_hidden(!include_hidden)
+        .follow_links(false)
+        .sort(true);
+    if let Some(depth) = max_depth {
+        walker = walker.max_depth(depth as usize);
+    }
+
+    let mut count = 0;
+    for entry in walker.into_iter().filter_map(|e| e.ok()).filter(|e| e.depth > 0) {
+        if count == max_entries {
+            return true;
+        }
+        let path = entry.path();
+        let metadata = match fs::metadata(&path).or_else(|_| fs::symlink_metadata(&path)) {
+            Ok(m) => m,
+            Err(_) => continue,
+        };
+        let mut file_entry = metadata_to_entry(&path, &metadata);
+        file_entry.depth = Some(entry.depth as u32);
+        count += 1;
+        if !on_entry(file_entry) {
+            break;
+        }
+    }
+    false
+}
+
+/// List every entry under `path` down to `max_depth` levels (unlimited by
+/// default), capped at `max_entries` (default 100,000).
+#[tauri::command]
+pub async fn list_directory_recursive(
+    path: String,
+    max_depth: Option<u32>,
+    include_hidden: bool,
+    max_entries: Option<usize>,
+) -> Result<RecursiveListing, AppError> {
+    let dir_path = PathBuf::from(&path);
+    check_listable(&dir_path, &path)?;
+
+    tokio::task::spawn_blocking(move || {
+        let mut entries = Vec::new();
+        let limit = max_entries.unwrap_or(MAX_RECURSIVE_ENTRIES);
+        let truncated = walk_recursive(&dir_path, max_depth, include_hidden, limit, |entry| {
+            entries.push(entry);
+            true
+        });
+        log::debug!(
+            "list_directory_recursive: {} entries (truncated={})",
+            entries.len(),
+            truncated
+        );
+        RecursiveListing {
+            path,
+            entries,
+            truncated,
+        }
+    })
+    .await
+    .map_err(|e| AppError::Other(format!("Task join error: {}", e)))
+}
+
+/// Streaming variant of `list_directory_recursive`. Returns a listing ID
+/// immediately and emits `directory-entries` batches while walking; the
+/// last one has `done` set. Cancel with `cancel_directory_listing`.
+#[tauri::command]
+pub fn start_streaming_directory_recursive(
+    app: AppHandle,
+    path: String,
+    max_depth: Option<u32>,
+    include_hidden: bool,
+    max_entries: Option<usize>,
+) -> Result<u64, AppError> {
+    let dir_path = PathBuf::from(&path);
+    check_listable(&dir_path, &path)?;
+
+    let (listing_id, cancelled) = LISTINGS.start();
+    std::thread::spawn(move || {
+        let mut batch = Vec::with_capacity(RECURSIVE_BATCH_SIZE);
+        let mut total_count = 0;
+        let emit = |entries: Vec<FileEntry>, total_count: usize, done: bool, truncated: bool| {
+            let _ = app.emit(
+                "directory-entries",
+                DirectoryEntriesEvent {
+                    listing_id,
+                    path: path.clone(),
+                    entries,
+                    done,
+                    total_count,
+                    truncated,
+                    sorted: true,
+                },
+            );
+        };
+
+        let limit = max_entries.unwrap_or(MAX_RECURSIVE_ENTRIES);
+        let truncated = walk_recursive(&dir_path, max_depth, include_hidden, limit, |entry| {
+            if cancelled.load(Ordering::Relaxed) {
+                return false;
+            }
+            batch.push(entry);
+            total_count += 1;
+            if batch.len() == RECURSIVE_BATCH_SIZE {
+                emit(std::mem::take(&mut batch), total_count, false, false);
+            }
+            true
+        });
+        if !cancelled.load(Ordering::Relaxed) {
+            emit(batch, total_count, true, truncated);
+        }
+        LISTINGS.cleanup(listing_id);
+    });
+    Ok(listing_id)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -293,6 +860,7 @@ mod tests {
         let result = rt
             .block_on(list_directory(
                 dir.path().to_string_lossy().to_string(),
+                None,
             ))
             .unwrap();
 
@@ -300,4 +868,293 @@ mod tests {
         assert!(matches!(result.entries[0].kind, FileKind::Directory));
         assert!(matches!(result.entries[1].kind, FileKind::File));
     }
+
+    #[test]
+    fn test_list_directory_totals() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join("a.txt"), "12345").unwrap();
+        fs::write(dir.path().join(".hidden"), "123").unwrap();
+        fs::create_dir(dir.path().join("subdir")).unwrap();
+        let path = dir.path().to_string_lossy().to_string();
+
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        let listing = rt.block_on(list_directory(path.clone(), None)).unwrap();
+        assert_eq!(listing.total_size, 8);
+        assert_eq!(listing.total_files, 2);
+        assert_eq!(listing.total_directories, 1);
+        assert!(listing.has_hidden_entries);
+
+        let options = ListDirectoryOptions {
+            include_hidden: false,
+            ..Default::default()
+        };
+        let listing = rt.block_on(list_directory(path, Some(options))).unwrap();
+        assert_eq!(listing.total_size, 5);
+        assert_eq!(listing.total_files, 1);
+        assert!(listing.has_hidden_entries);
+    }
+
+    #[test]
+    fn test_read_unsorted_batch() {
+        let dir = tempdir().unwrap();
+        for i in 0..5 {
+            File::create(dir.path().join(format!("file{}.txt", i))).unwrap();
+        }
+        fs::create_dir(dir.path().join("subdir")).unwrap();
+
+        let mut read_dir = fs::read_dir(dir.path()).unwrap();
+        let first = read_unsorted_batch(&mut read_dir, 4);
+        let rest = read_unsorted_batch(&mut read_dir, 4);
+        assert_eq!(first.len(), 4);
+        assert_eq!(rest.len(), 2);
+        assert!(read_unsorted_batch(&mut read_dir, 4).is_empty());
+
+        let mut names: Vec<String> = first.into_iter().chain(rest).map(|e| e.name).collect();
+        names.sort();
+        assert_eq!(names, ["file0.txt", "file1.txt", "file2.txt", "file3.txt", "file4.txt", "subdir"]);
+    }
+
+    #[test]
+    fn test_get_parent_chain() {
+        let dir = tempdir().unwrap();
+        let nested = dir.path().join("a").join("b c");
+        fs::create_dir_all(&nested).unwrap();
+        File::create(nested.join("ünïcode.txt")).unwrap();
+        let file = nested.join("ünïcode.txt");
+
+        let chain = get_parent_chain(file.to_string_lossy().to_string()).unwrap();
+        let mut expected: Vec<PathBuf> = file.ancestors().map(PathBuf::from).collect();
+        expected.reverse();
+        assert_eq!(chain.len(), expected.len());
+        for (entry, path) in chain.iter().zip(&expected) {
+            assert_eq!(PathBuf::from(&entry.path), *path);
+        }
+
+        let root = &chain[0];
+        assert!(!root.name.is_empty());
+        assert!(matches!(root.kind, FileKind::Directory));
+        let tail: Vec<&str> = chain.iter().rev().take(3).map(|e| e.name.as_str()).collect();
+        assert_eq!(tail, vec!["ünïcode.txt", "b c", "a"]);
+        assert!(matches!(chain.last().unwrap().kind, FileKind::File));
+    }
+
+    #[test]
+    fn test_get_parent_chain_errors() {
+        assert!(matches!(get_parent_chain("relative/path".into()), Err(AppError::InvalidPath(_))));
+        let dir = tempdir().unwrap();
+        let missing = dir.path().join("missing").to_string_lossy().to_string();
+        assert!(matches!(get_parent_chain(missing), Err(AppError::NotFound(_))));
+    }
+
+    #[test]
+    fn test_list_directory_filters() {
+        let dir = tempdir().unwrap();
+        for name in ["main.rs", "lib.RS", "notes.txt", ".hidden.rs"] {
+            File::create(dir.path().join(name)).unwrap();
+        }
+        fs::create_dir(dir.path().join("src.rs")).unwrap();
+        fs::create_dir(dir.path().join(".git")).unwrap();
+        let path = dir.path().to_string_lossy().to_string();
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        let list = |options: ListDirectoryOptions| -> Vec<String> {
+            rt.block_on(list_directory(path.clone(), Some(options)))
+                .unwrap()
+                .entries
+                .into_iter()
+                .map(|e| e.name)
+                .collect()
+        };
+
+        assert_eq!(list(ListDirectoryOptions::default()).len(), 6);
+        let visible = list(ListDirectoryOptions {
+            include_hidden: false,
+            ..Default::default()
+        });
+        assert_eq!(visible, vec!["src.rs", "lib.RS", "main.rs", "notes.txt"]);
+
+        let rust_files = list(ListDirectoryOptions {
+            kinds: Some(vec![FileKind::File]),
+            name_pattern: Some("*.rs".into()),
+            ..Default::default()
+        });
+        assert_eq!(rust_files, vec![".hidden.rs", "lib.RS", "main.rs"]);
+
+        let dirs = list(ListDirectoryOptions {
+            include_hidden: false,
+            kinds: Some(vec![FileKind::Directory]),
+            ..Default::default()
+        });
+        assert_eq!(dirs, vec!["src.rs"]);
+
+        let bad = rt.block_on(list_directory(
+            path.clone(),
+            Some(ListDirectoryOptions {
+                name_pattern: Some("[".into()),
+                ..Default::default()
+            }),
+        ));
+        assert!(bad.is_err());
+    }
+
+    #[test]
+    fn test_list_directory_sorting() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join("b.txt"), "12345").unwrap();
+        fs::write(dir.path().join("a.rs"), "1").unwrap();
+        fs::write(dir.path().join("c.md"), "123").unwrap();
+        fs::create_dir(dir.path().join("zdir")).unwrap();
+        let path = dir.path().to_string_lossy().to_string();
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        let list = |sort_by: SortField, sort_order: SortOrder, dirs_first: bool| -> Vec<String> {
+            let options = ListDirectoryOptions {
+                sort_by,
+                sort_order,
+                dirs_first,
+                ..Default::default()
+            };
+            rt.block_on(list_directory(path.clone(), Some(options)))
+                .unwrap()
+                .entries
+                .into_iter()
+                .map(|e| e.name)
+                .collect()
+        };
+
+        // Default: unchanged behaviour
+        assert_eq!(
+            rt.block_on(list_directory(path.clone(), None)).unwrap().entries[0].name,
+            "zdir"
+        );
+        assert_eq!(
+            list(SortField::Size, SortOrder::Descending, true),
+            vec!["zdir", "b.txt", "c.md", "a.rs"]
+        );
+        assert_eq!(
+            list(SortField::Size, SortOrder::Ascending, false),
+            vec!["zdir", "a.rs", "c.md", "b.txt"]
+        );
+        assert_eq!(
+            list(SortField::Extension, SortOrder::Ascending, true),
+            vec!["zdir", "c.md", "a.rs", "b.txt"]
+        );
+        assert_eq!(
+            list(SortField::Name, SortOrder::Descending, false),
+            vec!["zdir", "c.md", "b.txt", "a.rs"]
+        );
+        assert_eq!(
+            list(SortField::Kind, SortOrder::Descending, false),
+            vec!["a.rs", "b.txt", "c.md", "zdir"]
+        );
+    }
+
+    #[test]
+    fn test_sort_entries() {
+        let entries: Vec<FileEntry> = serde_json::from_str(
+            r#"[
+                {"name":"b.txt","path":"/d/b.txt","kind":"file","size":5,"modified":"2024-01-02T00:00:00Z"},
+                {"name":"A.rs","path":"/d/A.rs","kind":"file","size":1,"modified":"2024-01-03T00:00:00Z"},
+                {"name":"sub","path":"/d/sub","kind":"directory","size":0,"modified":"2024-01-01T00:00:00Z"}
+            ]"#,
+        )
+        .unwrap();
+        let names = |sorted: Vec<FileEntry>| sorted.into_iter().map(|e| e.name).collect::<Vec<_>>();
+
+        let sorted = sort_entries(entries.clone(), SortField::Modified, SortOrder::Descending, false).unwrap();
+        assert_eq!(names(sorted), ["A.rs", "b.txt", "sub"]);
+        let sorted = sort_entries(entries.clone(), SortField::Name, SortOrder::Descending, true).unwrap();
+        assert_eq!(names(sorted), ["sub", "b.txt", "A.rs"]);
+        assert!(sort_entries(Vec::new(), SortField::Size, SortOrder::Ascending, true).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_sort_order_aliases() {
+        let options: ListDirectoryOptions =
+            serde_json::from_str(r#"{"sortBy":"modified","sortOrder":"desc","dirsFirst":false}"#).unwrap();
+        assert_eq!(options.sort_by, SortField::Modified);
+        assert_eq!(options.sort_order, SortOrder::Descending);
+        assert!(!options.dirs_first && options.include_hidden);
+    }
+
+    #[test]
+    fn test_list_directory_recursive() {
+        let dir = tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("a/b/c")).unwrap();
+        fs::create_dir(dir.path().join(".hidden")).unwrap();
+        File::create(dir.path().join("a/one.txt")).unwrap();
+        File::create(dir.path().join("a/b/c/deep.txt")).unwrap();
+        File::create(dir.path().join(".hidden/secret")).unwrap();
+        let path = dir.path().to_string_lossy().to_string();
+        let rt = tokio::runtime::Runtime::new().unwrap();
+
+        let all = rt.block_on(list_directory_recursive(path.clone(), None, false, None)).unwrap();
+        assert!(!all.truncated);
+        let names: Vec<(String, u32)> = all.entries.iter().map(|e| (e.name.clone(), e.depth.unwrap())).collect();
+        assert_eq!(
+            names,
+            vec![
+                ("a".to_string(), 1),
+                ("b".to_string(), 2),
+                ("c".to_string(), 3),
+                ("deep.txt".to_string(), 4),
+                ("one.txt".to_string(), 2),
+            ]
+        );
+
+        let shallow = rt.block_on(list_directory_recursive(path.clone(), Some(2), true, None)).unwrap();
+        assert_eq!(shallow.entries.len(), 5, "a, a/b, a/one.txt, .hidden, .hidden/secret");
+        assert!(shallow.entries.iter().all(|e| e.depth.unwrap() <= 2));
+
+        let capped = rt.block_on(list_directory_recursive(path, None, true, Some(3))).unwrap();
+        assert!(capped.truncated);
+        assert_eq!(capped.entries.len(), 3);
+    }
+
+    #[test]
+    fn test_change_kind() {
+        use notify::event::{CreateKind, DataChange, RemoveKind, RenameMode};
+
+        assert_eq!(change_kind(&EventKind::Create(CreateKind::File)), Some(ChangeKind::Create));
+        assert_eq!(
+            change_kind(&EventKind::Modify(ModifyKind::Data(DataChange::Content))),
+            Some(ChangeKind::Modify)
+        );
+        assert_eq!(
+            change_kind(&EventKind::Modify(ModifyKind::Name(RenameMode::Both))),
+            Some(ChangeKind::Rename)
+        );
+        assert_eq!(change_kind(&EventKind::Remove(RemoveKind::Any)), Some(ChangeKind::Remove));
+        assert_eq!(change_kind(&EventKind::Any), None);
+    }
+
+    #[test]
+    fn test_watch_directory_reports_changes_until_cancelled() {
+        let dir = tempdir().unwrap();
+        let root = dir.path().to_path_buf();
+        let cancelled = std::sync::Arc::new(AtomicBool::new(false));
+        let (tx, rx) = mpsc::channel();
+
+        let watch_root = root.clone();
+        let watch_cancelled = cancelled.clone();
+        let watcher = std::thread::spawn(move || {
+            watch_directory(&watch_root, &watch_cancelled, |path, kind, entry| {
+                let _ = tx.send((path, kind, entry.is_some()));
+            })
+        });
+        // Give the watcher time to install before changing anything
+        std::thread::sleep(Duration::from_millis(200));
+
+        let file = root.join("new.txt");
+        File::create(&file).unwrap();
+        let (path, kind, has_entry) = rx.recv_timeout(Duration::from_secs(5)).unwrap();
+        assert_eq!((path.as_str(), kind, has_entry), (&*file.to_string_lossy(), ChangeKind::Create, true));
+
+        fs::remove_file(&file).unwrap();
+        let removed = std::iter::from_fn(|| rx.recv_timeout(Duration::from_secs(5)).ok())
+            .find(|(_, kind, _)| *kind == ChangeKind::Remove)
+            .unwrap();
+        assert!(!removed.2);
+
+        cancelled.store(true, Ordering::Relaxed);
+        watcher.join().unwrap();
+    }
 }
@@ -0,0 +1,675 @@
+//! Aggregate numbers about directory trees, without building entry lists,
+//! and whole-tree maintenance scans.
+
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::SystemTime;
+
+use jwalk::WalkDir;
+use serde::Serialize;
+use tauri::{AppHandle, Emitter};
+
+use crate::error::AppError;
+use crate::task_registry::TaskRegistry;
+use super::{metadata_to_entry, FileEntry};
+
+static ENTRY_COUNTS: TaskRegistry = TaskRegistry::new();
+static LARGE_FILE_SCANS: TaskRegistry = TaskRegistry::new();
+static STATISTICS_SCANS: TaskRegistry = TaskRegistry::new();
+
+/// Entries counted between `entry-count-progress` events.
+const COUNT_PROGRESS_INTERVAL: u64 = 1_000;
+
+/// Files scanned between `large-files-progress` events.
+const LARGE_FILES_PROGRESS_INTERVAL: u64 = 1_000;
+
+/// Upper bound on `find_large_files`' `limit`.
+const MAX_LARGE_FILES: usize = 1_000;
+
+/// Result of `get_directory_entry_counts`. Symlinks are counted on their own
+/// and never followed; `totalSize` sums regular files.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct EntryCounts {
+    pub files: u64,
+    pub directories: u64,
+    pub symlinks: u64,
+    #[serde(rename = "totalSize")]
+    pub total_size: u64,
+}
+
+/// Event payload for `large-files-progress` and `large-files-complete`.
+#[derive(Debug, Clone, Serialize)]
+pub struct LargeFilesEvent {
+    #[serde(rename = "scanId")]
+    pub scan_id: u64,
+    /// Largest files found so far, largest first.
+    pub files: Vec<FileEntry>,
+    #[serde(rename = "filesScanned")]
+    pub files_scanned: u64,
+}
+
+/// A symlink whose target can't be reached, from `find_broken_symlinks`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct BrokenSymlink {
+    #[serde(rename = "linkPath")]
+    pub link_path: String,
+    /// The link's target as stored, possibly relative.
+    pub target: String,
+    /// `target does not exist`, `circular reference` or `target not accessible`.
+    pub reason: String,
+}
+
+/// Files with one extension, in `DirectoryStatistics`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExtensionStats {
+    pub count: u64,
+    pub total_bytes: u64,
+}
+
+/// Event payload for `directory-statistics`. Only regular files are
+/// counted; symlinks are neither counted nor followed.
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DirectoryStatistics {
+    pub stats_id: u64,
+    pub total_files: u64,
+    /// Directories below the root.
+    pub total_dirs: u64,
+    pub total_size_bytes: u64,
+    /// `None` when there are no files.
+    pub largest_file: Option<FileEntry>,
+    pub oldest_file: Option<FileEntry>,
+    pub newest_file: Option<FileEntry>,
+    /// Keyed by lowercase extension without the dot; `""` for none.
+    pub extension_breakdown: HashMap<String, ExtensionStats>,
+    /// Bytes of the files at each depth; index 0 is files directly in the root.
+    pub size_by_depth: Vec<u64>,
+}
+
+/// Event payload for `entry-count-progress`.
+#[derive(Debug, Clone, Serialize)]
+pub struct EntryCountProgress {
+    #[serde(rename = "countId")]
+    pub count_id: u64,
+    #[serde(flatten)]
+    pub counts: EntryCounts,
+}
+
+#[derive(Default)]
+struct Counters {
+    files: AtomicU64,
+    directories: AtomicU64,
+    symlinks: AtomicU64,
+    total_size: AtomicU64,
+}
+
+impl Counters {
+    fn snapshot(&self) -> EntryCounts {
+        EntryCounts {
+            files: self.files.load(Ordering::Relaxed),
+            directories: self.directories.load(Ordering::Relaxed),
+            symlinks: self.symlinks.load(Ordering::Relaxed),
+            total_size: self.total_size.load(Ordering::Relaxed),
+        }
+    }
+
+    fn entries(&self) -> u64 {
+        self.files.load(Ordering::Relaxed)
+            + self.directories.load(Ordering::Relaxed)
+            + self.symlinks.load(Ordering::Relaxed)
+    }
+}
+
+/// Count files, directories and symlinks under `path`, recursively.
+///
+/// Emits `entry-count-progress` (`{ countId, files, directories, symlinks,
+/// totalSize }`) every 1,000 entries; pass its `countId` to
+/// `cancel_entry_count` to stop early.
+#[tauri::command]
+pub async fn get_directory_entry_counts(
+    app: AppHandle,
+    path: String,
+    include_hidden: bool,
+) -> Result<EntryCounts, AppError> {
+    let root = Path::new(&path).to_path_buf();
+    if !root.is_dir() {
+        return Err(AppError::NotFound(path));
+    }
+
+    let (count_id, cancelled) = ENTRY_COUNTS.start();
+    let result = tokio::task::spawn_blocking(move || {
+        count_entries(&root, include_hidden, cancelled, move |counts| {
+            let _ = app.emit("entry-count-progress", EntryCountProgress { count_id, counts });
+        })
+    })
+    .await;
+    ENTRY_COUNTS.cleanup(count_id);
+    result.map_err(|e| AppError::Other(format!("Task join error: {}", e)))?
+}
+
+/// Stop a running `get_directory_entry_counts`.
+#[tauri::command]
+pub fn cancel_entry_count(count_id: u64) -> Result<(), AppError> {
+    ENTRY_COUNTS.cancel(count_id);
+    Ok(())
+}
+
+/// Find the `limit` largest files under `root`, recursively. Returns a scan
+/// ID at once; the running top list arrives as `large-files-progress`
+/// events (`{ scanId, files, filesScanned }`) and the final list as
+/// `large-files-complete`. Files under `min_size_bytes` are ignored.
+/// Symlinks are not followed.
+#[tauri::command]
+pub fn find_large_files(
+    app: AppHandle,
+    root: String,
+    limit: usize,
+    min_size_bytes: Option<u64>,
+) -> Result<u64, AppError> {
+    let root_path = PathBuf::from(&root);
+    if !root_path.is_dir() {
+        return Err(AppError::NotFound(root));
+    }
+    let limit = limit.clamp(1, MAX_LARGE_FILES);
+    let (scan_id, cancelled) = LARGE_FILE_SCANS.start();
+
+    std::thread::spawn(move || {
+        let progress_app = app.clone();
+        let (files, files_scanned) = scan_large_files(
+            &root_path,
+            limit,
+            min_size_bytes.unwrap_or(0),
+            &cancelled,
+            |files, files_scanned| {
+                let _ = progress_app.emit(
+                    "large-files-progress",
+                    LargeFilesEvent { scan_id, files, files_scanned },
+                );
+            },
+        );
+        if !cancelled.load(Ordering::Relaxed) {
+            log::debug!("find_large_files({:?}): scanned {} files", root_path, files_scanned);
+            let _ = app.emit("large-files-complete", LargeFilesEvent { scan_id, files, files_scanned });
+        }
+        LARGE_FILE_SCANS.cleanup(scan_id);
+    });
+
+    Ok(scan_id)
+}
+
+/// Stop a running `find_large_files`.
+#[tauri::command]
+pub fn cancel_large_files_scan(scan_id: u64) -> Result<(), AppError> {
+    LARGE_FILE_SCANS.cancel(scan_id);
+    Ok(())
+}
+
+/// Size, counts, per-extension totals, extremes and size per depth of
+/// everything under `path`, down to `max_depth` levels (unlimited by
+/// default), in one walk. Returns an ID at once and emits a single
+/// `directory-statistics` event when done; hidden files are included.
+#[tauri::command]
+pub fn get_directory_statistics(app: AppHandle, path: String, max_depth: Option<u32>) -> Result<u64, AppError> {
+    let root = PathBuf::from(&path);
+    if !root.is_dir() {
+        return Err(AppError::NotFound(path));
+    }
+    let (stats_id, cancelled) = STATISTICS_SCANS.start();
+
+    std::thread::spawn(move || {
+        let mut stats = collect_statistics(&root, max_depth, &cancelled);
+        if !cancelled.load(Ordering::Relaxed) {
+            log::debug!("get_directory_statistics({:?}): {} files", root, stats.total_files);
+            stats.stats_id = stats_id;
+            let _ = app.emit("directory-statistics", stats);
+        }
+        STATISTICS_SCANS.cleanup(stats_id);
+    });
+
+    Ok(stats_id)
+}
+
+/// Stop a running `get_directory_statistics`; no event is sent.
+#[tauri::command]
+pub fn cancel_directory_statistics(stats_id: u64) -> Result<(), AppError> {
+    STATISTICS_SCANS.cancel(stats_id);
+    Ok(())
+}
+
+/// The walk behind `get_directory_statistics`. The extremes are kept as
+/// paths and only turned into entries at the end.
+fn collect_statistics(root: &Path, max_depth: Option<u32>, cancelled: &AtomicBool) -> DirectoryStatistics {
+    let mut walker = WalkDir::new(root).skip_hidden(false).follow_links(false);
+    if let Some(depth) = max_depth {
+        walker = walker.max_depth(depth as usize);
+    }
+
+    let mut stats = DirectoryStatistics::default();
+    let mut largest: Option<(u64, PathBuf)> = None;
+    let mut oldest: Option<(SystemTime, PathBuf)> = None;
+    let mut newest: Option<(SystemTime, PathBuf)> = None;
+    for entry in walker.into_iter().flatten() {
+        if cancelled.load(Ordering::Relaxed) {
+            break;
+        }
+        let file_type = entry.file_type();
+        if entry.depth == 0 || file_type.is_symlink() {
+            continue;
+        }
+        if file_type.is_dir() {
+            stats.total_dirs += 1;
+            continue;
+        }
+        let Ok(metadata) = entry.metadata() else {
+            continue;
+        };
+        let (size, path) = (metadata.len(), entry.path());
+        stats.total_files += 1;
+        stats.total_size_bytes += size;
+
+        let extension = path
+            .extension()
+            .map(|e| e.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+        let by_extension = stats.extension_breakdown.entry(extension).or_default();
+        by_extension.count += 1;
+        by_extension.total_bytes += size;
+
+        let depth = entry.depth - 1;
+        if stats.size_by_depth.len() <= depth {
+            stats.size_by_depth.resize(depth + 1, 0);
+        }
+        stats.size_by_depth[depth] += size;
+
+        if largest.as_ref().is_none_or(|(s, _)| size > *s) {
+            largest = Some((size, path.clone()));
+        }
+        if let Ok(modified) = metadata.modified() {
+            if oldest.as_ref().is_none_or(|(t, _)| modified < *t) {
+                oldest = Some((modified, path.clone()));
+            }
+            if newest.as_ref().is_none_or(|(t, _)| modified > *t) {
+                newest = Some((modified, path));
+            }
+        }
+    }
+
+    let to_entry = |path: PathBuf| fs::symlink_metadata(&path).ok().map(|m| metadata_to_entry(&path, &m));
+    stats.largest_file = largest.and_then(|(_, path)| to_entry(path));
+    stats.oldest_file = oldest.and_then(|(_, path)| to_entry(path));
+    stats.newest_file = newest.and_then(|(_, path)| to_entry(path));
+    stats
+}
+
+/// Walk `root` keeping the `limit` largest files in a min-heap, so memory
+/// stays bounded by `limit` however many files there are. `progress` gets
+/// the current top list every 1,000 files when it has changed. Returns the
+/// final list, largest first, and the number of files scanned.
+pub(crate) fn scan_large_files(
+    root: &Path,
+    limit: usize,
+    min_size: u64,
+    cancelled: &AtomicBool,
+    mut progress: impl FnMut(Vec<FileEntry>, u64),
+) -> (Vec<FileEntry>, u64) {
+    let mut heap: BinaryHeap<Reverse<(u64, PathBuf)>> = BinaryHeap::with_capacity(limit + 1);
+    let mut files_scanned = 0u64;
+    let mut changed = false;
+
+    let walker = WalkDir::new(root).skip_hidden(false).follow_links(false);
+    for entry in walker.into_iter().flatten() {
+        if cancelled.load(Ordering::Relaxed) {
+            break;
+        }
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        files_scanned += 1;
+
+        let size = entry.metadata().map(|m| m.len()).unwrap_or(0);
+        // Once the heap is full, anything no bigger than its smallest is out
+        let floor = match heap.peek() {
+            Some(Reverse((smallest, _))) if heap.len() >= limit => min_size.max(smallest + 1),
+            _ => min_size,
+        };
+        if size >= floor {
+            heap.push(Reverse((size, entry.path())));
+            if heap.len() > limit {
+                heap.pop();
+            }
+            changed = true;
+        }
+
+        if changed && files_scanned.is_multiple_of(LARGE_FILES_PROGRESS_INTERVAL) {
+            progress(sorted_entries(&heap), files_scanned);
+            changed = false;
+        }
+    }
+
+    (sorted_entries(&heap), files_scanned)
+}
+
+/// The heap's files as entries, largest first. Files deleted since they were
+/// seen are dropped.
+fn sorted_entries(heap: &BinaryHeap<Reverse<(u64, PathBuf)>>) -> Vec<FileEntry> {
+    let mut files: Vec<&(u64, PathBuf)> = heap.iter().map(|Reverse(item)| item).collect();
+    files.sort_by(|a, b| b.cmp(a));
+    files
+        .into_iter()
+        .filter_map(|(_, path)| {
+            let metadata = fs::symlink_metadata(path).ok()?;
+            Some(metadata_to_entry(path, &metadata))
+        })
+        .collect()
+}
+
+/// List symlinks under `root` whose targets don't resolve, down to
+/// `max_depth` levels (unlimited by default). Hidden entries are included.
+#[tauri::command]
+pub async fn find_broken_symlinks(
+    root: String,
+    max_depth: Option<u32>,
+) -> Result<Vec<BrokenSymlink>, AppError> {
+    let root_path = PathBuf::from(&root);
+    if !root_path.is_dir() {
+        return Err(AppError::NotFound(root));
+    }
+    tokio::task::spawn_blocking(move || scan_broken_symlinks(&root_path, max_depth))
+        .await
+        .map_err(|e| AppError::Other(format!("Task join error: {}", e)))
+}
+
+fn scan_broken_symlinks(root: &Path, max_depth: Option<u32>) -> Vec<BrokenSymlink> {
+    let mut walker = WalkDir::new(root).skip_hidden(false).follow_links(false);
+    if let Some(depth) = max_depth {
+        walker = walker.max_depth(depth as usize);
+    }
+
+    let mut broken: Vec<BrokenSymlink> = walker
+        .into_iter()
+        .flatten()
+        .filter(|entry| entry.file_type().is_symlink())
+        .filter_map(|entry| {
+            let path = entry.path();
+            // metadata follows the link; it only fails when the chain is broken
+            if fs::metadata(&path).is_ok() {
+                return None;
+            }
+            Some(BrokenSymlink {
+                link_path: path.to_string_lossy().to_string(),
+                target: fs::read_link(&path)
+                    .map(|t| t.to_string_lossy().to_string())
+                    .unwrap_or_default(),
+                reason: broken_link_reason(&path).to_string(),
+            })
+        })
+        .collect();
+    broken.sort_by(|a, b| a.link_path.cmp(&b.link_path));
+    log::debug!("find_broken_symlinks({:?}): {} found", root, broken.len());
+    broken
+}
+
+/// Identity of a link in a chain, for loop detection.
+#[cfg(unix)]
+type LinkId = (u64, u64);
+#[cfg(not(unix))]
+type LinkId = PathBuf;
+
+#[cfg(unix)]
+fn link_id(_path: &Path, metadata: &fs::Metadata) -> LinkId {
+    use std::os::unix::fs::MetadataExt;
+    (metadata.dev(), metadata.ino())
+}
+
+#[cfg(not(unix))]
+fn link_id(path: &Path, _metadata: &fs::Metadata) -> LinkId {
+    path.to_path_buf()
+}
+
+/// Follow the link chain one hop at a time to tell a dangling link from a
+/// loop: a link seen twice means the chain is circular.
+fn broken_link_reason(link: &Path) -> &'static str {
+    let mut visited: HashSet<LinkId> = HashSet::new();
+    let mut current = link.to_path_buf();
+    loop {
+        let Ok(metadata) = fs::symlink_metadata(&current) else {
+            return "target does not exist";
+        };
+        // The chain ends in something real that still couldn't be stat'ed
+        if !metadata.file_type().is_symlink() {
+            return "target not accessible";
+        }
+        if !visited.insert(link_id(&current, &metadata)) {
+            return "circular reference";
+        }
+        let Ok(target) = fs::read_link(&current) else {
+            return "target not accessible";
+        };
+        current = match current.parent() {
+            Some(parent) => parent.join(target),
+            None => target,
+        };
+    }
+}
+
+/// Count entries under `root`. Counting happens in jwalk's parallel
+/// read-dir callback, so no per-entry objects reach the caller.
+pub(crate) fn count_entries(
+    root: &Path,
+    include_hidden: bool,
+    cancelled: Arc<AtomicBool>,
+    progress: impl Fn(EntryCounts) + Send + Sync + 'static,
+) -> Result<EntryCounts, AppError> {
+    let counters = Arc::new(Counters::default());
+    let walk_counters = counters.clone();
+    let walk_cancelled = cancelled.clone();
+
+    let walker = WalkDir::new(root)
+        .skip_hidden(!include_hidden)
+        .follow_links(false)
+        .process_read_dir(move |depth, _path, _state, children| {
+            if walk_cancelled.load(Ordering::Relaxed) {
+                children.clear();
+                return;
+            }
+            // The root itself arrives alone with no depth
+            if depth.is_none() {
+                return;
+            }
+            let before = walk_counters.entries();
+            for entry in children.iter().flatten() {
+                let file_type = entry.file_type();
+                if file_type.is_symlink() {
+                    walk_counters.symlinks.fetch_add(1, Ordering::Relaxed);
+                } else if file_type.is_dir() {
+                    walk_counters.directories.fetch_add(1, Ordering::Relaxed);
+                } else {
+                    walk_counters.files.fetch_add(1, Ordering::Relaxed);
+                    let size = entry.metadata().map(|m| m.len()).unwrap_or(0);
+                    walk_counters.total_size.fetch_add(size, Ordering::Relaxed);
+                }
+            }
+            let after = walk_counters.entries();
+            if before / COUNT_PROGRESS_INTERVAL != after / COUNT_PROGRESS_INTERVAL {
+                progress(walk_counters.snapshot());
+            }
+            // Nothing downstream needs the entries; keep only directories to descend into
+            children.retain(|entry| entry.as_ref().is_ok_and(|e| e.file_type().is_dir()));
+        });
+    for _ in walker {}
+
+    if cancelled.load(Ordering::Relaxed) {
+        return Err(AppError::Other("Counting cancelled".into()));
+    }
+    let counts = counters.snapshot();
+    log::debug!(
+        "Counted {:?}: {} files, {} dirs, {} symlinks, {} bytes",
+        root,
+        counts.files,
+        counts.directories,
+        counts.symlinks,
+        counts.total_size
+    );
+    Ok(counts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_count_entries() {
+        let dir = tempdir().unwrap();
+        let root = dir.path();
+        fs::create_dir_all(root.join("a/b")).unwrap();
+        fs::create_dir(root.join(".hidden")).unwrap();
+        fs::write(root.join("one.txt"), "12345").unwrap();
+        fs::write(root.join("a/two.txt"), "123").unwrap();
+        fs::write(root.join("a/b/three.txt"), "1").unwrap();
+        fs::write(root.join(".hidden/four.txt"), "1234").unwrap();
+        #[cfg(unix)]
+        std::os::unix::fs::symlink(root.join("a"), root.join("link")).unwrap();
+
+        let visible = count_entries(root, false, Arc::new(AtomicBool::new(false)), |_| {}).unwrap();
+        assert_eq!((visible.files, visible.directories, visible.total_size), (3, 2, 9));
+        #[cfg(unix)]
+        assert_eq!(visible.symlinks, 1);
+
+        let all = count_entries(root, true, Arc::new(AtomicBool::new(false)), |_| {}).unwrap();
+        assert_eq!((all.files, all.directories, all.total_size), (4, 3, 13));
+    }
+
+    #[test]
+    fn test_count_entries_progress_and_cancel() {
+        let dir = tempdir().unwrap();
+        for i in 0..2_500 {
+            fs::write(dir.path().join(format!("{}.txt", i)), "").unwrap();
+        }
+
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let sink = events.clone();
+        let counts = count_entries(dir.path(), true, Arc::new(AtomicBool::new(false)), move |c| {
+            sink.lock().unwrap().push(c)
+        })
+        .unwrap();
+        assert_eq!(counts.files, 2_500);
+        assert!(!events.lock().unwrap().is_empty());
+
+        let result = count_entries(dir.path(), true, Arc::new(AtomicBool::new(true)), |_| {});
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_scan_large_files_keeps_top_n() {
+        let dir = tempdir().unwrap();
+        let root = dir.path();
+        fs::create_dir_all(root.join("nested/.cache")).unwrap();
+        for (name, size) in [("a", 10), ("nested/b", 50), ("nested/.cache/c", 30), ("d", 20), ("e", 1)] {
+            fs::write(root.join(name), vec![0u8; size]).unwrap();
+        }
+
+        let (top, scanned) = scan_large_files(root, 3, 0, &AtomicBool::new(false), |_, _| {});
+        assert_eq!(scanned, 5);
+        let sizes: Vec<u64> = top.iter().map(|f| f.size).collect();
+        assert_eq!(sizes, [50, 30, 20]);
+        assert_eq!(top[0].name, "b");
+
+        let (top, _) = scan_large_files(root, 10, 25, &AtomicBool::new(false), |_, _| {});
+        assert_eq!(top.iter().map(|f| f.size).collect::<Vec<_>>(), [50, 30]);
+    }
+
+    #[test]
+    fn test_collect_statistics() {
+        let dir = tempdir().unwrap();
+        let root = dir.path();
+        fs::create_dir_all(root.join("src/deep")).unwrap();
+        for (name, size) in [("README.md", 10), ("src/main.rs", 40), ("src/lib.RS", 5), ("src/deep/data", 100)] {
+            fs::write(root.join(name), vec![0u8; size]).unwrap();
+        }
+        let old = root.join("README.md");
+        fs::File::options()
+            .write(true)
+            .open(&old)
+            .unwrap()
+            .set_modified(SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(1_000_000))
+            .unwrap();
+
+        let stats = collect_statistics(root, None, &AtomicBool::new(false));
+        assert_eq!((stats.total_files, stats.total_dirs, stats.total_size_bytes), (4, 2, 155));
+        assert_eq!(stats.largest_file.unwrap().name, "data");
+        assert_eq!(stats.oldest_file.unwrap().name, "README.md");
+        assert_ne!(stats.newest_file.unwrap().name, "README.md");
+        assert_eq!(stats.extension_breakdown["rs"], ExtensionStats { count: 2, total_bytes: 45 });
+        assert_eq!(stats.extension_breakdown[""].count, 1);
+        assert_eq!(stats.size_by_depth, [10, 45, 100]);
+
+        let shallow = collect_statistics(root, Some(1), &AtomicBool::new(false));
+        assert_eq!((shallow.total_files, shallow.total_dirs), (1, 1));
+        assert_eq!(shallow.size_by_depth, [10]);
+
+        let empty = tempdir().unwrap();
+        let stats = collect_statistics(empty.path(), None, &AtomicBool::new(false));
+        assert!(stats.largest_file.is_none() && stats.size_by_depth.is_empty());
+    }
+
+    #[test]
+    fn test_scan_large_files_progress_and_cancel() {
+        let dir = tempdir().unwrap();
+        for i in 0..2_500 {
+            fs::write(dir.path().join(format!("{}.bin", i)), vec![0u8; i % 7]).unwrap();
+        }
+
+        let mut updates = 0;
+        let (top, _) = scan_large_files(dir.path(), 5, 0, &AtomicBool::new(false), |files, _| {
+            assert!(files.len() <= 5);
+            updates += 1;
+        });
+        assert!(updates > 0);
+        assert!(top.iter().all(|f| f.size == 6));
+
+        let (top, scanned) = scan_large_files(dir.path(), 5, 0, &AtomicBool::new(true), |_, _| {});
+        assert!(top.is_empty());
+        assert_eq!(scanned, 0);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_scan_broken_symlinks() {
+        use std::os::unix::fs::symlink;
+
+        let dir = tempdir().unwrap();
+        let root = dir.path();
+        fs::create_dir_all(root.join("deep/er")).unwrap();
+        fs::write(root.join("real.txt"), "x").unwrap();
+        symlink("real.txt", root.join("ok")).unwrap();
+        symlink("missing.txt", root.join("dangling")).unwrap();
+        symlink("loop_b", root.join("loop_a")).unwrap();
+        symlink("loop_a", root.join("loop_b")).unwrap();
+        symlink("../../gone", root.join("deep/er/nested")).unwrap();
+
+        let broken = scan_broken_symlinks(root, None);
+        let found: Vec<(&str, &str)> = broken
+            .iter()
+            .map(|b| (b.link_path.strip_prefix(&*root.to_string_lossy()).unwrap(), b.reason.as_str()))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                ("/dangling", "target does not exist"),
+                ("/deep/er/nested", "target does not exist"),
+                ("/loop_a", "circular reference"),
+                ("/loop_b", "circular reference"),
+            ]
+        );
+        assert_eq!(broken[0].target, "missing.txt");
+
+        assert_eq!(scan_broken_symlinks(root, Some(1)).len(), 3);
+    }
+}
@@ -1,7 +1,10 @@
 //! External application launching: open files, image viewers, terminals.
 
+use std::collections::HashSet;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
 
 use crate::error::AppError;
 
@@ -14,10 +17,15 @@ pub fn open_file(path: String) -> Result<(), AppError> {
         return Err(AppError::NotFound(path));
     }
 
-    opener::open(&file_path).map_err(|e| AppError::Other(e.to_string()))
+    opener::open(&file_path).map_err(|e| AppError::Other(e.to_string()))?;
+    crate::recent::record(&path);
+    Ok(())
 }
 
-/// Open a file with a specified application.
+/// Open a file with a specified application rather than the system
+/// default: `app` is spawned with the file on Linux, passed to `open -a` on
+/// macOS (a bundle path or app name), and launched through `ShellExecuteW`
+/// on Windows.
 #[tauri::command]
 pub fn open_file_with(path: String, app: String) -> Result<(), AppError> {
     let file_path = PathBuf::from(&path);
@@ -25,15 +33,68 @@ pub fn open_file_with(path: String, app: String) -> Result<(), AppError> {
     if !file_path.exists() {
         return Err(AppError::NotFound(path));
     }
+    if app.trim().is_empty() {
+        return Err(AppError::InvalidPath("No application given".into()));
+    }
 
-    std::process::Command::new(&app)
-        .arg(&file_path)
-        .spawn()
-        .map_err(|e| AppError::Io(e))?;
+    launch_with(&app, &file_path).map_err(|e| match e.kind() {
+        std::io::ErrorKind::NotFound => AppError::NotFound(app),
+        _ => AppError::Io(e),
+    })
+}
 
+#[cfg(target_os = "macos")]
+fn launch_with(app: &str, file: &Path) -> std::io::Result<()> {
+    let status = std::process::Command::new("open").arg("-a").arg(app).arg(file).status()?;
+    if !status.success() {
+        return Err(std::io::Error::new(std::io::ErrorKind::NotFound, "open -a failed"));
+    }
     Ok(())
 }
 
+/// `ShellExecuteW` with the `open` verb, `app` as the program and the
+/// quoted file as its argument.
+#[cfg(target_os = "windows")]
+fn launch_with(app: &str, file: &Path) -> std::io::Result<()> {
+    const SW_SHOWNORMAL: i32 = 1;
+
+    #[link(name = "shell32")]
+    extern "system" {
+        fn ShellExecuteW(
+            hwnd: *mut std::ffi::c_void,
+            operation: *const u16,
+            file: *const u16,
+            parameters: *const u16,
+            directory: *const u16,
+            show: i32,
+        ) -> isize;
+    }
+
+    let wide = |s: &str| s.encode_utf16().chain(std::iter::once(0)).collect::<Vec<u16>>();
+    let (verb, program) = (wide("open"), wide(app));
+    let parameters = wide(&format!("\"{}\"", file.display()));
+    let result = unsafe {
+        ShellExecuteW(
+            std::ptr::null_mut(),
+            verb.as_ptr(),
+            program.as_ptr(),
+            parameters.as_ptr(),
+            std::ptr::null(),
+            SW_SHOWNORMAL,
+        )
+    };
+    // Values up to 32 are errors, the low ones Win32 codes (2 = file not found)
+    if result <= 32 {
+        return Err(std::io::Error::from_raw_os_error(result as i32));
+    }
+    Ok(())
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+fn launch_with(app: &str, file: &Path) -> std::io::Result<()> {
+    std::process::Command::new(app).arg(file).spawn().map(|_| ())
+}
+
 /// Image extensions for sibling gathering.
 const IMAGE_EXTENSIONS: &[&str] = &[
     "jpg", "jpeg", "png", "gif", "bmp", "webp", "svg", "ico", "tiff", "tif",
@@ -125,8 +186,8 @@ fn try_spawn_terminal(term: &str, dir: &std::path::Path) -> bool {
         .unwrap_or(term);
 
     let result = match bin {
-        // ghostty and gnome-terminal use --working-directory=PATH (= syntax)
-        "ghostty" | "gnome-terminal" => std::process::Command::new(term)
+        // ghostty, gnome-terminal and xfce4-terminal use --working-directory=PATH (= syntax)
+        "ghostty" | "gnome-terminal" | "xfce4-terminal" => std::process::Command::new(term)
             .arg(format!("--working-directory={}", dir.display()))
             .spawn(),
         // kitty uses --directory
@@ -151,76 +212,736 @@ fn try_spawn_terminal(term: &str, dir: &std::path::Path) -> bool {
     result.is_ok()
 }
 
-/// Open a terminal at a directory path.
-/// If `terminal` is non-empty, use that command; otherwise auto-detect.
+/// Open a terminal at a directory path (its parent for files).
+/// If `terminal` is non-empty, that command is tried first; otherwise, or
+/// if it fails, the system terminal is auto-detected:
+///
+/// Windows: Windows Terminal, else `cmd.exe`. macOS: Terminal.app, else
+/// iTerm2. Linux: `xdg-terminal-exec`, then common emulators in order, then
+/// `x-terminal-emulator`. Fails with `NotFound` listing the commands tried
+/// when none starts.
 #[tauri::command]
 pub fn open_in_terminal(path: String, terminal: Option<String>) -> Result<(), AppError> {
-    let dir_path = PathBuf::from(&path);
+    let dir = terminal_dir(path)?;
+    let mut tried: Vec<&str> = Vec::new();
 
-    if !dir_path.exists() {
-        return Err(AppError::NotFound(path));
+    // Try user-configured terminal first
+    if let Some(term) = terminal.as_deref().filter(|t| !t.is_empty()) {
+        tried.push(term);
+        if try_spawn_terminal(term, &dir) {
+            return Ok(());
+        }
+        // Fall through to auto-detect if configured terminal fails
     }
 
-    // Use the directory itself or its parent for files
-    let dir = if dir_path.is_dir() {
-        dir_path
-    } else {
-        dir_path
-            .parent()
-            .map(|p| p.to_path_buf())
-            .unwrap_or(dir_path)
-    };
+    #[cfg(target_os = "windows")]
+    {
+        tried.push("wt");
+        if std::process::Command::new("wt").arg("-d").arg(&dir).spawn().is_ok() {
+            return Ok(());
+        }
+        tried.push("cmd.exe");
+        if std::process::Command::new("cmd")
+            .args(["/c", "start", "cmd.exe"])
+            .current_dir(&dir)
+            .spawn()
+            .is_ok()
+        {
+            return Ok(());
+        }
+    }
 
-    // Try user-configured terminal first
-    if let Some(ref term) = terminal {
-        if !term.is_empty() {
-            if try_spawn_terminal(term, &dir) {
+    #[cfg(target_os = "macos")]
+    {
+        for app in ["Terminal", "iTerm"] {
+            tried.push(app);
+            let opened = std::process::Command::new("open")
+                .args(["-a", app])
+                .arg(&dir)
+                .status()
+                .is_ok_and(|s| s.success());
+            if opened {
                 return Ok(());
             }
-            // Fall through to auto-detect if configured terminal fails
         }
     }
 
-    #[cfg(target_os = "linux")]
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
     {
         // Try common Linux terminal emulators with their correct arguments
         let terminals = [
+            "xdg-terminal-exec",
             "ghostty",
             "kitty",
             "alacritty",
             "gnome-terminal",
+            "xfce4-terminal",
             "konsole",
             "xterm",
+            "x-terminal-emulator",
         ];
-        for term in &terminals {
+        for term in terminals {
+            tried.push(term);
             if try_spawn_terminal(term, &dir) {
                 return Ok(());
             }
         }
-        // Fallback: use x-terminal-emulator
-        std::process::Command::new("x-terminal-emulator")
-            .current_dir(&dir)
-            .spawn()
-            .map_err(|e| AppError::Io(e))?;
     }
 
-    #[cfg(target_os = "macos")]
-    {
-        std::process::Command::new("open")
-            .args(["-a", "Terminal"])
-            .arg(&dir)
-            .spawn()
-            .map_err(|e| AppError::Io(e))?;
+    Err(AppError::NotFound(format!("No terminal found (tried {})", tried.join(", "))))
+}
+
+/// The directory a terminal should start in: `path` itself, or its parent
+/// for files.
+fn terminal_dir(path: String) -> Result<PathBuf, AppError> {
+    let dir_path = PathBuf::from(&path);
+
+    if !dir_path.exists() {
+        return Err(AppError::NotFound(path));
     }
 
-    #[cfg(target_os = "windows")]
-    {
-        std::process::Command::new("cmd")
-            .args(["/c", "start", "cmd.exe"])
-            .current_dir(&dir)
-            .spawn()
-            .map_err(|e| AppError::Io(e))?;
+    if dir_path.is_dir() {
+        Ok(dir_path)
+    } else {
+        Ok(dir_path
+            .parent()
+            .map(|p| p.to_path_buf())
+            .unwrap_or(dir_path))
     }
+}
 
-    Ok(())
+// ─── Open with a chosen application ─────────────────────────────────────────
+
+/// An application that can open a given file.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AppInfo {
+    pub name: String,
+    /// Program to pass to `open_file_with`: a binary on Linux/Windows, an `.app` bundle on macOS.
+    #[serde(rename = "executablePath")]
+    pub executable_path: String,
+    #[serde(rename = "iconPath")]
+    pub icon_path: Option<String>,
+}
+
+/// List applications registered to open the file at `path`, the system
+/// default first.
+#[tauri::command]
+pub async fn get_applications_for_file(path: String) -> Result<Vec<AppInfo>, AppError> {
+    let file = PathBuf::from(&path);
+    if !file.exists() {
+        return Err(AppError::NotFound(path));
+    }
+    tokio::task::spawn_blocking(move || applications_for_file(&file))
+        .await
+        .map_err(|e| AppError::Other(format!("Task join error: {}", e)))?
+}
+
+/// The application that opens a file by default, as returned by
+/// `get_file_association`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppAssociation {
+    pub app_name: String,
+    /// What to pass to `open_file_with`; empty when nothing opens the file.
+    pub app_executable: String,
+    pub app_icon_path: Option<String>,
+}
+
+impl From<AppInfo> for AppAssociation {
+    fn from(app: AppInfo) -> Self {
+        AppAssociation {
+            app_name: app.name,
+            app_executable: app.executable_path,
+            app_icon_path: app.icon_path,
+        }
+    }
+}
+
+/// The system default application for the file at `path`, for showing
+/// "Opens with …". When nothing is associated this returns an entry with
+/// an empty `app_executable` rather than an error.
+#[tauri::command]
+pub async fn get_file_association(path: String) -> Result<AppAssociation, AppError> {
+    let file = PathBuf::from(&path);
+    if !file.exists() {
+        return Err(AppError::NotFound(path));
+    }
+    let app = tokio::task::spawn_blocking(move || default_application(&file))
+        .await
+        .map_err(|e| AppError::Other(format!("Task join error: {}", e)))?;
+    Ok(app?.map(AppAssociation::from).unwrap_or_default())
+}
+
+/// Linux: the file's MIME type from `xdg-mime`, matched against the
+/// `MimeType=` lists of installed desktop entries.
+#[cfg(target_os = "linux")]
+fn applications_for_file(path: &Path) -> Result<Vec<AppInfo>, AppError> {
+    let mime = xdg_mime_type(path)?;
+    if mime.is_empty() {
+        return Ok(Vec::new());
+    }
+    let default_id = xdg_default_app(&mime);
+
+    // User entries shadow system ones with the same desktop id
+    let mut seen = HashSet::new();
+    let mut apps = Vec::new();
+    for dir in application_dirs() {
+        let Ok(entries) = fs::read_dir(&dir) else { continue };
+        let mut files: Vec<PathBuf> = entries
+            .filter_map(|e| e.ok())
+            .map(|e| e.path())
+            .filter(|p| p.extension().is_some_and(|e| e == "desktop"))
+            .collect();
+        files.sort();
+
+        for file in files {
+            let id = file.file_name().unwrap_or_default().to_string_lossy().to_string();
+            if !seen.insert(id.clone()) {
+                continue;
+            }
+            let Ok(content) = fs::read_to_string(&file) else { continue };
+            let Some(entry) = parse_desktop_entry(&content) else { continue };
+            if !entry.mime_types.iter().any(|m| m == &mime) {
+                continue;
+            }
+            let app = AppInfo {
+                name: entry.name,
+                executable_path: entry.program,
+                icon_path: entry.icon.as_deref().and_then(resolve_app_icon),
+            };
+            if id == default_id {
+                apps.insert(0, app);
+            } else {
+                apps.push(app);
+            }
+        }
+    }
+    Ok(apps)
+}
+
+/// The file's MIME type from `xdg-mime`; empty when it has none.
+#[cfg(target_os = "linux")]
+fn xdg_mime_type(path: &Path) -> Result<String, AppError> {
+    let output = std::process::Command::new("xdg-mime")
+        .args(["query", "filetype"])
+        .arg(path)
+        .output()
+        .map_err(|e| match e.kind() {
+            std::io::ErrorKind::NotFound => AppError::Other("xdg-mime not found: install xdg-utils".into()),
+            _ => AppError::Io(e),
+        })?;
+    if !output.status.success() {
+        return Err(AppError::Other(format!("xdg-mime query filetype failed for {}", path.display())));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Desktop id (`org.gnome.eog.desktop`) of the default app for `mime`;
+/// empty when there is none.
+#[cfg(target_os = "linux")]
+fn xdg_default_app(mime: &str) -> String {
+    std::process::Command::new("xdg-mime")
+        .args(["query", "default", mime])
+        .output()
+        .ok()
+        .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string())
+        .unwrap_or_default()
+}
+
+/// Linux: `xdg-mime`'s default desktop entry for the file's MIME type.
+/// Without a working `xdg-mime` nothing is known to open the file.
+#[cfg(target_os = "linux")]
+fn default_application(path: &Path) -> Result<Option<AppInfo>, AppError> {
+    let mime = match xdg_mime_type(path) {
+        Ok(mime) => mime,
+        Err(e) => {
+            log::debug!("default_application: {}", e);
+            return Ok(None);
+        }
+    };
+    let default_id = if mime.is_empty() { String::new() } else { xdg_default_app(&mime) };
+    if default_id.is_empty() {
+        return Ok(None);
+    }
+    // The first directory holding the id wins, as in `applications_for_file`
+    let entry = application_dirs()
+        .into_iter()
+        .map(|dir| dir.join(&default_id))
+        .find(|file| file.is_file())
+        .and_then(|file| parse_desktop_entry(&fs::read_to_string(file).ok()?));
+    Ok(entry.map(|entry| AppInfo {
+        name: entry.name,
+        executable_path: entry.program,
+        icon_path: entry.icon.as_deref().and_then(resolve_app_icon),
+    }))
+}
+
+/// `applications` directories in XDG precedence order (user first).
+#[cfg(target_os = "linux")]
+fn application_dirs() -> Vec<PathBuf> {
+    let mut dirs: Vec<PathBuf> = dirs::data_dir().map(|d| d.join("applications")).into_iter().collect();
+    let data_dirs = std::env::var("XDG_DATA_DIRS").unwrap_or_else(|_| "/usr/local/share:/usr/share".into());
+    dirs.extend(
+        data_dirs
+            .split(':')
+            .filter(|d| !d.is_empty())
+            .map(|d| Path::new(d).join("applications")),
+    );
+    dirs
+}
+
+/// Resolve a desktop entry `Icon=` value to a file: absolute paths as-is,
+/// theme names via hicolor and pixmaps.
+#[cfg(target_os = "linux")]
+fn resolve_app_icon(icon: &str) -> Option<String> {
+    let direct = Path::new(icon);
+    if direct.is_absolute() {
+        return direct.is_file().then(|| icon.to_string());
+    }
+
+    let mut candidates = Vec::new();
+    for dir in application_dirs() {
+        let Some(share) = dir.parent() else { continue };
+        for sub in ["48x48", "64x64", "128x128", "256x256", "scalable"] {
+            for ext in ["png", "svg"] {
+                candidates.push(share.join("icons/hicolor").join(sub).join("apps").join(format!("{}.{}", icon, ext)));
+            }
+        }
+    }
+    for ext in ["png", "svg", "xpm"] {
+        candidates.push(Path::new("/usr/share/pixmaps").join(format!("{}.{}", icon, ext)));
+    }
+    candidates
+        .into_iter()
+        .find(|p| p.is_file())
+        .map(|p| p.to_string_lossy().to_string())
+}
+
+/// The fields of a `.desktop` file needed to offer it as an "Open with" target.
+#[cfg(any(target_os = "linux", test))]
+#[derive(Debug, PartialEq)]
+struct DesktopEntry {
+    name: String,
+    program: String,
+    icon: Option<String>,
+    mime_types: Vec<String>,
+}
+
+/// Parse the `[Desktop Entry]` group. Hidden, `NoDisplay` and non-application
+/// entries are skipped.
+#[cfg(any(target_os = "linux", test))]
+fn parse_desktop_entry(content: &str) -> Option<DesktopEntry> {
+    let mut in_main = false;
+    let (mut name, mut exec, mut icon, mut mime_types) = (None, None, None, Vec::new());
+
+    for line in content.lines().map(str::trim) {
+        if line.starts_with('[') {
+            in_main = line == "[Desktop Entry]";
+            continue;
+        }
+        if !in_main || line.starts_with('#') {
+            continue;
+        }
+        let Some((key, value)) = line.split_once('=') else { continue };
+        match (key.trim(), value.trim()) {
+            ("Type", v) if v != "Application" => return None,
+            ("NoDisplay" | "Hidden", "true") => return None,
+            ("Name", v) => name = Some(v.to_string()),
+            ("Exec", v) => exec = Some(v.to_string()),
+            ("Icon", v) if !v.is_empty() => icon = Some(v.to_string()),
+            ("MimeType", v) => {
+                mime_types = v.split(';').filter(|m| !m.is_empty()).map(String::from).collect();
+            }
+            _ => {}
+        }
+    }
+
+    Some(DesktopEntry {
+        name: name?,
+        program: exec_program(&exec?)?,
+        icon,
+        mime_types,
+    })
+}
+
+/// The program from an `Exec=` line: first token, quotes removed. Field
+/// codes like `%U` only ever follow it.
+#[cfg(any(target_os = "linux", test))]
+fn exec_program(exec: &str) -> Option<String> {
+    let exec = exec.trim();
+    let program = if let Some(rest) = exec.strip_prefix('"') {
+        rest.split('"').next()?
+    } else {
+        exec.split_whitespace().next()?
+    };
+    (!program.is_empty() && !program.starts_with('%')).then(|| program.to_string())
+}
+
+/// macOS: `NSWorkspace.URLsForApplicationsToOpenURL` through JavaScript for
+/// Automation, so no Objective-C bindings are needed. Requires macOS 12+.
+#[cfg(target_os = "macos")]
+fn applications_for_file(path: &Path) -> Result<Vec<AppInfo>, AppError> {
+    const SCRIPT: &str = "function run(argv) {
+        ObjC.import('AppKit');
+        const ws = $.NSWorkspace.sharedWorkspace;
+        const url = $.NSURL.fileURLWithPath(argv[0]);
+        const def = ws.URLForApplicationToOpenURL(url);
+        const out = def.isNil() ? [] : [def.path.js];
+        const all = ws.URLsForApplicationsToOpenURL(url);
+        for (let i = 0; i < all.count; i++) out.push(all.objectAtIndex(i).path.js);
+        return out.join('\\n');
+    }";
+    let output = std::process::Command::new("osascript")
+        .args(["-l", "JavaScript", "-e", SCRIPT])
+        .arg(path)
+        .output()?;
+    if !output.status.success() {
+        return Err(AppError::Other(format!(
+            "Application lookup failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+
+    let mut seen = HashSet::new();
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty() && seen.insert(l.to_string()))
+        .map(bundle_app_info)
+        .collect())
+}
+
+/// macOS: `NSWorkspace.URLForApplicationToOpenURL` through JavaScript for
+/// Automation, like `applications_for_file`.
+#[cfg(target_os = "macos")]
+fn default_application(path: &Path) -> Result<Option<AppInfo>, AppError> {
+    const SCRIPT: &str = "function run(argv) {
+        ObjC.import('AppKit');
+        const app = $.NSWorkspace.sharedWorkspace.URLForApplicationToOpenURL($.NSURL.fileURLWithPath(argv[0]));
+        return app.isNil() ? '' : app.path.js;
+    }";
+    let output = std::process::Command::new("osascript")
+        .args(["-l", "JavaScript", "-e", SCRIPT])
+        .arg(path)
+        .output()?;
+    if !output.status.success() {
+        return Err(AppError::Other(format!(
+            "Application lookup failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    let bundle = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    Ok((!bundle.is_empty()).then(|| bundle_app_info(&bundle)))
+}
+
+/// Name (bundle name without `.app`) and `.icns` icon of an app bundle.
+#[cfg(target_os = "macos")]
+fn bundle_app_info(bundle: &str) -> AppInfo {
+    let bundle_path = Path::new(bundle);
+    let name = bundle_path
+        .file_stem()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| bundle.to_string());
+    let icon = fs::read_dir(bundle_path.join("Contents/Resources"))
+        .ok()
+        .and_then(|entries| {
+            entries
+                .filter_map(|e| e.ok())
+                .map(|e| e.path())
+                .find(|p| p.extension().is_some_and(|e| e == "icns"))
+        })
+        .map(|p| p.to_string_lossy().to_string());
+    AppInfo {
+        name,
+        executable_path: bundle.to_string(),
+        icon_path: icon,
+    }
+}
+
+/// Windows: the extension's default app plus Explorer's `OpenWithProgids`
+/// and per-extension `OpenWithList` entries, each resolved to its program
+/// with `AssocQueryStringW`.
+#[cfg(target_os = "windows")]
+fn applications_for_file(path: &Path) -> Result<Vec<AppInfo>, AppError> {
+    const ASSOCF_OPEN_BYEXENAME: u32 = 0x2;
+
+    let ext = path
+        .extension()
+        .map(|e| format!(".{}", e.to_string_lossy().to_lowercase()))
+        .ok_or_else(|| AppError::InvalidPath("File has no extension".into()))?;
+
+    // (AssocQueryStringW flags, extension / ProgID / exe name)
+    let mut candidates = vec![(0, ext.clone())];
+    let progids = reg_values(HKEY_CLASSES_ROOT, &format!(r"{}\OpenWithProgids", ext));
+    candidates.extend(progids.into_iter().filter(|(name, _)| !name.is_empty()).map(|(name, _)| (0, name)));
+    let list_key = format!(
+        r"Software\Microsoft\Windows\CurrentVersion\Explorer\FileExts\{}\OpenWithList",
+        ext
+    );
+    // Entries are named a, b, c...; MRUList only records their order
+    let recent = reg_values(HKEY_CURRENT_USER, &list_key);
+    candidates.extend(
+        recent
+            .into_iter()
+            .filter(|(name, exe)| name.len() == 1 && !exe.is_empty())
+            .map(|(_, exe)| (ASSOCF_OPEN_BYEXENAME, exe)),
+    );
+
+    let mut seen = HashSet::new();
+    Ok(candidates
+        .into_iter()
+        .filter_map(|(flags, assoc)| assoc_app(flags, &assoc))
+        .filter(|app| seen.insert(app.executable_path.to_lowercase()))
+        .collect())
+}
+
+#[cfg(target_os = "windows")]
+const HKEY_CLASSES_ROOT: isize = 0x8000_0000_u32 as i32 as isize;
+#[cfg(target_os = "windows")]
+const HKEY_CURRENT_USER: isize = 0x8000_0001_u32 as i32 as isize;
+
+/// Values under `root\key` as `(name, data)`, `data` empty for non-string
+/// values; empty when the key doesn't exist.
+#[cfg(target_os = "windows")]
+fn reg_values(root: isize, key: &str) -> Vec<(String, String)> {
+    const KEY_READ: u32 = 0x20019;
+    const REG_SZ: u32 = 1;
+    const REG_EXPAND_SZ: u32 = 2;
+    const ERROR_MORE_DATA: i32 = 234;
+
+    #[link(name = "advapi32")]
+    extern "system" {
+        fn RegOpenKeyExW(key: isize, sub_key: *const u16, options: u32, desired: u32, result: *mut isize) -> i32;
+        fn RegEnumValueW(
+            key: isize,
+            index: u32,
+            name: *mut u16,
+            name_len: *mut u32,
+            reserved: *mut u32,
+            kind: *mut u32,
+            data: *mut u8,
+            data_len: *mut u32,
+        ) -> i32;
+        fn RegCloseKey(key: isize) -> i32;
+    }
+
+    let sub_key: Vec<u16> = key.encode_utf16().chain(std::iter::once(0)).collect();
+    let mut hkey = 0isize;
+    if unsafe { RegOpenKeyExW(root, sub_key.as_ptr(), 0, KEY_READ, &mut hkey) } != 0 {
+        return Vec::new();
+    }
+
+    let mut values = Vec::new();
+    for index in 0u32.. {
+        let mut name = [0u16; 256];
+        let mut name_len = name.len() as u32;
+        let mut data = [0u16; 1024];
+        let mut data_len = (data.len() * 2) as u32;
+        let mut kind = 0u32;
+        let status = unsafe {
+            RegEnumValueW(
+                hkey,
+                index,
+                name.as_mut_ptr(),
+                &mut name_len,
+                std::ptr::null_mut(),
+                &mut kind,
+                data.as_mut_ptr().cast(),
+                &mut data_len,
+            )
+        };
+        // ERROR_NO_MORE_ITEMS (or any other failure) ends the list; an
+        // oversized value is only skipped
+        match status {
+            0 => {}
+            ERROR_MORE_DATA => continue,
+            _ => break,
+        }
+        let name = String::from_utf16_lossy(&name[..name_len as usize]);
+        let data = match kind {
+            REG_SZ | REG_EXPAND_SZ => String::from_utf16_lossy(&data[..data_len as usize / 2])
+                .trim_end_matches('\0')
+                .to_string(),
+            _ => String::new(),
+        };
+        values.push((name, data));
+    }
+    unsafe { RegCloseKey(hkey) };
+    values
+}
+
+/// The program `AssocQueryStringW` finds for the `open` verb of `assoc` (an
+/// extension, a ProgID, or an exe name with `ASSOCF_OPEN_BYEXENAME`).
+#[cfg(target_os = "windows")]
+fn assoc_app(flags: u32, assoc: &str) -> Option<AppInfo> {
+    const ASSOCF_INIT_IGNOREUNKNOWN: u32 = 0x400;
+    const ASSOCSTR_EXECUTABLE: u32 = 2;
+    const ASSOCSTR_FRIENDLYAPPNAME: u32 = 4;
+
+    #[link(name = "shlwapi")]
+    extern "system" {
+        fn AssocQueryStringW(
+            flags: u32,
+            str: u32,
+            assoc: *const u16,
+            extra: *const u16,
+            out: *mut u16,
+            out_len: *mut u32,
+        ) -> i32;
+    }
+
+    let assoc: Vec<u16> = assoc.encode_utf16().chain(std::iter::once(0)).collect();
+    let verb: Vec<u16> = "open".encode_utf16().chain(std::iter::once(0)).collect();
+    let query = |what: u32| {
+        let mut buf = [0u16; 1024];
+        let mut len = buf.len() as u32;
+        let hr = unsafe {
+            AssocQueryStringW(
+                ASSOCF_INIT_IGNOREUNKNOWN | flags,
+                what,
+                assoc.as_ptr(),
+                verb.as_ptr(),
+                buf.as_mut_ptr(),
+                &mut len,
+            )
+        };
+        // `len` counts the terminating NUL
+        (hr == 0 && len > 1).then(|| String::from_utf16_lossy(&buf[..len as usize - 1]))
+    };
+
+    let exe = query(ASSOCSTR_EXECUTABLE)?;
+    let name = query(ASSOCSTR_FRIENDLYAPPNAME).unwrap_or_else(|| {
+        Path::new(&exe)
+            .file_stem()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| exe.clone())
+    });
+    Some(AppInfo { name, icon_path: Some(exe.clone()), executable_path: exe })
+}
+
+/// Windows: the shell's association for the file's extension, from
+/// `AssocQueryStringW`.
+#[cfg(target_os = "windows")]
+fn default_application(path: &Path) -> Result<Option<AppInfo>, AppError> {
+    Ok(path
+        .extension()
+        .and_then(|ext| assoc_app(0, &format!(".{}", ext.to_string_lossy()))))
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+fn applications_for_file(_path: &Path) -> Result<Vec<AppInfo>, AppError> {
+    Err(AppError::Other("Application lookup is not supported on this platform".into()))
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+fn default_application(_path: &Path) -> Result<Option<AppInfo>, AppError> {
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    // ── Terminals ───────────────────────────────────────────────────────────
+
+    #[test]
+    fn test_terminal_dir() {
+        let dir = tempdir().unwrap();
+        let file = dir.path().join("notes.txt");
+        fs::write(&file, "").unwrap();
+
+        assert_eq!(terminal_dir(dir.path().to_string_lossy().to_string()).unwrap(), dir.path());
+        assert_eq!(terminal_dir(file.to_string_lossy().to_string()).unwrap(), dir.path());
+        let missing = dir.path().join("missing").to_string_lossy().to_string();
+        assert!(matches!(terminal_dir(missing), Err(AppError::NotFound(_))));
+    }
+
+    // ── Desktop entries ─────────────────────────────────────────────────────
+
+    #[test]
+    fn test_parse_desktop_entry() {
+        let content = "\
+[Desktop Entry]
+Type=Application
+Name=Text Editor
+Name[de]=Texteditor
+Exec=gnome-text-editor %U
+Icon=org.gnome.TextEditor
+MimeType=text/plain;text/x-csrc;
+
+[Desktop Action new-window]
+Name=New Window
+Exec=gnome-text-editor --new-window
+";
+        let entry = parse_desktop_entry(content).unwrap();
+        assert_eq!(entry.name, "Text Editor");
+        assert_eq!(entry.program, "gnome-text-editor");
+        assert_eq!(entry.icon.as_deref(), Some("org.gnome.TextEditor"));
+        assert_eq!(entry.mime_types, vec!["text/plain", "text/x-csrc"]);
+    }
+
+    #[test]
+    fn test_parse_desktop_entry_skips_hidden_and_links() {
+        assert!(parse_desktop_entry("[Desktop Entry]\nName=X\nExec=x\nNoDisplay=true\n").is_none());
+        assert!(parse_desktop_entry("[Desktop Entry]\nType=Link\nName=X\nURL=https://x\n").is_none());
+        assert!(parse_desktop_entry("[Desktop Entry]\nName=No exec\n").is_none());
+    }
+
+    #[test]
+    fn test_exec_program() {
+        assert_eq!(exec_program("code --new-window %F").as_deref(), Some("code"));
+        assert_eq!(
+            exec_program("\"/opt/My App/app\" %u").as_deref(),
+            Some("/opt/My App/app")
+        );
+        assert_eq!(exec_program("%U"), None);
+        assert_eq!(exec_program(""), None);
+    }
+
+    // ── get_file_association ────────────────────────────────────────────────
+
+    #[test]
+    fn test_file_association() {
+        let dir = tempdir().unwrap();
+        let missing = dir.path().join("missing.txt").to_string_lossy().to_string();
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        assert!(matches!(rt.block_on(get_file_association(missing)), Err(AppError::NotFound(_))));
+        // Succeeds whatever is installed, e.g. without xdg-mime
+        let file = dir.path().join("notes.zzz-unknown");
+        fs::write(&file, "hi").unwrap();
+        assert!(rt.block_on(get_file_association(file.to_string_lossy().to_string())).is_ok());
+
+        let app = AppInfo { name: "Viewer".into(), executable_path: "/usr/bin/viewer".into(), icon_path: None };
+        let association = AppAssociation::from(app);
+        assert_eq!(association.app_name, "Viewer");
+        assert_eq!(association.app_executable, "/usr/bin/viewer");
+        let json = serde_json::to_value(AppAssociation::default()).unwrap();
+        assert_eq!(json, serde_json::json!({ "appName": "", "appExecutable": "", "appIconPath": null }));
+    }
+
+    // ── open_file_with ──────────────────────────────────────────────────────
+
+    #[test]
+    fn test_open_file_with_missing_file() {
+        let dir = tempdir().unwrap();
+        let missing = dir.path().join("nope.txt").to_string_lossy().to_string();
+        assert!(matches!(open_file_with(missing, "true".into()), Err(AppError::NotFound(_))));
+    }
+
+    #[test]
+    fn test_open_file_with_missing_app() {
+        let dir = tempdir().unwrap();
+        let file = dir.path().join("a.txt");
+        fs::write(&file, "hi").unwrap();
+        let result = open_file_with(
+            file.to_string_lossy().to_string(),
+            dir.path().join("no-such-app").to_string_lossy().to_string(),
+        );
+        assert!(matches!(result, Err(AppError::NotFound(_))));
+        assert!(matches!(
+            open_file_with(file.to_string_lossy().to_string(), "  ".into()),
+            Err(AppError::InvalidPath(_))
+        ));
+    }
 }
@@ -4,8 +4,12 @@ use std::fs;
 use std::path::{Path, PathBuf};
 
 use crate::error::AppError;
+use crate::undo::{self, Operation, OperationKind};
 use log;
-use super::{metadata_to_entry, FileEntry, SizeEstimate};
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+use super::{emit_operation_event, metadata_to_entry, FileEntry, SizeEstimate};
 
 /// Get the user's home directory.
 #[tauri::command]
@@ -15,9 +19,63 @@ pub fn get_home_directory() -> Result<String, AppError> {
         .ok_or_else(|| AppError::NotFound("Home directory not found".to_string()))
 }
 
+/// Well-known user directories. A field is `None` when the platform has no
+/// such directory or it doesn't exist.
+#[derive(Debug, Serialize)]
+pub struct SpecialDirectories {
+    pub home: Option<String>,
+    pub desktop: Option<String>,
+    pub downloads: Option<String>,
+    pub documents: Option<String>,
+    pub pictures: Option<String>,
+    pub music: Option<String>,
+    pub videos: Option<String>,
+    pub temp: Option<String>,
+}
+
+/// Resolve a well-known directory. On Linux an `XDG_*_DIR` environment
+/// override wins over `user-dirs.dirs` (which `dirs` reads).
+fn special_dir(_xdg_var: &str, fallback: Option<PathBuf>) -> Option<String> {
+    #[cfg(target_os = "linux")]
+    let fallback = std::env::var_os(_xdg_var)
+        .map(PathBuf::from)
+        .filter(|p| p.is_absolute())
+        .or(fallback);
+    fallback
+        .filter(|p| p.is_dir())
+        .map(|p| p.to_string_lossy().to_string())
+}
+
+/// Get Desktop, Downloads, Documents, Pictures, Music, Videos and Temp
+/// paths for the current platform.
+#[tauri::command]
+pub fn get_special_directories() -> Result<SpecialDirectories, AppError> {
+    Ok(SpecialDirectories {
+        home: special_dir("HOME", dirs::home_dir()),
+        desktop: special_dir("XDG_DESKTOP_DIR", dirs::desktop_dir()),
+        downloads: special_dir("XDG_DOWNLOAD_DIR", dirs::download_dir()),
+        documents: special_dir("XDG_DOCUMENTS_DIR", dirs::document_dir()),
+        pictures: special_dir("XDG_PICTURES_DIR", dirs::picture_dir()),
+        music: special_dir("XDG_MUSIC_DIR", dirs::audio_dir()),
+        videos: special_dir("XDG_VIDEOS_DIR", dirs::video_dir()),
+        temp: special_dir("TMPDIR", Some(std::env::temp_dir())),
+    })
+}
+
 /// Create a new directory.
 #[tauri::command]
-pub fn create_directory(parent_path: String, name: String) -> Result<FileEntry, AppError> {
+pub fn create_directory(
+    app: AppHandle,
+    parent_path: String,
+    name: String,
+) -> Result<FileEntry, AppError> {
+    let entry = create_directory_unlogged(parent_path, name)?;
+    undo::record(&app, Operation::Create { paths: vec![PathBuf::from(&entry.path)] });
+    emit_operation_event(&app, OperationKind::Create, &entry.path, None, Some(&entry));
+    Ok(entry)
+}
+
+fn create_directory_unlogged(parent_path: String, name: String) -> Result<FileEntry, AppError> {
     if name.is_empty() {
         return Err(AppError::InvalidPath(
             "Directory name cannot be empty".to_string(),
@@ -46,9 +104,67 @@ pub fn create_directory(parent_path: String, name: String) -> Result<FileEntry,
     Ok(metadata_to_entry(&new_path, &metadata))
 }
 
+/// Create `path` and any missing parent directories, like `mkdir -p`.
+/// Returns entries for the directories that were created, outermost first;
+/// empty if `path` already was a directory.
+#[tauri::command]
+pub fn create_directory_recursive(
+    app: AppHandle,
+    path: String,
+) -> Result<Vec<FileEntry>, AppError> {
+    let created = create_directory_recursive_unlogged(path)?;
+    if !created.is_empty() {
+        let paths = created.iter().map(|entry| PathBuf::from(&entry.path)).collect();
+        undo::record(&app, Operation::Create { paths });
+        emit_operation_event(&app, OperationKind::Create, &created[0].path, None, created.last());
+    }
+    Ok(created)
+}
+
+fn create_directory_recursive_unlogged(path: String) -> Result<Vec<FileEntry>, AppError> {
+    if path.trim().is_empty() {
+        return Err(AppError::InvalidPath("Directory path cannot be empty".to_string()));
+    }
+    let target = PathBuf::from(&path);
+
+    // Components that don't exist yet, innermost first, up to the first one that does
+    let mut missing = Vec::new();
+    for ancestor in target.ancestors().filter(|a| !a.as_os_str().is_empty()) {
+        match fs::metadata(ancestor) {
+            Ok(metadata) if metadata.is_dir() => break,
+            Ok(_) => {
+                return Err(AppError::AlreadyExists(format!(
+                    "{} exists and is not a directory",
+                    ancestor.to_string_lossy()
+                )))
+            }
+            Err(_) => missing.push(ancestor.to_path_buf()),
+        }
+    }
+
+    fs::create_dir_all(&target)?;
+    log::info!("Created {} directories for {:?}", missing.len(), target);
+
+    missing
+        .iter()
+        .rev()
+        .map(|dir| {
+            let metadata = fs::metadata(dir)?;
+            Ok(metadata_to_entry(dir, &metadata))
+        })
+        .collect()
+}
+
 /// Rename a file or directory.
 #[tauri::command]
-pub fn rename_entry(path: String, new_name: String) -> Result<FileEntry, AppError> {
+pub fn rename_entry(app: AppHandle, path: String, new_name: String) -> Result<FileEntry, AppError> {
+    let entry = rename_entry_unlogged(path.clone(), new_name)?;
+    emit_operation_event(&app, OperationKind::Rename, &path, Some(&entry.path), Some(&entry));
+    undo::record(&app, Operation::Rename { source: path.into(), target: PathBuf::from(&entry.path) });
+    Ok(entry)
+}
+
+fn rename_entry_unlogged(path: String, new_name: String) -> Result<FileEntry, AppError> {
     if new_name.is_empty() {
         return Err(AppError::InvalidPath(
             "New name cannot be empty".to_string(),
@@ -77,8 +193,150 @@ pub fn rename_entry(path: String, new_name: String) -> Result<FileEntry, AppErro
     Ok(metadata_to_entry(&target, &metadata))
 }
 
+/// Rename `source` to the full path `target`, failing with `AlreadyExists`
+/// if `target` exists. Unlike `rename_entry`'s check-then-rename, the
+/// existence check and the rename are one OS call, so a file created at
+/// `target` concurrently is never replaced.
+#[tauri::command]
+pub fn atomic_rename(
+    app: AppHandle,
+    source: String,
+    target: String,
+) -> Result<FileEntry, AppError> {
+    let entry = atomic_rename_unlogged(source.clone(), target.clone())?;
+    emit_operation_event(&app, OperationKind::Rename, &source, Some(&target), Some(&entry));
+    undo::record(&app, Operation::Rename { source: source.into(), target: target.into() });
+    Ok(entry)
+}
+
+fn atomic_rename_unlogged(source: String, target: String) -> Result<FileEntry, AppError> {
+    let source_path = PathBuf::from(&source);
+    let target_path = PathBuf::from(&target);
+    if fs::symlink_metadata(&source_path).is_err() {
+        return Err(AppError::NotFound(source));
+    }
+
+    rename_noreplace(&source_path, &target_path).map_err(|e| match e.kind() {
+        std::io::ErrorKind::AlreadyExists => AppError::AlreadyExists(target.clone()),
+        std::io::ErrorKind::NotFound => AppError::NotFound(source.clone()),
+        std::io::ErrorKind::PermissionDenied => AppError::PermissionDenied(target.clone()),
+        _ => AppError::Io(e),
+    })?;
+
+    let metadata = fs::symlink_metadata(&target_path)?;
+    Ok(metadata_to_entry(&target_path, &metadata))
+}
+
+/// `renameat2(RENAME_NOREPLACE)`. Kernels before 3.15 and filesystems
+/// without support (some network and FUSE mounts) reject the flag, in which
+/// case this falls back to check-then-rename, which is not race-free.
+#[cfg(all(target_os = "linux", target_env = "gnu"))]
+pub(crate) fn rename_noreplace(source: &Path, target: &Path) -> std::io::Result<()> {
+    let (from, to) = (c_path(source)?, c_path(target)?);
+    let rc = unsafe {
+        libc::renameat2(
+            libc::AT_FDCWD,
+            from.as_ptr(),
+            libc::AT_FDCWD,
+            to.as_ptr(),
+            libc::RENAME_NOREPLACE,
+        )
+    };
+    if rc == 0 {
+        return Ok(());
+    }
+    let err = std::io::Error::last_os_error();
+    match err.raw_os_error() {
+        Some(libc::EINVAL) | Some(libc::ENOSYS) | Some(libc::EOPNOTSUPP) => {
+            log::debug!("renameat2 unsupported ({}), falling back to check-then-rename", err);
+            rename_checked(source, target)
+        }
+        _ => Err(err),
+    }
+}
+
+/// `renameatx_np(RENAME_EXCL)`; falls back to check-then-rename on
+/// filesystems that don't support it.
+#[cfg(target_os = "macos")]
+pub(crate) fn rename_noreplace(source: &Path, target: &Path) -> std::io::Result<()> {
+    let (from, to) = (c_path(source)?, c_path(target)?);
+    let rc = unsafe {
+        libc::renameatx_np(
+            libc::AT_FDCWD,
+            from.as_ptr(),
+            libc::AT_FDCWD,
+            to.as_ptr(),
+            libc::RENAME_EXCL,
+        )
+    };
+    if rc == 0 {
+        return Ok(());
+    }
+    let err = std::io::Error::last_os_error();
+    match err.raw_os_error() {
+        Some(libc::ENOTSUP) => rename_checked(source, target),
+        _ => Err(err),
+    }
+}
+
+/// `MoveFileExW` without `MOVEFILE_REPLACE_EXISTING` refuses to replace an
+/// existing target.
+#[cfg(windows)]
+pub(crate) fn rename_noreplace(source: &Path, target: &Path) -> std::io::Result<()> {
+    use std::os::windows::ffi::OsStrExt;
+
+    #[link(name = "kernel32")]
+    extern "system" {
+        fn MoveFileExW(existing: *const u16, new: *const u16, flags: u32) -> i32;
+    }
+
+    let wide = |p: &Path| -> Vec<u16> {
+        p.as_os_str().encode_wide().chain(std::iter::once(0)).collect()
+    };
+    let (from, to) = (wide(source), wide(target));
+    if unsafe { MoveFileExW(from.as_ptr(), to.as_ptr(), 0) } != 0 {
+        Ok(())
+    } else {
+        Err(std::io::Error::last_os_error())
+    }
+}
+
+/// Platforms without a no-replace rename primitive get check-then-rename.
+#[cfg(not(any(all(target_os = "linux", target_env = "gnu"), target_os = "macos", windows)))]
+pub(crate) fn rename_noreplace(source: &Path, target: &Path) -> std::io::Result<()> {
+    rename_checked(source, target)
+}
+
+#[cfg(any(all(target_os = "linux", target_env = "gnu"), target_os = "macos"))]
+fn c_path(path: &Path) -> std::io::Result<std::ffi::CString> {
+    use std::os::unix::ffi::OsStrExt;
+    std::ffi::CString::new(path.as_os_str().as_bytes())
+        .map_err(|_| std::io::Error::new(std::io::ErrorKind::InvalidInput, "path contains a NUL byte"))
+}
+
+/// Check-then-rename fallback: a target created between the two steps is
+/// replaced.
+#[cfg(not(windows))]
+fn rename_checked(source: &Path, target: &Path) -> std::io::Result<()> {
+    if fs::symlink_metadata(target).is_ok() {
+        return Err(std::io::Error::from(std::io::ErrorKind::AlreadyExists));
+    }
+    fs::rename(source, target)
+}
+
 /// Generate a unique copy name like "name - Copy.ext" or "name - Copy (2).ext".
 fn generate_copy_name(dest_dir: &Path, source_name: &str, is_directory: bool) -> PathBuf {
+    generate_copy_name_with(dest_dir, source_name, is_directory, |p| p.exists())
+}
+
+/// `generate_copy_name` with a custom "is this name taken" check, for
+/// callers that reserve names before creating them.
+pub(crate) fn generate_copy_name_with(
+    dest_dir: &Path,
+    source_name: &str,
+    is_directory: bool,
+    taken: impl Fn(&Path) -> bool,
+) -> PathBuf {
     let (base_name, extension) = if is_directory {
         (source_name.to_string(), String::new())
     } else if let Some(dot_pos) = source_name.rfind('.') {
@@ -92,14 +350,14 @@ fn generate_copy_name(dest_dir: &Path, source_name: &str, is_directory: bool) ->
 
     let copy_name = format!("{} - Copy{}", base_name, extension);
     let target = dest_dir.join(&copy_name);
-    if !target.exists() {
+    if !taken(&target) {
         return target;
     }
 
     for counter in 2..=1000 {
         let copy_name = format!("{} - Copy ({}){}", base_name, counter, extension);
         let target = dest_dir.join(&copy_name);
-        if !target.exists() {
+        if !taken(&target) {
             return target;
         }
     }
@@ -114,6 +372,18 @@ fn generate_copy_name(dest_dir: &Path, source_name: &str, is_directory: bool) ->
 /// If overwrite is true and target exists, replaces the existing entry.
 #[tauri::command]
 pub fn copy_entry(
+    app: AppHandle,
+    source: String,
+    dest_dir: String,
+    overwrite: Option<bool>,
+) -> Result<FileEntry, AppError> {
+    let entry = copy_entry_unlogged(source.clone(), dest_dir, overwrite)?;
+    emit_operation_event(&app, OperationKind::Copy, &source, Some(&entry.path), Some(&entry));
+    undo::record(&app, Operation::copy(source.into(), PathBuf::from(&entry.path)));
+    Ok(entry)
+}
+
+fn copy_entry_unlogged(
     source: String,
     dest_dir: String,
     overwrite: Option<bool>,
@@ -152,26 +422,43 @@ pub fn copy_entry(
         }
     }
 
-    if source_path.is_dir() {
-        fs::create_dir_all(&target)?;
+    copy_to(&source_path, &target)?;
+
+    log::info!("Copied entry (is_dir={}) overwrite={}", source_path.is_dir(), overwrite.unwrap_or(false));
+    let metadata = fs::metadata(&target)?;
+    Ok(metadata_to_entry(&target, &metadata))
+}
+
+/// Copy `source` to the not-yet-existing path `target`.
+fn copy_to(source: &Path, target: &Path) -> Result<(), AppError> {
+    if source.is_dir() {
+        fs::create_dir_all(target)?;
         let mut options = fs_extra::dir::CopyOptions::new();
         options.content_only = true;
         options.overwrite = false;
-        fs_extra::dir::copy(&source_path, &target, &options)
-            .map_err(|e| AppError::Other(e.to_string()))?;
+        fs_extra::dir::copy(source, target, &options).map_err(|e| AppError::Other(e.to_string()))?;
     } else {
-        fs::copy(&source_path, &target)?;
+        fs::copy(source, target)?;
     }
-
-    log::info!("Copied entry (is_dir={}) overwrite={}", source_path.is_dir(), overwrite.unwrap_or(false));
-    let metadata = fs::metadata(&target)?;
-    Ok(metadata_to_entry(&target, &metadata))
+    Ok(())
 }
 
 /// Move a file or directory.
 /// If overwrite is true and target exists, replaces the existing entry.
 #[tauri::command]
 pub fn move_entry(
+    app: AppHandle,
+    source: String,
+    dest_dir: String,
+    overwrite: Option<bool>,
+) -> Result<FileEntry, AppError> {
+    let entry = move_entry_unlogged(source.clone(), dest_dir, overwrite)?;
+    emit_operation_event(&app, OperationKind::Move, &source, Some(&entry.path), Some(&entry));
+    undo::record(&app, Operation::Move { source: source.into(), target: PathBuf::from(&entry.path) });
+    Ok(entry)
+}
+
+fn move_entry_unlogged(
     source: String,
     dest_dir: String,
     overwrite: Option<bool>,
@@ -212,35 +499,634 @@ pub fn move_entry(
         }
     }
 
+    move_to(&source_path, &target)?;
+
+    let metadata = fs::metadata(&target)?;
+    Ok(metadata_to_entry(&target, &metadata))
+}
+
+/// Move `source` to the not-yet-existing path `target`.
+pub(crate) fn move_to(source: &Path, target: &Path) -> Result<(), AppError> {
     // Try a simple rename first (works if same filesystem)
-    match fs::rename(&source_path, &target) {
+    match fs::rename(source, target) {
         Ok(()) => {}
         Err(e) => {
-            // Only fall back to copy+delete for cross-filesystem moves (EXDEV).
+            // Only fall back to copy+delete for cross-filesystem moves (EXDEV,
+            // or ERROR_NOT_SAME_DEVICE on Windows).
             // Other errors (permission denied, etc.) should be returned immediately.
-            let is_cross_device = e.raw_os_error() == Some(libc::EXDEV);
+            let is_cross_device = e.kind() == std::io::ErrorKind::CrossesDevices;
             if !is_cross_device {
                 log::warn!("Move failed (not cross-device): {}", e);
                 return Err(AppError::Io(e));
             }
             log::info!("Cross-device move detected, falling back to copy+delete");
             // Fall back to copy + delete for cross-filesystem moves
-            if source_path.is_dir() {
-                fs::create_dir_all(&target)?;
+            if source.is_dir() {
+                fs::create_dir_all(target)?;
                 let mut options = fs_extra::dir::CopyOptions::new();
                 options.content_only = true;
-                fs_extra::dir::copy(&source_path, &target, &options)
+                fs_extra::dir::copy(source, target, &options)
                     .map_err(|e| AppError::Other(e.to_string()))?;
-                fs::remove_dir_all(&source_path)?;
+                fs::remove_dir_all(source)?;
             } else {
-                fs::copy(&source_path, &target)?;
-                fs::remove_file(&source_path)?;
+                fs::copy(source, target)?;
+                fs::remove_file(source)?;
             }
         }
     }
+    Ok(())
+}
 
-    let metadata = fs::metadata(&target)?;
-    Ok(metadata_to_entry(&target, &metadata))
+/// Replace the existing entry at `target` with whatever `place` puts there.
+///
+/// The existing entry is renamed to a hidden sibling first and only deleted
+/// once `place` succeeds; if it fails, anything `place` left at `target` is
+/// removed and the original is renamed back. Refuses when `source` is
+/// `target` itself or lives inside it.
+pub(crate) fn replace_existing(
+    source: &Path,
+    target: &Path,
+    place: impl FnOnce() -> Result<(), AppError>,
+) -> Result<(), AppError> {
+    if entry_path(source)?.starts_with(entry_path(target)?) {
+        return Err(AppError::InvalidPath(format!(
+            "Can't overwrite {} with itself or something inside it",
+            target.display()
+        )));
+    }
+    let parked = parked_path(target);
+    fs::rename(target, &parked)?;
+    match place() {
+        Ok(()) => {
+            if let Err(e) = remove_any(&parked) {
+                log::warn!("Failed to remove replaced entry {}: {}", parked.display(), e);
+            }
+            Ok(())
+        }
+        Err(e) => {
+            if fs::symlink_metadata(target).is_ok() {
+                let _ = remove_any(target);
+            }
+            if let Err(restore) = fs::rename(&parked, target) {
+                log::error!(
+                    "Failed to restore {} from {}: {}",
+                    target.display(),
+                    parked.display(),
+                    restore
+                );
+            }
+            Err(e)
+        }
+    }
+}
+
+/// `path` with its parent canonicalized but the last component left alone,
+/// so a symlink names the link rather than what it points to.
+fn entry_path(path: &Path) -> Result<PathBuf, AppError> {
+    let name = path
+        .file_name()
+        .ok_or_else(|| AppError::InvalidPath(path.to_string_lossy().to_string()))?;
+    let parent = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    Ok(fs::canonicalize(parent)?.join(name))
+}
+
+/// An unused hidden sibling name to park `target` under while it's replaced.
+fn parked_path(target: &Path) -> PathBuf {
+    let name = target.file_name().unwrap_or_default().to_string_lossy();
+    (0u32..)
+        .map(|n| target.with_file_name(format!(".{}.replaced-{}-{}", name, std::process::id(), n)))
+        .find(|p| fs::symlink_metadata(p).is_err())
+        .expect("unbounded range")
+}
+
+/// Delete a file, symlink or directory tree.
+fn remove_any(path: &Path) -> std::io::Result<()> {
+    if fs::symlink_metadata(path)?.is_dir() {
+        fs::remove_dir_all(path)
+    } else {
+        fs::remove_file(path)
+    }
+}
+
+/// What `copy_entry_overwrite` and `move_entry_overwrite` do when the
+/// destination already has an entry with the source's name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ConflictAction {
+    /// Delete the existing entry first.
+    Overwrite,
+    /// Leave both alone and return the existing entry.
+    Skip,
+    /// Use a "name - Copy" name instead.
+    AutoRename,
+    /// Return `AlreadyExists`.
+    Fail,
+}
+
+/// Where a copy or move should go once `on_conflict` has been applied.
+enum Destination {
+    Target(PathBuf),
+    /// `Overwrite` hit a conflict: put the entry there with `replace_existing`.
+    Replace(PathBuf),
+    /// `Skip` hit a conflict: nothing to do, report this entry.
+    Existing(PathBuf),
+}
+
+/// Validate `source` and `dest_dir` and pick the destination path.
+fn resolve_destination(
+    source_path: &Path,
+    dest_dir: &str,
+    on_conflict: ConflictAction,
+) -> Result<Destination, AppError> {
+    let dest_dir_path = PathBuf::from(dest_dir);
+    if !source_path.exists() {
+        return Err(AppError::NotFound(source_path.to_string_lossy().to_string()));
+    }
+    if !dest_dir_path.is_dir() {
+        return Err(AppError::NotFound(format!(
+            "Destination directory does not exist: {}",
+            dest_dir
+        )));
+    }
+    let source_name = source_path
+        .file_name()
+        .ok_or_else(|| AppError::InvalidPath("Invalid source path".to_string()))?
+        .to_string_lossy()
+        .to_string();
+
+    let target = dest_dir_path.join(&source_name);
+    if fs::symlink_metadata(&target).is_err() {
+        return Ok(Destination::Target(target));
+    }
+    match on_conflict {
+        ConflictAction::Overwrite => Ok(Destination::Replace(target)),
+        ConflictAction::Skip => Ok(Destination::Existing(target)),
+        ConflictAction::AutoRename => Ok(Destination::Target(generate_copy_name(
+            &dest_dir_path,
+            &source_name,
+            source_path.is_dir(),
+        ))),
+        ConflictAction::Fail => Err(AppError::AlreadyExists(target.to_string_lossy().to_string())),
+    }
+}
+
+/// Copy a file or directory into `dest_dir`, resolving a name clash with
+/// `on_conflict`.
+#[tauri::command]
+pub fn copy_entry_overwrite(
+    app: AppHandle,
+    source: String,
+    dest_dir: String,
+    on_conflict: ConflictAction,
+) -> Result<FileEntry, AppError> {
+    let skipped = is_skipped(&source, &dest_dir, on_conflict);
+    let entry = copy_entry_overwrite_unlogged(source.clone(), dest_dir, on_conflict)?;
+    if !skipped {
+        emit_operation_event(&app, OperationKind::Copy, &source, Some(&entry.path), Some(&entry));
+        undo::record(&app, Operation::copy(source.into(), PathBuf::from(&entry.path)));
+    }
+    Ok(entry)
+}
+
+fn copy_entry_overwrite_unlogged(
+    source: String,
+    dest_dir: String,
+    on_conflict: ConflictAction,
+) -> Result<FileEntry, AppError> {
+    let source_path = PathBuf::from(&source);
+    let target = match resolve_destination(&source_path, &dest_dir, on_conflict)? {
+        Destination::Target(target) => {
+            copy_to(&source_path, &target)?;
+            target
+        }
+        Destination::Replace(target) => {
+            replace_existing(&source_path, &target, || copy_to(&source_path, &target))?;
+            target
+        }
+        Destination::Existing(existing) => return entry_at(&existing),
+    };
+    log::info!("Copied entry (is_dir={}) on_conflict={:?}", source_path.is_dir(), on_conflict);
+    entry_at(&target)
+}
+
+/// Move a file or directory into `dest_dir`, resolving a name clash with
+/// `on_conflict`. With `Skip` the source stays where it is.
+#[tauri::command]
+pub fn move_entry_overwrite(
+    app: AppHandle,
+    source: String,
+    dest_dir: String,
+    on_conflict: ConflictAction,
+) -> Result<FileEntry, AppError> {
+    let skipped = is_skipped(&source, &dest_dir, on_conflict);
+    let entry = move_entry_overwrite_unlogged(source.clone(), dest_dir, on_conflict)?;
+    if !skipped {
+        emit_operation_event(&app, OperationKind::Move, &source, Some(&entry.path), Some(&entry));
+        undo::record(&app, Operation::Move { source: source.into(), target: PathBuf::from(&entry.path) });
+    }
+    Ok(entry)
+}
+
+fn move_entry_overwrite_unlogged(
+    source: String,
+    dest_dir: String,
+    on_conflict: ConflictAction,
+) -> Result<FileEntry, AppError> {
+    let source_path = PathBuf::from(&source);
+    let target = match resolve_destination(&source_path, &dest_dir, on_conflict)? {
+        Destination::Target(target) => {
+            move_to(&source_path, &target)?;
+            target
+        }
+        Destination::Replace(target) => {
+            replace_existing(&source_path, &target, || move_to(&source_path, &target))?;
+            target
+        }
+        Destination::Existing(existing) => return entry_at(&existing),
+    };
+    log::info!("Moved entry (is_dir={}) on_conflict={:?}", target.is_dir(), on_conflict);
+    entry_at(&target)
+}
+
+/// Summary returned by `merge_directories`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MergeResult {
+    /// Files moved in without a clash, or under a "name - Copy" name.
+    pub files_merged: u64,
+    /// Files left in the source because the destination had one already.
+    pub files_skipped: u64,
+    /// Files that replaced an existing one.
+    pub files_overwritten: u64,
+    /// One message per entry that couldn't be moved; the rest still are.
+    pub errors: Vec<String>,
+    /// Each entry moved, as (from, to), for the undo log.
+    #[serde(skip)]
+    moved: Vec<(PathBuf, PathBuf)>,
+}
+
+/// Move the contents of `source` into `destination`, merging directories
+/// present on both sides. `on_conflict` decides file clashes (and clashes
+/// between a file and a directory); with `Fail` nothing is moved if any
+/// file clashes. Source directories are removed once emptied.
+#[tauri::command]
+pub fn merge_directories(
+    app: AppHandle,
+    source: String,
+    destination: String,
+    on_conflict: ConflictAction,
+) -> Result<MergeResult, AppError> {
+    let result = merge_directories_unlogged(source.clone(), destination.clone(), on_conflict)?;
+    record_moves(&app, &destination, &result.moved);
+    emit_operation_event(&app, OperationKind::Move, &source, Some(&destination), None);
+    Ok(result)
+}
+
+fn merge_directories_unlogged(
+    source: String,
+    destination: String,
+    on_conflict: ConflictAction,
+) -> Result<MergeResult, AppError> {
+    let source_path = PathBuf::from(&source);
+    let dest_path = PathBuf::from(&destination);
+    if !source_path.is_dir() {
+        return Err(AppError::NotFound(source));
+    }
+    if !dest_path.is_dir() {
+        return Err(AppError::NotFound(destination));
+    }
+    if fs::canonicalize(&dest_path)?.starts_with(fs::canonicalize(&source_path)?) {
+        return Err(AppError::InvalidPath(format!("Can't merge {} into itself", source)));
+    }
+    if on_conflict == ConflictAction::Fail {
+        if let Some(clash) = first_file_conflict(&source_path, &dest_path)? {
+            return Err(AppError::AlreadyExists(clash.to_string_lossy().to_string()));
+        }
+    }
+
+    let mut result = MergeResult::default();
+    merge_into(&source_path, &dest_path, on_conflict, &mut result);
+    log::info!(
+        "Merged directories: {} merged, {} skipped, {} overwritten, {} errors",
+        result.files_merged,
+        result.files_skipped,
+        result.files_overwritten,
+        result.errors.len()
+    );
+    Ok(result)
+}
+
+/// Log the moves made by a merge or flatten as one undoable batch.
+fn record_moves(app: &AppHandle, destination: &str, moved: &[(PathBuf, PathBuf)]) {
+    if moved.is_empty() {
+        return;
+    }
+    let operations = moved
+        .iter()
+        .map(|(source, target)| Operation::Move { source: source.clone(), target: target.clone() })
+        .collect();
+    undo::record(app, Operation::Batch { kind: OperationKind::Move, destination: destination.into(), operations });
+}
+
+/// The first destination path a file in `source` would clash with.
+fn first_file_conflict(source: &Path, dest: &Path) -> Result<Option<PathBuf>, AppError> {
+    for entry in fs::read_dir(source)?.flatten() {
+        let target = dest.join(entry.file_name());
+        let Ok(existing) = fs::symlink_metadata(&target) else {
+            continue;
+        };
+        if !(entry.file_type()?.is_dir() && existing.is_dir()) {
+            return Ok(Some(target));
+        }
+        if let Some(clash) = first_file_conflict(&entry.path(), &target)? {
+            return Ok(Some(clash));
+        }
+    }
+    Ok(None)
+}
+
+fn merge_into(source: &Path, dest: &Path, on_conflict: ConflictAction, result: &mut MergeResult) {
+    let entries = match fs::read_dir(source) {
+        Ok(entries) => entries,
+        Err(e) => {
+            result.errors.push(format!("{}: {}", source.display(), e));
+            return;
+        }
+    };
+    for entry in entries.flatten() {
+        let from = entry.path();
+        let target = dest.join(entry.file_name());
+        let is_dir = entry.file_type().is_ok_and(|t| t.is_dir());
+        let file_count = || {
+            let (mut files, mut bytes) = (0, 0);
+            estimate_path_size(&from, &mut files, &mut bytes);
+            if is_dir { files } else { 1 }
+        };
+        let outcome = match fs::symlink_metadata(&target) {
+            Err(_) => {
+                let count = file_count();
+                move_to(&from, &target).map(|_| {
+                    result.files_merged += count;
+                    result.moved.push((from.clone(), target.clone()));
+                })
+            }
+            Ok(existing) if is_dir && existing.is_dir() => {
+                merge_into(&from, &target, on_conflict, result);
+                continue;
+            }
+            Ok(_) => match on_conflict {
+                ConflictAction::Overwrite => {
+                    let count = file_count();
+                    replace_existing(&from, &target, || move_to(&from, &target)).map(|_| {
+                        result.files_overwritten += count;
+                        result.moved.push((from.clone(), target.clone()));
+                    })
+                }
+                ConflictAction::Skip => {
+                    result.files_skipped += file_count();
+                    Ok(())
+                }
+                ConflictAction::AutoRename => {
+                    let count = file_count();
+                    let name = entry.file_name().to_string_lossy().to_string();
+                    let renamed = generate_copy_name(dest, &name, is_dir);
+                    move_to(&from, &renamed).map(|_| {
+                        result.files_merged += count;
+                        result.moved.push((from.clone(), renamed.clone()));
+                    })
+                }
+                // Only reachable if the clash appeared after the up-front check
+                ConflictAction::Fail => Err(AppError::AlreadyExists(target.to_string_lossy().to_string())),
+            },
+        };
+        if let Err(e) = outcome {
+            result.errors.push(format!("{}: {}", from.display(), e));
+        }
+    }
+    // Fails, leaving the directory, if anything was skipped or failed in it
+    let _ = fs::remove_dir(source);
+}
+
+/// Summary returned by `flatten_directory`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FlattenResult {
+    pub files_moved: u64,
+    /// Name clashes settled by `on_conflict` (skipped files included).
+    pub conflicts_resolved: u64,
+    pub errors: Vec<String>,
+    /// Each file moved, as (from, to), for the undo log.
+    #[serde(skip)]
+    moved: Vec<(PathBuf, PathBuf)>,
+}
+
+/// Event payload for `flatten-progress`.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FlattenProgress {
+    pub path: String,
+    pub processed: u64,
+    pub total: u64,
+    pub current_file: String,
+}
+
+/// Minimum interval between `flatten-progress` events.
+const FLATTEN_PROGRESS_INTERVAL: std::time::Duration = std::time::Duration::from_millis(100);
+
+/// Move every file below `path`'s subdirectories directly into `path`.
+/// Name clashes (with files already in `path` or with each other) follow
+/// `on_conflict`; with `Fail` nothing moves if any name clashes. With
+/// `remove_empty` the emptied subdirectories are removed. Refuses trees
+/// holding a relative symlink or one that points outside `path`. Emits `flatten-progress`.
+#[tauri::command]
+pub async fn flatten_directory(
+    app: AppHandle,
+    path: String,
+    on_conflict: ConflictAction,
+    remove_empty: bool,
+) -> Result<FlattenResult, AppError> {
+    let emitter = app.clone();
+    let root = path.clone();
+    let result = tokio::task::spawn_blocking(move || {
+        flatten_directory_unlogged(&root, on_conflict, remove_empty, &mut |progress| {
+            use tauri::Emitter;
+            let _ = emitter.emit("flatten-progress", progress);
+        })
+    })
+    .await
+    .map_err(|e| AppError::Other(format!("Task join error: {}", e)))??;
+    record_moves(&app, &path, &result.moved);
+    emit_operation_event(&app, OperationKind::Move, &path, Some(&path), None);
+    Ok(result)
+}
+
+fn flatten_directory_unlogged(
+    path: &str,
+    on_conflict: ConflictAction,
+    remove_empty: bool,
+    progress: &mut dyn FnMut(FlattenProgress),
+) -> Result<FlattenResult, AppError> {
+    let root = PathBuf::from(path);
+    if !root.is_dir() {
+        return Err(AppError::NotFound(path.to_string()));
+    }
+    let canonical_root = fs::canonicalize(&root)?;
+
+    let (mut files, mut dirs) = (Vec::new(), Vec::new());
+    let mut subdirs: Vec<_> = fs::read_dir(&root)?.flatten().collect();
+    subdirs.sort_by_key(|e| e.file_name());
+    for entry in subdirs {
+        if entry.file_type().is_ok_and(|t| t.is_dir()) {
+            collect_flatten_entries(&entry.path(), &canonical_root, &mut files, &mut dirs)?;
+        }
+    }
+
+    if on_conflict == ConflictAction::Fail {
+        let mut names: std::collections::HashSet<std::ffi::OsString> =
+            fs::read_dir(&root)?.flatten().map(|e| e.file_name()).collect();
+        for file in &files {
+            if let Some(name) = file.file_name().filter(|name| !names.insert(name.to_os_string())) {
+                return Err(AppError::AlreadyExists(root.join(name).to_string_lossy().to_string()));
+            }
+        }
+    }
+
+    let mut result = FlattenResult::default();
+    let total = files.len() as u64;
+    let mut last_emit = std::time::Instant::now();
+    for (i, file) in files.iter().enumerate() {
+        let Some(name) = file.file_name() else { continue };
+        let mut target = root.join(name);
+        let outcome = match fs::symlink_metadata(&target) {
+            Err(_) => move_to(file, &target).map(|_| true),
+            Ok(existing) => {
+                result.conflicts_resolved += 1;
+                match on_conflict {
+                    ConflictAction::Overwrite if existing.is_dir() => {
+                        Err(AppError::AlreadyExists(target.to_string_lossy().to_string()))
+                    }
+                    ConflictAction::Overwrite => {
+                        replace_existing(file, &target, || move_to(file, &target)).map(|_| true)
+                    }
+                    ConflictAction::Skip => Ok(false),
+                    ConflictAction::AutoRename => {
+                        target = generate_copy_name(&root, &name.to_string_lossy(), false);
+                        move_to(file, &target).map(|_| true)
+                    }
+                    // Only reachable if the clash appeared after the up-front check
+                    ConflictAction::Fail => Err(AppError::AlreadyExists(target.to_string_lossy().to_string())),
+                }
+            }
+        };
+        match outcome {
+            Ok(true) => {
+                result.files_moved += 1;
+                result.moved.push((file.clone(), target));
+            }
+            Ok(false) => {}
+            Err(e) => result.errors.push(format!("{}: {}", file.display(), e)),
+        }
+        if last_emit.elapsed() >= FLATTEN_PROGRESS_INTERVAL || i + 1 == files.len() {
+            last_emit = std::time::Instant::now();
+            progress(FlattenProgress {
+                path: path.to_string(),
+                processed: i as u64 + 1,
+                total,
+                current_file: file.to_string_lossy().to_string(),
+            });
+        }
+    }
+
+    if remove_empty {
+        // Deepest first, so parents are empty by the time they're tried
+        for dir in dirs.iter().rev() {
+            let _ = fs::remove_dir(dir);
+        }
+    }
+    log::info!(
+        "Flattened directory: {} moved, {} conflicts, {} errors",
+        result.files_moved,
+        result.conflicts_resolved,
+        result.errors.len()
+    );
+    Ok(result)
+}
+
+/// Files (and symlinks, which aren't followed) below `dir`, plus `dir` and
+/// its subdirectories in pre-order. Errors on a relative symlink or one that
+/// leads outside `root`.
+fn collect_flatten_entries(
+    dir: &Path,
+    root: &Path,
+    files: &mut Vec<PathBuf>,
+    dirs: &mut Vec<PathBuf>,
+) -> Result<(), AppError> {
+    dirs.push(dir.to_path_buf());
+    let mut entries: Vec<_> = fs::read_dir(dir)?.flatten().collect();
+    entries.sort_by_key(|e| e.file_name());
+    for entry in entries {
+        let path = entry.path();
+        let file_type = entry.file_type()?;
+        if file_type.is_dir() {
+            collect_flatten_entries(&path, root, files, dirs)?;
+            continue;
+        }
+        if file_type.is_symlink() {
+            // A relative link would point somewhere else once moved up into `root`
+            let link = fs::read_link(&path)?;
+            if link.is_relative() {
+                return Err(AppError::InvalidPath(format!(
+                    "{} is a relative link ({})",
+                    path.display(),
+                    link.display()
+                )));
+            }
+            // Broken links are judged by where they point, resolved lexically
+            let target = fs::canonicalize(&path).unwrap_or_else(|_| normalize_lexically(&link));
+            if !target.starts_with(root) {
+                return Err(AppError::InvalidPath(format!(
+                    "{} links outside {} ({})",
+                    path.display(),
+                    root.display(),
+                    target.display()
+                )));
+            }
+        }
+        files.push(path);
+    }
+    Ok(())
+}
+
+/// `path` with `.` and `..` components folded away, without touching disk.
+fn normalize_lexically(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            std::path::Component::CurDir => {}
+            std::path::Component::ParentDir => {
+                normalized.pop();
+            }
+            other => normalized.push(other),
+        }
+    }
+    normalized
+}
+
+/// Whether `on_conflict` will leave `source` alone because `dest_dir`
+/// already has an entry with its name.
+fn is_skipped(source: &str, dest_dir: &str, on_conflict: ConflictAction) -> bool {
+    on_conflict == ConflictAction::Skip
+        && Path::new(source)
+            .file_name()
+            .is_some_and(|name| fs::symlink_metadata(Path::new(dest_dir).join(name)).is_ok())
+}
+
+fn entry_at(path: &Path) -> Result<FileEntry, AppError> {
+    let metadata = fs::metadata(path).or_else(|_| fs::symlink_metadata(path))?;
+    Ok(metadata_to_entry(path, &metadata))
 }
 
 /// Read a text file's contents with a size limit (default 1MB).
@@ -273,7 +1159,18 @@ pub fn read_text_file(path: String, max_bytes: Option<u64>) -> Result<String, Ap
 
 /// Write text content to a new file.
 #[tauri::command]
-pub fn write_text_file(path: String, content: String) -> Result<FileEntry, AppError> {
+pub fn write_text_file(
+    app: AppHandle,
+    path: String,
+    content: String,
+) -> Result<FileEntry, AppError> {
+    let entry = write_text_file_unlogged(path, content)?;
+    undo::record(&app, Operation::Create { paths: vec![PathBuf::from(&entry.path)] });
+    emit_operation_event(&app, OperationKind::Create, &entry.path, None, Some(&entry));
+    Ok(entry)
+}
+
+fn write_text_file_unlogged(path: String, content: String) -> Result<FileEntry, AppError> {
     let file_path = PathBuf::from(&path);
 
     if file_path.exists() {
@@ -287,7 +1184,14 @@ pub fn write_text_file(path: String, content: String) -> Result<FileEntry, AppEr
 
 /// Delete a file or directory permanently (not to trash).
 #[tauri::command]
-pub fn delete_entry_permanent(path: String) -> Result<(), AppError> {
+pub fn delete_entry_permanent(app: AppHandle, path: String) -> Result<(), AppError> {
+    delete_entry_permanent_unlogged(path.clone())?;
+    emit_operation_event(&app, OperationKind::Delete, &path, None, None);
+    undo::record(&app, Operation::Delete { paths: vec![path.into()] });
+    Ok(())
+}
+
+fn delete_entry_permanent_unlogged(path: String) -> Result<(), AppError> {
     let file_path = PathBuf::from(&path);
 
     if !file_path.exists() {
@@ -305,9 +1209,140 @@ pub fn delete_entry_permanent(path: String) -> Result<(), AppError> {
     Ok(())
 }
 
+/// Outcome of `force_delete_entry`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ForceDeleteResult {
+    /// The entry is gone (or will be once the last open handle closes).
+    pub deleted: bool,
+    /// The entry couldn't be deleted now and is scheduled for the next boot.
+    pub pending_reboot: bool,
+    /// Processes that were closed because they held the file open.
+    pub processes_terminated: Vec<u32>,
+}
+
+/// Delete an entry even if another process holds it open. On Windows the
+/// processes locking a file are shut down through the Restart Manager and,
+/// failing that, the delete is scheduled for the next reboot. Unix unlinks
+/// open files anyway, so there this is `delete_entry_permanent`.
+#[tauri::command]
+pub fn force_delete_entry(app: AppHandle, path: String) -> Result<ForceDeleteResult, AppError> {
+    let result = force_delete_entry_unlogged(path.clone())?;
+    if result.deleted {
+        emit_operation_event(&app, OperationKind::Delete, &path, None, None);
+        undo::record(&app, Operation::Delete { paths: vec![path.into()] });
+    }
+    Ok(result)
+}
+
+#[cfg(not(windows))]
+fn force_delete_entry_unlogged(path: String) -> Result<ForceDeleteResult, AppError> {
+    delete_entry_permanent_unlogged(path)?;
+    Ok(ForceDeleteResult { deleted: true, ..Default::default() })
+}
+
+#[cfg(windows)]
+fn force_delete_entry_unlogged(path: String) -> Result<ForceDeleteResult, AppError> {
+    use std::os::windows::ffi::OsStrExt;
+
+    use super::processes::restart_manager::Session;
+
+    #[link(name = "kernel32")]
+    extern "system" {
+        fn CreateFileW(
+            name: *const u16,
+            access: u32,
+            share_mode: u32,
+            security: *const std::ffi::c_void,
+            disposition: u32,
+            flags: u32,
+            template: *mut std::ffi::c_void,
+        ) -> *mut std::ffi::c_void;
+        fn CloseHandle(handle: *mut std::ffi::c_void) -> i32;
+        fn MoveFileExW(existing: *const u16, new: *const u16, flags: u32) -> i32;
+    }
+    const DELETE: u32 = 0x0001_0000;
+    const FILE_SHARE_ALL: u32 = 0x1 | 0x2 | 0x4;
+    const OPEN_EXISTING: u32 = 3;
+    const FILE_FLAG_DELETE_ON_CLOSE: u32 = 0x0400_0000;
+    const MOVEFILE_DELAY_UNTIL_REBOOT: u32 = 0x4;
+
+    let file_path = PathBuf::from(&path);
+    let metadata = fs::symlink_metadata(&file_path).map_err(|_| AppError::NotFound(path.clone()))?;
+    let first_attempt = if metadata.is_dir() {
+        fs::remove_dir_all(&file_path)
+    } else {
+        fs::remove_file(&file_path)
+    };
+    if first_attempt.is_ok() {
+        return Ok(ForceDeleteResult { deleted: true, ..Default::default() });
+    }
+
+    let wide: Vec<u16> = file_path.as_os_str().encode_wide().chain(std::iter::once(0)).collect();
+    // The Restart Manager only tracks files; a locked directory goes
+    // straight to the reboot fallback
+    let mut result = ForceDeleteResult::default();
+    if !metadata.is_dir() {
+        let session = Session::for_file(&path)?;
+        result.processes_terminated = session.processes()?.into_iter().map(|p| p.pid).collect();
+        if !result.processes_terminated.is_empty() {
+            if let Err(e) = session.shutdown() {
+                log::warn!("Restart Manager shutdown failed: {}", e);
+            }
+        }
+
+        if fs::remove_file(&file_path).is_ok() {
+            result.deleted = true;
+        } else {
+            // Handles opened with FILE_SHARE_DELETE don't block this; the file
+            // goes away when the last of them closes
+            let handle = unsafe {
+                CreateFileW(
+                    wide.as_ptr(),
+                    DELETE,
+                    FILE_SHARE_ALL,
+                    std::ptr::null(),
+                    OPEN_EXISTING,
+                    FILE_FLAG_DELETE_ON_CLOSE,
+                    std::ptr::null_mut(),
+                )
+            };
+            if handle as isize != -1 {
+                unsafe { CloseHandle(handle) };
+                result.deleted = true;
+            }
+        }
+        if result.deleted {
+            log::info!("Force-deleted file after closing {} process(es)", result.processes_terminated.len());
+            return Ok(result);
+        }
+    }
+
+    if unsafe { MoveFileExW(wide.as_ptr(), std::ptr::null(), MOVEFILE_DELAY_UNTIL_REBOOT) } == 0 {
+        return Err(match std::io::Error::last_os_error() {
+            e if e.kind() == std::io::ErrorKind::PermissionDenied => AppError::PermissionDenied(path),
+            e => AppError::Io(e),
+        });
+    }
+    result.pending_reboot = true;
+    log::info!("Scheduled {} for deletion on next reboot", path);
+    Ok(result)
+}
+
 /// Create a symbolic link.
 #[tauri::command]
-pub fn create_symlink(target_path: String, link_path: String) -> Result<FileEntry, AppError> {
+pub fn create_symlink(
+    app: AppHandle,
+    target_path: String,
+    link_path: String,
+) -> Result<FileEntry, AppError> {
+    let entry = create_symlink_unlogged(target_path, link_path)?;
+    undo::record(&app, Operation::Create { paths: vec![PathBuf::from(&entry.path)] });
+    emit_operation_event(&app, OperationKind::Create, &entry.path, None, Some(&entry));
+    Ok(entry)
+}
+
+fn create_symlink_unlogged(target_path: String, link_path: String) -> Result<FileEntry, AppError> {
     let target = PathBuf::from(&target_path);
     let link = PathBuf::from(&link_path);
 
@@ -361,7 +1396,82 @@ pub fn check_paths_exist(paths: Vec<String>) -> Vec<bool> {
     paths.iter().map(|p| PathBuf::from(p).exists()).collect()
 }
 
-fn estimate_path_size(path: &Path, file_count: &mut u64, total_bytes: &mut u64) {
+/// Result of `is_path_accessible`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct AccessResult {
+    pub exists: bool,
+    pub readable: bool,
+    pub writable: bool,
+    /// Files: can be run. Directories: can be entered.
+    pub executable: bool,
+}
+
+/// Check what the current user may do with `path` without opening it for
+/// real. `readable`/`writable` are only checked when requested and are
+/// `false` otherwise. Permission problems are reported in the result, never
+/// as an error.
+#[tauri::command]
+pub fn is_path_accessible(path: String, check_read: bool, check_write: bool) -> Result<AccessResult, AppError> {
+    let file_path = PathBuf::from(&path);
+    let Ok(metadata) = fs::metadata(&file_path) else {
+        return Ok(AccessResult::default());
+    };
+    Ok(AccessResult {
+        exists: true,
+        readable: check_read && can_access(&file_path, &metadata, Access::Read),
+        writable: check_write && can_access(&file_path, &metadata, Access::Write),
+        executable: can_access(&file_path, &metadata, Access::Execute),
+    })
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Access {
+    Read,
+    Write,
+    Execute,
+}
+
+#[cfg(unix)]
+fn can_access(path: &Path, _metadata: &fs::Metadata, access: Access) -> bool {
+    use std::ffi::CString;
+    use std::os::unix::ffi::OsStrExt;
+
+    let Ok(c_path) = CString::new(path.as_os_str().as_bytes()) else {
+        return false;
+    };
+    let mode = match access {
+        Access::Read => libc::R_OK,
+        Access::Write => libc::W_OK,
+        Access::Execute => libc::X_OK,
+    };
+    unsafe { libc::access(c_path.as_ptr(), mode) == 0 }
+}
+
+/// Windows has no `access()` that honours ACLs, so try opening the path with
+/// the access in question (directories: list them, or check the read-only
+/// attribute for writes).
+#[cfg(windows)]
+fn can_access(path: &Path, metadata: &fs::Metadata, access: Access) -> bool {
+    const EXECUTABLE_EXTENSIONS: &[&str] = &["exe", "com", "bat", "cmd", "ps1", "msi"];
+
+    match access {
+        Access::Read if metadata.is_dir() => fs::read_dir(path).is_ok(),
+        Access::Read => fs::File::open(path).is_ok(),
+        Access::Write if metadata.is_dir() => !metadata.permissions().readonly(),
+        Access::Write => {
+            !metadata.permissions().readonly() && fs::OpenOptions::new().write(true).open(path).is_ok()
+        }
+        Access::Execute if metadata.is_dir() => fs::read_dir(path).is_ok(),
+        Access::Execute => {
+            path.extension()
+                .and_then(|e| e.to_str())
+                .is_some_and(|e| EXECUTABLE_EXTENSIONS.contains(&e.to_lowercase().as_str()))
+                && fs::File::open(path).is_ok()
+        }
+    }
+}
+
+pub(crate) fn estimate_path_size(path: &Path, file_count: &mut u64, total_bytes: &mut u64) {
     if path.is_file() {
         *file_count += 1;
         if let Ok(metadata) = fs::metadata(path) {
@@ -385,7 +1495,7 @@ mod tests {
     #[test]
     fn test_create_directory() {
         let dir = tempdir().unwrap();
-        let result = create_directory(
+        let result = create_directory_unlogged(
             dir.path().to_string_lossy().to_string(),
             "new_folder".to_string(),
         )
@@ -403,7 +1513,7 @@ mod tests {
         File::create(&file_path).unwrap();
 
         let result =
-            rename_entry(file_path.to_string_lossy().to_string(), "new_name.txt".to_string())
+            rename_entry_unlogged(file_path.to_string_lossy().to_string(), "new_name.txt".to_string())
                 .unwrap();
 
         assert_eq!(result.name, "new_name.txt");
@@ -411,6 +1521,30 @@ mod tests {
         assert!(dir.path().join("new_name.txt").exists());
     }
 
+    #[test]
+    fn test_operation_event_payload() {
+        let dir = tempdir().unwrap();
+        let source = dir.path().join("a.txt");
+        File::create(&source).unwrap();
+        let entry = rename_entry_unlogged(source.to_string_lossy().to_string(), "b.txt".to_string()).unwrap();
+
+        let event = super::super::operation_event(
+            OperationKind::Rename,
+            &source.to_string_lossy(),
+            Some(&entry.path),
+            Some(&entry),
+        );
+        let json = serde_json::to_value(&event).unwrap();
+        assert_eq!(json["kind"], "rename");
+        assert_eq!(json["destination"], entry.path.as_str());
+        assert_eq!(json["entry"]["name"], "b.txt");
+        assert_eq!(json["timestamp"].as_str().unwrap().len(), 19);
+
+        let deleted = super::super::operation_event(OperationKind::Delete, &entry.path, None, None);
+        let json = serde_json::to_value(&deleted).unwrap();
+        assert!(json["destination"].is_null() && json["entry"].is_null());
+    }
+
     #[test]
     fn test_generate_copy_name() {
         let dir = tempdir().unwrap();
@@ -444,7 +1578,7 @@ mod tests {
         let dest_dir = dir.path().join("dest");
         fs::create_dir(&dest_dir).unwrap();
 
-        let result = copy_entry(
+        let result = copy_entry_unlogged(
             source_dir.to_string_lossy().to_string(),
             dest_dir.to_string_lossy().to_string(),
             None,
@@ -474,7 +1608,7 @@ mod tests {
         fs::create_dir(&source_dir).unwrap();
         fs::write(source_dir.join("file1.txt"), "hello").unwrap();
 
-        let result = copy_entry(
+        let result = copy_entry_unlogged(
             source_dir.to_string_lossy().to_string(),
             dir.path().to_string_lossy().to_string(),
             None,
@@ -493,6 +1627,324 @@ mod tests {
         assert!(copied.join("file1.txt").exists());
     }
 
+    #[test]
+    fn test_copy_and_move_entry_conflict_actions() {
+        let dir = tempdir().unwrap();
+        let src_dir = dir.path().join("src");
+        let dest = dir.path().join("dest");
+        fs::create_dir_all(&src_dir).unwrap();
+        fs::create_dir_all(&dest).unwrap();
+        let source = src_dir.join("a.txt");
+        fs::write(&source, "new").unwrap();
+        fs::write(dest.join("a.txt"), "old").unwrap();
+        let (source_str, dest_str) = (source.to_string_lossy().to_string(), dest.to_string_lossy().to_string());
+
+        let skipped = move_entry_overwrite_unlogged(source_str.clone(), dest_str.clone(), ConflictAction::Skip).unwrap();
+        assert_eq!(skipped.size, 3);
+        assert!(source.exists(), "skip leaves the source in place");
+        assert_eq!(fs::read_to_string(dest.join("a.txt")).unwrap(), "old");
+
+        let failed = copy_entry_overwrite_unlogged(source_str.clone(), dest_str.clone(), ConflictAction::Fail);
+        assert!(matches!(failed, Err(AppError::AlreadyExists(_))));
+
+        let renamed = copy_entry_overwrite_unlogged(source_str.clone(), dest_str.clone(), ConflictAction::AutoRename).unwrap();
+        assert_eq!(renamed.name, "a - Copy.txt");
+
+        let same_dir = src_dir.to_string_lossy().to_string();
+        let itself = copy_entry_overwrite_unlogged(source_str.clone(), same_dir, ConflictAction::Overwrite);
+        assert!(matches!(itself, Err(AppError::InvalidPath(_))));
+        assert!(source.exists());
+
+        move_entry_overwrite_unlogged(source_str, dest_str, ConflictAction::Overwrite).unwrap();
+        assert!(!source.exists());
+        assert_eq!(fs::read_to_string(dest.join("a.txt")).unwrap(), "new");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_copy_overwrite_keeps_existing_when_copy_fails() {
+        let dir = tempdir().unwrap();
+        let (source, dest) = (dir.path().join("src").join("pkg"), dir.path().join("dest"));
+        fs::create_dir_all(&source).unwrap();
+        fs::create_dir_all(dest.join("pkg")).unwrap();
+        fs::write(source.join("a.txt"), "a").unwrap();
+        // Copying follows the link, so the copy fails part way
+        std::os::unix::fs::symlink("missing", source.join("broken")).unwrap();
+        fs::write(dest.join("pkg").join("keep.txt"), "keep").unwrap();
+
+        let result = copy_entry_overwrite_unlogged(
+            source.to_string_lossy().to_string(),
+            dest.to_string_lossy().to_string(),
+            ConflictAction::Overwrite,
+        );
+        assert!(result.is_err());
+        assert_eq!(fs::read_to_string(dest.join("pkg").join("keep.txt")).unwrap(), "keep");
+        assert_eq!(fs::read_dir(dest.join("pkg")).unwrap().count(), 1);
+        assert_eq!(fs::read_dir(&dest).unwrap().count(), 1, "no parked entry left behind");
+    }
+
+    #[test]
+    fn test_replace_existing_restores_on_failure() {
+        let dir = tempdir().unwrap();
+        let (source, target) = (dir.path().join("new.txt"), dir.path().join("old"));
+        fs::write(&source, "new").unwrap();
+        fs::create_dir(&target).unwrap();
+        fs::write(target.join("keep.txt"), "keep").unwrap();
+
+        let result = replace_existing(&source, &target, || {
+            fs::write(&target, "partial")?;
+            Err(AppError::Other("copy failed".into()))
+        });
+        assert!(result.is_err());
+        assert_eq!(fs::read_to_string(target.join("keep.txt")).unwrap(), "keep");
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 2);
+
+        replace_existing(&source, &target, || move_to(&source, &target)).unwrap();
+        assert_eq!(fs::read_to_string(&target).unwrap(), "new");
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1);
+
+        // A target holding the source can't be replaced by it
+        fs::remove_file(&target).unwrap();
+        fs::create_dir_all(target.join("inner")).unwrap();
+        let inner = target.join("inner");
+        assert!(matches!(
+            replace_existing(&inner, &target, || move_to(&inner, &target)),
+            Err(AppError::InvalidPath(_))
+        ));
+        assert!(inner.is_dir());
+    }
+
+    #[test]
+    fn test_merge_directories() {
+        let dir = tempdir().unwrap();
+        let setup = |name: &str| {
+            let (src, dest) = (dir.path().join(name).join("src"), dir.path().join(name).join("dest"));
+            fs::create_dir_all(src.join("shared/inner")).unwrap();
+            fs::create_dir_all(src.join("only_src")).unwrap();
+            fs::create_dir_all(dest.join("shared")).unwrap();
+            fs::write(src.join("shared/clash.txt"), "new").unwrap();
+            fs::write(src.join("shared/inner/a.txt"), "a").unwrap();
+            fs::write(src.join("only_src/b.txt"), "b").unwrap();
+            fs::write(src.join("top.txt"), "top").unwrap();
+            fs::write(dest.join("shared/clash.txt"), "old").unwrap();
+            (src.to_string_lossy().to_string(), dest)
+        };
+        let dest_str = |dest: &Path| dest.to_string_lossy().to_string();
+
+        let (src, dest) = setup("skip");
+        let result = merge_directories_unlogged(src.clone(), dest_str(&dest), ConflictAction::Skip).unwrap();
+        assert_eq!((result.files_merged, result.files_skipped, result.files_overwritten), (3, 1, 0));
+        assert!(result.errors.is_empty());
+        assert_eq!(fs::read_to_string(dest.join("shared/clash.txt")).unwrap(), "old");
+        assert!(dest.join("shared/inner/a.txt").exists() && dest.join("only_src/b.txt").exists());
+        // Only the skipped file (and its directories) stay behind
+        assert!(Path::new(&src).join("shared/clash.txt").exists());
+        assert!(!Path::new(&src).join("only_src").exists());
+
+        let (src, dest) = setup("overwrite");
+        let result = merge_directories_unlogged(src.clone(), dest_str(&dest), ConflictAction::Overwrite).unwrap();
+        assert_eq!((result.files_merged, result.files_skipped, result.files_overwritten), (3, 0, 1));
+        assert_eq!(fs::read_to_string(dest.join("shared/clash.txt")).unwrap(), "new");
+        assert!(!Path::new(&src).exists());
+
+        let (src, dest) = setup("rename");
+        let result = merge_directories_unlogged(src, dest_str(&dest), ConflictAction::AutoRename).unwrap();
+        assert_eq!(result.files_merged, 4);
+        assert_eq!(fs::read_to_string(dest.join("shared/clash - Copy.txt")).unwrap(), "new");
+
+        let (src, dest) = setup("fail");
+        let failed = merge_directories_unlogged(src.clone(), dest_str(&dest), ConflictAction::Fail);
+        assert!(matches!(failed, Err(AppError::AlreadyExists(_))));
+        assert!(Path::new(&src).join("top.txt").exists(), "nothing moves when a file clashes");
+
+        let into_itself = merge_directories_unlogged(src.clone(), format!("{}/shared", src), ConflictAction::Skip);
+        assert!(matches!(into_itself, Err(AppError::InvalidPath(_))));
+    }
+
+    #[test]
+    fn test_flatten_directory() {
+        let dir = tempdir().unwrap();
+        let setup = |name: &str| {
+            let root = dir.path().join(name);
+            fs::create_dir_all(root.join("a/deep")).unwrap();
+            fs::create_dir_all(root.join("b")).unwrap();
+            fs::write(root.join("top.txt"), "top").unwrap();
+            fs::write(root.join("a/one.txt"), "one").unwrap();
+            fs::write(root.join("a/deep/dup.txt"), "first").unwrap();
+            fs::write(root.join("b/dup.txt"), "second").unwrap();
+            root
+        };
+
+        let root = setup("rename");
+        let mut events = Vec::new();
+        let result = flatten_directory_unlogged(&root.to_string_lossy(), ConflictAction::AutoRename, true, &mut |p| {
+            events.push(p)
+        })
+        .unwrap();
+        assert_eq!((result.files_moved, result.conflicts_resolved), (3, 1));
+        assert!(result.errors.is_empty());
+        let mut names: Vec<String> =
+            fs::read_dir(&root).unwrap().map(|e| e.unwrap().file_name().to_string_lossy().to_string()).collect();
+        names.sort();
+        assert_eq!(names, ["dup - Copy.txt", "dup.txt", "one.txt", "top.txt"]);
+        assert_eq!(fs::read_to_string(root.join("dup.txt")).unwrap(), "first");
+        assert_eq!(events.last().map(|e| (e.processed, e.total)), Some((3, 3)));
+
+        let root = setup("skip");
+        let result = flatten_directory_unlogged(&root.to_string_lossy(), ConflictAction::Skip, true, &mut |_| {}).unwrap();
+        assert_eq!((result.files_moved, result.conflicts_resolved), (2, 1));
+        assert!(root.join("b/dup.txt").exists(), "skipped file and its folder stay");
+        assert!(!root.join("a").exists());
+
+        let root = setup("fail");
+        let failed = flatten_directory_unlogged(&root.to_string_lossy(), ConflictAction::Fail, true, &mut |_| {});
+        assert!(matches!(failed, Err(AppError::AlreadyExists(_))));
+        assert!(root.join("a/one.txt").exists(), "nothing moves when names clash");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_flatten_directory_refuses_outside_symlinks() {
+        let dir = tempdir().unwrap();
+        let root = dir.path().join("root");
+        fs::create_dir_all(root.join("sub")).unwrap();
+        fs::write(dir.path().join("outside.txt"), "x").unwrap();
+        fs::write(root.join("inside.txt"), "inside").unwrap();
+        std::os::unix::fs::symlink(root.join("inside.txt"), root.join("sub/ok-link")).unwrap();
+        let path = root.to_string_lossy().to_string();
+        std::os::unix::fs::symlink(dir.path().join("outside.txt"), root.join("sub/bad-link")).unwrap();
+
+        let refused = flatten_directory_unlogged(&path, ConflictAction::Skip, false, &mut |_| {});
+        assert!(matches!(refused, Err(AppError::InvalidPath(_))));
+        assert!(root.join("sub/ok-link").exists());
+
+        fs::remove_file(root.join("sub/bad-link")).unwrap();
+        let result = flatten_directory_unlogged(&path, ConflictAction::Skip, false, &mut |_| {}).unwrap();
+        assert_eq!(result.files_moved, 1);
+        assert_eq!(fs::read_to_string(root.join("ok-link")).unwrap(), "inside");
+        assert!(root.join("sub").is_dir(), "kept without remove_empty");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_flatten_directory_overwrite_keeps_existing_when_move_fails() {
+        use std::os::unix::fs::PermissionsExt;
+        let dir = tempdir().unwrap();
+        let root = dir.path().join("root");
+        fs::create_dir_all(root.join("sub")).unwrap();
+        fs::write(root.join("x.txt"), "keep").unwrap();
+        fs::write(root.join("sub/x.txt"), "new").unwrap();
+        // Nothing can be renamed out of a read-only directory
+        fs::set_permissions(root.join("sub"), fs::Permissions::from_mode(0o555)).unwrap();
+
+        let path = root.to_string_lossy().to_string();
+        let result = flatten_directory_unlogged(&path, ConflictAction::Overwrite, false, &mut |_| {}).unwrap();
+        fs::set_permissions(root.join("sub"), fs::Permissions::from_mode(0o755)).unwrap();
+        // root ignores permission bits
+        if unsafe { libc::geteuid() } == 0 {
+            return;
+        }
+        assert_eq!(result.files_moved, 0);
+        assert_eq!(result.errors.len(), 1);
+        assert_eq!(fs::read_to_string(root.join("x.txt")).unwrap(), "keep");
+        assert_eq!(fs::read_to_string(root.join("sub/x.txt")).unwrap(), "new");
+        assert_eq!(fs::read_dir(&root).unwrap().count(), 2, "no parked entry left behind");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_flatten_directory_refuses_relative_symlinks() {
+        let dir = tempdir().unwrap();
+        let root = dir.path().join("root");
+        fs::create_dir_all(root.join("sub")).unwrap();
+        fs::write(root.join("inside.txt"), "inside").unwrap();
+        fs::write(root.join("sub/file.txt"), "x").unwrap();
+        std::os::unix::fs::symlink("../inside.txt", root.join("sub/rel-link")).unwrap();
+        let path = root.to_string_lossy().to_string();
+
+        let refused = flatten_directory_unlogged(&path, ConflictAction::Skip, false, &mut |_| {});
+        assert!(matches!(refused, Err(AppError::InvalidPath(_))));
+        assert!(root.join("sub/file.txt").exists(), "nothing moves");
+        assert_eq!(fs::read_to_string(root.join("sub/rel-link")).unwrap(), "inside");
+    }
+
+    #[test]
+    fn test_create_directory_recursive() {
+        let dir = tempdir().unwrap();
+        let nested = dir.path().join("a").join("b").join("c");
+
+        let created = create_directory_recursive_unlogged(nested.to_string_lossy().to_string()).unwrap();
+        let names: Vec<&str> = created.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, ["a", "b", "c"]);
+        assert!(nested.is_dir());
+
+        let again = create_directory_recursive_unlogged(nested.to_string_lossy().to_string()).unwrap();
+        assert!(again.is_empty());
+
+        fs::write(dir.path().join("file"), "x").unwrap();
+        let blocked = dir.path().join("file").join("sub");
+        assert!(matches!(
+            create_directory_recursive_unlogged(blocked.to_string_lossy().to_string()),
+            Err(AppError::AlreadyExists(_))
+        ));
+    }
+
+    #[test]
+    fn test_atomic_rename() {
+        let dir = tempdir().unwrap();
+        let source = dir.path().join("a.txt");
+        let taken = dir.path().join("taken.txt");
+        fs::write(&source, "a").unwrap();
+        fs::write(&taken, "keep").unwrap();
+        let path_str = |p: &Path| p.to_string_lossy().to_string();
+
+        let refused = atomic_rename_unlogged(path_str(&source), path_str(&taken));
+        assert!(matches!(refused, Err(AppError::AlreadyExists(_))));
+        assert_eq!(fs::read_to_string(&taken).unwrap(), "keep");
+
+        let target = dir.path().join("b.txt");
+        let entry = atomic_rename_unlogged(path_str(&source), path_str(&target)).unwrap();
+        assert_eq!(entry.name, "b.txt");
+        assert!(!source.exists());
+        assert!(matches!(atomic_rename_unlogged(path_str(&source), path_str(&taken)), Err(AppError::NotFound(_))));
+    }
+
+    #[test]
+    fn test_atomic_rename_race_has_one_winner() {
+        let dir = tempdir().unwrap();
+        for round in 0..50 {
+            let target = dir.path().join(format!("target{}", round));
+            let sources: Vec<PathBuf> = (0..2)
+                .map(|i| {
+                    let path = dir.path().join(format!("src{}_{}", round, i));
+                    fs::write(&path, i.to_string()).unwrap();
+                    path
+                })
+                .collect();
+
+            let barrier = std::sync::Arc::new(std::sync::Barrier::new(2));
+            let handles: Vec<_> = sources
+                .iter()
+                .map(|source| {
+                    let (source, target) = (source.to_string_lossy().to_string(), target.to_string_lossy().to_string());
+                    let barrier = barrier.clone();
+                    std::thread::spawn(move || {
+                        barrier.wait();
+                        atomic_rename_unlogged(source, target)
+                    })
+                })
+                .collect();
+            let results: Vec<_> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+
+            let winners: Vec<usize> = (0..2).filter(|&i| results[i].is_ok()).collect();
+            assert_eq!(winners.len(), 1, "round {}: {:?}", round, results);
+            let loser = 1 - winners[0];
+            assert!(matches!(results[loser], Err(AppError::AlreadyExists(_))));
+            assert!(sources[loser].exists(), "the losing source is untouched");
+            assert_eq!(fs::read_to_string(&target).unwrap(), winners[0].to_string());
+        }
+    }
+
     #[test]
     fn test_estimate_size() {
         let dir = tempdir().unwrap();
@@ -505,4 +1957,72 @@ mod tests {
         assert_eq!(result.file_count, 3);
         assert_eq!(result.total_bytes, 14);
     }
+
+    #[test]
+    fn test_get_special_directories_only_existing() {
+        let dirs = get_special_directories().unwrap();
+        let temp = dirs.temp.expect("temp dir always exists");
+        assert!(PathBuf::from(temp).is_dir());
+        for path in [dirs.home, dirs.desktop, dirs.downloads, dirs.documents, dirs.pictures, dirs.music, dirs.videos]
+            .into_iter()
+            .flatten()
+        {
+            assert!(PathBuf::from(&path).is_dir(), "{}", path);
+        }
+    }
+
+    #[test]
+    fn test_is_path_accessible() {
+        let dir = tempdir().unwrap();
+        let file = dir.path().join("notes.txt");
+        fs::write(&file, "x").unwrap();
+        let path = file.to_string_lossy().to_string();
+
+        let result = is_path_accessible(path.clone(), true, true).unwrap();
+        assert!(result.exists && result.readable && result.writable);
+        assert!(!result.executable);
+
+        let unchecked = is_path_accessible(path, false, false).unwrap();
+        assert!(unchecked.exists && !unchecked.readable && !unchecked.writable);
+
+        let folder = is_path_accessible(dir.path().to_string_lossy().to_string(), true, false).unwrap();
+        assert!(folder.readable && folder.executable);
+
+        let missing = dir.path().join("missing").to_string_lossy().to_string();
+        assert_eq!(is_path_accessible(missing, true, true).unwrap(), AccessResult::default());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_is_path_accessible_read_only_file() {
+        use std::os::unix::fs::PermissionsExt;
+        let dir = tempdir().unwrap();
+        let file = dir.path().join("locked.txt");
+        fs::write(&file, "x").unwrap();
+        fs::set_permissions(&file, fs::Permissions::from_mode(0o444)).unwrap();
+
+        let result = is_path_accessible(file.to_string_lossy().to_string(), true, true).unwrap();
+        assert!(result.readable);
+        // root ignores permission bits
+        if unsafe { libc::geteuid() } != 0 {
+            assert!(!result.writable);
+        }
+    }
+
+    // On Windows this would shut down the test process itself
+    #[cfg(unix)]
+    #[test]
+    fn test_force_delete_open_file() {
+        let dir = tempdir().unwrap();
+        let file = dir.path().join("open.txt");
+        fs::write(&file, "x").unwrap();
+        let _handle = File::open(&file).unwrap();
+
+        let result = force_delete_entry_unlogged(file.to_string_lossy().to_string()).unwrap();
+        assert!(result.deleted && !result.pending_reboot);
+        assert!(!file.exists());
+
+        let missing = force_delete_entry_unlogged(file.to_string_lossy().to_string());
+        assert!(matches!(missing, Err(AppError::NotFound(_))));
+    }
 }
@@ -0,0 +1,137 @@
+//! File type detection from content (magic bytes) rather than extension.
+
+use std::fs;
+use std::io::Read;
+use std::path::Path;
+
+use infer::MatcherType;
+use serde::Serialize;
+
+use crate::error::AppError;
+
+/// Bytes read from the start of a file for detection.
+const SNIFF_LEN: usize = 512;
+
+/// Result of `get_file_type`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FileTypeInfo {
+    #[serde(rename = "mimeType")]
+    pub mime_type: String,
+    /// Canonical extension for the detected type, without the dot.
+    pub extension: String,
+    /// `image`, `video`, `audio`, `archive`, `document`, `text`, `binary`
+    /// or `unknown`.
+    pub category: String,
+}
+
+fn category_of(matcher: MatcherType) -> &'static str {
+    match matcher {
+        MatcherType::Image => "image",
+        MatcherType::Video => "video",
+        MatcherType::Audio => "audio",
+        MatcherType::Archive => "archive",
+        MatcherType::Doc | MatcherType::Book => "document",
+        MatcherType::Text => "text",
+        MatcherType::App | MatcherType::Font => "binary",
+        MatcherType::Custom => "unknown",
+    }
+}
+
+/// Classify a file from its leading bytes. Content with no known signature
+/// is `text/plain` unless it contains NUL bytes.
+pub(crate) fn detect_file_type(path: &Path) -> Result<FileTypeInfo, AppError> {
+    let mut head = Vec::with_capacity(SNIFF_LEN);
+    fs::File::open(path)?
+        .take(SNIFF_LEN as u64)
+        .read_to_end(&mut head)?;
+
+    if let Some(kind) = infer::get(&head) {
+        return Ok(FileTypeInfo {
+            mime_type: kind.mime_type().to_string(),
+            extension: kind.extension().to_string(),
+            category: category_of(kind.matcher_type()).to_string(),
+        });
+    }
+
+    let info = if head.contains(&0) {
+        FileTypeInfo {
+            mime_type: "application/octet-stream".to_string(),
+            extension: "bin".to_string(),
+            category: "binary".to_string(),
+        }
+    } else {
+        FileTypeInfo {
+            mime_type: "text/plain".to_string(),
+            extension: "txt".to_string(),
+            category: "text".to_string(),
+        }
+    };
+    Ok(info)
+}
+
+/// Whether content search should skip `path`: anything detected as
+/// non-text. Unreadable files are left to the searcher to report.
+pub(crate) fn is_binary_content(path: &Path) -> bool {
+    detect_file_type(path).is_ok_and(|info| info.category != "text")
+}
+
+/// Identify a file's MIME type from its first 512 bytes.
+#[tauri::command]
+pub fn get_file_type(path: String) -> Result<FileTypeInfo, AppError> {
+    let file_path = Path::new(&path);
+    if !file_path.exists() {
+        return Err(AppError::NotFound(path));
+    }
+    if file_path.is_dir() {
+        return Err(AppError::InvalidPath(format!("{} is a directory", path)));
+    }
+    detect_file_type(file_path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    const PNG_HEADER: &[u8] = b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR\0\0\0\x01\0\0\0\x01\x08\x06\0\0\0";
+
+    #[test]
+    fn test_detects_misnamed_png() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("notes.txt");
+        fs::write(&path, PNG_HEADER).unwrap();
+
+        let info = get_file_type(path.to_string_lossy().to_string()).unwrap();
+        assert_eq!(info.mime_type, "image/png");
+        assert_eq!(info.extension, "png");
+        assert_eq!(info.category, "image");
+        assert!(is_binary_content(&path));
+    }
+
+    #[test]
+    fn test_text_and_unknown_binary() {
+        let dir = tempdir().unwrap();
+        let text = dir.path().join("data.bin");
+        fs::write(&text, "plain words, no signature").unwrap();
+        assert_eq!(detect_file_type(&text).unwrap().category, "text");
+        assert!(!is_binary_content(&text));
+
+        let blob = dir.path().join("code.rs");
+        fs::write(&blob, [1u8, 0, 2, 0, 3]).unwrap();
+        assert_eq!(detect_file_type(&blob).unwrap().category, "binary");
+        assert!(is_binary_content(&blob));
+
+        let zip = dir.path().join("archive.dat");
+        fs::write(&zip, b"PK\x03\x04\x14\0\0\0\0\0").unwrap();
+        assert_eq!(detect_file_type(&zip).unwrap().category, "archive");
+    }
+
+    #[test]
+    fn test_get_file_type_errors() {
+        let dir = tempdir().unwrap();
+        let missing = dir.path().join("missing").to_string_lossy().to_string();
+        assert!(matches!(get_file_type(missing), Err(AppError::NotFound(_))));
+        let folder = dir.path().to_string_lossy().to_string();
+        assert!(matches!(get_file_type(folder), Err(AppError::InvalidPath(_))));
+    }
+}
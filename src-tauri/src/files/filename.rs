@@ -0,0 +1,291 @@
+//! Filename validation and sanitizing against per-platform naming rules.
+
+use serde::Serialize;
+
+use crate::error::AppError;
+
+/// Longest file name: 255 UTF-16 units on Windows, 255 bytes elsewhere.
+const MAX_NAME_LENGTH: usize = 255;
+
+/// Characters Windows rejects in file names, besides control characters.
+const WINDOWS_ILLEGAL_CHARS: &[char] = &['<', '>', ':', '"', '/', '\\', '|', '?', '*'];
+
+/// Device names Windows reserves regardless of extension (`CON.txt` too).
+const WINDOWS_RESERVED_NAMES: &[&str] = &[
+    "CON", "PRN", "AUX", "NUL", "COM1", "COM2", "COM3", "COM4", "COM5", "COM6", "COM7", "COM8",
+    "COM9", "COM¹", "COM²", "COM³", "LPT1", "LPT2", "LPT3", "LPT4", "LPT5", "LPT6", "LPT7", "LPT8",
+    "LPT9", "LPT¹", "LPT²", "LPT³",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Platform {
+    Windows,
+    MacOs,
+    Linux,
+}
+
+impl Platform {
+    fn current() -> Self {
+        if cfg!(target_os = "windows") {
+            Self::Windows
+        } else if cfg!(target_os = "macos") {
+            Self::MacOs
+        } else {
+            Self::Linux
+        }
+    }
+
+    fn parse(platform: Option<String>) -> Result<Self, AppError> {
+        match platform.as_deref().map(str::to_lowercase).as_deref() {
+            None | Some("") => Ok(Self::current()),
+            Some("windows" | "win32") => Ok(Self::Windows),
+            Some("macos" | "darwin" | "mac") => Ok(Self::MacOs),
+            Some("linux" | "unix") => Ok(Self::Linux),
+            Some(other) => Err(AppError::Other(format!(
+                "Unknown platform: {} (expected windows, macos or linux)",
+                other
+            ))),
+        }
+    }
+
+    fn name_length(self, name: &str) -> usize {
+        match self {
+            Self::Windows => name.encode_utf16().count(),
+            _ => name.len(),
+        }
+    }
+
+    fn is_illegal_char(self, c: char) -> bool {
+        match self {
+            Self::Windows => c < ' ' || WINDOWS_ILLEGAL_CHARS.contains(&c),
+            // Finder maps ':' to '/' and the HFS+ layer rejects it
+            Self::MacOs => c == '/' || c == ':' || c == '\0',
+            Self::Linux => c == '/' || c == '\0',
+        }
+    }
+}
+
+/// Result of `validate_filename`.
+#[derive(Debug, Clone, Serialize)]
+pub struct FilenameValidationResult {
+    pub valid: bool,
+    /// Human-readable problems, empty when `valid`.
+    pub issues: Vec<String>,
+}
+
+/// Windows reserves device names by the part before the first dot, ignoring
+/// case and trailing spaces.
+fn is_windows_reserved(name: &str) -> bool {
+    let stem = name.split('.').next().unwrap_or(name).trim_end_matches(' ');
+    WINDOWS_RESERVED_NAMES.iter().any(|reserved| reserved.eq_ignore_ascii_case(stem))
+}
+
+fn filename_issues(name: &str, platform: Platform) -> Vec<String> {
+    let mut issues = Vec::new();
+    if name.is_empty() {
+        issues.push("Name is empty".to_string());
+        return issues;
+    }
+    if name == "." || name == ".." {
+        issues.push(format!("\"{}\" is reserved for directory navigation", name));
+        return issues;
+    }
+
+    let mut illegal: Vec<char> = name.chars().filter(|&c| platform.is_illegal_char(c)).collect();
+    illegal.dedup();
+    for c in illegal {
+        if c.is_control() {
+            issues.push(format!("Contains control character U+{:04X}", c as u32));
+        } else {
+            issues.push(format!("Contains illegal character '{}'", c));
+        }
+    }
+
+    let length = platform.name_length(name);
+    if length > MAX_NAME_LENGTH {
+        issues.push(format!("Name is too long ({} of {} allowed)", length, MAX_NAME_LENGTH));
+    }
+
+    if platform == Platform::Windows {
+        if is_windows_reserved(name) {
+            issues.push(format!(
+                "\"{}\" is a reserved device name",
+                name.split('.').next().unwrap_or(name).trim_end()
+            ));
+        }
+        if name.ends_with('.') {
+            issues.push("Ends with a dot".to_string());
+        } else if name.ends_with(' ') {
+            issues.push("Ends with a space".to_string());
+        }
+    }
+    issues
+}
+
+/// Check `name` against the naming rules of `platform` (`windows`, `macos`
+/// or `linux`; defaults to the running OS).
+#[tauri::command]
+pub fn validate_filename(name: String, platform: Option<String>) -> Result<FilenameValidationResult, AppError> {
+    let issues = filename_issues(&name, Platform::parse(platform)?);
+    Ok(FilenameValidationResult {
+        valid: issues.is_empty(),
+        issues,
+    })
+}
+
+/// Turn `name` into one that passes `validate_filename` for `platform`:
+/// illegal characters become `_`, control characters are dropped, reserved
+/// names get a `_` suffix and long names are shortened keeping the extension.
+#[tauri::command]
+pub fn sanitize_filename(name: String, platform: Option<String>) -> Result<String, AppError> {
+    Ok(sanitize(&name, Platform::parse(platform)?))
+}
+
+fn sanitize(name: &str, platform: Platform) -> String {
+    let mut out: String = name
+        .chars()
+        .filter(|c| !c.is_control())
+        .map(|c| if platform.is_illegal_char(c) { '_' } else { c })
+        .collect();
+
+    if platform == Platform::Windows {
+        out.truncate(out.trim_end_matches(['.', ' ']).len());
+        if is_windows_reserved(&out) {
+            let stem_end = out.find('.').unwrap_or(out.len());
+            let stem_trimmed = out[..stem_end].trim_end_matches(' ').len();
+            out.insert(stem_trimmed, '_');
+        }
+    }
+
+    if out.is_empty() || out == "." || out == ".." {
+        return "_".to_string();
+    }
+
+    if platform.name_length(&out) > MAX_NAME_LENGTH {
+        out = shorten(&out, platform);
+    }
+    out
+}
+
+/// Drop characters from the end of the stem until the name fits, keeping a
+/// short extension.
+fn shorten(name: &str, platform: Platform) -> String {
+    let (stem, ext) = match name.rfind('.') {
+        Some(dot) if dot > 0 && name.len() - dot <= 16 => name.split_at(dot),
+        _ => (name, ""),
+    };
+    let budget = MAX_NAME_LENGTH - platform.name_length(ext);
+    let mut kept = String::new();
+    for c in stem.chars() {
+        if platform.name_length(&kept) + platform.name_length(c.encode_utf8(&mut [0; 4])) > budget {
+            break;
+        }
+        kept.push(c);
+    }
+    if platform == Platform::Windows {
+        kept.truncate(kept.trim_end_matches(['.', ' ']).len());
+    }
+    kept + ext
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn issues(name: &str, platform: &str) -> Vec<String> {
+        validate_filename(name.to_string(), Some(platform.to_string())).unwrap().issues
+    }
+
+    // ── Validation ──────────────────────────────────────────────────────────
+
+    #[test]
+    fn test_all_windows_reserved_names() {
+        for reserved in WINDOWS_RESERVED_NAMES {
+            for name in [
+                reserved.to_string(),
+                reserved.to_lowercase(),
+                format!("{}.txt", reserved),
+                format!("{} .tar.gz", reserved),
+            ] {
+                let found = issues(&name, "windows");
+                assert!(found.iter().any(|i| i.contains("reserved device name")), "{}: {:?}", name, found);
+                assert!(issues(&name, "linux").is_empty(), "{} is fine on Linux", name);
+            }
+        }
+        assert!(issues("CONSOLE.txt", "windows").is_empty());
+        assert!(issues("my.CON", "windows").is_empty());
+        assert!(issues("COM0", "windows").is_empty());
+    }
+
+    #[test]
+    fn test_illegal_characters_and_trailing() {
+        let found = issues("a<b>c:d\"e|f?g*h\\i", "windows");
+        assert_eq!(found.len(), 8, "{:?}", found);
+        assert!(issues("tab\there", "windows")[0].contains("U+0009"));
+        assert_eq!(issues("report.", "windows"), vec!["Ends with a dot"]);
+        assert_eq!(issues("report ", "windows"), vec!["Ends with a space"]);
+        assert!(issues("report.", "linux").is_empty());
+
+        assert_eq!(issues("12:30", "macos"), vec!["Contains illegal character ':'"]);
+        assert!(issues("12:30", "linux").is_empty());
+        assert_eq!(issues("a/b", "linux"), vec!["Contains illegal character '/'"]);
+        assert_eq!(issues("..", "linux").len(), 1);
+        assert_eq!(issues("", "linux"), vec!["Name is empty"]);
+    }
+
+    #[test]
+    fn test_length_limits() {
+        assert!(issues(&"a".repeat(255), "linux").is_empty());
+        assert_eq!(issues(&"a".repeat(256), "linux").len(), 1);
+
+        // 128 two-byte chars: 256 bytes on Unix, 128 UTF-16 units on Windows
+        let accented = "é".repeat(128);
+        assert!(issues(&accented, "linux")[0].contains("too long"));
+        assert!(issues(&accented, "windows").is_empty());
+        assert!(issues(&"é".repeat(256), "windows")[0].contains("too long"));
+    }
+
+    #[test]
+    fn test_unknown_platform() {
+        assert!(validate_filename("a".into(), Some("beos".into())).is_err());
+        assert!(validate_filename("a".into(), None).unwrap().valid);
+    }
+
+    // ── Sanitizing ──────────────────────────────────────────────────────────
+
+    #[test]
+    fn test_sanitize_produces_valid_names() {
+        let nasty = [
+            "a<b>c:d\"e|f?g*h\\i.txt",
+            "CON",
+            "con.txt",
+            "LPT¹ .log",
+            "trailing. . ",
+            "...",
+            "..",
+            "",
+            "tab\tand\nnewline",
+        ];
+        for platform in ["windows", "macos", "linux"] {
+            for name in nasty {
+                let safe = sanitize_filename(name.into(), Some(platform.into())).unwrap();
+                assert!(issues(&safe, platform).is_empty(), "{:?} -> {:?} on {}", name, safe, platform);
+            }
+        }
+        assert_eq!(sanitize_filename("CON.txt".into(), Some("windows".into())).unwrap(), "CON_.txt");
+        assert_eq!(sanitize_filename("a:b".into(), Some("windows".into())).unwrap(), "a_b");
+        assert_eq!(sanitize_filename("a:b".into(), Some("linux".into())).unwrap(), "a:b");
+    }
+
+    #[test]
+    fn test_sanitize_shortens_keeping_extension() {
+        let long = format!("{}.json", "ü".repeat(300));
+        let unix = sanitize_filename(long.clone(), Some("linux".into())).unwrap();
+        assert!(unix.len() <= MAX_NAME_LENGTH);
+        assert!(unix.ends_with(".json"));
+
+        let windows = sanitize_filename(long, Some("windows".into())).unwrap();
+        assert_eq!(windows.encode_utf16().count(), MAX_NAME_LENGTH);
+        assert!(windows.ends_with(".json"));
+    }
+}
@@ -0,0 +1,810 @@
+//! What the filesystem holding a path can do: type, case sensitivity, name
+//! length limit and link/xattr support, so the frontend can warn before an
+//! operation the target can't represent (e.g. a symlink onto a FAT stick).
+//! Also the volume itself: label, UUID, mount point and space.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter};
+
+use crate::error::AppError;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FilesystemInfo {
+    /// As the OS names it: `ext4`, `btrfs`, `apfs`, `NTFS`, `FAT32`, ...
+    #[serde(rename = "fsType")]
+    pub fs_type: String,
+    #[serde(rename = "isCaseSensitive")]
+    pub is_case_sensitive: bool,
+    /// Longest file name (one path component) the filesystem accepts.
+    #[serde(rename = "maxFilenameBytes")]
+    pub max_filename_bytes: u32,
+    #[serde(rename = "supportsSymlinks")]
+    pub supports_symlinks: bool,
+    #[serde(rename = "supportsHardlinks")]
+    pub supports_hardlinks: bool,
+    #[serde(rename = "supportsXattrs")]
+    pub supports_xattrs: bool,
+}
+
+/// Filesystem type and capabilities for the volume containing `path`.
+#[tauri::command]
+pub fn get_filesystem_type(path: String) -> Result<FilesystemInfo, AppError> {
+    let resolved = fs::canonicalize(&path).map_err(|_| AppError::NotFound(path.clone()))?;
+    filesystem_info(&resolved)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct VolumeInfo {
+    /// Volume name shown by the OS; `None` for unlabelled volumes.
+    pub label: Option<String>,
+    /// Filesystem UUID (the volume GUID on Windows).
+    pub uuid: Option<String>,
+    #[serde(rename = "mountPoint")]
+    pub mount_point: String,
+    pub filesystem: String,
+    #[serde(rename = "isReadOnly")]
+    pub is_read_only: bool,
+    #[serde(rename = "isRemovable")]
+    pub is_removable: bool,
+    #[serde(rename = "totalBytes")]
+    pub total_bytes: u64,
+    /// Space available to the current user.
+    #[serde(rename = "freeBytes")]
+    pub free_bytes: u64,
+}
+
+/// How long `get_volume_info` answers from the cache; the UI asks for the
+/// same volume on every refresh.
+const VOLUME_CACHE_TTL: Duration = Duration::from_secs(5);
+
+static VOLUME_CACHE: Mutex<Vec<(PathBuf, Instant, VolumeInfo)>> = Mutex::new(Vec::new());
+
+/// Label, UUID, mount point and space of the volume containing `path`.
+#[tauri::command]
+pub fn get_volume_info(path: String) -> Result<VolumeInfo, AppError> {
+    let resolved = fs::canonicalize(&path).map_err(|_| AppError::NotFound(path.clone()))?;
+    {
+        let mut cache = VOLUME_CACHE.lock().unwrap_or_else(|e| e.into_inner());
+        cache.retain(|(_, fetched, _)| fetched.elapsed() < VOLUME_CACHE_TTL);
+        if let Some((_, _, info)) = cache.iter().find(|(p, _, _)| *p == resolved) {
+            return Ok(info.clone());
+        }
+    }
+    let info = volume_info(&resolved)?;
+    VOLUME_CACHE
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .push((resolved, Instant::now(), info.clone()));
+    Ok(info)
+}
+
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+fn statfs(path: &Path) -> Result<libc::statfs, AppError> {
+    use std::os::unix::ffi::OsStrExt;
+
+    let c_path = std::ffi::CString::new(path.as_os_str().as_bytes())
+        .map_err(|_| AppError::InvalidPath(path.to_string_lossy().to_string()))?;
+    let mut stat: libc::statfs = unsafe { std::mem::zeroed() };
+    if unsafe { libc::statfs(c_path.as_ptr(), &mut stat) } != 0 {
+        return Err(std::io::Error::last_os_error().into());
+    }
+    Ok(stat)
+}
+
+/// The type comes from the longest matching mount point in `/proc/mounts`
+/// (which tells ext2/3/4 apart, unlike the `statfs` magic number); the name
+/// limit from `statfs`. Capabilities are per filesystem type, so a casefolded
+/// ext4 directory still reports case-sensitive.
+#[cfg(target_os = "linux")]
+fn filesystem_info(path: &Path) -> Result<FilesystemInfo, AppError> {
+    let stat = statfs(path)?;
+    let mounts = fs::read_to_string("/proc/mounts").unwrap_or_default();
+    #[allow(clippy::unnecessary_cast)] // `f_type` is narrower on 32-bit targets
+    let magic = stat.f_type as i64;
+    let fs_type = find_mount(&mounts, path)
+        .map(|m| m.fs_type)
+        .unwrap_or_else(|| magic_name(magic).to_string());
+    Ok(capabilities(fs_type, stat.f_namelen as u32))
+}
+
+/// `libc::statfs` is the 64-bit-inode `statfs64` on macOS; APFS and HFS+
+/// answer case sensitivity per volume through `pathconf`.
+#[cfg(target_os = "macos")]
+fn filesystem_info(path: &Path) -> Result<FilesystemInfo, AppError> {
+    use std::ffi::CStr;
+    use std::os::unix::ffi::OsStrExt;
+
+    let stat = statfs(path)?;
+    let fs_type = unsafe { CStr::from_ptr(stat.f_fstypename.as_ptr()) }
+        .to_string_lossy()
+        .into_owned();
+    let c_path = std::ffi::CString::new(path.as_os_str().as_bytes())
+        .map_err(|_| AppError::InvalidPath(path.to_string_lossy().to_string()))?;
+    let name_max = unsafe { libc::pathconf(c_path.as_ptr(), libc::_PC_NAME_MAX) };
+    let case_sensitive = unsafe { libc::pathconf(c_path.as_ptr(), libc::_PC_CASE_SENSITIVE) };
+
+    let mut info = capabilities(fs_type, if name_max > 0 { name_max as u32 } else { 255 });
+    if case_sensitive >= 0 {
+        info.is_case_sensitive = case_sensitive == 1;
+    }
+    Ok(info)
+}
+
+/// `GetVolumeInformationW` on the volume root from `GetVolumePathNameW`;
+/// capabilities come from the volume's flags rather than the type name.
+#[cfg(windows)]
+fn filesystem_info(path: &Path) -> Result<FilesystemInfo, AppError> {
+    use std::ffi::OsString;
+    use std::os::windows::ffi::{OsStrExt, OsStringExt};
+
+    #[link(name = "kernel32")]
+    extern "system" {
+        fn GetVolumePathNameW(file_name: *const u16, volume_path: *mut u16, length: u32) -> i32;
+        fn GetVolumeInformationW(
+            root: *const u16,
+            volume_name: *mut u16,
+            volume_name_size: u32,
+            serial_number: *mut u32,
+            max_component_length: *mut u32,
+            flags: *mut u32,
+            fs_name: *mut u16,
+            fs_name_size: u32,
+        ) -> i32;
+    }
+    const FILE_CASE_SENSITIVE_SEARCH: u32 = 0x1;
+    const FILE_SUPPORTS_REPARSE_POINTS: u32 = 0x80;
+    const FILE_SUPPORTS_HARD_LINKS: u32 = 0x0040_0000;
+    const FILE_SUPPORTS_EXTENDED_ATTRIBUTES: u32 = 0x0080_0000;
+
+    let wide: Vec<u16> = path.as_os_str().encode_wide().chain(std::iter::once(0)).collect();
+    let mut root = vec![0u16; 1024];
+    if unsafe { GetVolumePathNameW(wide.as_ptr(), root.as_mut_ptr(), root.len() as u32) } == 0 {
+        return Err(std::io::Error::last_os_error().into());
+    }
+
+    let (mut max_component, mut flags) = (0u32, 0u32);
+    let mut fs_name = [0u16; 64];
+    let ok = unsafe {
+        GetVolumeInformationW(
+            root.as_ptr(),
+            std::ptr::null_mut(),
+            0,
+            std::ptr::null_mut(),
+            &mut max_component,
+            &mut flags,
+            fs_name.as_mut_ptr(),
+            fs_name.len() as u32,
+        )
+    };
+    if ok == 0 {
+        return Err(std::io::Error::last_os_error().into());
+    }
+    let len = fs_name.iter().position(|&c| c == 0).unwrap_or(fs_name.len());
+
+    Ok(FilesystemInfo {
+        fs_type: OsString::from_wide(&fs_name[..len]).to_string_lossy().into_owned(),
+        is_case_sensitive: flags & FILE_CASE_SENSITIVE_SEARCH != 0,
+        // Windows reports UTF-16 code units; a unit is at most 3 UTF-8 bytes
+        max_filename_bytes: max_component.saturating_mul(3),
+        supports_symlinks: flags & FILE_SUPPORTS_REPARSE_POINTS != 0,
+        supports_hardlinks: flags & FILE_SUPPORTS_HARD_LINKS != 0,
+        supports_xattrs: flags & FILE_SUPPORTS_EXTENDED_ATTRIBUTES != 0,
+    })
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", windows)))]
+fn filesystem_info(_path: &Path) -> Result<FilesystemInfo, AppError> {
+    Err(AppError::Other("Filesystem info is not supported on this platform".into()))
+}
+
+/// Capabilities of a Unix-side filesystem type, by name.
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+fn capabilities(fs_type: String, max_filename_bytes: u32) -> FilesystemInfo {
+    let kind = fs_type.to_lowercase();
+    let is = |names: &[&str]| names.contains(&kind.as_str());
+    let fat = is(&["vfat", "msdos", "exfat", "fat", "fat32"]);
+    let smb = is(&["cifs", "smb3", "smbfs"]);
+    FilesystemInfo {
+        is_case_sensitive: !(fat || smb || is(&["hfs", "ntfs"])),
+        supports_symlinks: !fat,
+        supports_hardlinks: !(fat || is(&["iso9660", "udf"])),
+        supports_xattrs: !(fat || is(&["iso9660", "proc", "sysfs", "devpts"])),
+        max_filename_bytes,
+        fs_type,
+    }
+}
+
+/// One line of `/proc/mounts`.
+#[cfg(target_os = "linux")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct MountEntry {
+    device: String,
+    mount_point: String,
+    fs_type: String,
+    options: String,
+}
+
+/// The mount that contains `path`, from `/proc/mounts` content. Stacked
+/// mounts on the same point resolve to the last one.
+#[cfg(target_os = "linux")]
+fn find_mount(mounts: &str, path: &Path) -> Option<MountEntry> {
+    let mut best: Option<MountEntry> = None;
+    for line in mounts.lines() {
+        let mut fields = line.split_whitespace();
+        let (Some(device), Some(mount_point), Some(fs_type)) = (fields.next(), fields.next(), fields.next()) else {
+            continue;
+        };
+        let mount_point = unescape_mount_path(mount_point);
+        if path.starts_with(&mount_point) && best.as_ref().is_none_or(|b| mount_point.len() >= b.mount_point.len()) {
+            best = Some(MountEntry {
+                device: unescape_mount_path(device),
+                mount_point,
+                fs_type: fs_type.to_string(),
+                options: fields.next().unwrap_or_default().to_string(),
+            });
+        }
+    }
+    best
+}
+
+/// `/proc/mounts` writes space, tab, newline and backslash as `\ooo` octal.
+#[cfg(target_os = "linux")]
+pub(crate) fn unescape_mount_path(field: &str) -> String {
+    let bytes = field.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let octal = bytes.get(i + 1..i + 4).and_then(|digits| {
+            std::str::from_utf8(digits).ok().and_then(|d| u8::from_str_radix(d, 8).ok())
+        });
+        match (bytes[i], octal) {
+            (b'\\', Some(byte)) => {
+                out.push(byte);
+                i += 4;
+            }
+            (byte, _) => {
+                out.push(byte);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+/// Mount from `/proc/mounts`, space from `statvfs`, label and UUID from the
+/// `/dev/disk/by-label` and `/dev/disk/by-uuid` links udev maintains (no
+/// `blkid`, which needs root for uncached devices). Removable means the
+/// disk's `removable` flag or a USB connection.
+#[cfg(target_os = "linux")]
+fn volume_info(path: &Path) -> Result<VolumeInfo, AppError> {
+    use std::os::unix::ffi::OsStrExt;
+
+    let mounts = fs::read_to_string("/proc/mounts")?;
+    let mount = find_mount(&mounts, path)
+        .ok_or_else(|| AppError::NotFound(format!("No mount found for {}", path.display())))?;
+
+    let c_path = std::ffi::CString::new(path.as_os_str().as_bytes())
+        .map_err(|_| AppError::InvalidPath(path.to_string_lossy().to_string()))?;
+    let mut stat: libc::statvfs = unsafe { std::mem::zeroed() };
+    if unsafe { libc::statvfs(c_path.as_ptr(), &mut stat) } != 0 {
+        return Err(std::io::Error::last_os_error().into());
+    }
+    #[allow(clippy::unnecessary_cast)] // the field widths vary by target
+    let (block_size, blocks, available) = (stat.f_frsize as u64, stat.f_blocks as u64, stat.f_bavail as u64);
+
+    // Pseudo filesystems have no device node; they get no label or UUID
+    let device = fs::canonicalize(&mount.device).ok();
+    let disk_link = |dir: &str| device.as_deref().and_then(|d| disk_link_name(Path::new(dir), d));
+    Ok(VolumeInfo {
+        label: disk_link("/dev/disk/by-label").map(|l| unescape_udev_name(&l)),
+        uuid: disk_link("/dev/disk/by-uuid"),
+        is_read_only: mount.options.split(',').any(|o| o == "ro"),
+        is_removable: device.as_deref().is_some_and(is_removable_device),
+        mount_point: mount.mount_point,
+        filesystem: mount.fs_type,
+        total_bytes: blocks * block_size,
+        free_bytes: available * block_size,
+    })
+}
+
+/// Name of the link in `dir` that points at `device`.
+#[cfg(target_os = "linux")]
+fn disk_link_name(dir: &Path, device: &Path) -> Option<String> {
+    fs::read_dir(dir)
+        .ok()?
+        .flatten()
+        .find(|entry| fs::canonicalize(entry.path()).is_ok_and(|target| target == device))
+        .map(|entry| entry.file_name().to_string_lossy().into_owned())
+}
+
+/// udev writes unsafe characters in link names as `\xHH`.
+#[cfg(target_os = "linux")]
+fn unescape_udev_name(name: &str) -> String {
+    let mut out = Vec::with_capacity(name.len());
+    let bytes = name.as_bytes();
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = bytes
+            .get(i + 2..i + 4)
+            .filter(|_| bytes[i..].starts_with(b"\\x"))
+            .and_then(|h| std::str::from_utf8(h).ok())
+            .and_then(|h| u8::from_str_radix(h, 16).ok());
+        match hex {
+            Some(byte) => {
+                out.push(byte);
+                i += 4;
+            }
+            None => {
+                out.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+/// Partitions have no `removable` flag of their own; `..` from the
+/// partition's sysfs entry is the whole disk.
+#[cfg(target_os = "linux")]
+fn is_removable_device(device: &Path) -> bool {
+    let Some(name) = device.file_name() else {
+        return false;
+    };
+    let sys = Path::new("/sys/class/block").join(name);
+    let flag = |p: PathBuf| fs::read_to_string(p).is_ok_and(|v| v.trim() == "1");
+    flag(sys.join("removable"))
+        || flag(sys.join("../removable"))
+        || fs::canonicalize(&sys).is_ok_and(|p| p.to_string_lossy().contains("/usb"))
+}
+
+/// `statfs` for the mount, space and flags; `getattrlist` on the mount point
+/// for the volume name and UUID. Removable means a local volume mounted
+/// under `/Volumes`, which is where macOS puts external disks.
+#[cfg(target_os = "macos")]
+fn volume_info(path: &Path) -> Result<VolumeInfo, AppError> {
+    use std::ffi::CStr;
+
+    let stat = statfs(path)?;
+    let text = |chars: &[libc::c_char]| unsafe { CStr::from_ptr(chars.as_ptr()) }.to_string_lossy().into_owned();
+    let mount_point = text(&stat.f_mntonname);
+    let flags = stat.f_flags as u32;
+    let (label, uuid) = volume_name_and_uuid(&mount_point);
+    Ok(VolumeInfo {
+        label,
+        uuid,
+        filesystem: text(&stat.f_fstypename),
+        is_read_only: flags & libc::MNT_RDONLY as u32 != 0,
+        is_removable: flags & libc::MNT_LOCAL as u32 != 0 && mount_point.starts_with("/Volumes/"),
+        total_bytes: stat.f_blocks * stat.f_bsize as u64,
+        free_bytes: stat.f_bavail * stat.f_bsize as u64,
+        mount_point,
+    })
+}
+
+#[cfg(target_os = "macos")]
+fn volume_name_and_uuid(mount_point: &str) -> (Option<String>, Option<String>) {
+    #[repr(C)]
+    struct AttrList {
+        bitmap_count: u16,
+        reserved: u16,
+        common: u32,
+        volume: u32,
+        directory: u32,
+        file: u32,
+        fork: u32,
+    }
+    extern "C" {
+        fn getattrlist(
+            path: *const libc::c_char,
+            attrs: *mut AttrList,
+            buf: *mut libc::c_void,
+            size: libc::size_t,
+            options: libc::c_ulong,
+        ) -> libc::c_int;
+    }
+    const ATTR_BIT_MAP_COUNT: u16 = 5;
+    const ATTR_VOL_NAME: u32 = 0x0000_2000;
+    const ATTR_VOL_UUID: u32 = 0x0004_0000;
+    const ATTR_VOL_INFO: u32 = 0x8000_0000;
+
+    let Ok(c_path) = std::ffi::CString::new(mount_point) else {
+        return (None, None);
+    };
+    let mut attrs = AttrList {
+        bitmap_count: ATTR_BIT_MAP_COUNT,
+        reserved: 0,
+        common: 0,
+        volume: ATTR_VOL_INFO | ATTR_VOL_NAME | ATTR_VOL_UUID,
+        directory: 0,
+        file: 0,
+        fork: 0,
+    };
+    let mut buf = [0u8; 1024];
+    if unsafe { getattrlist(c_path.as_ptr(), &mut attrs, buf.as_mut_ptr().cast(), buf.len(), 0) } != 0 {
+        return (None, None);
+    }
+
+    // Layout: u32 total length, the name's attrreference_t (offset from the
+    // reference itself, then length including the NUL), then the 16-byte UUID
+    let u32_at = |at: usize| u32::from_ne_bytes(buf[at..at + 4].try_into().unwrap());
+    let (offset, len) = (u32_at(4) as usize, u32_at(8) as usize);
+    let label = buf
+        .get(4 + offset..4 + offset + len)
+        .map(|name| String::from_utf8_lossy(name.split(|&b| b == 0).next().unwrap_or_default()).into_owned())
+        .filter(|name| !name.is_empty());
+    let bytes = &buf[12..28];
+    let uuid = bytes.iter().any(|&b| b != 0).then(|| {
+        let hex = hex::encode_upper(bytes);
+        format!("{}-{}-{}-{}-{}", &hex[..8], &hex[8..12], &hex[12..16], &hex[16..20], &hex[20..])
+    });
+    (label, uuid)
+}
+
+/// `GetVolumeInformationW` on the volume root for label, type and flags;
+/// the UUID is the GUID of the `\\?\Volume{...}\` name.
+#[cfg(windows)]
+fn volume_info(path: &Path) -> Result<VolumeInfo, AppError> {
+    use std::os::windows::ffi::OsStrExt;
+
+    #[link(name = "kernel32")]
+    extern "system" {
+        fn GetVolumePathNameW(file_name: *const u16, volume_path: *mut u16, length: u32) -> i32;
+        fn GetVolumeNameForVolumeMountPointW(mount_point: *const u16, volume_name: *mut u16, length: u32) -> i32;
+        fn GetVolumeInformationW(
+            root: *const u16,
+            volume_name: *mut u16,
+            volume_name_size: u32,
+            serial_number: *mut u32,
+            max_component_length: *mut u32,
+            flags: *mut u32,
+            fs_name: *mut u16,
+            fs_name_size: u32,
+        ) -> i32;
+        fn GetDiskFreeSpaceExW(root: *const u16, available: *mut u64, total: *mut u64, free: *mut u64) -> i32;
+        fn GetDriveTypeW(root: *const u16) -> u32;
+    }
+    const FILE_READ_ONLY_VOLUME: u32 = 0x0008_0000;
+    const DRIVE_REMOVABLE: u32 = 2;
+    const DRIVE_CDROM: u32 = 5;
+
+    let from_wide = |units: &[u16]| {
+        let len = units.iter().position(|&c| c == 0).unwrap_or(units.len());
+        String::from_utf16_lossy(&units[..len])
+    };
+
+    let wide: Vec<u16> = path.as_os_str().encode_wide().chain(std::iter::once(0)).collect();
+    let mut root = vec![0u16; 1024];
+    if unsafe { GetVolumePathNameW(wide.as_ptr(), root.as_mut_ptr(), root.len() as u32) } == 0 {
+        return Err(std::io::Error::last_os_error().into());
+    }
+
+    let mut label = [0u16; 261];
+    let mut fs_name = [0u16; 64];
+    let (mut max_component, mut flags) = (0u32, 0u32);
+    let ok = unsafe {
+        GetVolumeInformationW(
+            root.as_ptr(),
+            label.as_mut_ptr(),
+            label.len() as u32,
+            std::ptr::null_mut(),
+            &mut max_component,
+            &mut flags,
+            fs_name.as_mut_ptr(),
+            fs_name.len() as u32,
+        )
+    };
+    if ok == 0 {
+        return Err(std::io::Error::last_os_error().into());
+    }
+
+    let mut volume_name = [0u16; 64];
+    let uuid = (unsafe {
+        GetVolumeNameForVolumeMountPointW(root.as_ptr(), volume_name.as_mut_ptr(), volume_name.len() as u32)
+    } != 0)
+        .then(|| from_wide(&volume_name))
+        .and_then(|name| Some(name.split_once('{')?.1.split_once('}')?.0.to_string()));
+
+    let (mut available, mut total) = (0u64, 0u64);
+    if unsafe { GetDiskFreeSpaceExW(root.as_ptr(), &mut available, &mut total, std::ptr::null_mut()) } == 0 {
+        return Err(std::io::Error::last_os_error().into());
+    }
+
+    Ok(VolumeInfo {
+        label: Some(from_wide(&label)).filter(|l| !l.is_empty()),
+        uuid,
+        mount_point: from_wide(&root),
+        filesystem: from_wide(&fs_name),
+        is_read_only: flags & FILE_READ_ONLY_VOLUME != 0,
+        is_removable: matches!(unsafe { GetDriveTypeW(root.as_ptr()) }, DRIVE_REMOVABLE | DRIVE_CDROM),
+        total_bytes: total,
+        free_bytes: available,
+    })
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", windows)))]
+fn volume_info(_path: &Path) -> Result<VolumeInfo, AppError> {
+    Err(AppError::Other("Volume info is not supported on this platform".into()))
+}
+
+/// Unmount and eject the removable drive mounted at `mount_point`, then emit
+/// `drive-ejected` with the mount point. `PermissionDenied` means files on
+/// the drive are still open.
+#[tauri::command]
+pub async fn eject_device(app: AppHandle, mount_point: String) -> Result<(), AppError> {
+    let target = mount_point.clone();
+    tokio::task::spawn_blocking(move || eject(&target))
+        .await
+        .map_err(|e| AppError::Other(format!("Task join error: {}", e)))??;
+    VOLUME_CACHE
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .retain(|(path, _, _)| !path.starts_with(&mount_point));
+    let _ = app.emit("drive-ejected", &mount_point);
+    log::info!("Ejected {}", mount_point);
+    Ok(())
+}
+
+/// Run an eject helper, turning a "busy" complaint into `PermissionDenied`.
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+fn run_eject_command(program: &str, args: &[&str], mount_point: &str) -> Result<(), AppError> {
+    let output = std::process::Command::new(program)
+        .args(args)
+        .stdin(std::process::Stdio::null())
+        .output()
+        .map_err(|e| match e.kind() {
+            std::io::ErrorKind::NotFound => AppError::Other(format!("{} not found", program)),
+            _ => AppError::Io(e),
+        })?;
+    if output.status.success() {
+        return Ok(());
+    }
+    let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+    Err(eject_error(&stderr, mount_point))
+}
+
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+fn eject_error(stderr: &str, mount_point: &str) -> AppError {
+    let message = stderr.to_lowercase();
+    // udisks: "target is busy" / DeviceBusy; diskutil: "dissented" by a process
+    if ["busy", "in use", "dissent"].iter().any(|m| message.contains(m)) {
+        AppError::PermissionDenied(format!("{} is in use: {}", mount_point, stderr))
+    } else {
+        AppError::Other(format!("Failed to eject {}: {}", mount_point, stderr))
+    }
+}
+
+/// `udisksctl` unmounts as the desktop user without root, then powers the
+/// drive off. A failed power-off (e.g. another partition is still mounted)
+/// leaves the drive unmounted, which is already safe to unplug.
+#[cfg(target_os = "linux")]
+fn eject(mount_point: &str) -> Result<(), AppError> {
+    let resolved = fs::canonicalize(mount_point).map_err(|_| AppError::NotFound(mount_point.to_string()))?;
+    let mounts = fs::read_to_string("/proc/mounts")?;
+    let mount = find_mount(&mounts, &resolved)
+        .filter(|m| Path::new(&m.mount_point) == resolved && m.device.starts_with("/dev/"))
+        .ok_or_else(|| AppError::InvalidPath(format!("{} is not a mounted drive", mount_point)))?;
+
+    let flags = ["--no-user-interaction", "-b", mount.device.as_str()];
+    run_eject_command("udisksctl", &[&["unmount"], &flags[..]].concat(), mount_point)?;
+    if let Err(e) = run_eject_command("udisksctl", &[&["power-off"], &flags[..]].concat(), mount_point) {
+        log::warn!("Unmounted {} but could not power it off: {}", mount.device, e);
+    }
+    Ok(())
+}
+
+/// `diskutil eject` on the device node, which unmounts every volume of the
+/// disk before ejecting it.
+#[cfg(target_os = "macos")]
+fn eject(mount_point: &str) -> Result<(), AppError> {
+    use std::ffi::CStr;
+
+    let resolved = fs::canonicalize(mount_point).map_err(|_| AppError::NotFound(mount_point.to_string()))?;
+    let stat = statfs(&resolved)?;
+    let on = unsafe { CStr::from_ptr(stat.f_mntonname.as_ptr()) }.to_string_lossy().into_owned();
+    let device = unsafe { CStr::from_ptr(stat.f_mntfromname.as_ptr()) }.to_string_lossy().into_owned();
+    if Path::new(&on) != resolved || !device.starts_with("/dev/") {
+        return Err(AppError::InvalidPath(format!("{} is not a mounted drive", mount_point)));
+    }
+    run_eject_command("diskutil", &["eject", &device], mount_point)
+}
+
+/// Lock and dismount the volume, then `IOCTL_STORAGE_EJECT_MEDIA`. The lock
+/// fails while any file on the volume is open.
+#[cfg(windows)]
+fn eject(mount_point: &str) -> Result<(), AppError> {
+    use std::ffi::c_void;
+    use std::os::windows::ffi::OsStrExt;
+
+    #[link(name = "kernel32")]
+    extern "system" {
+        fn CreateFileW(
+            name: *const u16,
+            access: u32,
+            share_mode: u32,
+            security: *const c_void,
+            disposition: u32,
+            flags: u32,
+            template: *mut c_void,
+        ) -> *mut c_void;
+        fn DeviceIoControl(
+            device: *mut c_void,
+            code: u32,
+            in_buffer: *const c_void,
+            in_size: u32,
+            out_buffer: *mut c_void,
+            out_size: u32,
+            returned: *mut u32,
+            overlapped: *mut c_void,
+        ) -> i32;
+        fn CloseHandle(handle: *mut c_void) -> i32;
+    }
+    const GENERIC_READ: u32 = 0x8000_0000;
+    const GENERIC_WRITE: u32 = 0x4000_0000;
+    const FILE_SHARE_READ_WRITE: u32 = 0x1 | 0x2;
+    const OPEN_EXISTING: u32 = 3;
+    const FSCTL_LOCK_VOLUME: u32 = 0x0009_0018;
+    const FSCTL_DISMOUNT_VOLUME: u32 = 0x0009_0020;
+    const IOCTL_STORAGE_MEDIA_REMOVAL: u32 = 0x002D_4804;
+    const IOCTL_STORAGE_EJECT_MEDIA: u32 = 0x002D_4808;
+
+    let letter = mount_point
+        .chars()
+        .next()
+        .filter(|c| c.is_ascii_alphabetic() && mount_point[1..].trim_end_matches(['\\', '/']) == ":")
+        .ok_or_else(|| AppError::InvalidPath(format!("{} is not a drive root", mount_point)))?;
+    let device: Vec<u16> = std::ffi::OsStr::new(&format!("\\\\.\\{}:", letter))
+        .encode_wide()
+        .chain(std::iter::once(0))
+        .collect();
+    let handle = unsafe {
+        CreateFileW(
+            device.as_ptr(),
+            GENERIC_READ | GENERIC_WRITE,
+            FILE_SHARE_READ_WRITE,
+            std::ptr::null(),
+            OPEN_EXISTING,
+            0,
+            std::ptr::null_mut(),
+        )
+    };
+    if handle as isize == -1 {
+        return Err(std::io::Error::last_os_error().into());
+    }
+
+    let control = |code: u32, input: &[u8]| {
+        let mut returned = 0u32;
+        let ok = unsafe {
+            DeviceIoControl(
+                handle,
+                code,
+                input.as_ptr().cast(),
+                input.len() as u32,
+                std::ptr::null_mut(),
+                0,
+                &mut returned,
+                std::ptr::null_mut(),
+            )
+        };
+        if ok == 0 {
+            Err(std::io::Error::last_os_error())
+        } else {
+            Ok(())
+        }
+    };
+    let result = control(FSCTL_LOCK_VOLUME, &[])
+        .map_err(|_| AppError::PermissionDenied(format!("{} is in use", mount_point)))
+        .and_then(|_| {
+            control(FSCTL_DISMOUNT_VOLUME, &[])?;
+            // PREVENT_MEDIA_REMOVAL { PreventMediaRemoval: FALSE }
+            control(IOCTL_STORAGE_MEDIA_REMOVAL, &[0])?;
+            Ok(control(IOCTL_STORAGE_EJECT_MEDIA, &[])?)
+        });
+    unsafe { CloseHandle(handle) };
+    result
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", windows)))]
+fn eject(_mount_point: &str) -> Result<(), AppError> {
+    Err(AppError::Other("Ejecting drives is not supported on this platform".into()))
+}
+
+/// Name for a `statfs` magic number, for paths missing from `/proc/mounts`.
+#[cfg(target_os = "linux")]
+fn magic_name(magic: i64) -> &'static str {
+    match magic {
+        0xEF53 => "ext4",
+        0x9123_683E => "btrfs",
+        0x5846_5342 => "xfs",
+        0x0102_1994 => "tmpfs",
+        0x4d44 => "vfat",
+        0x2011_BAB0 => "exfat",
+        0x5346_544e => "ntfs",
+        0x6969 => "nfs",
+        0xFF53_4D42 => "cifs",
+        0x794c_7630 => "overlay",
+        0x6573_5546 => "fuse",
+        0x2FC1_2FC1 => "zfs",
+        0x9660 => "iso9660",
+        _ => "unknown",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_mount_fs_type_picks_longest_mount_point() {
+        let mounts = "\
+/dev/sda1 / ext4 rw,relatime 0 0
+tmpfs /tmp tmpfs rw 0 0
+/dev/sdb1 /media/usb\\040stick vfat rw 0 0
+/dev/sdc1 /mnt/data xfs rw 0 0
+/dev/sdc2 /mnt/data btrfs rw 0 0
+";
+        let fs_type = |p: &str| find_mount(mounts, Path::new(p)).map(|m| m.fs_type);
+        assert_eq!(fs_type("/home/user").as_deref(), Some("ext4"));
+        assert_eq!(fs_type("/tmp/x").as_deref(), Some("tmpfs"));
+        assert_eq!(fs_type("/tmpfoo").as_deref(), Some("ext4"));
+        assert_eq!(fs_type("/media/usb stick/a.txt").as_deref(), Some("vfat"));
+        assert_eq!(fs_type("/mnt/data").as_deref(), Some("btrfs"));
+        assert_eq!(unescape_mount_path("a\\134b\\011c"), "a\\b\tc");
+
+        let usb = find_mount(mounts, Path::new("/media/usb stick")).unwrap();
+        assert_eq!(usb.device, "/dev/sdb1");
+        assert_eq!(usb.options, "rw");
+        assert_eq!(unescape_udev_name("My\\x20Disk\\x2f2"), "My Disk/2");
+        assert_eq!(unescape_udev_name("EFI\\x"), "EFI\\x");
+    }
+
+    #[cfg(any(target_os = "linux", target_os = "macos"))]
+    #[test]
+    fn test_capabilities_by_type() {
+        let fat = capabilities("vfat".into(), 255);
+        assert!(!fat.is_case_sensitive && !fat.supports_symlinks && !fat.supports_hardlinks);
+        let ext4 = capabilities("ext4".into(), 255);
+        assert!(ext4.is_case_sensitive && ext4.supports_symlinks && ext4.supports_xattrs);
+
+        let dir = tempfile::tempdir().unwrap();
+        let info = get_filesystem_type(dir.path().to_string_lossy().to_string()).unwrap();
+        assert!(!info.fs_type.is_empty());
+        assert!(info.max_filename_bytes > 0);
+        let missing = dir.path().join("missing").to_string_lossy().to_string();
+        assert!(matches!(get_filesystem_type(missing), Err(AppError::NotFound(_))));
+    }
+
+    #[cfg(any(target_os = "linux", target_os = "macos"))]
+    #[test]
+    fn test_get_volume_info() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().to_string_lossy().to_string();
+        let info = get_volume_info(path.clone()).unwrap();
+        assert!(dir.path().canonicalize().unwrap().starts_with(&info.mount_point));
+        assert!(!info.filesystem.is_empty());
+        assert!(info.free_bytes <= info.total_bytes);
+        assert_eq!(get_volume_info(path).unwrap(), info);
+        let missing = dir.path().join("missing").to_string_lossy().to_string();
+        assert!(matches!(get_volume_info(missing), Err(AppError::NotFound(_))));
+    }
+
+    #[cfg(any(target_os = "linux", target_os = "macos"))]
+    #[test]
+    fn test_eject_rejects_non_mount_points() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(matches!(eject(&dir.path().to_string_lossy()), Err(AppError::InvalidPath(_))));
+        let missing = dir.path().join("missing");
+        assert!(matches!(eject(&missing.to_string_lossy()), Err(AppError::NotFound(_))));
+
+        let busy = eject_error("Error unmounting /dev/sdb1: target is busy", "/media/usb");
+        assert!(matches!(busy, AppError::PermissionDenied(_)));
+        let dissented = eject_error("Unmount of disk4 failed: Unmount was dissented by PID 812", "/Volumes/X");
+        assert!(matches!(dissented, AppError::PermissionDenied(_)));
+        assert!(matches!(eject_error("Object is not a mountable filesystem", "/mnt"), AppError::Other(_)));
+    }
+}
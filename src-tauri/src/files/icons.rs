@@ -0,0 +1,560 @@
+//! System file-type icons as PNG data URIs.
+//!
+//! Icons are looked up through whatever the platform offers without extra
+//! crates: `gio` + the icon theme on Linux, `qlmanage` on macOS and the
+//! shell's `IShellItemImageFactory` on Windows. Results are cached by
+//! extension, since icons are mostly type-based, and a generic icon is drawn
+//! when lookup fails.
+
+use base64::Engine as _;
+use std::collections::HashMap;
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+
+use crate::error::AppError;
+
+/// Default icon edge length in pixels
+const DEFAULT_ICON_SIZE: u32 = 32;
+
+/// Largest icon size served
+const MAX_ICON_SIZE: u32 = 256;
+
+/// Cache of data URIs keyed by (`icon_cache_key`, size)
+static ICON_CACHE: Mutex<Option<HashMap<(String, u32), String>>> = Mutex::new(None);
+
+/// Get the system icon for a file as a PNG data URI.
+/// Falls back to a generic icon when the platform lookup fails.
+#[tauri::command]
+pub async fn get_file_icon(path: String, size: Option<u32>) -> Result<String, AppError> {
+    tokio::task::spawn_blocking(move || get_file_icon_sync(path, size))
+        .await
+        .map_err(|e| AppError::Other(format!("Task join error: {}", e)))?
+}
+
+fn get_file_icon_sync(path: String, size: Option<u32>) -> Result<String, AppError> {
+    let file_path = PathBuf::from(&path);
+    if !file_path.exists() {
+        return Err(AppError::NotFound(path));
+    }
+    let size = size.unwrap_or(DEFAULT_ICON_SIZE).clamp(16, MAX_ICON_SIZE);
+    let key = (icon_cache_key(&file_path), size);
+
+    if let Some(uri) = ICON_CACHE.lock().unwrap().as_ref().and_then(|c| c.get(&key)) {
+        return Ok(uri.clone());
+    }
+
+    let png = match system_icon_png(&file_path, size).and_then(|raw| normalize_png(&raw, size)) {
+        Ok(png) => png,
+        Err(e) => {
+            log::debug!("get_file_icon: falling back to generic icon for {}: {}", path, e);
+            generic_icon_png(size, file_path.is_dir())?
+        }
+    };
+    let uri = format!(
+        "data:image/png;base64,{}",
+        base64::engine::general_purpose::STANDARD.encode(&png)
+    );
+
+    ICON_CACHE
+        .lock()
+        .unwrap()
+        .get_or_insert_with(HashMap::new)
+        .insert(key, uri.clone());
+    Ok(uri)
+}
+
+/// Extensions whose files carry their own icon.
+const PER_FILE_ICON_EXTENSIONS: &[&str] = &["exe", "lnk", "ico"];
+
+/// Icons are mostly per type, so files share a cache entry per lowercase
+/// extension. Files without an extension, whose type comes from their
+/// content, and files that carry their own icon are cached by full path.
+fn icon_cache_key(path: &Path) -> String {
+    if path.is_dir() {
+        return "<dir>".to_string();
+    }
+    match path.extension().map(|e| e.to_string_lossy().to_lowercase()) {
+        Some(ext) if !PER_FILE_ICON_EXTENSIONS.contains(&ext.as_str()) => ext,
+        _ => path.to_string_lossy().to_string(),
+    }
+}
+
+/// Decode whatever the platform returned and re-encode as a `size`×`size`-bounded PNG.
+fn normalize_png(raw: &[u8], size: u32) -> Result<Vec<u8>, AppError> {
+    let img = image::load_from_memory(raw)
+        .map_err(|e| AppError::Other(format!("Failed to decode icon: {}", e)))?;
+    let img = if img.width() != size || img.height() != size {
+        img.resize(size, size, image::imageops::FilterType::Lanczos3)
+    } else {
+        img
+    };
+    encode_png(&img.to_rgba8())
+}
+
+fn encode_png(img: &image::RgbaImage) -> Result<Vec<u8>, AppError> {
+    let mut buffer = Cursor::new(Vec::new());
+    img.write_to(&mut buffer, image::ImageFormat::Png)
+        .map_err(|e| AppError::Other(format!("Failed to encode PNG: {}", e)))?;
+    Ok(buffer.into_inner())
+}
+
+/// Draw a plain page (or folder) icon for when no system icon is available.
+fn generic_icon_png(size: u32, is_dir: bool) -> Result<Vec<u8>, AppError> {
+    let s = size as f32;
+    let (fill, edge) = if is_dir {
+        (image::Rgba([240, 196, 84, 255]), image::Rgba([196, 150, 48, 255]))
+    } else {
+        (image::Rgba([250, 250, 250, 255]), image::Rgba([140, 140, 140, 255]))
+    };
+    let inside = |x: f32, y: f32| {
+        if is_dir {
+            // Body plus a tab on the top left
+            let body = x >= s * 0.08 && x <= s * 0.92 && y >= s * 0.28 && y <= s * 0.84;
+            let tab = x >= s * 0.08 && x <= s * 0.45 && y >= s * 0.18 && y <= s * 0.3;
+            body || tab
+        } else {
+            // Page with the top-right corner folded off
+            let page = x >= s * 0.2 && x <= s * 0.8 && y >= s * 0.08 && y <= s * 0.92;
+            page && x - s * 0.58 <= y - s * 0.08
+        }
+    };
+    let border = (s / 32.0).max(1.0);
+
+    let img = image::RgbaImage::from_fn(size, size, |x, y| {
+        let (x, y) = (x as f32 + 0.5, y as f32 + 0.5);
+        if !inside(x, y) {
+            image::Rgba([0, 0, 0, 0])
+        } else if [(border, 0.0), (-border, 0.0), (0.0, border), (0.0, -border)]
+            .iter()
+            .any(|(dx, dy)| !inside(x + dx, y + dy))
+        {
+            edge
+        } else {
+            fill
+        }
+    });
+    encode_png(&img)
+}
+
+// ─── Platform lookup ────────────────────────────────────────────────────────
+
+#[cfg(target_os = "linux")]
+fn system_icon_png(path: &Path, size: u32) -> Result<Vec<u8>, AppError> {
+    let output = std::process::Command::new("gio")
+        .args(["info", "--attributes=standard::icon"])
+        .arg(path)
+        .output()?;
+    if !output.status.success() {
+        return Err(AppError::Other("gio info failed".into()));
+    }
+
+    let names = parse_gio_icon_names(&String::from_utf8_lossy(&output.stdout));
+    let icon = find_theme_icon(&names, size)
+        .ok_or_else(|| AppError::Other(format!("No theme icon found for {:?}", names)))?;
+
+    if icon.extension().is_some_and(|e| e == "svg") {
+        encode_png(&crate::thumbnails::render_svg(&icon, size, false)?.to_rgba8())
+    } else {
+        Ok(std::fs::read(icon)?)
+    }
+}
+
+/// Extract icon names from `gio info --attributes=standard::icon` output,
+/// e.g. `  standard::icon: text-x-generic, text-x-generic-symbolic`.
+/// Symbolic (monochrome) variants are dropped.
+#[cfg(any(target_os = "linux", test))]
+fn parse_gio_icon_names(output: &str) -> Vec<String> {
+    output
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("standard::icon:"))
+        .map(|names| {
+            names
+                .split(',')
+                .map(|n| n.trim().to_string())
+                .filter(|n| !n.is_empty() && !n.ends_with("-symbolic"))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Current GTK icon theme, if gsettings knows it.
+#[cfg(target_os = "linux")]
+fn current_icon_theme() -> Option<String> {
+    static THEME: OnceLock<Option<String>> = OnceLock::new();
+    THEME
+        .get_or_init(|| {
+            let output = std::process::Command::new("gsettings")
+                .args(["get", "org.gnome.desktop.interface", "icon-theme"])
+                .output()
+                .ok()?;
+            let theme = String::from_utf8_lossy(&output.stdout).trim().trim_matches('\'').to_string();
+            (!theme.is_empty()).then_some(theme)
+        })
+        .clone()
+}
+
+/// Search icon theme directories for the first of `names`, preferring the
+/// exact size, then scalable, then common fixed sizes.
+#[cfg(target_os = "linux")]
+fn find_theme_icon(names: &[String], size: u32) -> Option<PathBuf> {
+    let mut roots: Vec<PathBuf> = Vec::new();
+    if let Some(data) = dirs::data_dir() {
+        roots.push(data.join("icons"));
+    }
+    let data_dirs = std::env::var("XDG_DATA_DIRS").unwrap_or_else(|_| "/usr/local/share:/usr/share".into());
+    roots.extend(data_dirs.split(':').filter(|d| !d.is_empty()).map(|d| Path::new(d).join("icons")));
+
+    let mut themes: Vec<String> = current_icon_theme().into_iter().collect();
+    themes.extend(["Adwaita", "breeze", "Papirus", "hicolor"].map(String::from));
+
+    let sized = format!("{0}x{0}", size);
+    let subdirs: Vec<String> = [
+        format!("{}/mimetypes", sized),
+        format!("{}/places", sized),
+        "scalable/mimetypes".into(),
+        "scalable/places".into(),
+        format!("mimetypes/{}", size),
+        format!("places/{}", size),
+        "48x48/mimetypes".into(),
+        "48x48/places".into(),
+    ]
+    .into();
+
+    for name in names {
+        for theme in &themes {
+            for root in &roots {
+                for sub in &subdirs {
+                    for ext in ["png", "svg"] {
+                        let candidate = root.join(theme).join(sub).join(format!("{}.{}", name, ext));
+                        if candidate.is_file() {
+                            return Some(candidate);
+                        }
+                    }
+                }
+            }
+        }
+        let pixmap = Path::new("/usr/share/pixmaps").join(format!("{}.png", name));
+        if pixmap.is_file() {
+            return Some(pixmap);
+        }
+    }
+    None
+}
+
+/// Quick Look renders the Finder icon in icon mode (`-i`) to a PNG file.
+#[cfg(target_os = "macos")]
+fn system_icon_png(path: &Path, size: u32) -> Result<Vec<u8>, AppError> {
+    let out_dir = std::env::temp_dir().join(format!("tauri-explorer-icons-{}", std::process::id()));
+    std::fs::create_dir_all(&out_dir)?;
+
+    let status = std::process::Command::new("qlmanage")
+        .args(["-t", "-i", "-s", &size.to_string(), "-o"])
+        .arg(&out_dir)
+        .arg(path)
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .status()?;
+
+    let name = path.file_name().unwrap_or_default().to_string_lossy();
+    let png_path = out_dir.join(format!("{}.png", name));
+    let data = std::fs::read(&png_path);
+    let _ = std::fs::remove_file(&png_path);
+    if !status.success() {
+        return Err(AppError::Other("qlmanage failed".into()));
+    }
+    Ok(data?)
+}
+
+/// The shell renders the icon at the requested size through the item's
+/// `IShellItemImageFactory`; the bitmap it returns is read back with `GetDIBits`.
+#[cfg(target_os = "windows")]
+fn system_icon_png(path: &Path, size: u32) -> Result<Vec<u8>, AppError> {
+    use std::ffi::c_void;
+    use std::os::windows::ffi::OsStrExt;
+
+    const COINIT_APARTMENTTHREADED: u32 = 0x2;
+    const SIIGBF_ICONONLY: u32 = 0x4;
+    const DIB_RGB_COLORS: u32 = 0;
+    /// {bcc18b79-ba16-442f-80c4-8a59c30c463b}
+    const IID_ISHELLITEMIMAGEFACTORY: Guid = Guid {
+        data1: 0xbcc1_8b79,
+        data2: 0xba16,
+        data3: 0x442f,
+        data4: [0x80, 0xc4, 0x8a, 0x59, 0xc3, 0x0c, 0x46, 0x3b],
+    };
+
+    #[repr(C)]
+    #[allow(dead_code)] // Read or filled in by Windows
+    struct Guid {
+        data1: u32,
+        data2: u16,
+        data3: u16,
+        data4: [u8; 8],
+    }
+
+    #[repr(C)]
+    #[allow(dead_code)] // Read or filled in by Windows
+    struct Size {
+        cx: i32,
+        cy: i32,
+    }
+
+    /// Leading part of the interface's vtable, in declaration order.
+    #[repr(C)]
+    #[allow(dead_code)] // Read or filled in by Windows
+    struct ImageFactoryVtbl {
+        query_interface: usize,
+        add_ref: usize,
+        release: unsafe extern "system" fn(*mut ImageFactory) -> u32,
+        get_image: unsafe extern "system" fn(*mut ImageFactory, Size, u32, *mut isize) -> i32,
+    }
+
+    #[repr(C)]
+    struct ImageFactory {
+        vtbl: *const ImageFactoryVtbl,
+    }
+
+    #[repr(C)]
+    #[allow(dead_code)] // Read or filled in by Windows
+    struct Bitmap {
+        kind: i32,
+        width: i32,
+        height: i32,
+        width_bytes: i32,
+        planes: u16,
+        bits_pixel: u16,
+        bits: *mut c_void,
+    }
+
+    #[repr(C)]
+    #[allow(dead_code)] // Read or filled in by Windows
+    struct BitmapInfo {
+        size: u32,
+        width: i32,
+        height: i32,
+        planes: u16,
+        bit_count: u16,
+        compression: u32,
+        size_image: u32,
+        x_pels_per_meter: i32,
+        y_pels_per_meter: i32,
+        clr_used: u32,
+        clr_important: u32,
+        colors: [u32; 1],
+    }
+
+    #[link(name = "ole32")]
+    extern "system" {
+        fn CoInitializeEx(reserved: *mut c_void, flags: u32) -> i32;
+        fn CoUninitialize();
+    }
+
+    #[link(name = "shell32")]
+    extern "system" {
+        fn SHCreateItemFromParsingName(
+            path: *const u16,
+            bind_ctx: *mut c_void,
+            riid: *const Guid,
+            out: *mut *mut c_void,
+        ) -> i32;
+    }
+
+    #[link(name = "gdi32")]
+    extern "system" {
+        fn GetObjectW(object: isize, len: i32, out: *mut c_void) -> i32;
+        fn GetDIBits(
+            dc: isize,
+            bitmap: isize,
+            start: u32,
+            lines: u32,
+            bits: *mut c_void,
+            info: *mut BitmapInfo,
+            usage: u32,
+        ) -> i32;
+        fn DeleteObject(object: isize) -> i32;
+    }
+
+    #[link(name = "user32")]
+    extern "system" {
+        fn GetDC(hwnd: isize) -> isize;
+        fn ReleaseDC(hwnd: isize, dc: isize) -> i32;
+    }
+
+    let wide: Vec<u16> = path.as_os_str().encode_wide().chain(std::iter::once(0)).collect();
+    // S_FALSE (already initialized) also needs balancing; a thread already in
+    // the multithreaded apartment fails here but can still use the factory
+    let initialized = unsafe { CoInitializeEx(std::ptr::null_mut(), COINIT_APARTMENTTHREADED) } >= 0;
+
+    let result = (|| {
+        let mut factory: *mut ImageFactory = std::ptr::null_mut();
+        let hr = unsafe {
+            SHCreateItemFromParsingName(
+                wide.as_ptr(),
+                std::ptr::null_mut(),
+                &IID_ISHELLITEMIMAGEFACTORY,
+                (&mut factory as *mut *mut ImageFactory).cast(),
+            )
+        };
+        if hr < 0 || factory.is_null() {
+            return Err(AppError::Other(format!(
+                "SHCreateItemFromParsingName failed: {:#x}",
+                hr
+            )));
+        }
+        let mut hbitmap = 0isize;
+        let hr = unsafe {
+            let vtbl = &*(*factory).vtbl;
+            let hr = (vtbl.get_image)(
+                factory,
+                Size {
+                    cx: size as i32,
+                    cy: size as i32,
+                },
+                SIIGBF_ICONONLY,
+                &mut hbitmap,
+            );
+            (vtbl.release)(factory);
+            hr
+        };
+        if hr < 0 || hbitmap == 0 {
+            return Err(AppError::Other(format!(
+                "IShellItemImageFactory::GetImage failed: {:#x}",
+                hr
+            )));
+        }
+
+        let mut bitmap: Bitmap = unsafe { std::mem::zeroed() };
+        let pixels = unsafe {
+            let read = GetObjectW(
+                hbitmap,
+                std::mem::size_of::<Bitmap>() as i32,
+                (&mut bitmap as *mut Bitmap).cast(),
+            );
+            let (width, height) = (bitmap.width, bitmap.height.abs());
+            let mut pixels = vec![0u8; (width.max(0) * height * 4) as usize];
+            let mut info = BitmapInfo {
+                size: (std::mem::size_of::<BitmapInfo>() - std::mem::size_of::<[u32; 1]>()) as u32,
+                width,
+                // Negative for top-down rows
+                height: -height,
+                planes: 1,
+                bit_count: 32,
+                compression: 0,
+                size_image: 0,
+                x_pels_per_meter: 0,
+                y_pels_per_meter: 0,
+                clr_used: 0,
+                clr_important: 0,
+                colors: [0],
+            };
+            let dc = GetDC(0);
+            let lines = if read == 0 || pixels.is_empty() {
+                0
+            } else {
+                GetDIBits(
+                    dc,
+                    hbitmap,
+                    0,
+                    height as u32,
+                    pixels.as_mut_ptr().cast(),
+                    &mut info,
+                    DIB_RGB_COLORS,
+                )
+            };
+            ReleaseDC(0, dc);
+            DeleteObject(hbitmap);
+            if lines == 0 {
+                return Err(AppError::Other("GetDIBits failed".into()));
+            }
+            pixels
+        };
+
+        // BGRA with premultiplied alpha; an all-zero alpha channel means none
+        let has_alpha = pixels.chunks_exact(4).any(|px| px[3] != 0);
+        let mut rgba = pixels;
+        for px in rgba.chunks_exact_mut(4) {
+            px.swap(0, 2);
+            match px[3] {
+                _ if !has_alpha => px[3] = 255,
+                0 | 255 => {}
+                alpha => {
+                    for channel in &mut px[..3] {
+                        *channel = (u32::from(*channel) * 255 / u32::from(alpha)).min(255) as u8;
+                    }
+                }
+            }
+        }
+        let img = image::RgbaImage::from_raw(bitmap.width as u32, bitmap.height.unsigned_abs(), rgba)
+            .ok_or_else(|| AppError::Other("Unexpected icon bitmap size".into()))?;
+        encode_png(&img)
+    })();
+
+    if initialized {
+        unsafe { CoUninitialize() };
+    }
+    result
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+fn system_icon_png(_path: &Path, _size: u32) -> Result<Vec<u8>, AppError> {
+    Err(AppError::Other("System icons are not supported on this platform".into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_parse_gio_icon_names() {
+        let output = "uri: file:///tmp/a.txt\nattributes:\n  standard::icon: text-plain, text-x-generic, text-plain-symbolic\n";
+        assert_eq!(parse_gio_icon_names(output), vec!["text-plain", "text-x-generic"]);
+        assert!(parse_gio_icon_names("attributes:\n").is_empty());
+    }
+
+    #[test]
+    fn test_icon_cache_key() {
+        let dir = tempdir().unwrap();
+        let file = dir.path().join("Report.PDF");
+        std::fs::write(&file, "").unwrap();
+
+        assert_eq!(icon_cache_key(&file), "pdf");
+        assert_eq!(icon_cache_key(dir.path()), "<dir>");
+
+        // Each of these gets its own entry
+        let mut keys = std::collections::HashSet::new();
+        for name in ["Makefile", "LICENSE", "setup.exe", "other.EXE", "link.lnk", "app.ico"] {
+            let path = dir.path().join(name);
+            std::fs::write(&path, "").unwrap();
+            assert_eq!(icon_cache_key(&path), path.to_string_lossy());
+            assert!(keys.insert(icon_cache_key(&path)));
+        }
+    }
+
+    #[test]
+    fn test_generic_icon_is_valid_png() {
+        for is_dir in [false, true] {
+            let png = generic_icon_png(48, is_dir).unwrap();
+            let img = image::load_from_memory(&png).unwrap();
+            assert_eq!((img.width(), img.height()), (48, 48));
+            // Transparent corner, opaque middle
+            let rgba = img.to_rgba8();
+            assert_eq!(rgba.get_pixel(0, 0)[3], 0);
+            assert_eq!(rgba.get_pixel(24, 30)[3], 255);
+        }
+    }
+
+    #[test]
+    fn test_get_file_icon_returns_png_data_uri() {
+        let dir = tempdir().unwrap();
+        let file = dir.path().join("notes.zzz-unknown");
+        std::fs::write(&file, "hi").unwrap();
+
+        let uri = get_file_icon_sync(file.to_string_lossy().to_string(), Some(24)).unwrap();
+        assert!(uri.starts_with("data:image/png;base64,"));
+
+        let missing = dir.path().join("missing.txt").to_string_lossy().to_string();
+        assert!(matches!(get_file_icon_sync(missing, None), Err(AppError::NotFound(_))));
+    }
+}
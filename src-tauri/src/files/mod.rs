@@ -1,15 +1,35 @@
 //! File operations module for Tauri commands.
 //! Issue: tauri-explorer-nv2y, tauri-explorer-hgt6, tauri-explorer-3b5s, tauri-explorer-9djf.6
 
+pub mod attributes;
+pub mod backups;
+pub mod batch;
+pub mod checksums;
+pub mod compare;
 pub mod dir_listing;
+pub mod dir_stats;
 pub mod external_apps;
 pub mod file_ops;
+pub mod file_type;
+pub mod filename;
+pub mod filesystem;
+pub mod icons;
+pub mod network;
+pub mod path_info;
+pub mod processes;
+pub mod project_type;
+pub mod text_file;
+pub mod xattrs;
 
 use chrono::{DateTime, Local};
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::Path;
 
+use tauri::AppHandle;
+
+use crate::undo::OperationKind;
+
 /// File system entry representation.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FileEntry {
@@ -22,9 +42,12 @@ pub struct FileEntry {
     pub is_symlink: bool,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub symlink_target: Option<String>,
+    /// Levels below the listed root; only set by recursive listings.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub depth: Option<u32>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum FileKind {
     File,
@@ -37,6 +60,39 @@ pub struct DirectoryListing {
     pub path: String,
     pub entries: Vec<FileEntry>,
     pub listing_id: Option<u64>,
+    /// Sum of the file sizes in the listing (directories count as 0).
+    pub total_size: u64,
+    pub total_files: u32,
+    pub total_directories: u32,
+    /// Whether the directory has dotfiles, even if they were filtered out.
+    pub has_hidden_entries: bool,
+}
+
+impl DirectoryListing {
+    /// A listing of `entries` with the totals computed from them.
+    pub(crate) fn new(path: String, entries: Vec<FileEntry>, listing_id: Option<u64>) -> Self {
+        let mut listing = Self {
+            path,
+            entries: Vec::new(),
+            listing_id,
+            total_size: 0,
+            total_files: 0,
+            total_directories: 0,
+            has_hidden_entries: false,
+        };
+        for entry in &entries {
+            match entry.kind {
+                FileKind::File => {
+                    listing.total_size += entry.size;
+                    listing.total_files += 1;
+                }
+                FileKind::Directory => listing.total_directories += 1,
+            }
+            listing.has_hidden_entries |= entry.name.starts_with('.');
+        }
+        listing.entries = entries;
+        listing
+    }
 }
 
 /// Convert metadata to FileEntry, detecting symlinks.
@@ -81,6 +137,7 @@ pub(crate) fn metadata_to_entry(path: &Path, metadata: &fs::Metadata) -> FileEnt
         modified,
         is_symlink,
         symlink_target,
+        depth: None,
     }
 }
 
@@ -93,3 +150,57 @@ pub struct SizeEstimate {
     #[serde(rename = "totalBytes")]
     pub total_bytes: u64,
 }
+
+/// Payload of the `file-operation` event.
+#[derive(Debug, Clone, Serialize)]
+pub struct FileOperationEvent {
+    pub kind: OperationKind,
+    pub source: String,
+    pub destination: Option<String>,
+    /// ISO 8601, local time.
+    pub timestamp: String,
+    /// The created, copied, moved or renamed entry; `None` for deletes.
+    pub entry: Option<FileEntry>,
+}
+
+#[cfg_attr(not(feature = "audit-log"), allow(dead_code))]
+pub(crate) fn operation_event(
+    kind: OperationKind,
+    source: &str,
+    destination: Option<&str>,
+    entry: Option<&FileEntry>,
+) -> FileOperationEvent {
+    FileOperationEvent {
+        kind,
+        source: source.to_string(),
+        destination: destination.map(str::to_string),
+        timestamp: Local::now().format("%Y-%m-%dT%H:%M:%S").to_string(),
+        entry: entry.cloned(),
+    }
+}
+
+/// Emit `file-operation` after a mutating command succeeds, for the
+/// frontend's activity panel. Compiled out without the `audit-log` feature.
+#[cfg(feature = "audit-log")]
+pub(crate) fn emit_operation_event(
+    app: &AppHandle,
+    kind: OperationKind,
+    source: &str,
+    destination: Option<&str>,
+    entry: Option<&FileEntry>,
+) {
+    use tauri::Emitter;
+    if let Err(e) = app.emit("file-operation", operation_event(kind, source, destination, entry)) {
+        log::warn!("Failed to emit file-operation event: {}", e);
+    }
+}
+
+#[cfg(not(feature = "audit-log"))]
+pub(crate) fn emit_operation_event(
+    _app: &AppHandle,
+    _kind: OperationKind,
+    _source: &str,
+    _destination: Option<&str>,
+    _entry: Option<&FileEntry>,
+) {
+}
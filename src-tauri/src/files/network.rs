@@ -0,0 +1,393 @@
+//! Network shares (SMB, NFS, WebDAV) the machine is connected to, for the
+//! "Network" sidebar section.
+//!
+//! Windows lists mapped and remembered connections through `WNetEnumResourceW`.
+//! Linux reads `/proc/mounts` plus the GVFS mounts of the desktop session;
+//! macOS parses the output of `mount`.
+
+use serde::Serialize;
+
+use crate::error::AppError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ShareType {
+    Smb,
+    Nfs,
+    WebDav,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct NetworkShare {
+    /// `\\server\share` for SMB; `server:/export` for NFS; the URL for WebDAV.
+    #[serde(rename = "uncPath")]
+    pub unc_path: String,
+    #[serde(rename = "displayName")]
+    pub display_name: String,
+    pub server: String,
+    #[serde(rename = "shareType")]
+    pub share_type: ShareType,
+    /// Where the share can be browsed locally (mount point or drive letter).
+    #[serde(rename = "mountPoint")]
+    pub mount_point: Option<String>,
+    /// False for remembered connections that aren't currently established
+    /// and for mounts whose server doesn't answer.
+    #[serde(rename = "isConnected")]
+    pub is_connected: bool,
+}
+
+/// Network shares that are mounted or remembered, sorted by server and name.
+#[tauri::command]
+pub async fn list_network_shares() -> Result<Vec<NetworkShare>, AppError> {
+    tokio::task::spawn_blocking(|| {
+        let mut shares = network_shares()?;
+        shares.sort_by(|a, b| (&a.server, &a.display_name).cmp(&(&b.server, &b.display_name)));
+        Ok(shares)
+    })
+    .await
+    .map_err(|e| AppError::Other(format!("Task join error: {}", e)))?
+}
+
+/// A share from a mount's source (`//server/share`, `server:/export`,
+/// `https://host/path`) and filesystem type; `None` for local filesystems.
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+fn share_from_mount(source: &str, mount_point: &str, fs_type: &str) -> Option<NetworkShare> {
+    let share_type = match fs_type {
+        "cifs" | "smb3" | "smbfs" => ShareType::Smb,
+        "nfs" | "nfs4" => ShareType::Nfs,
+        "davfs" | "fuse.davfs" | "webdav" => ShareType::WebDav,
+        _ => return None,
+    };
+    let (server, unc_path, remote_path) = match share_type {
+        ShareType::Smb => {
+            let (host, path) = source.trim_start_matches('/').split_once('/')?;
+            // macOS writes `//[domain;]user@server/share`
+            let host = host.rsplit_once('@').map_or(host, |(_, h)| h);
+            let path = path.trim_end_matches('/');
+            (host.to_string(), format!("\\\\{}\\{}", host, path.replace('/', "\\")), path)
+        }
+        ShareType::Nfs => {
+            let (host, path) = source.split_once(':')?;
+            (host.to_string(), source.to_string(), path.trim_end_matches('/'))
+        }
+        ShareType::WebDav => {
+            let rest = source.split_once("://").map_or(source, |(_, rest)| rest);
+            let (host, path) = rest.split_once('/').unwrap_or((rest, ""));
+            let host = host.rsplit_once('@').map_or(host, |(_, h)| h);
+            (host.to_string(), source.to_string(), path.trim_end_matches('/'))
+        }
+    };
+    let display_name = remote_path.rsplit('/').find(|s| !s.is_empty()).unwrap_or(&server).to_string();
+    Some(NetworkShare {
+        unc_path,
+        display_name,
+        server,
+        share_type,
+        mount_point: Some(mount_point.to_string()),
+        is_connected: true,
+    })
+}
+
+/// How long a mount gets to answer `stat` before it counts as offline.
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+const PROBE_TIMEOUT: std::time::Duration = std::time::Duration::from_millis(800);
+
+/// Mark shares whose mount point doesn't answer within `PROBE_TIMEOUT` as
+/// disconnected. A hard NFS mount can block `stat` indefinitely, so each
+/// probe runs on its own thread, which is left behind if it hangs.
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+fn probe_mounts(shares: &mut [NetworkShare]) {
+    let deadline = std::time::Instant::now() + PROBE_TIMEOUT;
+    let probes: Vec<_> = shares
+        .iter()
+        .map(|share| {
+            let (tx, rx) = std::sync::mpsc::channel();
+            let mount_point = share.mount_point.clone().unwrap_or_default();
+            std::thread::spawn(move || {
+                let _ = tx.send(std::fs::metadata(mount_point).is_ok());
+            });
+            rx
+        })
+        .collect();
+    for (share, probe) in shares.iter_mut().zip(probes) {
+        let remaining = deadline.saturating_duration_since(std::time::Instant::now());
+        share.is_connected = probe.recv_timeout(remaining).unwrap_or(false);
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn network_shares() -> Result<Vec<NetworkShare>, AppError> {
+    use super::filesystem::unescape_mount_path;
+
+    let mounts = std::fs::read_to_string("/proc/mounts")?;
+    let mut shares: Vec<NetworkShare> = mounts
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let (source, mount_point, fs_type) = (fields.next()?, fields.next()?, fields.next()?);
+            share_from_mount(&unescape_mount_path(source), &unescape_mount_path(mount_point), fs_type)
+        })
+        .collect();
+    probe_mounts(&mut shares);
+
+    // GVFS (Nautilus "Connect to Server") mounts live under one FUSE mount
+    let runtime_dir = std::env::var_os("XDG_RUNTIME_DIR")
+        .map(std::path::PathBuf::from)
+        .unwrap_or_else(|| format!("/run/user/{}", unsafe { libc::getuid() }).into());
+    if let Ok(entries) = std::fs::read_dir(runtime_dir.join("gvfs")) {
+        shares.extend(entries.flatten().filter_map(|entry| {
+            let mount_point = entry.path().to_string_lossy().into_owned();
+            gvfs_share(&entry.file_name().to_string_lossy(), &mount_point)
+        }));
+    }
+    Ok(shares)
+}
+
+/// A share from a GVFS mount directory name such as
+/// `smb-share:server=nas,share=media` or `davs:host=dav.example.com,prefix=%2Fremote`.
+#[cfg(target_os = "linux")]
+fn gvfs_share(name: &str, mount_point: &str) -> Option<NetworkShare> {
+    let (scheme, params) = name.split_once(':')?;
+    let param = |key: &str| {
+        params
+            .split(',')
+            .find_map(|p| p.strip_prefix(key)?.strip_prefix('='))
+            .map(|v| v.replace("%2F", "/").replace("%20", " "))
+    };
+    let (server, unc_path, display_name, share_type) = match scheme {
+        "smb-share" => {
+            let (server, share) = (param("server")?, param("share")?);
+            (server.clone(), format!("\\\\{}\\{}", server, share), share, ShareType::Smb)
+        }
+        "dav" | "davs" => {
+            let host = param("host")?;
+            let prefix = param("prefix").unwrap_or_default();
+            let protocol = if scheme == "davs" { "https" } else { "http" };
+            let display_name = prefix.rsplit('/').find(|s| !s.is_empty()).unwrap_or(&host).to_string();
+            (host.clone(), format!("{}://{}{}", protocol, host, prefix), display_name, ShareType::WebDav)
+        }
+        "nfs" => {
+            let host = param("host")?;
+            let prefix = param("prefix").unwrap_or_else(|| "/".into());
+            let display_name = prefix.rsplit('/').find(|s| !s.is_empty()).unwrap_or(&host).to_string();
+            (host.clone(), format!("{}:{}", host, prefix), display_name, ShareType::Nfs)
+        }
+        _ => return None,
+    };
+    Some(NetworkShare {
+        unc_path,
+        display_name,
+        server,
+        share_type,
+        mount_point: Some(mount_point.to_string()),
+        is_connected: true,
+    })
+}
+
+/// `mount` prints `//user@server/share on /Volumes/share (smbfs, nodev, ...)`.
+#[cfg(target_os = "macos")]
+fn network_shares() -> Result<Vec<NetworkShare>, AppError> {
+    let output = std::process::Command::new("/sbin/mount").output()?;
+    let mut shares: Vec<NetworkShare> = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(parse_mount_line)
+        .collect();
+    probe_mounts(&mut shares);
+    Ok(shares)
+}
+
+#[cfg(any(target_os = "macos", all(target_os = "linux", test)))]
+fn parse_mount_line(line: &str) -> Option<NetworkShare> {
+    let (source, rest) = line.split_once(" on ")?;
+    let (mount_point, options) = rest.rsplit_once(" (")?;
+    let fs_type = options.split([',', ')']).next()?.trim();
+    share_from_mount(source, mount_point, fs_type)
+}
+
+/// Current connections (`RESOURCE_CONNECTED`) plus remembered ones
+/// (`RESOURCE_REMEMBERED`); a remembered share missing from the first list
+/// is reported as disconnected.
+#[cfg(windows)]
+fn network_shares() -> Result<Vec<NetworkShare>, AppError> {
+    let connected = win::enumerate(win::RESOURCE_CONNECTED)?;
+    let remembered = win::enumerate(win::RESOURCE_REMEMBERED).unwrap_or_default();
+
+    let mut shares: Vec<NetworkShare> = connected.into_iter().filter_map(|r| win::share(r, true)).collect();
+    for resource in remembered {
+        if !shares.iter().any(|s| s.unc_path.eq_ignore_ascii_case(&resource.remote_name)) {
+            shares.extend(win::share(resource, false));
+        }
+    }
+    Ok(shares)
+}
+
+#[cfg(windows)]
+mod win {
+    use std::ffi::c_void;
+
+    use super::{NetworkShare, ShareType};
+
+    pub const RESOURCE_CONNECTED: u32 = 0x1;
+    pub const RESOURCE_REMEMBERED: u32 = 0x3;
+    const RESOURCETYPE_DISK: u32 = 0x1;
+    const NO_ERROR: u32 = 0;
+    const ERROR_NO_MORE_ITEMS: u32 = 259;
+
+    #[repr(C)]
+    struct NetResourceW {
+        scope: u32,
+        resource_type: u32,
+        display_type: u32,
+        usage: u32,
+        local_name: *mut u16,
+        remote_name: *mut u16,
+        comment: *mut u16,
+        provider: *mut u16,
+    }
+
+    #[link(name = "mpr")]
+    extern "system" {
+        fn WNetOpenEnumW(
+            scope: u32,
+            resource_type: u32,
+            usage: u32,
+            resource: *const NetResourceW,
+            handle: *mut *mut c_void,
+        ) -> u32;
+        fn WNetEnumResourceW(handle: *mut c_void, count: *mut u32, buffer: *mut c_void, size: *mut u32) -> u32;
+        fn WNetCloseEnum(handle: *mut c_void) -> u32;
+    }
+
+    /// The strings of one `NETRESOURCEW`, copied out of the enum buffer.
+    pub struct Resource {
+        local_name: Option<String>,
+        pub remote_name: String,
+        provider: String,
+    }
+
+    unsafe fn wide_ptr(ptr: *const u16) -> Option<String> {
+        if ptr.is_null() {
+            return None;
+        }
+        let len = (0..).take_while(|&i| *ptr.add(i) != 0).count();
+        Some(String::from_utf16_lossy(std::slice::from_raw_parts(ptr, len)))
+    }
+
+    fn check(code: u32) -> std::io::Result<()> {
+        match code {
+            NO_ERROR => Ok(()),
+            code => Err(std::io::Error::from_raw_os_error(code as i32)),
+        }
+    }
+
+    pub fn enumerate(scope: u32) -> std::io::Result<Vec<Resource>> {
+        let mut handle = std::ptr::null_mut();
+        check(unsafe { WNetOpenEnumW(scope, RESOURCETYPE_DISK, 0, std::ptr::null(), &mut handle) })?;
+
+        let mut resources = Vec::new();
+        // u64 elements keep the buffer aligned for the pointers inside it
+        let mut buffer = vec![0u64; 16 * 1024 / 8];
+        let result = loop {
+            let mut count = u32::MAX;
+            let mut size = (buffer.len() * 8) as u32;
+            match unsafe { WNetEnumResourceW(handle, &mut count, buffer.as_mut_ptr().cast(), &mut size) } {
+                NO_ERROR => {
+                    let entries = buffer.as_ptr() as *const NetResourceW;
+                    for i in 0..count as usize {
+                        let entry = unsafe { &*entries.add(i) };
+                        let Some(remote_name) = (unsafe { wide_ptr(entry.remote_name) }) else {
+                            continue;
+                        };
+                        resources.push(Resource {
+                            local_name: unsafe { wide_ptr(entry.local_name) }.filter(|n| !n.is_empty()),
+                            remote_name,
+                            provider: unsafe { wide_ptr(entry.provider) }.unwrap_or_default(),
+                        });
+                    }
+                }
+                ERROR_NO_MORE_ITEMS => break Ok(resources),
+                code => break Err(std::io::Error::from_raw_os_error(code as i32)),
+            }
+        };
+        unsafe { WNetCloseEnum(handle) };
+        result
+    }
+
+    /// WebDAV connections come from the "Web Client Network" provider and
+    /// NFS from "NFS Network"; everything else is treated as SMB.
+    pub fn share(resource: Resource, is_connected: bool) -> Option<NetworkShare> {
+        let provider = resource.provider.to_lowercase();
+        let share_type = if provider.contains("web client") {
+            ShareType::WebDav
+        } else if provider.contains("nfs") {
+            ShareType::Nfs
+        } else {
+            ShareType::Smb
+        };
+        let mut parts = resource.remote_name.trim_start_matches('\\').split('\\').filter(|s| !s.is_empty());
+        let server = parts.next()?.to_string();
+        let display_name = parts.last().map_or_else(|| server.clone(), str::to_string);
+        Some(NetworkShare {
+            unc_path: resource.remote_name,
+            display_name,
+            server,
+            share_type,
+            mount_point: resource.local_name,
+            is_connected,
+        })
+    }
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", windows)))]
+fn network_shares() -> Result<Vec<NetworkShare>, AppError> {
+    Ok(Vec::new())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(any(target_os = "linux", target_os = "macos"))]
+    #[test]
+    fn test_share_from_mount() {
+        let smb = share_from_mount("//bob@nas.local/media/films/", "/mnt/films", "cifs").unwrap();
+        assert_eq!(smb.server, "nas.local");
+        assert_eq!(smb.unc_path, "\\\\nas.local\\media\\films");
+        assert_eq!(smb.display_name, "films");
+        assert_eq!(smb.share_type, ShareType::Smb);
+        assert_eq!(smb.mount_point.as_deref(), Some("/mnt/films"));
+
+        let nfs = share_from_mount("fileserver:/export/home", "/home", "nfs4").unwrap();
+        assert_eq!((nfs.server.as_str(), nfs.unc_path.as_str()), ("fileserver", "fileserver:/export/home"));
+        assert_eq!(nfs.display_name, "home");
+
+        let dav = share_from_mount("https://dav.example.com/remote.php/webdav", "/mnt/dav", "davfs").unwrap();
+        assert_eq!((dav.server.as_str(), dav.share_type), ("dav.example.com", ShareType::WebDav));
+        assert_eq!(dav.display_name, "webdav");
+
+        assert!(share_from_mount("/dev/sda1", "/", "ext4").is_none());
+    }
+
+    #[cfg(any(target_os = "linux", target_os = "macos"))]
+    #[test]
+    fn test_parse_mount_line() {
+        let share = parse_mount_line("//alice@server/Public on /Volumes/Public (smbfs, nodev, nosuid, mounted by alice)")
+            .unwrap();
+        assert_eq!((share.server.as_str(), share.display_name.as_str()), ("server", "Public"));
+        assert_eq!(share.mount_point.as_deref(), Some("/Volumes/Public"));
+        assert!(parse_mount_line("/dev/disk3s1s1 on / (apfs, sealed, local, read-only, journaled)").is_none());
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_gvfs_share() {
+        let smb = gvfs_share("smb-share:domain=corp,server=nas,share=team%20docs,user=bob", "/run/user/1000/gvfs/x")
+            .unwrap();
+        assert_eq!(smb.unc_path, "\\\\nas\\team docs");
+        assert_eq!(smb.display_name, "team docs");
+        let dav = gvfs_share("davs:host=dav.example.com,prefix=%2Fremote%2Ffiles", "/m").unwrap();
+        assert_eq!(dav.unc_path, "https://dav.example.com/remote/files");
+        assert_eq!(dav.display_name, "files");
+        assert!(gvfs_share("mtp:host=phone", "/m").is_none());
+    }
+}
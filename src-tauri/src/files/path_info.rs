@@ -0,0 +1,215 @@
+//! Everything the detail pane shows about one path, in a single call.
+
+use std::fs;
+use std::path::Path;
+
+use chrono::{DateTime, Local};
+use serde::Serialize;
+
+use crate::error::AppError;
+use super::{file_type, metadata_to_entry, xattrs, FileEntry};
+
+/// Result of `get_path_info`. The `FileEntry` fields are flattened in.
+#[derive(Debug, Clone, Serialize)]
+pub struct PathInfo {
+    #[serde(flatten)]
+    pub entry: FileEntry,
+    /// Detected from content for files, `inode/directory` for directories;
+    /// `None` when the file can't be read.
+    #[serde(rename = "mimeType")]
+    pub mime_type: Option<String>,
+    #[serde(rename = "isHidden")]
+    pub is_hidden: bool,
+    /// `rwxr-xr-x` on Unix; attribute letters (`R`, `H`, `S`, `A`) on Windows.
+    pub permissions: String,
+    /// Owning user name (Unix only).
+    pub owner: Option<String>,
+    #[serde(rename = "hardLinkCount")]
+    pub hard_link_count: u64,
+    /// Inode number (Unix only).
+    pub inode: Option<u64>,
+    /// ISO 8601, when the filesystem records a creation time.
+    #[serde(rename = "createdAt")]
+    pub created_at: Option<String>,
+    /// Times the app has opened or listed this path. Operating systems keep
+    /// no such counter, so this comes from the recent list.
+    #[serde(rename = "accessCount")]
+    pub access_count: Option<u32>,
+    /// Extended attribute names, sorted (Unix only); `list_xattrs` has the values.
+    #[serde(rename = "xattrNames")]
+    pub xattr_names: Vec<String>,
+}
+
+/// Stat, content type, permissions and ownership of `path` in one IPC call.
+#[tauri::command]
+pub fn get_path_info(path: String) -> Result<PathInfo, AppError> {
+    let file_path = Path::new(&path);
+    // Broken symlinks still get a result describing the link itself
+    let metadata = fs::metadata(file_path)
+        .or_else(|_| fs::symlink_metadata(file_path))
+        .map_err(|_| AppError::NotFound(path.clone()))?;
+    let entry = metadata_to_entry(file_path, &metadata);
+
+    let mime_type = if metadata.is_dir() {
+        Some("inode/directory".to_string())
+    } else {
+        file_type::detect_file_type(file_path).ok().map(|info| info.mime_type)
+    };
+    let created_at = metadata
+        .created()
+        .ok()
+        .map(|t| DateTime::<Local>::from(t).format("%Y-%m-%dT%H:%M:%S").to_string());
+
+    Ok(PathInfo {
+        is_hidden: is_hidden(file_path, &metadata),
+        permissions: permissions_string(&metadata),
+        owner: owner_name(&metadata),
+        hard_link_count: hard_link_count(&metadata),
+        inode: inode(&metadata),
+        access_count: crate::recent::access_count(&path),
+        xattr_names: xattrs::xattr_names(file_path),
+        mime_type,
+        created_at,
+        entry,
+    })
+}
+
+fn is_hidden(path: &Path, _metadata: &fs::Metadata) -> bool {
+    let hidden = path
+        .file_name()
+        .is_some_and(|n| n.to_string_lossy().starts_with('.'));
+    #[cfg(windows)]
+    let hidden = hidden || {
+        use std::os::windows::fs::MetadataExt;
+        const FILE_ATTRIBUTE_HIDDEN: u32 = 0x2;
+        _metadata.file_attributes() & FILE_ATTRIBUTE_HIDDEN != 0
+    };
+    hidden
+}
+
+#[cfg(unix)]
+fn permissions_string(metadata: &fs::Metadata) -> String {
+    use std::os::unix::fs::PermissionsExt;
+    mode_string(metadata.permissions().mode())
+}
+
+#[cfg(unix)]
+fn mode_string(mode: u32) -> String {
+    const FLAGS: [char; 3] = ['r', 'w', 'x'];
+    (0..9)
+        .map(|i| {
+            if mode & (0o400 >> i) != 0 {
+                FLAGS[i % 3]
+            } else {
+                '-'
+            }
+        })
+        .collect()
+}
+
+#[cfg(windows)]
+fn permissions_string(metadata: &fs::Metadata) -> String {
+    use std::os::windows::fs::MetadataExt;
+    const ATTRIBUTES: [(u32, char); 4] = [(0x1, 'R'), (0x2, 'H'), (0x4, 'S'), (0x20, 'A')];
+    let attributes = metadata.file_attributes();
+    ATTRIBUTES
+        .iter()
+        .filter(|(bit, _)| attributes & bit != 0)
+        .map(|(_, letter)| *letter)
+        .collect()
+}
+
+#[cfg(unix)]
+fn owner_name(metadata: &fs::Metadata) -> Option<String> {
+    use std::ffi::CStr;
+    use std::os::unix::fs::MetadataExt;
+
+    let mut passwd: libc::passwd = unsafe { std::mem::zeroed() };
+    let mut buf = vec![0 as libc::c_char; 1024];
+    let mut result: *mut libc::passwd = std::ptr::null_mut();
+    let rc = unsafe {
+        libc::getpwuid_r(metadata.uid(), &mut passwd, buf.as_mut_ptr(), buf.len(), &mut result)
+    };
+    if rc != 0 || result.is_null() {
+        // No passwd entry (e.g. files from another machine): show the uid
+        return Some(metadata.uid().to_string());
+    }
+    let name = unsafe { CStr::from_ptr(passwd.pw_name) };
+    Some(name.to_string_lossy().into_owned())
+}
+
+#[cfg(not(unix))]
+fn owner_name(_metadata: &fs::Metadata) -> Option<String> {
+    None
+}
+
+#[cfg(unix)]
+fn hard_link_count(metadata: &fs::Metadata) -> u64 {
+    use std::os::unix::fs::MetadataExt;
+    metadata.nlink()
+}
+
+/// std exposes the link count on Windows only on nightly.
+#[cfg(not(unix))]
+fn hard_link_count(_metadata: &fs::Metadata) -> u64 {
+    1
+}
+
+#[cfg(unix)]
+fn inode(metadata: &fs::Metadata) -> Option<u64> {
+    use std::os::unix::fs::MetadataExt;
+    Some(metadata.ino())
+}
+
+#[cfg(not(unix))]
+fn inode(_metadata: &fs::Metadata) -> Option<u64> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_get_path_info_file() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join(".notes.md");
+        fs::write(&path, "# Title\n").unwrap();
+
+        let info = get_path_info(path.to_string_lossy().to_string()).unwrap();
+        assert_eq!(info.entry.name, ".notes.md");
+        assert_eq!(info.entry.size, 8);
+        assert_eq!(info.mime_type.as_deref(), Some("text/plain"));
+        assert!(info.is_hidden);
+        assert_eq!(info.hard_link_count, 1);
+        #[cfg(unix)]
+        {
+            assert_eq!(info.permissions.len(), 9);
+            assert!(info.owner.is_some());
+            assert!(info.inode.is_some());
+        }
+
+        let json = serde_json::to_value(&info).unwrap();
+        assert_eq!(json["name"], ".notes.md");
+        assert_eq!(json["mimeType"], "text/plain");
+    }
+
+    #[test]
+    fn test_get_path_info_directory_and_missing() {
+        let dir = tempdir().unwrap();
+        let info = get_path_info(dir.path().to_string_lossy().to_string()).unwrap();
+        assert_eq!(info.mime_type.as_deref(), Some("inode/directory"));
+
+        let missing = dir.path().join("missing").to_string_lossy().to_string();
+        assert!(matches!(get_path_info(missing), Err(AppError::NotFound(_))));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_mode_string() {
+        assert_eq!(mode_string(0o755), "rwxr-xr-x");
+        assert_eq!(mode_string(0o640), "rw-r-----");
+        assert_eq!(mode_string(0o100644), "rw-r--r--");
+    }
+}
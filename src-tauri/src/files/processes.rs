@@ -0,0 +1,281 @@
+//! Which processes have a file open, so "file in use" errors can name them.
+//!
+//! Windows asks the Restart Manager; macOS and Linux run `lsof`.
+
+use serde::Serialize;
+
+use crate::error::AppError;
+
+/// A process holding a file open.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ProcessInfo {
+    pub pid: u32,
+    /// Application name as the OS reports it (`chrome`, `Microsoft Word`).
+    pub name: String,
+    /// Full path of the executable, when it can be read.
+    pub executable: Option<String>,
+}
+
+/// Processes that have `path` open; empty when none do.
+#[tauri::command]
+pub fn get_process_list_for_file(path: String) -> Result<Vec<ProcessInfo>, AppError> {
+    if std::fs::symlink_metadata(&path).is_err() {
+        return Err(AppError::NotFound(path));
+    }
+    locking_processes(&path)
+}
+
+#[cfg(unix)]
+fn locking_processes(path: &str) -> Result<Vec<ProcessInfo>, AppError> {
+    let output = std::process::Command::new("lsof")
+        .args(["-w", "-F", "pc", "--", path])
+        .stdin(std::process::Stdio::null())
+        .output()
+        .map_err(|e| match e.kind() {
+            std::io::ErrorKind::NotFound => AppError::Other("lsof not found: install lsof to see open files".into()),
+            _ => AppError::Io(e),
+        })?;
+    // lsof exits with 1 and prints nothing when no process has the file open
+    Ok(parse_lsof(&String::from_utf8_lossy(&output.stdout))
+        .into_iter()
+        .map(|(pid, name)| ProcessInfo { pid, name, executable: executable_path(pid) })
+        .collect())
+}
+
+/// `(pid, command)` pairs from `lsof -F pc` output, one per process.
+#[cfg(unix)]
+fn parse_lsof(output: &str) -> Vec<(u32, String)> {
+    let mut processes: Vec<(u32, String)> = Vec::new();
+    let mut current = None;
+    for line in output.lines() {
+        match line.split_at_checked(1) {
+            Some(("p", pid)) => {
+                current = pid.parse().ok().map(|pid| {
+                    processes.iter().position(|(p, _)| *p == pid).unwrap_or_else(|| {
+                        processes.push((pid, String::new()));
+                        processes.len() - 1
+                    })
+                });
+            }
+            Some(("c", command)) => {
+                if let Some(index) = current {
+                    processes[index].1 = command.to_string();
+                }
+            }
+            _ => {}
+        }
+    }
+    processes
+}
+
+#[cfg(target_os = "linux")]
+fn executable_path(pid: u32) -> Option<String> {
+    std::fs::read_link(format!("/proc/{}/exe", pid))
+        .ok()
+        .map(|p| p.to_string_lossy().into_owned())
+}
+
+#[cfg(target_os = "macos")]
+fn executable_path(pid: u32) -> Option<String> {
+    let mut buf = vec![0u8; libc::PROC_PIDPATHINFO_MAXSIZE as usize];
+    let len = unsafe { libc::proc_pidpath(pid as i32, buf.as_mut_ptr().cast(), buf.len() as u32) };
+    (len > 0).then(|| String::from_utf8_lossy(&buf[..len as usize]).into_owned())
+}
+
+#[cfg(all(unix, not(any(target_os = "linux", target_os = "macos"))))]
+fn executable_path(_pid: u32) -> Option<String> {
+    None
+}
+
+#[cfg(windows)]
+fn locking_processes(path: &str) -> Result<Vec<ProcessInfo>, AppError> {
+    let session = restart_manager::Session::for_file(path)?;
+    Ok(session.processes()?)
+}
+
+#[cfg(not(any(unix, windows)))]
+fn locking_processes(_path: &str) -> Result<Vec<ProcessInfo>, AppError> {
+    Ok(Vec::new())
+}
+
+/// Restart Manager (`rstrtmgr.dll`) session over one file: lists the
+/// processes using it and can shut them down.
+#[cfg(windows)]
+pub(crate) mod restart_manager {
+    use std::ffi::c_void;
+    use std::os::windows::ffi::OsStrExt;
+
+    use super::ProcessInfo;
+
+    const CCH_RM_SESSION_KEY: usize = 32;
+    const CCH_RM_MAX_APP_NAME: usize = 255;
+    const CCH_RM_MAX_SVC_NAME: usize = 63;
+    const ERROR_MORE_DATA: u32 = 234;
+    const RM_FORCE_SHUTDOWN: u32 = 0x1;
+    const PROCESS_QUERY_LIMITED_INFORMATION: u32 = 0x1000;
+
+    #[repr(C)]
+    #[derive(Clone, Copy)]
+    #[allow(dead_code)] // only some fields are read; the rest fix the layout
+    struct RmUniqueProcess {
+        process_id: u32,
+        start_time_low: u32,
+        start_time_high: u32,
+    }
+
+    #[repr(C)]
+    #[derive(Clone, Copy)]
+    #[allow(dead_code)]
+    struct RmProcessInfo {
+        process: RmUniqueProcess,
+        app_name: [u16; CCH_RM_MAX_APP_NAME + 1],
+        service_short_name: [u16; CCH_RM_MAX_SVC_NAME + 1],
+        application_type: i32,
+        app_status: u32,
+        ts_session_id: u32,
+        restartable: i32,
+    }
+
+    #[link(name = "rstrtmgr")]
+    extern "system" {
+        fn RmStartSession(session: *mut u32, flags: u32, session_key: *mut u16) -> u32;
+        fn RmEndSession(session: u32) -> u32;
+        fn RmRegisterResources(
+            session: u32,
+            n_files: u32,
+            files: *const *const u16,
+            n_applications: u32,
+            applications: *const c_void,
+            n_services: u32,
+            services: *const *const u16,
+        ) -> u32;
+        fn RmGetList(
+            session: u32,
+            needed: *mut u32,
+            count: *mut u32,
+            infos: *mut RmProcessInfo,
+            reboot_reasons: *mut u32,
+        ) -> u32;
+        fn RmShutdown(session: u32, flags: u32, progress: *const c_void) -> u32;
+    }
+
+    #[link(name = "kernel32")]
+    extern "system" {
+        fn OpenProcess(access: u32, inherit: i32, pid: u32) -> *mut c_void;
+        fn QueryFullProcessImageNameW(process: *mut c_void, flags: u32, name: *mut u16, size: *mut u32) -> i32;
+        fn CloseHandle(handle: *mut c_void) -> i32;
+    }
+
+    fn check(code: u32) -> std::io::Result<()> {
+        match code {
+            0 => Ok(()),
+            code => Err(std::io::Error::from_raw_os_error(code as i32)),
+        }
+    }
+
+    fn from_wide(units: &[u16]) -> String {
+        let len = units.iter().position(|&c| c == 0).unwrap_or(units.len());
+        String::from_utf16_lossy(&units[..len])
+    }
+
+    pub(crate) struct Session(u32);
+
+    impl Session {
+        pub(crate) fn for_file(path: &str) -> std::io::Result<Self> {
+            let mut handle = 0u32;
+            let mut key = [0u16; CCH_RM_SESSION_KEY + 1];
+            check(unsafe { RmStartSession(&mut handle, 0, key.as_mut_ptr()) })?;
+            let session = Session(handle);
+
+            let wide: Vec<u16> = std::ffi::OsStr::new(path).encode_wide().chain(std::iter::once(0)).collect();
+            let files = [wide.as_ptr()];
+            check(unsafe {
+                RmRegisterResources(session.0, 1, files.as_ptr(), 0, std::ptr::null(), 0, std::ptr::null())
+            })?;
+            Ok(session)
+        }
+
+        pub(crate) fn processes(&self) -> std::io::Result<Vec<ProcessInfo>> {
+            let mut infos: Vec<RmProcessInfo> = Vec::new();
+            loop {
+                let (mut needed, mut count, mut reasons) = (0u32, infos.len() as u32, 0u32);
+                let code = unsafe { RmGetList(self.0, &mut needed, &mut count, infos.as_mut_ptr(), &mut reasons) };
+                match code {
+                    // The list can grow between calls; retry with room for it
+                    ERROR_MORE_DATA => infos = vec![unsafe { std::mem::zeroed() }; needed as usize],
+                    code => {
+                        check(code)?;
+                        infos.truncate(count as usize);
+                        break;
+                    }
+                }
+            }
+            Ok(infos
+                .iter()
+                .map(|info| ProcessInfo {
+                    pid: info.process.process_id,
+                    name: from_wide(&info.app_name),
+                    executable: executable_path(info.process.process_id),
+                })
+                .collect())
+        }
+
+        /// Close the processes using the file, killing any that don't exit
+        /// when asked.
+        pub(crate) fn shutdown(&self) -> std::io::Result<()> {
+            check(unsafe { RmShutdown(self.0, RM_FORCE_SHUTDOWN, std::ptr::null()) })
+        }
+    }
+
+    impl Drop for Session {
+        fn drop(&mut self) {
+            unsafe { RmEndSession(self.0) };
+        }
+    }
+
+    fn executable_path(pid: u32) -> Option<String> {
+        let process = unsafe { OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, 0, pid) };
+        if process.is_null() {
+            return None;
+        }
+        let mut buf = [0u16; 1024];
+        let mut size = buf.len() as u32;
+        let ok = unsafe { QueryFullProcessImageNameW(process, 0, buf.as_mut_ptr(), &mut size) };
+        unsafe { CloseHandle(process) };
+        (ok != 0).then(|| String::from_utf16_lossy(&buf[..size as usize]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(unix)]
+    #[test]
+    fn test_parse_lsof() {
+        let output = "p101\ncvim\nf3\np202\ncless\nf4\np101\ncvim\n";
+        assert_eq!(parse_lsof(output), [(101, "vim".to_string()), (202, "less".to_string())]);
+        assert!(parse_lsof("").is_empty());
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_finds_own_process() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("open.txt");
+        std::fs::write(&file, "").unwrap();
+        let path = file.to_string_lossy().to_string();
+
+        let processes = match get_process_list_for_file(path.clone()) {
+            Ok(processes) => processes,
+            Err(AppError::Other(_)) => return, // no lsof
+            Err(e) => panic!("{:?}", e),
+        };
+        assert!(processes.is_empty());
+
+        let _handle = std::fs::File::open(&file).unwrap();
+        let processes = get_process_list_for_file(path).unwrap();
+        assert!(processes.iter().any(|p| p.pid == std::process::id()), "{:?}", processes);
+        assert!(matches!(get_process_list_for_file("/no/such/file".into()), Err(AppError::NotFound(_))));
+    }
+}
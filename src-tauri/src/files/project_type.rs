@@ -0,0 +1,199 @@
+//! Project badges ("Node", "Rust", "Git"...) for a directory, from the
+//! marker files in it.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use regex::Regex;
+use serde::Serialize;
+
+use crate::error::AppError;
+
+/// Config files larger than this are not read for a version.
+const MAX_CONFIG_SIZE: u64 = 1024 * 1024;
+
+/// Marker files per project kind, in report order. A kind's files are
+/// alternatives; the first one present is reported.
+const MARKERS: &[(&str, &[&str])] = &[
+    ("node", &["package.json"]),
+    ("rust", &["Cargo.toml"]),
+    ("python", &["pyproject.toml", "setup.py"]),
+    ("go", &["go.mod"]),
+    ("maven", &["pom.xml"]),
+    ("gradle", &["build.gradle", "build.gradle.kts"]),
+    ("make", &["Makefile"]),
+    ("git", &[".git"]),
+    ("docker", &["docker-compose.yml", "docker-compose.yaml", "compose.yaml", "compose.yml"]),
+];
+
+/// One technology found in a directory.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectTypeHint {
+    /// `node`, `rust`, `python`, `go`, `maven`, `gradle`, `make`, `git` or `docker`.
+    pub kind: String,
+    /// Name of the marker file, e.g. `Cargo.toml`.
+    pub config_file: String,
+    /// The project's version from the config file (the Go version for
+    /// `go.mod`, the file format version for compose files), if it has one.
+    pub version: Option<String>,
+}
+
+/// Every project kind whose marker file is in `path`, e.g. a Rust crate
+/// that is also a Git repo gives `rust` and `git`. Only `path` itself is
+/// checked, not its subdirectories.
+#[tauri::command]
+pub fn detect_project_type(path: String) -> Result<Vec<ProjectTypeHint>, AppError> {
+    let dir = PathBuf::from(&path);
+    let metadata = fs::metadata(&dir).map_err(|_| AppError::NotFound(path.clone()))?;
+    if !metadata.is_dir() {
+        return Err(AppError::InvalidPath(format!("Not a directory: {}", path)));
+    }
+
+    Ok(MARKERS
+        .iter()
+        .filter_map(|(kind, files)| {
+            let file = files.iter().find(|f| fs::symlink_metadata(dir.join(f)).is_ok())?;
+            Some(ProjectTypeHint {
+                kind: kind.to_string(),
+                config_file: file.to_string(),
+                version: read_version(&dir.join(file)),
+            })
+        })
+        .collect())
+}
+
+fn read_version(file: &Path) -> Option<String> {
+    if fs::metadata(file).ok().filter(|m| m.is_file())?.len() > MAX_CONFIG_SIZE {
+        return None;
+    }
+    let content = fs::read_to_string(file).ok()?;
+    let name = file.file_name()?.to_str()?;
+    let version = match name {
+        "package.json" => serde_json::from_str::<serde_json::Value>(&content)
+            .ok()?
+            .get("version")?
+            .as_str()
+            .map(String::from),
+        "Cargo.toml" => toml_string(&content, "package", "version")
+            .or_else(|| toml_string(&content, "workspace.package", "version")),
+        "pyproject.toml" => toml_string(&content, "project", "version")
+            .or_else(|| toml_string(&content, "tool.poetry", "version")),
+        "pom.xml" => {
+            // The project's own version comes before any dependency's
+            let content = Regex::new(r"(?s)<parent>.*?</parent>").ok()?.replace(&content, "");
+            let end = ["<dependencies>", "<dependencyManagement>", "<build>", "<profiles>"]
+                .iter()
+                .filter_map(|tag| content.find(tag))
+                .min()
+                .unwrap_or(content.len());
+            capture(r"<version>\s*([^<\s]+)\s*</version>", &content[..end])
+        }
+        "go.mod" => capture(r"(?m)^go\s+(\S+)", &content),
+        "setup.py" | "build.gradle" | "build.gradle.kts" => {
+            capture(r#"(?m)^\s*version\s*=\s*['"]([^'"]+)['"]"#, &content)
+        }
+        _ if name.contains("compose") => capture(r#"(?m)^version:\s*['"]?([^'"\s]+)"#, &content),
+        _ => None,
+    };
+    version.filter(|v| !v.is_empty())
+}
+
+/// First capture group of `pattern` in `content`.
+fn capture(pattern: &str, content: &str) -> Option<String> {
+    let re = Regex::new(pattern).ok()?;
+    Some(re.captures(content)?.get(1)?.as_str().to_string())
+}
+
+/// A string `key = "value"` in `[section]` of a TOML file. Not a TOML
+/// parser, just enough for the version fields of Cargo.toml and
+/// pyproject.toml; `version.workspace = true` and the like give `None`.
+fn toml_string(content: &str, section: &str, key: &str) -> Option<String> {
+    let mut in_section = false;
+    for line in content.lines().map(str::trim) {
+        if line.starts_with('[') {
+            in_section = line.trim_matches(['[', ']']).trim() == section;
+            continue;
+        }
+        let Some((name, value)) = line.split_once('=').filter(|_| in_section) else {
+            continue;
+        };
+        if name.trim() == key {
+            let value = value.trim();
+            let quote = value.chars().next().filter(|c| *c == '"' || *c == '\'')?;
+            return value[1..].split(quote).next().map(String::from);
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_detect_project_type() {
+        let dir = tempdir().unwrap();
+        let root = dir.path();
+        fs::write(root.join("package.json"), r#"{ "name": "app", "version": "1.2.3" }"#).unwrap();
+        fs::write(
+            root.join("Cargo.toml"),
+            "[workspace]\nmembers = [\"a\"]\n\n[workspace.package]\nversion = \"0.4.0\" # shared\n",
+        )
+        .unwrap();
+        fs::write(root.join("setup.py"), "setup(\n    name='pkg',\n    version='2.0',\n)\n").unwrap();
+        fs::write(root.join("go.mod"), "module example.com/app\n\ngo 1.22\n").unwrap();
+        fs::write(
+            root.join("pom.xml"),
+            "<project><parent><version>9.9</version></parent><version>3.1</version>\
+             <dependencies><dependency><version>1.0</version></dependency></dependencies></project>",
+        )
+        .unwrap();
+        fs::write(root.join("Makefile"), "all:\n").unwrap();
+        fs::create_dir(root.join(".git")).unwrap();
+
+        let hints = detect_project_type(root.to_string_lossy().to_string()).unwrap();
+        let found: Vec<(&str, &str, Option<&str>)> = hints
+            .iter()
+            .map(|h| (h.kind.as_str(), h.config_file.as_str(), h.version.as_deref()))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                ("node", "package.json", Some("1.2.3")),
+                ("rust", "Cargo.toml", Some("0.4.0")),
+                ("python", "setup.py", Some("2.0")),
+                ("go", "go.mod", Some("1.22")),
+                ("maven", "pom.xml", Some("3.1")),
+                ("make", "Makefile", None),
+                ("git", ".git", None),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_toml_string() {
+        let cargo = "[package]\nname = \"app\"\nversion.workspace = true\n\n[dependencies]\nversion = \"1\"\n";
+        assert_eq!(toml_string(cargo, "package", "name").as_deref(), Some("app"));
+        assert_eq!(toml_string(cargo, "package", "version"), None);
+        let poetry = "[tool.poetry]\nversion = '0.1.0'\n";
+        assert_eq!(toml_string(poetry, "tool.poetry", "version").as_deref(), Some("0.1.0"));
+    }
+
+    #[test]
+    fn test_detect_project_type_errors() {
+        let dir = tempdir().unwrap();
+        assert!(detect_project_type(dir.path().to_string_lossy().to_string()).unwrap().is_empty());
+        let file = dir.path().join("file.txt");
+        fs::write(&file, "").unwrap();
+        assert!(matches!(
+            detect_project_type(file.to_string_lossy().to_string()),
+            Err(AppError::InvalidPath(_))
+        ));
+        assert!(matches!(
+            detect_project_type(dir.path().join("missing").to_string_lossy().to_string()),
+            Err(AppError::NotFound(_))
+        ));
+    }
+}
@@ -0,0 +1,670 @@
+//! Reading and writing small text files for in-app viewing and editing,
+//! preserving the file's encoding across a round trip.
+
+use std::fs;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+
+use base64::Engine as _;
+use encoding_rs::{Encoding, UTF_16BE, UTF_16LE, UTF_8, WINDOWS_1252};
+use serde::Serialize;
+
+use crate::error::AppError;
+use super::{metadata_to_entry, FileEntry};
+
+/// Default read limit for `read_file_text`.
+const DEFAULT_READ_LIMIT: usize = 1_048_576;
+
+/// Files above this size are only overwritten with `force`.
+const MAX_UNFORCED_WRITE_SIZE: u64 = 10 * 1024 * 1024;
+
+/// `get_file_line_count` refuses files over this size.
+const MAX_LINE_COUNT_SIZE: u64 = 1024 * 1024 * 1024;
+
+/// Read size for `get_file_line_count`; a multiple of every code unit width.
+const LINE_COUNT_CHUNK: usize = 64 * 1024;
+
+/// Largest chunk `read_file_chunk` returns.
+const MAX_CHUNK_SIZE: usize = 16 * 1024 * 1024;
+
+/// Bytes `detect_file_encoding` looks at.
+pub(crate) const ENCODING_SAMPLE_SIZE: u64 = 8 * 1024;
+
+/// Result of `read_file_text`.
+#[derive(Debug, Clone, Serialize)]
+pub struct TextFileContent {
+    pub content: String,
+    /// Encoding name (`UTF-8`, `UTF-16LE`, `windows-1252`, …).
+    pub encoding: String,
+    #[serde(rename = "sizeBytes")]
+    pub size_bytes: u64,
+    #[serde(rename = "isTruncated")]
+    pub is_truncated: bool,
+}
+
+/// Result of `read_file_chunk`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FileChunk {
+    /// The chunk as text, or base64 when `encoding` is `binary`.
+    pub data: String,
+    /// `utf-8` or `binary`.
+    pub encoding: String,
+    /// Where the chunk starts. Text chunks skip up to 3 bytes past the
+    /// requested offset rather than start inside a character.
+    pub offset: u64,
+    /// Bytes covered by `data`; the next chunk starts at `offset + bytesRead`.
+    #[serde(rename = "bytesRead")]
+    pub bytes_read: usize,
+    #[serde(rename = "totalSize")]
+    pub total_size: u64,
+    #[serde(rename = "isEnd")]
+    pub is_end: bool,
+}
+
+/// Result of `get_file_line_count`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LineCountResult {
+    /// Line breaks, plus one for a last line without one.
+    #[serde(rename = "lineCount")]
+    pub line_count: u64,
+    #[serde(rename = "byteSize")]
+    pub byte_size: u64,
+    /// From the BOM if there is one, else guessed from the first 64 KB.
+    #[serde(rename = "estimatedEncoding")]
+    pub estimated_encoding: String,
+    #[serde(rename = "hasBom")]
+    pub has_bom: bool,
+}
+
+/// Result of `detect_file_encoding`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct EncodingDetectionResult {
+    /// Encoding name, as `read_file_text` reports it.
+    pub encoding: String,
+    /// 1.0 for a BOM or pure ASCII, lower for a guess, 0.0 for broken UTF-8.
+    pub confidence: f32,
+    #[serde(rename = "hasBom")]
+    pub has_bom: bool,
+    /// Whether the sample is valid UTF-8 (after any UTF-8 BOM).
+    #[serde(rename = "isUtf8")]
+    pub is_utf8: bool,
+}
+
+/// A byte order mark at the start of `bytes`: encoding name, BOM length,
+/// code unit width and whether units are big-endian. UTF-32 is checked
+/// first since its little-endian BOM starts with UTF-16LE's.
+fn unicode_bom(bytes: &[u8]) -> Option<(&'static str, usize, usize, bool)> {
+    match bytes {
+        [0xFF, 0xFE, 0x00, 0x00, ..] => Some(("UTF-32LE", 4, 4, false)),
+        [0x00, 0x00, 0xFE, 0xFF, ..] => Some(("UTF-32BE", 4, 4, true)),
+        [0xEF, 0xBB, 0xBF, ..] => Some(("UTF-8", 3, 1, false)),
+        [0xFF, 0xFE, ..] => Some(("UTF-16LE", 2, 2, false)),
+        [0xFE, 0xFF, ..] => Some(("UTF-16BE", 2, 2, true)),
+        _ => None,
+    }
+}
+
+/// Detect the encoding of `bytes`: a byte order mark wins, then valid UTF-8
+/// (allowing a character cut off at the end when `bytes` is only the start
+/// of the file), else windows-1252, which decodes and re-encodes any byte
+/// sequence unchanged. Returns the encoding and the BOM length.
+pub(crate) fn detect_encoding(bytes: &[u8], partial: bool) -> (&'static Encoding, usize) {
+    if let Some((encoding, bom_len)) = Encoding::for_bom(bytes) {
+        return (encoding, bom_len);
+    }
+    match utf8_error(bytes, partial) {
+        None => (UTF_8, 0),
+        Some(_) => (WINDOWS_1252, 0),
+    }
+}
+
+/// How to decode a file for content search, from its leading `bytes`: the
+/// encoding, BOM length and a 0-1 confidence. A BOM or valid UTF-8 is
+/// certain and broken UTF-8 scores 0; other text is scored as windows-1252.
+pub(crate) fn guess_encoding(bytes: &[u8], partial: bool) -> (&'static Encoding, usize, f32) {
+    if let Some((encoding, bom_len)) = Encoding::for_bom(bytes) {
+        return (encoding, bom_len, 1.0);
+    }
+    match utf8_error(bytes, partial) {
+        None => (UTF_8, 0, 1.0),
+        Some(_) if has_multibyte_utf8(bytes) => (UTF_8, 0, 0.0),
+        Some(_) => (WINDOWS_1252, 0, windows_1252_confidence(bytes)),
+    }
+}
+
+/// Share of the non-ASCII bytes in `bytes` that are printable windows-1252
+/// characters (letters like `é`, `€`, curly quotes) rather than the C1
+/// control codes legacy text never uses. Samples that are mostly non-ASCII
+/// are unlikely to be Western European text and score at most half.
+fn windows_1252_confidence(bytes: &[u8]) -> f32 {
+    let high: Vec<u8> = bytes.iter().copied().filter(|b| !b.is_ascii()).collect();
+    if high.is_empty() {
+        return 1.0;
+    }
+    let (decoded, _) = WINDOWS_1252.decode_without_bom_handling(&high);
+    let printable = decoded.chars().filter(|c| !c.is_control()).count();
+    let confidence = printable as f32 / high.len() as f32;
+    if high.len() * 3 > bytes.len() { confidence / 2.0 } else { confidence }
+}
+
+/// Classify a file's leading `bytes` (`partial` when the file goes on).
+/// A BOM is trusted; otherwise valid UTF-8 is UTF-8, text with multi-byte
+/// UTF-8 sequences but also invalid bytes is reported as broken UTF-8, and
+/// anything else is the windows-1252 fallback `detect_encoding` uses.
+fn classify_encoding(bytes: &[u8], partial: bool) -> EncodingDetectionResult {
+    if let Some((name, bom_len, _, _)) = unicode_bom(bytes) {
+        let is_utf8 = name == "UTF-8" && utf8_error(&bytes[bom_len..], partial).is_none();
+        return EncodingDetectionResult {
+            encoding: name.to_string(),
+            confidence: if name == "UTF-8" && !is_utf8 { 0.0 } else { 1.0 },
+            has_bom: true,
+            is_utf8,
+        };
+    }
+
+    let (encoding, confidence, is_utf8) = match utf8_error(bytes, partial) {
+        None if bytes.is_ascii() => ("UTF-8", 1.0, true),
+        // Legacy 8-bit text is almost never valid multi-byte UTF-8 by accident
+        None => ("UTF-8", 0.99, true),
+        Some(_) if has_multibyte_utf8(bytes) => ("UTF-8", 0.0, false),
+        Some(_) => (detect_encoding(bytes, partial).0.name(), 0.5, false),
+    };
+    EncodingDetectionResult { encoding: encoding.to_string(), confidence, has_bom: false, is_utf8 }
+}
+
+/// The first invalid UTF-8 sequence in `bytes`, ignoring a character cut
+/// off at the end of a `partial` sample.
+fn utf8_error(bytes: &[u8], partial: bool) -> Option<std::str::Utf8Error> {
+    match std::str::from_utf8(bytes) {
+        Ok(_) => None,
+        Err(e) if partial && e.error_len().is_none() => None,
+        Err(e) => Some(e),
+    }
+}
+
+fn has_multibyte_utf8(bytes: &[u8]) -> bool {
+    bytes.utf8_chunks().any(|chunk| !chunk.valid().is_ascii())
+}
+
+/// Encode `content`, failing rather than substituting characters the
+/// encoding can't represent. encoding_rs has no UTF-16 encoder, so those
+/// are handled here.
+fn encode(content: &str, encoding: &'static Encoding, with_bom: bool) -> Result<Vec<u8>, AppError> {
+    let mut out = Vec::with_capacity(content.len() + 3);
+    if encoding == UTF_16LE || encoding == UTF_16BE {
+        let big_endian = encoding == UTF_16BE;
+        let units = std::iter::once('\u{FEFF}')
+            .filter(|_| with_bom)
+            .chain(content.chars())
+            .collect::<String>();
+        for unit in units.encode_utf16() {
+            out.extend_from_slice(&if big_endian { unit.to_be_bytes() } else { unit.to_le_bytes() });
+        }
+        return Ok(out);
+    }
+
+    if with_bom && encoding == UTF_8 {
+        out.extend_from_slice(b"\xEF\xBB\xBF");
+    }
+    let (bytes, _, had_errors) = encoding.encode(content);
+    if had_errors {
+        return Err(AppError::Other(format!(
+            "Content has characters that can't be saved as {}",
+            encoding.name()
+        )));
+    }
+    out.extend_from_slice(&bytes);
+    Ok(out)
+}
+
+/// Read a text file for display or editing. Only the first `max_bytes`
+/// (default 1 MB) are read; `isTruncated` reports whether there was more.
+#[tauri::command]
+pub fn read_file_text(path: String, max_bytes: Option<usize>) -> Result<TextFileContent, AppError> {
+    let file_path = PathBuf::from(&path);
+    if !file_path.exists() {
+        return Err(AppError::NotFound(path));
+    }
+    if file_path.is_dir() {
+        return Err(AppError::InvalidPath(format!("{} is a directory", path)));
+    }
+
+    let size_bytes = fs::metadata(&file_path)?.len();
+    let limit = max_bytes.unwrap_or(DEFAULT_READ_LIMIT);
+    let mut bytes = Vec::new();
+    fs::File::open(&file_path)?
+        .take(limit as u64)
+        .read_to_end(&mut bytes)?;
+
+    let is_truncated = size_bytes > bytes.len() as u64;
+    let (encoding, bom_len) = detect_encoding(&bytes, is_truncated);
+    if bom_len == 0 && bytes.contains(&0) {
+        return Err(AppError::Io(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            "File contains NUL bytes (likely binary)",
+        )));
+    }
+    let (content, _) = encoding.decode_without_bom_handling(&bytes[bom_len..]);
+
+    Ok(TextFileContent {
+        content: content.into_owned(),
+        encoding: encoding.name().to_string(),
+        size_bytes,
+        is_truncated,
+    })
+}
+
+/// Guess the encoding of a text file from its first 8 KB: BOM, UTF-8
+/// validity, then the same windows-1252 fallback `read_file_text` decodes with.
+#[tauri::command]
+pub fn detect_file_encoding(path: String) -> Result<EncodingDetectionResult, AppError> {
+    let file_path = PathBuf::from(&path);
+    let metadata = fs::metadata(&file_path).map_err(|_| AppError::NotFound(path.clone()))?;
+    if metadata.is_dir() {
+        return Err(AppError::InvalidPath(format!("{} is a directory", path)));
+    }
+
+    let mut sample = Vec::new();
+    fs::File::open(&file_path)?
+        .take(ENCODING_SAMPLE_SIZE)
+        .read_to_end(&mut sample)?;
+    Ok(classify_encoding(&sample, metadata.len() > sample.len() as u64))
+}
+
+/// Save `content` to `path` atomically (temp file + rename), creating the
+/// file if needed. Without `encoding`, an existing file keeps its detected
+/// encoding and byte order mark; new files are UTF-8. Existing files over
+/// 10 MB are refused unless `force` is set.
+#[tauri::command]
+pub fn write_file_text(
+    path: String,
+    content: String,
+    encoding: Option<String>,
+    force: Option<bool>,
+) -> Result<FileEntry, AppError> {
+    let file_path = PathBuf::from(&path);
+    if file_path.is_dir() {
+        return Err(AppError::InvalidPath(format!("{} is a directory", path)));
+    }
+
+    let existing = fs::metadata(&file_path).ok();
+    if let Some(meta) = &existing {
+        if meta.len() > MAX_UNFORCED_WRITE_SIZE && !force.unwrap_or(false) {
+            return Err(AppError::Other(format!(
+                "Refusing to overwrite {} ({} bytes); pass force to write files over {} bytes",
+                path,
+                meta.len(),
+                MAX_UNFORCED_WRITE_SIZE
+            )));
+        }
+    }
+
+    let (detected, with_bom) = match &existing {
+        Some(meta) => {
+            let mut head = Vec::new();
+            fs::File::open(&file_path)?.take(DEFAULT_READ_LIMIT as u64).read_to_end(&mut head)?;
+            let (encoding, bom_len) = detect_encoding(&head, meta.len() > head.len() as u64);
+            (encoding, bom_len > 0)
+        }
+        None => (UTF_8, false),
+    };
+    let target = match encoding.as_deref() {
+        Some(label) => Encoding::for_label(label.trim().as_bytes())
+            .ok_or_else(|| AppError::Other(format!("Unknown encoding: {}", label)))?,
+        None => detected,
+    };
+    // UTF-16 always gets a BOM: without one it can't be told apart from binary
+    let is_utf16 = target == UTF_16LE || target == UTF_16BE;
+    let bytes = encode(&content, target, is_utf16 || (with_bom && target == detected))?;
+
+    write_atomic(&file_path, &bytes)?;
+    log::debug!("Saved {} bytes as {}", bytes.len(), target.name());
+    let metadata = fs::metadata(&file_path)?;
+    Ok(metadata_to_entry(&file_path, &metadata))
+}
+
+/// Read `length` bytes (at most 16 MB) of a file from `offset`, e.g. to
+/// page through a large log from its end. A chunk that is UTF-8 apart from
+/// characters cut off at its edges comes back as text, trimmed to whole
+/// characters; anything else (or text with NUL bytes) is base64 with
+/// `encoding: "binary"`. An offset past the end gives an empty chunk.
+#[tauri::command]
+pub fn read_file_chunk(path: String, offset: u64, length: usize) -> Result<FileChunk, AppError> {
+    let file_path = PathBuf::from(&path);
+    let metadata = fs::metadata(&file_path).map_err(|_| AppError::NotFound(path.clone()))?;
+    if metadata.is_dir() {
+        return Err(AppError::InvalidPath(format!("{} is a directory", path)));
+    }
+
+    let mut file = fs::File::open(&file_path)?;
+    file.seek(SeekFrom::Start(offset))?;
+    let mut bytes = Vec::new();
+    file.take(length.min(MAX_CHUNK_SIZE) as u64).read_to_end(&mut bytes)?;
+    Ok(chunk_from_bytes(&bytes, offset, metadata.len()))
+}
+
+fn chunk_from_bytes(bytes: &[u8], offset: u64, total_size: u64) -> FileChunk {
+    let at_end = offset + bytes.len() as u64 >= total_size;
+    let (encoding, data, start, end) = match utf8_span(bytes, at_end) {
+        Some((start, end)) => {
+            let text = std::str::from_utf8(&bytes[start..end]).unwrap_or_default();
+            ("utf-8", text.to_string(), start, end)
+        }
+        None => {
+            let data = base64::engine::general_purpose::STANDARD.encode(bytes);
+            ("binary", data, 0, bytes.len())
+        }
+    };
+    let offset = offset + start as u64;
+    FileChunk {
+        data,
+        encoding: encoding.to_string(),
+        offset,
+        bytes_read: end - start,
+        total_size,
+        is_end: offset + (end - start) as u64 >= total_size,
+    }
+}
+
+/// The byte range of `bytes` that is whole UTF-8 characters, once up to 3
+/// leading continuation bytes and (unless the chunk ends the file) a
+/// truncated last character are cut off. `None` if it isn't text.
+fn utf8_span(bytes: &[u8], at_end: bool) -> Option<(usize, usize)> {
+    let start = bytes.iter().take(3).take_while(|&&b| b & 0xC0 == 0x80).count();
+    let end = match std::str::from_utf8(&bytes[start..]) {
+        Ok(_) => bytes.len(),
+        Err(e) if e.error_len().is_none() && !at_end => start + e.valid_up_to(),
+        Err(_) => return None,
+    };
+    let text = &bytes[start..end];
+    (!text.contains(&0) && (end > start || bytes.is_empty())).then_some((start, end))
+}
+
+/// Count the lines of a text file without loading it, reading 64 KB at a
+/// time. Files over 1 GB are refused.
+#[tauri::command]
+pub async fn get_file_line_count(path: String) -> Result<LineCountResult, AppError> {
+    tokio::task::spawn_blocking(move || count_lines(Path::new(&path)))
+        .await
+        .map_err(|e| AppError::Other(format!("Task join error: {}", e)))?
+}
+
+fn count_lines(path: &Path) -> Result<LineCountResult, AppError> {
+    let metadata = fs::metadata(path).map_err(|_| AppError::NotFound(path.to_string_lossy().to_string()))?;
+    if metadata.is_dir() {
+        return Err(AppError::InvalidPath(format!("{} is a directory", path.to_string_lossy())));
+    }
+    if metadata.len() > MAX_LINE_COUNT_SIZE {
+        return Err(AppError::Io(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            format!("File too large: {} bytes (limit: {})", metadata.len(), MAX_LINE_COUNT_SIZE),
+        )));
+    }
+
+    let mut file = fs::File::open(path)?;
+    let mut buf = vec![0u8; LINE_COUNT_CHUNK];
+    // Fill the first chunk completely so the BOM and encoding guess see it all
+    let mut filled = 0;
+    while filled < buf.len() {
+        match file.read(&mut buf[filled..])? {
+            0 => break,
+            n => filled += n,
+        }
+    }
+    let head = &buf[..filled];
+    let bom = unicode_bom(head);
+    let (estimated_encoding, bom_len, width, big_endian) = match bom {
+        Some(bom) => bom,
+        None => (detect_encoding(head, metadata.len() > filled as u64).0.name(), 0, 1, false),
+    };
+
+    // Newlines are counted per code unit, so UTF-16/32 characters whose
+    // encoding merely contains a 0x0A byte don't count
+    let newline: Vec<u8> = match (width, big_endian) {
+        (1, _) => vec![b'\n'],
+        (2, false) => vec![b'\n', 0],
+        (2, true) => vec![0, b'\n'],
+        (_, false) => vec![b'\n', 0, 0, 0],
+        (_, true) => vec![0, 0, 0, b'\n'],
+    };
+    let mut line_count = 0u64;
+    let mut last_unit = Vec::new();
+    let mut chunk = &buf[bom_len..filled];
+    let mut carry = Vec::new();
+    loop {
+        // A short read can split a code unit; carry the tail into the next chunk
+        carry.extend_from_slice(chunk);
+        let whole = carry.len() - carry.len() % width;
+        line_count += if width == 1 {
+            carry.iter().filter(|&&b| b == b'\n').count() as u64
+        } else {
+            carry[..whole].chunks_exact(width).filter(|unit| *unit == newline.as_slice()).count() as u64
+        };
+        if whole > 0 {
+            last_unit = carry[whole - width..whole].to_vec();
+        }
+        carry.drain(..whole);
+
+        let n = file.read(&mut buf)?;
+        if n == 0 {
+            break;
+        }
+        chunk = &buf[..n];
+    }
+    if !last_unit.is_empty() && last_unit != newline {
+        line_count += 1;
+    }
+
+    Ok(LineCountResult {
+        line_count,
+        byte_size: metadata.len(),
+        estimated_encoding: estimated_encoding.to_string(),
+        has_bom: bom.is_some(),
+    })
+}
+
+/// Write via a sibling temp file and rename, so readers never see a partial file.
+fn write_atomic(path: &Path, bytes: &[u8]) -> Result<(), AppError> {
+    let name = path
+        .file_name()
+        .ok_or_else(|| AppError::InvalidPath(path.to_string_lossy().to_string()))?;
+    let tmp = path.with_file_name(format!(".{}.edit-tmp", name.to_string_lossy()));
+
+    fs::write(&tmp, bytes)?;
+    if let Ok(meta) = fs::metadata(path) {
+        let _ = fs::set_permissions(&tmp, meta.permissions());
+    }
+    if let Err(e) = fs::rename(&tmp, path) {
+        let _ = fs::remove_file(&tmp);
+        return Err(e.into());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn path_str(path: &Path) -> String {
+        path.to_string_lossy().to_string()
+    }
+
+    #[test]
+    fn test_count_lines() {
+        let dir = tempdir().unwrap();
+        let count = |name: &str, bytes: &[u8]| {
+            let path = dir.path().join(name);
+            fs::write(&path, bytes).unwrap();
+            count_lines(&path).unwrap()
+        };
+
+        let plain = count("plain.rs", b"fn main() {}\n\nlast");
+        assert_eq!((plain.line_count, plain.byte_size), (3, 18));
+        assert_eq!(plain.estimated_encoding, "UTF-8");
+        assert!(!plain.has_bom);
+        assert_eq!(count("empty.txt", b"").line_count, 0);
+        assert_eq!(count("trailing.txt", b"a\nb\n").line_count, 2);
+
+        let bom = count("bom.txt", b"\xEF\xBB\xBFa\r\nb\r\n");
+        assert_eq!((bom.line_count, bom.has_bom), (2, true));
+
+        // U+0A0A (Gurmukhi) has 0x0A bytes but isn't a line break
+        let utf16: Vec<u8> = [0xFEFFu16, 0x0A0A, 0x000A, 0x0062]
+            .iter()
+            .flat_map(|u| u.to_le_bytes())
+            .collect();
+        let utf16 = count("utf16.txt", &utf16);
+        assert_eq!((utf16.line_count, utf16.estimated_encoding.as_str()), (2, "UTF-16LE"));
+
+        let utf32: Vec<u8> = [0xFEFFu32, 0x61, 0x0A].iter().flat_map(|u| u.to_be_bytes()).collect();
+        let utf32 = count("utf32.txt", &utf32);
+        assert_eq!((utf32.line_count, utf32.estimated_encoding.as_str()), (1, "UTF-32BE"));
+
+        let long = count("long.txt", "line\n".repeat(40_000).as_bytes());
+        assert_eq!(long.line_count, 40_000);
+        assert_eq!(count("latin.txt", b"caf\xE9").estimated_encoding, "windows-1252");
+    }
+
+    #[test]
+    fn test_detect_file_encoding() {
+        let dir = tempdir().unwrap();
+        let detect = |name: &str, bytes: &[u8]| {
+            let path = dir.path().join(name);
+            fs::write(&path, bytes).unwrap();
+            detect_file_encoding(path_str(&path)).unwrap()
+        };
+        let summary = |r: EncodingDetectionResult| (r.encoding, r.confidence, r.has_bom, r.is_utf8);
+
+        assert_eq!(summary(detect("ascii.txt", b"plain")), ("UTF-8".into(), 1.0, false, true));
+        assert_eq!(summary(detect("utf8.txt", "naïve".as_bytes())), ("UTF-8".into(), 0.99, false, true));
+        assert_eq!(summary(detect("bom.txt", b"\xEF\xBB\xBFok")), ("UTF-8".into(), 1.0, true, true));
+        assert_eq!(summary(detect("bad-bom.txt", b"\xEF\xBB\xBFbad \xFF")), ("UTF-8".into(), 0.0, true, false));
+        assert_eq!(summary(detect("mixed.txt", b"na\xC3\xAFve caf\xE9")), ("UTF-8".into(), 0.0, false, false));
+        assert_eq!(summary(detect("latin.txt", b"caf\xE9")), ("windows-1252".into(), 0.5, false, false));
+        assert_eq!(summary(detect("utf16.txt", b"\xFF\xFEh\x00")), ("UTF-16LE".into(), 1.0, true, false));
+
+        // A character split by the 8 KB sample boundary isn't an error
+        let mut long = vec![b'a'; ENCODING_SAMPLE_SIZE as usize - 1];
+        long.extend_from_slice("é and more".as_bytes());
+        assert!(detect("long.txt", &long).is_utf8);
+
+        assert!(matches!(detect_file_encoding(path_str(&dir.path().join("missing"))), Err(AppError::NotFound(_))));
+    }
+
+    #[test]
+    fn test_guess_encoding() {
+        let guess = |bytes: &[u8]| {
+            let (encoding, bom_len, confidence) = guess_encoding(bytes, false);
+            (encoding.name(), bom_len, confidence)
+        };
+        assert_eq!(guess("naïve".as_bytes()), ("UTF-8", 0, 1.0));
+        assert_eq!(guess(b"\xFF\xFEh\x00"), ("UTF-16LE", 2, 1.0));
+        assert_eq!(guess(b"na\xC3\xAFve caf\xE9"), ("UTF-8", 0, 0.0));
+        assert_eq!(guess(b"caf\xE9 cr\xE8me \x93quoted\x94"), ("windows-1252", 0, 1.0));
+        // Half the high bytes are C1 controls
+        assert_eq!(guess(b"caf\xE9 and \x81 and more text"), ("windows-1252", 0, 0.5));
+        assert!(guess(b"\xE9\xE8\xE0\xF9").2 <= 0.5);
+    }
+
+    #[test]
+    fn test_round_trip_preserves_windows_1252() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("latin.txt");
+        // "café" in windows-1252: é is a lone 0xE9, invalid as UTF-8
+        fs::write(&path, b"caf\xE9").unwrap();
+
+        let read = read_file_text(path_str(&path), None).unwrap();
+        assert_eq!(read.content, "café");
+        assert_eq!(read.encoding, "windows-1252");
+
+        write_file_text(path_str(&path), "café au lait".into(), None, None).unwrap();
+        assert_eq!(fs::read(&path).unwrap(), b"caf\xE9 au lait");
+    }
+
+    #[test]
+    fn test_round_trip_preserves_utf16_bom() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("wide.txt");
+        fs::write(&path, b"\xFF\xFEh\0i\0").unwrap();
+
+        let read = read_file_text(path_str(&path), None).unwrap();
+        assert_eq!((read.content.as_str(), read.encoding.as_str()), ("hi", "UTF-16LE"));
+
+        write_file_text(path_str(&path), "ok".into(), None, None).unwrap();
+        assert_eq!(fs::read(&path).unwrap(), b"\xFF\xFEo\0k\0");
+    }
+
+    #[test]
+    fn test_read_truncates_and_rejects_binary() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("long.txt");
+        fs::write(&path, "ü".repeat(10)).unwrap();
+
+        let read = read_file_text(path_str(&path), Some(5)).unwrap();
+        assert!(read.is_truncated);
+        assert_eq!(read.size_bytes, 20);
+        assert_eq!(read.encoding, "UTF-8");
+        assert!(read.content.starts_with("üü"));
+
+        let binary = dir.path().join("blob");
+        fs::write(&binary, [0u8, 1, 2]).unwrap();
+        assert!(read_file_text(path_str(&binary), None).is_err());
+    }
+
+    #[test]
+    fn test_read_file_chunk() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("log.txt");
+        fs::write(&path, "aü\nline two\n").unwrap();
+
+        let chunk = read_file_chunk(path_str(&path), 0, 4).unwrap();
+        assert_eq!((chunk.data.as_str(), chunk.encoding.as_str()), ("aü\n", "utf-8"));
+        assert_eq!((chunk.offset, chunk.bytes_read, chunk.total_size), (0, 4, 13));
+        assert!(!chunk.is_end);
+
+        // Starting inside "ü" skips its second byte; ending inside it drops it
+        let chunk = read_file_chunk(path_str(&path), 2, 100).unwrap();
+        assert_eq!((chunk.data.as_str(), chunk.offset), ("\nline two\n", 3));
+        assert!(chunk.is_end);
+        let chunk = read_file_chunk(path_str(&path), 0, 2).unwrap();
+        assert_eq!((chunk.data.as_str(), chunk.bytes_read), ("a", 1));
+
+        let past_end = read_file_chunk(path_str(&path), 50, 10).unwrap();
+        assert_eq!((past_end.data.as_str(), past_end.bytes_read), ("", 0));
+        assert!(past_end.is_end);
+
+        let binary = dir.path().join("blob");
+        fs::write(&binary, [0xFFu8, 0, 1]).unwrap();
+        let chunk = read_file_chunk(path_str(&binary), 0, 10).unwrap();
+        assert_eq!((chunk.data.as_str(), chunk.encoding.as_str(), chunk.bytes_read), ("/wAB", "binary", 3));
+        assert!(matches!(read_file_chunk(path_str(&dir.path().join("missing")), 0, 1), Err(AppError::NotFound(_))));
+    }
+
+    #[test]
+    fn test_write_new_file_and_explicit_encoding() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("new.txt");
+        let entry = write_file_text(path_str(&path), "naïve".into(), None, None).unwrap();
+        assert_eq!(entry.size, "naïve".len() as u64);
+
+        write_file_text(path_str(&path), "naïve".into(), Some("latin1".into()), None).unwrap();
+        assert_eq!(fs::read(&path).unwrap(), b"na\xEFve");
+
+        let unmappable = write_file_text(path_str(&path), "日本".into(), Some("windows-1252".into()), None);
+        assert!(unmappable.is_err());
+        assert!(write_file_text(path_str(&path), "x".into(), Some("klingon".into()), None).is_err());
+    }
+
+    #[test]
+    fn test_write_refuses_large_files_without_force() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("big.log");
+        let file = fs::File::create(&path).unwrap();
+        file.set_len(MAX_UNFORCED_WRITE_SIZE + 1).unwrap();
+
+        assert!(write_file_text(path_str(&path), "small".into(), None, None).is_err());
+        write_file_text(path_str(&path), "small".into(), None, Some(true)).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "small");
+    }
+}
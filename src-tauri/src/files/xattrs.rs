@@ -0,0 +1,349 @@
+//! Extended attributes (SELinux labels, Finder comments, `user.*` metadata),
+//! macOS Finder tags stored in one, and NTFS alternate data streams, their
+//! nearest Windows equivalent.
+//!
+//! Extended attributes are Unix only, via the `xattr` crate; symlinks are
+//! followed. On Windows those commands are no-ops, and likewise the tag
+//! commands off macOS and the stream listing off Windows, so the frontend
+//! can call them all unconditionally.
+
+use std::collections::HashMap;
+#[cfg(unix)]
+use std::path::Path;
+
+use serde::Serialize;
+
+use crate::error::AppError;
+
+/// Finder tags, as a binary plist array of `"<name>\n<color index>"` strings.
+#[cfg(target_os = "macos")]
+const TAGS_XATTR: &str = "com.apple.metadata:_kMDItemUserTags";
+
+/// Finder's label colors, by color index.
+#[cfg(target_os = "macos")]
+const TAG_COLORS: [&str; 8] = ["None", "Gray", "Green", "Purple", "Blue", "Yellow", "Red", "Orange"];
+
+/// A named NTFS data stream, from `get_ntfs_alternate_data_streams`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct AdsEntry {
+    /// Stream name without the `:` prefix and `:$DATA` suffix, e.g. `Zone.Identifier`.
+    pub name: String,
+    pub size: u64,
+}
+
+/// Longest attribute name Linux and macOS accept (`XATTR_NAME_MAX`).
+const MAX_NAME_BYTES: usize = 255;
+
+/// Reject names the OS would refuse, before making the call.
+fn validate_name(name: &str) -> Result<(), AppError> {
+    if name.is_empty() || name.contains('\0') || name.len() > MAX_NAME_BYTES {
+        return Err(AppError::InvalidPath(format!(
+            "Invalid extended attribute name: {:?} (must be 1-{} bytes, no NUL)",
+            name, MAX_NAME_BYTES
+        )));
+    }
+    Ok(())
+}
+
+/// Attribute values are bytes. Text (ignoring the C-string NUL some writers,
+/// such as SELinux, append) comes back as is; anything else as `0x` + hex.
+#[cfg(unix)]
+fn display_value(value: &[u8]) -> String {
+    let text = value.strip_suffix(&[0]).unwrap_or(value);
+    match std::str::from_utf8(text) {
+        Ok(text) => text.to_string(),
+        Err(_) => format!("0x{}", hex::encode(value)),
+    }
+}
+
+#[cfg(unix)]
+fn xattr_error(path: &str, name: &str, e: std::io::Error) -> AppError {
+    match e.raw_os_error() {
+        Some(libc::ENOTSUP) => {
+            AppError::Other(format!("Extended attributes are not supported for {}", path))
+        }
+        #[cfg(target_os = "linux")]
+        Some(libc::ENODATA) => AppError::NotFound(format!("{}: {}", path, name)),
+        #[cfg(target_os = "macos")]
+        Some(libc::ENOATTR) => AppError::NotFound(format!("{}: {}", path, name)),
+        _ if e.kind() == std::io::ErrorKind::NotFound => AppError::NotFound(path.to_string()),
+        _ if e.kind() == std::io::ErrorKind::PermissionDenied => AppError::PermissionDenied(path.to_string()),
+        _ => AppError::Io(e),
+    }
+}
+
+/// Names of `path`'s extended attributes, sorted. For the detail pane;
+/// empty when there are none or they can't be read.
+#[cfg(unix)]
+pub(crate) fn xattr_names(path: &Path) -> Vec<String> {
+    let mut names: Vec<String> = match xattr::list_deref(path) {
+        Ok(names) => names.map(|n| n.to_string_lossy().into_owned()).collect(),
+        Err(_) => Vec::new(),
+    };
+    names.sort();
+    names
+}
+
+#[cfg(not(unix))]
+pub(crate) fn xattr_names(_path: &std::path::Path) -> Vec<String> {
+    Vec::new()
+}
+
+/// All extended attributes of `path` as name → value.
+#[cfg(unix)]
+#[tauri::command]
+pub fn list_xattrs(path: String) -> Result<HashMap<String, String>, AppError> {
+    let names = xattr::list_deref(&path).map_err(|e| xattr_error(&path, "", e))?;
+    let mut attributes = HashMap::new();
+    for name in names {
+        // Attributes can vanish or be unreadable (e.g. `trusted.*` without
+        // CAP_SYS_ADMIN) between listing and reading; skip those
+        if let Ok(Some(value)) = xattr::get_deref(&path, &name) {
+            attributes.insert(name.to_string_lossy().into_owned(), display_value(&value));
+        }
+    }
+    Ok(attributes)
+}
+
+/// Set extended attribute `name` on `path` to `value` (stored as UTF-8).
+/// On Linux the name needs a namespace, usually `user.`.
+#[cfg(unix)]
+#[tauri::command]
+pub fn set_xattr(path: String, name: String, value: String) -> Result<(), AppError> {
+    validate_name(&name)?;
+    xattr::set_deref(&path, &name, value.as_bytes()).map_err(|e| xattr_error(&path, &name, e))?;
+    log::info!("Set xattr {} on {}", name, path);
+    Ok(())
+}
+
+/// Remove extended attribute `name` from `path`; `NotFound` if it isn't set.
+#[cfg(unix)]
+#[tauri::command]
+pub fn remove_xattr(path: String, name: String) -> Result<(), AppError> {
+    validate_name(&name)?;
+    xattr::remove_deref(&path, &name).map_err(|e| xattr_error(&path, &name, e))?;
+    log::info!("Removed xattr {} from {}", name, path);
+    Ok(())
+}
+
+#[cfg(not(unix))]
+#[tauri::command]
+pub fn list_xattrs(_path: String) -> Result<HashMap<String, String>, AppError> {
+    Ok(HashMap::new())
+}
+
+#[cfg(not(unix))]
+#[tauri::command]
+pub fn set_xattr(_path: String, name: String, _value: String) -> Result<(), AppError> {
+    validate_name(&name)
+}
+
+#[cfg(not(unix))]
+#[tauri::command]
+pub fn remove_xattr(_path: String, name: String) -> Result<(), AppError> {
+    validate_name(&name)
+}
+
+/// Finder tag names of `path` (`Red`, `Work`, …), in Finder's order.
+#[cfg(target_os = "macos")]
+#[tauri::command]
+pub fn get_macos_tags(path: String) -> Result<Vec<String>, AppError> {
+    let Some(data) = xattr::get_deref(&path, TAGS_XATTR).map_err(|e| xattr_error(&path, TAGS_XATTR, e))? else {
+        return Ok(Vec::new());
+    };
+    let entries: Vec<String> = plist::from_bytes(&data)
+        .map_err(|e| AppError::Other(format!("Invalid Finder tags on {}: {}", path, e)))?;
+    Ok(entries.iter().map(|entry| tag_name(entry).to_string()).collect())
+}
+
+/// Replace the Finder tags of `path`. The seven color names get their label
+/// color; other names are plain tags. An empty list removes all tags.
+#[cfg(target_os = "macos")]
+#[tauri::command]
+pub fn set_macos_tags(path: String, tags: Vec<String>) -> Result<(), AppError> {
+    if tags.is_empty() {
+        return match xattr::remove_deref(&path, TAGS_XATTR) {
+            Ok(()) => Ok(()),
+            Err(e) if e.raw_os_error() == Some(libc::ENOATTR) => Ok(()),
+            Err(e) => Err(xattr_error(&path, TAGS_XATTR, e)),
+        };
+    }
+    let entries: Vec<String> = tags.iter().map(|tag| tag_entry(tag)).collect();
+    let mut data = Vec::new();
+    plist::to_writer_binary(&mut data, &entries)
+        .map_err(|e| AppError::Other(format!("Failed to encode Finder tags: {}", e)))?;
+    xattr::set_deref(&path, TAGS_XATTR, &data).map_err(|e| xattr_error(&path, TAGS_XATTR, e))?;
+    log::info!("Set {} Finder tags on {}", tags.len(), path);
+    Ok(())
+}
+
+/// `"Red\n6"` → `"Red"`; entries without a color are just the name.
+#[cfg(target_os = "macos")]
+fn tag_name(entry: &str) -> &str {
+    entry.split_once('\n').map_or(entry, |(name, _)| name)
+}
+
+/// Plist entry for a tag: color names (any case) carry their color index.
+#[cfg(target_os = "macos")]
+fn tag_entry(tag: &str) -> String {
+    match TAG_COLORS.iter().skip(1).position(|color| color.eq_ignore_ascii_case(tag)) {
+        Some(index) => format!("{}\n{}", TAG_COLORS[index + 1], index + 1),
+        None => tag.to_string(),
+    }
+}
+
+#[cfg(not(target_os = "macos"))]
+#[tauri::command]
+pub fn get_macos_tags(_path: String) -> Result<Vec<String>, AppError> {
+    Ok(Vec::new())
+}
+
+#[cfg(not(target_os = "macos"))]
+#[tauri::command]
+pub fn set_macos_tags(_path: String, _tags: Vec<String>) -> Result<(), AppError> {
+    Ok(())
+}
+
+/// Alternate data streams of `path` (the browser's `Zone.Identifier`
+/// download marker, for one), via `FindFirstStreamW`. The file's unnamed
+/// main stream is left out.
+#[cfg(windows)]
+#[tauri::command]
+pub fn get_ntfs_alternate_data_streams(path: String) -> Result<Vec<AdsEntry>, AppError> {
+    use std::ffi::c_void;
+    use std::os::windows::ffi::OsStrExt;
+
+    #[repr(C)]
+    struct Win32FindStreamData {
+        stream_size: i64,
+        stream_name: [u16; 260 + 36], // MAX_PATH + 36
+    }
+
+    #[link(name = "kernel32")]
+    extern "system" {
+        fn FindFirstStreamW(file_name: *const u16, info_level: u32, data: *mut c_void, flags: u32) -> *mut c_void;
+        fn FindNextStreamW(find: *mut c_void, data: *mut c_void) -> i32;
+        fn FindClose(find: *mut c_void) -> i32;
+    }
+    const FIND_STREAM_INFO_STANDARD: u32 = 0;
+    const INVALID_HANDLE_VALUE: *mut c_void = -1isize as *mut c_void;
+    const ERROR_HANDLE_EOF: i32 = 38;
+
+    let wide: Vec<u16> = std::ffi::OsStr::new(&path).encode_wide().chain(std::iter::once(0)).collect();
+    let mut data = Win32FindStreamData { stream_size: 0, stream_name: [0; 296] };
+    let data_ptr = &mut data as *mut Win32FindStreamData as *mut c_void;
+
+    let find = unsafe { FindFirstStreamW(wide.as_ptr(), FIND_STREAM_INFO_STANDARD, data_ptr, 0) };
+    if find == INVALID_HANDLE_VALUE {
+        let e = std::io::Error::last_os_error();
+        return match e.raw_os_error() {
+            // No streams at all, as for most directories
+            Some(ERROR_HANDLE_EOF) => Ok(Vec::new()),
+            _ if e.kind() == std::io::ErrorKind::NotFound => Err(AppError::NotFound(path)),
+            _ if e.kind() == std::io::ErrorKind::PermissionDenied => Err(AppError::PermissionDenied(path)),
+            _ => Err(AppError::Io(e)),
+        };
+    }
+
+    let mut streams = Vec::new();
+    loop {
+        let len = data.stream_name.iter().position(|&c| c == 0).unwrap_or(data.stream_name.len());
+        let raw = String::from_utf16_lossy(&data.stream_name[..len]);
+        if let Some(name) = ads_name(&raw) {
+            streams.push(AdsEntry { name: name.to_string(), size: data.stream_size.max(0) as u64 });
+        }
+        if unsafe { FindNextStreamW(find, data_ptr) } == 0 {
+            break;
+        }
+    }
+    unsafe { FindClose(find) };
+    Ok(streams)
+}
+
+/// `:Zone.Identifier:$DATA` → `Zone.Identifier`; None for the unnamed
+/// main stream (`::$DATA`).
+#[cfg(windows)]
+fn ads_name(raw: &str) -> Option<&str> {
+    let name = raw.strip_prefix(':')?;
+    let name = name.strip_suffix(":$DATA").unwrap_or(name);
+    (!name.is_empty()).then_some(name)
+}
+
+#[cfg(not(windows))]
+#[tauri::command]
+pub fn get_ntfs_alternate_data_streams(_path: String) -> Result<Vec<AdsEntry>, AppError> {
+    Ok(Vec::new())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_name() {
+        assert!(validate_name("user.comment").is_ok());
+        assert!(validate_name(&"a".repeat(MAX_NAME_BYTES)).is_ok());
+        assert!(matches!(validate_name(""), Err(AppError::InvalidPath(_))));
+        assert!(matches!(validate_name("user.a\0b"), Err(AppError::InvalidPath(_))));
+        assert!(matches!(validate_name(&"a".repeat(MAX_NAME_BYTES + 1)), Err(AppError::InvalidPath(_))));
+    }
+
+    #[cfg(target_os = "macos")]
+    #[test]
+    fn test_macos_tags_round_trip() {
+        assert_eq!(tag_entry("red"), "Red\n6");
+        assert_eq!(tag_entry("Work"), "Work");
+        assert_eq!(tag_name("Orange\n7"), "Orange");
+
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("tagged.txt");
+        std::fs::write(&file, "").unwrap();
+        let path = file.to_string_lossy().to_string();
+
+        assert!(get_macos_tags(path.clone()).unwrap().is_empty());
+        set_macos_tags(path.clone(), vec!["Red".into(), "Work".into()]).unwrap();
+        assert_eq!(get_macos_tags(path.clone()).unwrap(), ["Red", "Work"]);
+        set_macos_tags(path.clone(), Vec::new()).unwrap();
+        assert!(get_macos_tags(path).unwrap().is_empty());
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn test_ads_name() {
+        assert_eq!(ads_name(":Zone.Identifier:$DATA"), Some("Zone.Identifier"));
+        assert_eq!(ads_name("::$DATA"), None);
+        assert_eq!(ads_name("no-colon"), None);
+    }
+
+    #[cfg(not(windows))]
+    #[test]
+    fn test_no_alternate_data_streams_off_windows() {
+        assert_eq!(get_ntfs_alternate_data_streams("/tmp".into()).unwrap(), Vec::new());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_set_list_remove_xattr() {
+        assert_eq!(display_value(b"label\0"), "label");
+        assert_eq!(display_value(&[0xff, 0x00, 0x01]), "0xff0001");
+
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("a.txt");
+        std::fs::write(&file, "").unwrap();
+        let path = file.to_string_lossy().to_string();
+
+        match set_xattr(path.clone(), "user.comment".into(), "hello".into()) {
+            Ok(()) => {}
+            // tmpfs before Linux 6.6 and some overlay setups refuse user xattrs
+            Err(AppError::Other(_)) | Err(AppError::PermissionDenied(_)) => return,
+            Err(e) => panic!("set_xattr failed: {:?}", e),
+        }
+        let attributes = list_xattrs(path.clone()).unwrap();
+        assert_eq!(attributes.get("user.comment").map(String::as_str), Some("hello"));
+        assert!(xattr_names(&file).contains(&"user.comment".to_string()));
+
+        remove_xattr(path.clone(), "user.comment".into()).unwrap();
+        assert!(!list_xattrs(path.clone()).unwrap().contains_key("user.comment"));
+        assert!(matches!(remove_xattr(path, "user.comment".into()), Err(AppError::NotFound(_))));
+    }
+}
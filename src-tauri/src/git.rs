@@ -0,0 +1,225 @@
+//! Git status of files, for overlaying indicators on the file list.
+//!
+//! Shells out to `git status --porcelain=v1 -z` rather than linking
+//! libgit2, so results follow the user's Git config and ignore rules
+//! exactly as `git` does.
+
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+
+use crate::error::AppError;
+
+/// State of one changed, untracked or ignored path.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum GitStatus {
+    Untracked,
+    /// Changed in the working tree (whether or not also staged).
+    Modified,
+    /// Changed in the index only.
+    Staged,
+    Deleted,
+    /// Renamed in the index; `from` is the old absolute path.
+    Renamed { from: String },
+    /// Unmerged, e.g. both sides modified during a merge.
+    Conflicted,
+    Ignored,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct GitFileStatus {
+    /// Absolute path. Ignored and untracked directories are reported once,
+    /// with a trailing separator, instead of per file.
+    pub path: String,
+    pub status: GitStatus,
+}
+
+/// The nearest directory at or above `path` that contains `.git` (a
+/// directory, or a file for worktrees and submodules).
+fn find_git_root(path: &Path) -> Option<PathBuf> {
+    let start = if path.is_dir() { path } else { path.parent()? };
+    start
+        .ancestors()
+        .find(|dir| dir.join(".git").symlink_metadata().is_ok())
+        .map(Path::to_path_buf)
+}
+
+/// Root of the Git repository containing `path`.
+#[tauri::command]
+pub fn get_git_root(path: String) -> Result<String, AppError> {
+    let file_path = PathBuf::from(&path);
+    if !file_path.exists() {
+        return Err(AppError::NotFound(path));
+    }
+    find_git_root(&file_path)
+        .map(|root| root.to_string_lossy().to_string())
+        .ok_or_else(|| AppError::InvalidPath(format!("Not in a Git repository: {}", path)))
+}
+
+/// Status of every changed, untracked and ignored path under `path` (a
+/// directory or a single file), from `git status --porcelain=v1 -z
+/// --ignored`. Unchanged tracked files are left out.
+#[tauri::command]
+pub async fn get_git_status(path: String) -> Result<Vec<GitFileStatus>, AppError> {
+    tokio::task::spawn_blocking(move || git_status_sync(&path))
+        .await
+        .map_err(|e| AppError::Other(format!("Task join error: {}", e)))?
+}
+
+fn git_status_sync(path: &str) -> Result<Vec<GitFileStatus>, AppError> {
+    let file_path = PathBuf::from(path);
+    if !file_path.exists() {
+        return Err(AppError::NotFound(path.to_string()));
+    }
+    let root = find_git_root(&file_path)
+        .ok_or_else(|| AppError::InvalidPath(format!("Not in a Git repository: {}", path)))?;
+
+    let output = std::process::Command::new("git")
+        .arg("-C")
+        .arg(&root)
+        .args(["status", "--porcelain=v1", "-z", "--ignored", "--"])
+        .arg(&file_path)
+        .stdin(std::process::Stdio::null())
+        .output()
+        .map_err(|e| match e.kind() {
+            std::io::ErrorKind::NotFound => AppError::Other("git not found: install Git to see file status".into()),
+            _ => AppError::Io(e),
+        })?;
+    if !output.status.success() {
+        return Err(AppError::Other(format!(
+            "git status failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+
+    // Porcelain paths are relative to the repository root, always with `/`
+    let absolute = |relative: &str| {
+        root.join(relative.replace('/', std::path::MAIN_SEPARATOR_STR))
+            .to_string_lossy()
+            .to_string()
+    };
+    Ok(parse_porcelain(&String::from_utf8_lossy(&output.stdout))
+        .into_iter()
+        .map(|(relative, status)| GitFileStatus {
+            path: absolute(&relative),
+            status: match status {
+                GitStatus::Renamed { from } => GitStatus::Renamed { from: absolute(&from) },
+                status => status,
+            },
+        })
+        .collect())
+}
+
+/// `(path, status)` pairs from `git status --porcelain=v1 -z` output:
+/// `XY path` records separated by NULs, where renames and copies are
+/// followed by a record holding the old path.
+fn parse_porcelain(output: &str) -> Vec<(String, GitStatus)> {
+    let mut statuses = Vec::new();
+    let mut records = output.split('\0').filter(|r| !r.is_empty());
+    while let Some(record) = records.next() {
+        let Some((xy, path)) = record.split_at_checked(2) else {
+            continue;
+        };
+        let path = path.strip_prefix(' ').unwrap_or(path).to_string();
+        let mut codes = xy.chars();
+        let (x, y) = (codes.next().unwrap_or(' '), codes.next().unwrap_or(' '));
+        // The old path of a rename or copy is its own record
+        let from = matches!(x, 'R' | 'C').then(|| records.next()).flatten();
+
+        let status = match (x, y) {
+            ('?', '?') => GitStatus::Untracked,
+            ('!', '!') => GitStatus::Ignored,
+            ('U', _) | (_, 'U') | ('A', 'A') | ('D', 'D') => GitStatus::Conflicted,
+            ('R', _) => GitStatus::Renamed { from: from.unwrap_or_default().to_string() },
+            ('D', _) | (_, 'D') => GitStatus::Deleted,
+            (_, ' ') => GitStatus::Staged,
+            _ => GitStatus::Modified,
+        };
+        statuses.push((path, status));
+    }
+    statuses
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_porcelain() {
+        let output = concat!(
+            " M src/main.rs\0",
+            "M  staged.rs\0",
+            "MM both.rs\0",
+            "A  new.rs\0",
+            " D gone.rs\0",
+            "R  lib/new name.rs\0lib/old.rs\0",
+            "UU merge.rs\0",
+            "?? notes.txt\0",
+            "!! target/\0",
+        );
+        let parsed = parse_porcelain(output);
+        let expected = [
+            ("src/main.rs", GitStatus::Modified),
+            ("staged.rs", GitStatus::Staged),
+            ("both.rs", GitStatus::Modified),
+            ("new.rs", GitStatus::Staged),
+            ("gone.rs", GitStatus::Deleted),
+            ("lib/new name.rs", GitStatus::Renamed { from: "lib/old.rs".into() }),
+            ("merge.rs", GitStatus::Conflicted),
+            ("notes.txt", GitStatus::Untracked),
+            ("target/", GitStatus::Ignored),
+        ];
+        assert_eq!(parsed, expected.map(|(path, status)| (path.to_string(), status)));
+        assert!(parse_porcelain("").is_empty());
+    }
+
+    #[test]
+    fn test_git_status_serialization() {
+        let renamed = GitStatus::Renamed { from: "/repo/a.rs".into() };
+        assert_eq!(serde_json::to_string(&renamed).unwrap(), r#"{"kind":"renamed","from":"/repo/a.rs"}"#);
+        assert_eq!(serde_json::to_string(&GitStatus::Untracked).unwrap(), r#"{"kind":"untracked"}"#);
+    }
+
+    #[test]
+    fn test_git_status_in_repo() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = dir.path();
+        let git = |args: &[&str]| std::process::Command::new("git").arg("-C").arg(repo).args(args).output();
+        // Skip where git isn't installed
+        if git(&["init", "-q"]).is_err() {
+            return;
+        }
+        std::fs::write(repo.join(".gitignore"), "*.log\n").unwrap();
+        std::fs::write(repo.join("tracked.txt"), "one").unwrap();
+        git(&["add", "tracked.txt"]).unwrap();
+        std::fs::write(repo.join("new.txt"), "").unwrap();
+        std::fs::write(repo.join("debug.log"), "").unwrap();
+
+        let status = git_status_sync(&repo.to_string_lossy()).unwrap();
+        let find = |name: &str| {
+            let path = repo.join(name).to_string_lossy().to_string();
+            status.iter().find(|s| s.path == path).map(|s| &s.status)
+        };
+        assert_eq!(find("tracked.txt"), Some(&GitStatus::Staged));
+        assert_eq!(find("new.txt"), Some(&GitStatus::Untracked));
+        assert_eq!(find("debug.log"), Some(&GitStatus::Ignored));
+    }
+
+    #[test]
+    fn test_get_git_root() {
+        let dir = tempfile::tempdir().unwrap();
+        let nested = dir.path().join("repo/src/deep");
+        std::fs::create_dir_all(&nested).unwrap();
+        std::fs::create_dir(dir.path().join("repo/.git")).unwrap();
+        std::fs::write(nested.join("file.rs"), "").unwrap();
+
+        let root = dir.path().join("repo").to_string_lossy().to_string();
+        assert_eq!(get_git_root(nested.to_string_lossy().to_string()).unwrap(), root);
+        assert_eq!(get_git_root(nested.join("file.rs").to_string_lossy().to_string()).unwrap(), root);
+        assert!(matches!(
+            get_git_root(dir.path().join("missing").to_string_lossy().to_string()),
+            Err(AppError::NotFound(_))
+        ));
+    }
+}
@@ -2,20 +2,29 @@
 //! Issue: tauri-explorer-nv2y, tauri-explorer-hgt6, tauri-explorer-im3m, tauri-explorer-bo8l, tauri-explorer-yclf
 
 mod archive;
+mod bookmarks;
 mod clipboard;
 mod config;
 mod content_search;
 pub mod error;
+mod export;
 mod files;
+mod git;
+mod protocol;
+mod recent;
 mod search;
+mod search_history;
+mod tags;
 pub mod task_registry;
 mod thumbnails;
+mod undo;
 mod wallpaper;
 
 use std::path::PathBuf;
 
 use error::AppError;
 use log;
+use serde::Serialize;
 use tauri_plugin_log::{RotationStrategy, Target, TargetKind, TimezoneStrategy};
 
 /// Stores the working directory from which the app was launched.
@@ -24,7 +33,7 @@ struct LaunchCwd(String);
 /// Move a file or directory to the system trash/recycle bin.
 /// Cross-platform: Windows Recycle Bin, macOS Trash, Linux Freedesktop Trash.
 #[tauri::command]
-fn move_to_trash(path: String) -> Result<(), AppError> {
+fn move_to_trash(app: tauri::AppHandle, path: String) -> Result<(), AppError> {
     let pathbuf = PathBuf::from(&path);
 
     if !pathbuf.exists() {
@@ -34,25 +43,72 @@ fn move_to_trash(path: String) -> Result<(), AppError> {
     trash::delete(&pathbuf).map_err(|e| {
         log::error!("Failed to move to trash: {}", e);
         AppError::Other(format!("Failed to move to trash: {}", e))
-    })
+    })?;
+    undo::record(&app, undo::Operation::Trash { paths: vec![pathbuf] });
+    Ok(())
+}
+
+/// Per-path outcome of a batch trash operation.
+#[derive(Debug, Default, Serialize)]
+struct TrashResult {
+    trashed: Vec<String>,
+    #[serde(rename = "notFound")]
+    not_found: Vec<String>,
+    /// (path, error message)
+    failed: Vec<(String, String)>,
 }
 
 /// Move multiple files/directories to trash.
+/// Missing paths are reported rather than aborting the batch, so one stale
+/// selection doesn't leave the rest untouched.
 #[tauri::command]
-fn move_multiple_to_trash(paths: Vec<String>) -> Result<(), AppError> {
-    let pathbufs: Vec<PathBuf> = paths.iter().map(PathBuf::from).collect();
+fn move_multiple_to_trash(app: tauri::AppHandle, paths: Vec<String>) -> Result<TrashResult, AppError> {
+    let result = trash_paths(paths, |batch| trash::delete_all(batch).map_err(|e| e.to_string()));
+    if !result.trashed.is_empty() {
+        let paths = result.trashed.iter().map(PathBuf::from).collect();
+        undo::record(&app, undo::Operation::Trash { paths });
+    }
+    Ok(result)
+}
 
-    for (i, path) in pathbufs.iter().enumerate() {
-        if !path.exists() {
-            return Err(AppError::NotFound(paths[i].clone()));
-        }
+/// Trash every existing path with `delete`. Tries the whole batch in one call
+/// first; if that fails, retries path by path to find out which ones failed.
+/// A failed batch may still have trashed some paths before stopping, so
+/// paths that are gone by the retry count as trashed.
+fn trash_paths<F>(paths: Vec<String>, delete: F) -> TrashResult
+where
+    F: Fn(&[PathBuf]) -> Result<(), String>,
+{
+    let mut result = TrashResult::default();
+    let (existing, missing): (Vec<String>, Vec<String>) =
+        paths.into_iter().partition(|p| PathBuf::from(p).symlink_metadata().is_ok());
+    result.not_found = missing;
+
+    if existing.is_empty() {
+        return result;
     }
 
-    log::info!("Moving {} items to trash", pathbufs.len());
-    trash::delete_all(&pathbufs).map_err(|e| {
-        log::error!("Failed to move {} items to trash: {}", pathbufs.len(), e);
-        AppError::Other(format!("Failed to move items to trash: {}", e))
-    })
+    log::info!("Moving {} items to trash ({} not found)", existing.len(), result.not_found.len());
+    let pathbufs: Vec<PathBuf> = existing.iter().map(PathBuf::from).collect();
+    if delete(&pathbufs).is_ok() {
+        result.trashed = existing;
+        return result;
+    }
+
+    for (path, pathbuf) in existing.into_iter().zip(pathbufs) {
+        if pathbuf.symlink_metadata().is_err() {
+            result.trashed.push(path);
+            continue;
+        }
+        match delete(std::slice::from_ref(&pathbuf)) {
+            Ok(()) => result.trashed.push(path),
+            Err(e) => {
+                log::error!("Failed to move {} to trash: {}", path, e);
+                result.failed.push((path, e));
+            }
+        }
+    }
+    result
 }
 
 /// Get the directory the app was launched from.
@@ -72,8 +128,135 @@ fn get_log_dir(app: tauri::AppHandle) -> Result<String, AppError> {
     Ok(log_dir.to_string_lossy().to_string())
 }
 
+/// An item currently in the system trash.
+#[derive(Debug, Serialize)]
+struct TrashItem {
+    name: String,
+    /// Where the item was deleted from. Unknown on macOS.
+    #[serde(rename = "originalPath")]
+    original_path: Option<String>,
+    /// Local time of deletion, `%Y-%m-%dT%H:%M:%S`.
+    #[serde(rename = "deletedAt")]
+    deleted_at: Option<String>,
+    /// Where the item currently lives inside the trash.
+    #[serde(rename = "trashPath")]
+    trash_path: String,
+}
+
+fn format_unix_time(secs: i64) -> Option<String> {
+    chrono::DateTime::from_timestamp(secs, 0)
+        .map(|t| t.with_timezone(&chrono::Local).format("%Y-%m-%dT%H:%M:%S").to_string())
+}
+
+/// Location of a trashed item on disk.
+/// Freedesktop ids point at `info/<name>.trashinfo`; the data is in `files/<name>`.
+/// Windows ids are already the path inside `$Recycle.Bin`.
+#[cfg(not(target_os = "macos"))]
+fn trash_item_path(item: &trash::TrashItem) -> PathBuf {
+    let id = PathBuf::from(&item.id);
+    if cfg!(windows) {
+        return id;
+    }
+    match (id.parent().and_then(|info| info.parent()), id.file_stem()) {
+        (Some(trash_dir), Some(stem)) => trash_dir.join("files").join(stem),
+        _ => id,
+    }
+}
+
+/// macOS has no trash listing API, so read `~/.Trash` directly.
+#[cfg(target_os = "macos")]
+fn mac_trash_entries() -> Result<Vec<PathBuf>, AppError> {
+    let trash_dir = dirs::home_dir()
+        .ok_or_else(|| AppError::Other("Cannot determine home directory".to_string()))?
+        .join(".Trash");
+    Ok(std::fs::read_dir(trash_dir)?.flatten().map(|e| e.path()).collect())
+}
+
+/// List everything in the system trash, most recently deleted first.
+#[tauri::command]
+fn list_trash_contents() -> Result<Vec<TrashItem>, AppError> {
+    #[cfg(not(target_os = "macos"))]
+    let mut items: Vec<(i64, TrashItem)> = trash::os_limited::list()
+        .map_err(|e| AppError::Other(format!("Failed to list trash: {}", e)))?
+        .into_iter()
+        .map(|item| {
+            let entry = TrashItem {
+                name: item.name.to_string_lossy().to_string(),
+                original_path: Some(item.original_path().to_string_lossy().to_string()),
+                deleted_at: format_unix_time(item.time_deleted),
+                trash_path: trash_item_path(&item).to_string_lossy().to_string(),
+            };
+            (item.time_deleted, entry)
+        })
+        .collect();
+
+    // ctime changes when Finder moves an item into the trash, so it is the
+    // closest thing to a deletion time available.
+    #[cfg(target_os = "macos")]
+    let mut items: Vec<(i64, TrashItem)> = mac_trash_entries()?
+        .into_iter()
+        .filter(|path| path.file_name().is_some_and(|n| n != ".DS_Store"))
+        .map(|path| {
+            use std::os::unix::fs::MetadataExt;
+            let deleted = std::fs::symlink_metadata(&path).map(|m| m.ctime()).unwrap_or(0);
+            let entry = TrashItem {
+                name: path.file_name().unwrap_or_default().to_string_lossy().to_string(),
+                original_path: None,
+                deleted_at: format_unix_time(deleted),
+                trash_path: path.to_string_lossy().to_string(),
+            };
+            (deleted, entry)
+        })
+        .collect();
+
+    items.sort_by_key(|(deleted, _)| std::cmp::Reverse(*deleted));
+    Ok(items.into_iter().map(|(_, item)| item).collect())
+}
+
+/// Permanently delete everything in the trash. Returns the number of bytes freed.
+#[tauri::command]
+fn empty_trash() -> Result<u64, AppError> {
+    let mut file_count = 0;
+    let mut bytes_freed = 0;
+
+    #[cfg(not(target_os = "macos"))]
+    {
+        let items = trash::os_limited::list()
+            .map_err(|e| AppError::Other(format!("Failed to list trash: {}", e)))?;
+        for item in &items {
+            files::file_ops::estimate_path_size(&trash_item_path(item), &mut file_count, &mut bytes_freed);
+        }
+        trash::os_limited::purge_all(items)
+            .map_err(|e| AppError::Other(format!("Failed to empty trash: {}", e)))?;
+    }
+
+    #[cfg(target_os = "macos")]
+    for path in mac_trash_entries()? {
+        files::file_ops::estimate_path_size(&path, &mut file_count, &mut bytes_freed);
+        if path.is_dir() && !path.is_symlink() {
+            std::fs::remove_dir_all(&path)?;
+        } else {
+            std::fs::remove_file(&path)?;
+        }
+    }
+
+    log::info!("Emptied trash: {} files, {} bytes", file_count, bytes_freed);
+    Ok(bytes_freed)
+}
+
+/// Restore files from the system trash by their original paths.
+/// Finds the most recently deleted item matching each path and restores it.
+/// Fails without restoring anything if an original folder no longer exists.
+#[cfg(target_os = "macos")]
+#[tauri::command]
+fn restore_from_trash(_paths: Vec<String>) -> Result<(), AppError> {
+    Err(AppError::Other("Restoring from trash is not supported on macOS".to_string()))
+}
+
 /// Restore files from the system trash by their original paths.
 /// Finds the most recently deleted item matching each path and restores it.
+/// Fails without restoring anything if an original folder no longer exists.
+#[cfg(not(target_os = "macos"))]
 #[tauri::command]
 fn restore_from_trash(paths: Vec<String>) -> Result<(), AppError> {
     let trash_items = trash::os_limited::list()
@@ -99,8 +282,17 @@ fn restore_from_trash(paths: Vec<String>) -> Result<(), AppError> {
         return Err(AppError::Other("No matching items found in trash".to_string()));
     }
 
-    trash::os_limited::restore_all(to_restore)
-        .map_err(|e| AppError::Other(format!("Failed to restore from trash: {}", e)))
+    if let Some(item) = to_restore.iter().find(|item| !item.original_parent.is_dir()) {
+        return Err(AppError::NotFound(format!(
+            "Original location no longer exists: {}",
+            item.original_parent.display()
+        )));
+    }
+
+    trash::os_limited::restore_all(to_restore).map_err(|e| match e {
+        trash::Error::RestoreCollision { path, .. } => AppError::AlreadyExists(path.to_string_lossy().to_string()),
+        e => AppError::Other(format!("Failed to restore from trash: {}", e)),
+    })
 }
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
@@ -118,6 +310,14 @@ pub fn run(launch_dir: Option<String>) {
         .unwrap_or_else(|| PathBuf::from("/"))
         .to_string_lossy()
         .to_string();
+    // Launched to handle an `explorer://` link: start in the linked folder
+    let launch_dir = launch_dir.and_then(|dir| {
+        if dir.starts_with(&format!("{}://", protocol::SCHEME)) {
+            protocol::launch_dir(&dir)
+        } else {
+            Some(dir)
+        }
+    });
     let launch_cwd = launch_dir.unwrap_or_else(|| home_dir.clone());
 
     // Inject launch data into the webview as a synchronous JS global,
@@ -139,6 +339,7 @@ pub fn run(launch_dir: Option<String>) {
 
     tauri::Builder::default()
         .manage(LaunchCwd(launch_cwd_for_state))
+        .manage(undo::UndoLog::default())
         .plugin({
             let mut targets = vec![
                 Target::new(TargetKind::LogDir { file_name: None }),
@@ -169,50 +370,164 @@ pub fn run(launch_dir: Option<String>) {
             move_to_trash,
             move_multiple_to_trash,
             restore_from_trash,
+            list_trash_contents,
+            empty_trash,
             // File operations — directory listing
             files::dir_listing::list_directory,
             files::dir_listing::invalidate_dir_cache,
             files::dir_listing::start_streaming_directory,
+            files::dir_listing::start_streaming_directory_unsorted,
             files::dir_listing::cancel_directory_listing,
+            files::dir_listing::get_parent_chain,
+            files::dir_listing::sort_entries,
+            files::dir_listing::list_directory_recursive,
+            files::dir_listing::start_streaming_directory_recursive,
+            files::dir_stats::get_directory_entry_counts,
+            files::dir_stats::cancel_entry_count,
+            files::dir_stats::find_large_files,
+            files::dir_stats::cancel_large_files_scan,
+            files::dir_stats::find_broken_symlinks,
+            files::dir_stats::get_directory_statistics,
+            files::dir_stats::cancel_directory_statistics,
             // File operations — CRUD
             files::file_ops::get_home_directory,
+            files::file_ops::get_special_directories,
             files::file_ops::create_directory,
+            files::file_ops::create_directory_recursive,
             files::file_ops::rename_entry,
+            files::file_ops::atomic_rename,
             files::file_ops::copy_entry,
+            files::file_ops::copy_entry_overwrite,
             files::file_ops::move_entry,
+            files::file_ops::move_entry_overwrite,
+            files::file_ops::merge_directories,
+            files::file_ops::flatten_directory,
             files::file_ops::read_text_file,
             files::file_ops::write_text_file,
             files::file_ops::delete_entry_permanent,
+            files::file_ops::force_delete_entry,
             files::file_ops::create_symlink,
             files::file_ops::estimate_size,
             files::file_ops::check_paths_exist,
+            files::file_ops::is_path_accessible,
+            // Undo
+            undo::get_undo_stack,
+            undo::undo_last_operation,
             // File operations — external apps
             files::external_apps::open_file,
             files::external_apps::open_file_with,
             files::external_apps::open_image_with_siblings,
             files::external_apps::open_in_terminal,
+            files::external_apps::get_applications_for_file,
+            files::external_apps::get_file_association,
+            files::icons::get_file_icon,
+            files::file_type::get_file_type,
+            files::path_info::get_path_info,
+            files::filesystem::get_filesystem_type,
+            files::filesystem::get_volume_info,
+            files::filesystem::eject_device,
+            files::xattrs::list_xattrs,
+            files::xattrs::set_xattr,
+            files::xattrs::remove_xattr,
+            files::xattrs::get_macos_tags,
+            files::xattrs::set_macos_tags,
+            files::xattrs::get_ntfs_alternate_data_streams,
+            files::attributes::get_windows_file_attributes,
+            files::attributes::set_windows_file_attributes,
+            files::processes::get_process_list_for_file,
+            files::network::list_network_shares,
+            files::backups::backup_file,
+            files::backups::list_file_backups,
+            files::backups::restore_file_backup,
+            files::text_file::read_file_text,
+            files::text_file::read_file_chunk,
+            files::text_file::write_file_text,
+            files::text_file::get_file_line_count,
+            files::text_file::detect_file_encoding,
+            files::filename::validate_filename,
+            files::filename::sanitize_filename,
+            files::compare::compare_directories,
+            files::compare::sync_directories,
+            files::checksums::batch_checksum,
+            files::checksums::cancel_checksum,
+            files::checksums::verify_directory_checksums,
+            files::project_type::detect_project_type,
+            files::compare::cancel_sync,
+            files::batch::copy_multiple_entries,
+            files::batch::cancel_batch_operation,
+            files::batch::move_multiple_entries,
+            files::batch::resolve_move_conflict,
             // Search
             search::fuzzy_search,
             search::start_streaming_search,
             search::cancel_search,
+            search::search_combined,
+            search::export_search_results,
+            search::search_by_date,
+            search::start_date_search,
+            search::search_by_extension,
+            search::start_extension_search,
+            search::search_with_glob,
+            search::start_glob_search,
+            search::find_files_by_content_type,
             // Content search (ripgrep)
             content_search::start_content_search,
             content_search::cancel_content_search,
+            content_search::get_content_search_stats,
+            content_search::pause_search,
+            content_search::resume_search,
+            content_search::export_content_search_results,
+            content_search::preview_replace,
+            content_search::apply_replace,
             // Clipboard (Linux native)
             clipboard::clipboard_has_files,
             clipboard::clipboard_read_files,
             clipboard::clipboard_write_files,
             clipboard::clipboard_has_image,
             clipboard::clipboard_paste_image,
+            clipboard::copy_path_to_clipboard,
             // Thumbnails
             thumbnails::get_thumbnail,
             thumbnails::get_thumbnail_data,
             thumbnails::get_micro_thumbnail,
+            thumbnails::get_thumbnail_batch,
+            thumbnails::get_video_thumbnail,
+            thumbnails::get_pdf_thumbnail,
+            thumbnails::is_supported_format,
+            thumbnails::preload_thumbnails,
+            thumbnails::cancel_preload,
             thumbnails::clear_thumbnail_cache,
+            thumbnails::clear_thumbnail_cache_for_directory,
             thumbnails::get_thumbnail_cache_stats,
+            thumbnails::set_thumbnail_cache_limit,
             // Archive operations
             archive::compress_to_zip,
             archive::extract_archive,
+            archive::create_archive,
+            archive::cancel_archive,
+            archive::list_archive_contents,
+            archive::compress_file,
+            archive::decompress_file,
+            // Bookmarks
+            bookmarks::add_bookmark,
+            bookmarks::remove_bookmark,
+            bookmarks::list_bookmarks,
+            // Recent files and directories
+            recent::record_access,
+            recent::list_recent,
+            recent::search_recent,
+            recent::get_most_frequent,
+            recent::clear_recent,
+            // Search history
+            search_history::record_search,
+            search_history::get_search_suggestions,
+            search_history::clear_search_history,
+            // Tags
+            tags::tag_file,
+            tags::untag_file,
+            tags::get_tags,
+            tags::list_all_tags,
+            tags::search_by_tag,
             // Config file persistence
             config::read_config_file,
             config::write_config_file,
@@ -220,10 +535,19 @@ pub fn run(launch_dir: Option<String>) {
             config::list_user_themes,
             // Wallpaper
             wallpaper::set_as_wallpaper,
+            // URL handler
+            protocol::register_protocol_handler,
+            // Git
+            git::get_git_root,
+            git::get_git_status,
         ])
         .setup(move |app| {
             let t_setup = std::time::Instant::now();
 
+            if let Err(e) = bookmarks::ensure_bookmarks_file() {
+                log::warn!("Failed to create bookmarks file: {}", e);
+            }
+
             // Create window programmatically so we can inject initialization_script.
             // This replaces the static window definition in tauri.conf.json.
             tauri::WebviewWindowBuilder::new(
@@ -248,6 +572,135 @@ pub fn run(launch_dir: Option<String>) {
             );
             Ok(())
         })
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(tauri::generate_context!())
+        .expect("error while running tauri application")
+        .run(|_app, _event| {
+            // macOS hands `explorer://` links to the running app rather than
+            // starting a new process with them
+            #[cfg(target_os = "macos")]
+            if let tauri::RunEvent::Opened { urls } = _event {
+                for url in urls {
+                    protocol::dispatch(_app, url.as_str());
+                }
+            }
+        });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(all(unix, not(target_os = "macos")))]
+    #[test]
+    fn test_trash_item_path_freedesktop() {
+        let item = trash::TrashItem {
+            id: "/home/u/.local/share/Trash/info/notes.txt.trashinfo".into(),
+            name: "notes.txt".into(),
+            original_parent: PathBuf::from("/home/u/docs"),
+            time_deleted: 0,
+        };
+
+        assert_eq!(
+            trash_item_path(&item),
+            PathBuf::from("/home/u/.local/share/Trash/files/notes.txt")
+        );
+    }
+
+    #[test]
+    fn test_trash_paths_reports_missing() {
+        let dir = tempfile::tempdir().unwrap();
+        let a = dir.path().join("a.txt");
+        let b = dir.path().join("b.txt");
+        std::fs::write(&a, "a").unwrap();
+        std::fs::write(&b, "b").unwrap();
+        let missing = dir.path().join("gone.txt").to_string_lossy().to_string();
+        let paths = vec![a.to_string_lossy().to_string(), missing.clone(), b.to_string_lossy().to_string()];
+
+        let result = trash_paths(paths, |batch| {
+            assert_eq!(batch.len(), 2);
+            Ok(())
+        });
+
+        assert_eq!(result.trashed.len(), 2);
+        assert_eq!(result.not_found, vec![missing]);
+        assert!(result.failed.is_empty());
+    }
+
+    #[test]
+    fn test_trash_paths_isolates_failures() {
+        let dir = tempfile::tempdir().unwrap();
+        let ok = dir.path().join("ok.txt");
+        let locked = dir.path().join("locked.txt");
+        std::fs::write(&ok, "").unwrap();
+        std::fs::write(&locked, "").unwrap();
+        let paths = vec![ok.to_string_lossy().to_string(), locked.to_string_lossy().to_string()];
+
+        // Batch fails as a whole; the per-path retry pins it on `locked.txt`
+        let result = trash_paths(paths, |batch| {
+            if batch.iter().any(|p| p.ends_with("locked.txt")) {
+                Err("permission denied".to_string())
+            } else {
+                Ok(())
+            }
+        });
+
+        assert_eq!(result.trashed, vec![ok.to_string_lossy().to_string()]);
+        assert!(result.not_found.is_empty());
+        assert_eq!(
+            result.failed,
+            vec![(locked.to_string_lossy().to_string(), "permission denied".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_trash_paths_counts_partial_batch_as_trashed() {
+        let dir = tempfile::tempdir().unwrap();
+        let first = dir.path().join("first.txt");
+        let locked = dir.path().join("locked.txt");
+        let last = dir.path().join("last.txt");
+        for path in [&first, &locked, &last] {
+            std::fs::write(path, "").unwrap();
+        }
+        let paths: Vec<String> =
+            [&first, &locked, &last].iter().map(|p| p.to_string_lossy().to_string()).collect();
+
+        // Like `trash::delete_all`: trashes in order and stops at the first failure
+        let result = trash_paths(paths, |batch| {
+            for path in batch {
+                if path.ends_with("locked.txt") {
+                    return Err("permission denied".to_string());
+                }
+                std::fs::remove_file(path).unwrap();
+            }
+            Ok(())
+        });
+
+        assert_eq!(
+            result.trashed,
+            vec![first.to_string_lossy().to_string(), last.to_string_lossy().to_string()]
+        );
+        assert_eq!(
+            result.failed,
+            vec![(locked.to_string_lossy().to_string(), "permission denied".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_trash_result_serialization() {
+        let result = TrashResult {
+            trashed: vec!["/a".to_string()],
+            not_found: vec!["/b".to_string()],
+            failed: vec![("/c".to_string(), "busy".to_string())],
+        };
+
+        let json = serde_json::to_string(&result).unwrap();
+        assert_eq!(json, r#"{"trashed":["/a"],"notFound":["/b"],"failed":[["/c","busy"]]}"#);
+    }
+
+    #[test]
+    fn test_format_unix_time() {
+        let formatted = format_unix_time(86_400 * 365).unwrap();
+        assert!(formatted.starts_with("1971-01-0") || formatted.starts_with("1970-12-31"));
+        assert_eq!(formatted.len(), "1971-01-01T00:00:00".len());
+    }
 }
@@ -0,0 +1,238 @@
+//! `explorer://` deep links, so browsers and other tools can open a folder
+//! in the explorer (`explorer://open?path=/home/user/docs`).
+//!
+//! Linux and Windows start the app with the URL as its first argument;
+//! macOS delivers it to the running app as `RunEvent::Opened`.
+
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+
+use crate::error::AppError;
+
+/// URL scheme the app registers for.
+pub const SCHEME: &str = "explorer";
+
+/// A parsed `explorer://` link, also the `protocol-request` event payload.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ProtocolRequest {
+    /// `open` shows `path` if it's a directory; `reveal` shows its parent
+    /// with `path` selected.
+    pub action: String,
+    pub path: String,
+}
+
+/// Parse `explorer://open?path=...` or `explorer://reveal?path=...`.
+/// `None` for other schemes, unknown actions or a missing or relative path.
+pub fn parse_protocol_url(url: &str) -> Option<ProtocolRequest> {
+    let url = tauri::Url::parse(url).ok()?;
+    if url.scheme() != SCHEME {
+        return None;
+    }
+    let action = url.host_str()?.to_lowercase();
+    if action != "open" && action != "reveal" {
+        return None;
+    }
+    let path = url.query_pairs().find(|(key, _)| key == "path")?.1.into_owned();
+    Path::new(&path).is_absolute().then_some(ProtocolRequest { action, path })
+}
+
+/// The directory to open for a link passed on the command line: the path
+/// for `open` on a directory, otherwise its parent.
+pub fn launch_dir(url: &str) -> Option<String> {
+    let request = parse_protocol_url(url)?;
+    let path = PathBuf::from(&request.path);
+    let dir = match request.action.as_str() {
+        "open" if path.is_dir() => path,
+        _ => path.parent()?.to_path_buf(),
+    };
+    Some(dir.to_string_lossy().to_string())
+}
+
+/// Forward a link received while running to the frontend as a
+/// `protocol-request` event.
+#[cfg(target_os = "macos")]
+pub fn dispatch(app: &tauri::AppHandle, url: &str) {
+    use tauri::Emitter;
+
+    match parse_protocol_url(url) {
+        Some(request) => {
+            log::info!("Protocol request: {} {}", request.action, request.path);
+            let _ = app.emit("protocol-request", request);
+        }
+        None => log::warn!("Ignoring unsupported URL: {}", url),
+    }
+}
+
+/// Register the app as the handler for `explorer://` links for the current
+/// user. Safe to call repeatedly; an existing registration is updated.
+#[tauri::command]
+pub fn register_protocol_handler() -> Result<(), AppError> {
+    let exe = std::env::current_exe()?;
+    register(&exe)?;
+    log::info!("Registered {}:// handler: {}", SCHEME, exe.display());
+    Ok(())
+}
+
+/// Desktop entry that hands `explorer://` links to `exe`.
+#[cfg(any(target_os = "linux", test))]
+fn handler_desktop_entry(exe: &Path) -> String {
+    format!(
+        concat!(
+            "[Desktop Entry]\nType=Application\nName=Tauri Explorer\n",
+            "Exec=\"{}\" %u\nMimeType=x-scheme-handler/{};\nNoDisplay=true\nTerminal=false\n",
+        ),
+        exe.display(),
+        SCHEME
+    )
+}
+
+/// Linux: a hidden desktop entry for `x-scheme-handler/explorer` in the
+/// user's applications directory, made the default with `xdg-mime`.
+#[cfg(target_os = "linux")]
+fn register(exe: &Path) -> Result<(), AppError> {
+    const DESKTOP_ID: &str = "tauri-explorer-url-handler.desktop";
+
+    let dir = dirs::data_dir()
+        .ok_or_else(|| AppError::Other("Could not determine data directory".into()))?
+        .join("applications");
+    std::fs::create_dir_all(&dir)?;
+    std::fs::write(dir.join(DESKTOP_ID), handler_desktop_entry(exe))?;
+
+    let status = std::process::Command::new("xdg-mime")
+        .args(["default", DESKTOP_ID, &format!("x-scheme-handler/{}", SCHEME)])
+        .status()
+        .map_err(|e| match e.kind() {
+            std::io::ErrorKind::NotFound => AppError::Other("xdg-mime not found: install xdg-utils".into()),
+            _ => AppError::Io(e),
+        })?;
+    if !status.success() {
+        return Err(AppError::Other("xdg-mime failed to set the URL handler".into()));
+    }
+    Ok(())
+}
+
+/// Windows: `HKCU\Software\Classes\explorer` marked as a URL protocol, with
+/// an `open` command that passes the link to `exe`.
+#[cfg(target_os = "windows")]
+fn register(exe: &Path) -> Result<(), AppError> {
+    let key = format!(r"HKCU\Software\Classes\{}", SCHEME);
+    let command = format!("\"{}\" \"%1\"", exe.display());
+    let values: [(String, &str, String); 3] = [
+        (key.clone(), "/ve", format!("URL:{} Protocol", SCHEME)),
+        (key.clone(), "URL Protocol", String::new()),
+        (format!(r"{}\shell\open\command", key), "/ve", command),
+    ];
+    for (key, name, data) in values {
+        let mut reg = std::process::Command::new("reg");
+        reg.args(["add", &key]);
+        match name {
+            "/ve" => reg.arg("/ve"),
+            name => reg.args(["/v", name]),
+        };
+        let status = reg.args(["/t", "REG_SZ", "/d", &data, "/f"]).status()?;
+        if !status.success() {
+            return Err(AppError::PermissionDenied(format!("Failed to write registry key {}", key)));
+        }
+    }
+    Ok(())
+}
+
+/// macOS: add the scheme to `CFBundleURLTypes` in the bundle's Info.plist
+/// if it's missing, then re-register the bundle with Launch Services.
+/// Editing the plist invalidates a code signature, so signed builds should
+/// declare the scheme at build time instead.
+#[cfg(target_os = "macos")]
+fn register(exe: &Path) -> Result<(), AppError> {
+    // .../Tauri Explorer.app/Contents/MacOS/tauri-explorer
+    let contents = exe
+        .parent()
+        .and_then(Path::parent)
+        .filter(|dir| dir.file_name().is_some_and(|n| n == "Contents"))
+        .ok_or_else(|| AppError::InvalidPath("Not running from an app bundle".into()))?;
+    let info_plist = contents.join("Info.plist");
+    let mut info = plist::Value::from_file(&info_plist)
+        .map_err(|e| AppError::Other(format!("Failed to read {}: {}", info_plist.display(), e)))?;
+    let dict = info
+        .as_dictionary_mut()
+        .ok_or_else(|| AppError::Other("Info.plist is not a dictionary".into()))?;
+
+    let registered = dict
+        .get("CFBundleURLTypes")
+        .and_then(plist::Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|url_type| url_type.as_dictionary()?.get("CFBundleURLSchemes")?.as_array())
+        .flatten()
+        .any(|scheme| scheme.as_string() == Some(SCHEME));
+    if !registered {
+        let mut url_type = plist::Dictionary::new();
+        url_type.insert("CFBundleURLName".into(), format!("com.explorer.app.{}", SCHEME).into());
+        url_type.insert("CFBundleURLSchemes".into(), vec![plist::Value::from(SCHEME)].into());
+        match dict.get_mut("CFBundleURLTypes").and_then(plist::Value::as_array_mut) {
+            Some(types) => types.push(url_type.into()),
+            None => {
+                dict.insert("CFBundleURLTypes".into(), vec![plist::Value::from(url_type)].into());
+            }
+        }
+        info.to_file_xml(&info_plist)
+            .map_err(|e| AppError::Other(format!("Failed to write {}: {}", info_plist.display(), e)))?;
+    }
+
+    let bundle = contents.parent().unwrap_or(contents);
+    let status = std::process::Command::new(
+        "/System/Library/Frameworks/CoreServices.framework/Frameworks/LaunchServices.framework/Support/lsregister",
+    )
+    .arg("-f")
+    .arg(bundle)
+    .status()?;
+    if !status.success() {
+        return Err(AppError::Other("lsregister failed to register the app".into()));
+    }
+    Ok(())
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "windows", target_os = "macos")))]
+fn register(_exe: &Path) -> Result<(), AppError> {
+    Err(AppError::Other("URL handlers are not supported on this platform".into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_protocol_url() {
+        let request = parse_protocol_url("explorer://open?path=/home/user/My%20Docs").unwrap();
+        assert_eq!(request, ProtocolRequest { action: "open".into(), path: "/home/user/My Docs".into() });
+        assert_eq!(parse_protocol_url("explorer://REVEAL?path=/tmp/a.txt").unwrap().action, "reveal");
+
+        assert!(parse_protocol_url("explorer://delete?path=/tmp").is_none());
+        assert!(parse_protocol_url("explorer://open?path=relative/dir").is_none());
+        assert!(parse_protocol_url("explorer://open").is_none());
+        assert!(parse_protocol_url("https://open?path=/tmp").is_none());
+        assert!(parse_protocol_url("/home/user").is_none());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_launch_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("notes.txt");
+        std::fs::write(&file, "").unwrap();
+        let url = |action: &str, path: &Path| format!("explorer://{}?path={}", action, path.display());
+        let expected = Some(dir.path().to_string_lossy().to_string());
+
+        assert_eq!(launch_dir(&url("open", dir.path())), expected);
+        assert_eq!(launch_dir(&url("open", &file)), expected);
+        assert_eq!(launch_dir(&url("reveal", &file)), expected);
+        assert_eq!(launch_dir("/not/a/url"), None);
+    }
+
+    #[test]
+    fn test_handler_desktop_entry() {
+        let entry = handler_desktop_entry(Path::new("/opt/explorer/tauri-explorer"));
+        assert!(entry.contains("Exec=\"/opt/explorer/tauri-explorer\" %u\n"));
+        assert!(entry.contains("MimeType=x-scheme-handler/explorer;\n"));
+    }
+}
@@ -0,0 +1,278 @@
+//! Recently opened files and visited directories.
+//!
+//! Stored most-recent-first as a JSON array in the app's local data directory
+//! (~/.local/share/tauri-explorer/recent.json on Linux).
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use chrono::Local;
+use nucleo_matcher::pattern::{CaseMatching, Normalization, Pattern};
+use nucleo_matcher::{Config, Matcher, Utf32Str};
+use serde::{Deserialize, Serialize};
+
+use crate::error::AppError;
+use crate::files::FileKind;
+
+/// Entries kept in the recent list; the least recently used fall off.
+const MAX_RECENT_ENTRIES: usize = 100;
+
+/// Serializes read-modify-write cycles on the recent file.
+static RECENT_LOCK: Mutex<()> = Mutex::new(());
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecentEntry {
+    pub path: String,
+    /// ISO 8601, local time.
+    #[serde(rename = "lastAccessed")]
+    pub last_accessed: String,
+    #[serde(rename = "accessCount")]
+    pub access_count: u32,
+    /// Kind at the time of the last access.
+    pub kind: FileKind,
+    /// Whether the path still exists; recomputed on every listing.
+    #[serde(default = "default_exists", skip_deserializing)]
+    pub exists: bool,
+}
+
+fn default_exists() -> bool {
+    true
+}
+
+/// Path of the recent file.
+fn recent_file() -> Result<PathBuf, AppError> {
+    let base = dirs::data_local_dir()
+        .ok_or_else(|| AppError::Other("Could not determine data directory".into()))?;
+    Ok(base.join("tauri-explorer").join("recent.json"))
+}
+
+fn load(file: &Path) -> Result<Vec<RecentEntry>, AppError> {
+    if !file.exists() {
+        return Ok(Vec::new());
+    }
+    let data = fs::read_to_string(file)?;
+    if data.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+    serde_json::from_str(&data).map_err(|e| AppError::Other(format!("Corrupt recent file: {}", e)))
+}
+
+fn save(file: &Path, entries: &[RecentEntry]) -> Result<(), AppError> {
+    if let Some(parent) = file.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let data = serde_json::to_string(entries)
+        .map_err(|e| AppError::Other(format!("Failed to serialize recent entries: {}", e)))?;
+    let tmp = file.with_extension("json.tmp");
+    fs::write(&tmp, data)?;
+    fs::rename(&tmp, file)?;
+    Ok(())
+}
+
+fn record_access_in(file: &Path, path: &str) -> Result<(), AppError> {
+    let metadata = fs::metadata(path).map_err(|_| AppError::NotFound(path.to_string()))?;
+    let kind = if metadata.is_dir() {
+        FileKind::Directory
+    } else {
+        FileKind::File
+    };
+
+    let _guard = RECENT_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut entries = load(file)?;
+    let access_count = match entries.iter().position(|e| e.path == path) {
+        Some(i) => entries.remove(i).access_count.saturating_add(1),
+        None => 1,
+    };
+    entries.insert(
+        0,
+        RecentEntry {
+            path: path.to_string(),
+            last_accessed: Local::now().format("%Y-%m-%dT%H:%M:%S").to_string(),
+            access_count,
+            kind,
+            exists: true,
+        },
+    );
+    entries.truncate(MAX_RECENT_ENTRIES);
+    save(file, &entries)
+}
+
+fn list_recent_in(file: &Path, limit: Option<usize>) -> Result<Vec<RecentEntry>, AppError> {
+    Ok(finish(load(file)?, limit))
+}
+
+/// Entries whose path fuzzy-matches `query`, best match first; ties keep
+/// the most recent first. An empty query matches everything.
+fn search_recent_in(file: &Path, query: &str, limit: Option<usize>) -> Result<Vec<RecentEntry>, AppError> {
+    let entries = load(file)?;
+    let mut matcher = Matcher::new(Config::DEFAULT.match_paths());
+    let pattern = Pattern::parse(query, CaseMatching::Ignore, Normalization::Smart);
+    let mut buf = Vec::new();
+    let mut scored: Vec<(u32, RecentEntry)> = entries
+        .into_iter()
+        .filter_map(|entry| {
+            let score = pattern.score(Utf32Str::new(&entry.path, &mut buf), &mut matcher)?;
+            Some((score, entry))
+        })
+        .collect();
+    scored.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+    Ok(finish(scored.into_iter().map(|(_, entry)| entry).collect(), limit))
+}
+
+/// Entries by `access_count`, highest first; ties keep the most recent first.
+fn most_frequent_in(file: &Path, limit: Option<usize>) -> Result<Vec<RecentEntry>, AppError> {
+    let mut entries = load(file)?;
+    entries.sort_by_key(|e| std::cmp::Reverse(e.access_count));
+    Ok(finish(entries, limit))
+}
+
+/// Apply `limit` and fill in `exists`.
+fn finish(mut entries: Vec<RecentEntry>, limit: Option<usize>) -> Vec<RecentEntry> {
+    entries.truncate(limit.unwrap_or(MAX_RECENT_ENTRIES));
+    for entry in &mut entries {
+        entry.exists = Path::new(&entry.path).exists();
+    }
+    entries
+}
+
+/// Best-effort hook for commands that open or list a path; failures are
+/// logged and never surface to the caller.
+pub(crate) fn record(path: &str) {
+    // Keep unit tests of the calling commands from touching the real file
+    if cfg!(test) {
+        return;
+    }
+    if let Err(e) = recent_file().and_then(|file| record_access_in(&file, path)) {
+        log::debug!("Not recording access to {}: {}", path, e);
+    }
+}
+
+/// How often the app has recorded `path`, if it is in the recent list.
+pub(crate) fn access_count(path: &str) -> Option<u32> {
+    let file = recent_file().ok()?;
+    load(&file).ok()?.into_iter().find(|e| e.path == path).map(|e| e.access_count)
+}
+
+/// Mark `path` as just accessed, moving it to the top of the recent list.
+#[tauri::command]
+pub fn record_access(path: String) -> Result<(), AppError> {
+    record_access_in(&recent_file()?, &path)
+}
+
+/// Recent entries, most recent first. Paths that have since disappeared are
+/// returned with `exists: false`.
+#[tauri::command]
+pub fn list_recent(limit: Option<usize>) -> Result<Vec<RecentEntry>, AppError> {
+    list_recent_in(&recent_file()?, limit)
+}
+
+/// Recent entries whose path fuzzy-matches `query` (nucleo, path
+/// scoring), best match first. Nothing is read but the recent list.
+#[tauri::command]
+pub fn search_recent(query: String, limit: Option<usize>) -> Result<Vec<RecentEntry>, AppError> {
+    search_recent_in(&recent_file()?, &query, limit)
+}
+
+/// Recent entries, most accessed first, for a "most used" view.
+#[tauri::command]
+pub fn get_most_frequent(limit: Option<usize>) -> Result<Vec<RecentEntry>, AppError> {
+    most_frequent_in(&recent_file()?, limit)
+}
+
+/// Forget all recent entries.
+#[tauri::command]
+pub fn clear_recent() -> Result<(), AppError> {
+    let file = recent_file()?;
+    let _guard = RECENT_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    if file.exists() {
+        fs::remove_file(&file)?;
+    }
+    log::info!("Cleared recent entries");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn path_str(path: &Path) -> String {
+        path.to_string_lossy().to_string()
+    }
+
+    #[test]
+    fn test_record_dedupes_and_orders_by_recency() {
+        let dir = tempdir().unwrap();
+        let file = dir.path().join("recent.json");
+        let a = dir.path().join("a.txt");
+        let b = dir.path().join("b");
+        fs::write(&a, "a").unwrap();
+        fs::create_dir(&b).unwrap();
+
+        record_access_in(&file, &path_str(&a)).unwrap();
+        record_access_in(&file, &path_str(&b)).unwrap();
+        record_access_in(&file, &path_str(&a)).unwrap();
+
+        let entries = list_recent_in(&file, None).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].path, path_str(&a));
+        assert_eq!(entries[0].access_count, 2);
+        assert_eq!(entries[0].kind, FileKind::File);
+        assert_eq!(entries[1].kind, FileKind::Directory);
+        assert_eq!(list_recent_in(&file, Some(1)).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_missing_paths_are_flagged_not_errors() {
+        let dir = tempdir().unwrap();
+        let file = dir.path().join("recent.json");
+        let gone = dir.path().join("gone.txt");
+        fs::write(&gone, "x").unwrap();
+        record_access_in(&file, &path_str(&gone)).unwrap();
+        fs::remove_file(&gone).unwrap();
+
+        let entries = list_recent_in(&file, None).unwrap();
+        assert!(!entries[0].exists);
+        assert!(matches!(record_access_in(&file, &path_str(&gone)), Err(AppError::NotFound(_))));
+    }
+
+    #[test]
+    fn test_search_recent_and_most_frequent() {
+        let dir = tempdir().unwrap();
+        let file = dir.path().join("recent.json");
+        let docs = dir.path().join("docs");
+        let src = dir.path().join("src");
+        let main = src.join("main.rs");
+        fs::create_dir(&docs).unwrap();
+        fs::create_dir(&src).unwrap();
+        fs::write(&main, "").unwrap();
+        for path in [&docs, &docs, &src, &main, &docs, &main] {
+            record_access_in(&file, &path_str(path)).unwrap();
+        }
+
+        let found = search_recent_in(&file, "srcmain", None).unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].path, path_str(&main));
+        assert_eq!(search_recent_in(&file, "", None).unwrap().len(), 3);
+        assert!(search_recent_in(&file, "zzz", None).unwrap().is_empty());
+
+        let frequent: Vec<String> = most_frequent_in(&file, None).unwrap().into_iter().map(|e| e.path).collect();
+        assert_eq!(frequent, [path_str(&docs), path_str(&main), path_str(&src)]);
+        assert_eq!(most_frequent_in(&file, Some(1)).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_recent_list_is_capped() {
+        let dir = tempdir().unwrap();
+        let file = dir.path().join("recent.json");
+        for i in 0..MAX_RECENT_ENTRIES + 5 {
+            let path = dir.path().join(format!("{}.txt", i));
+            fs::write(&path, "").unwrap();
+            record_access_in(&file, &path_str(&path)).unwrap();
+        }
+        let entries = list_recent_in(&file, None).unwrap();
+        assert_eq!(entries.len(), MAX_RECENT_ENTRIES);
+        assert!(entries[0].path.ends_with(&format!("{}.txt", MAX_RECENT_ENTRIES + 4)));
+    }
+}
@@ -1,13 +1,19 @@
 //! Fuzzy search module for Tauri commands.
 //! Issue: tauri-explorer-az6w, tauri-explorer-nv2y
 
+use crate::content_search::{run_linked_content_search, ContentSearchEvent, ContentSearchResult};
 use crate::error::AppError;
+use crate::export::{write_export, ExportFormat};
+use chrono::{DateTime, Local, NaiveDate, NaiveDateTime, TimeZone};
+use globset::{GlobBuilder, GlobMatcher};
 use jwalk::WalkDir;
 use nucleo_matcher::pattern::{CaseMatching, Normalization, Pattern};
 use nucleo_matcher::{Config, Matcher, Utf32Str};
-use serde::Serialize;
-use std::path::PathBuf;
-use std::sync::atomic::Ordering;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
 use tauri::{AppHandle, Emitter};
 
 /// Directories to skip during recursive search (for performance).
@@ -31,7 +37,7 @@ const SKIP_DIRS: &[&str] = &[
 ];
 
 /// Search result from fuzzy file search.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SearchResult {
     pub name: String,
     pub path: String,
@@ -39,6 +45,211 @@ pub struct SearchResult {
     pub relative_path: String,
     pub score: u32,
     pub kind: String, // "file" or "directory"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub modified: Option<String>, // ISO 8601, only set by date-ordered searches
+    /// Byte offsets within `name` of the characters the fuzzy query matched,
+    /// for highlighting. Only set by fuzzy searches.
+    #[serde(rename = "matchIndices", default, skip_serializing_if = "Vec::is_empty")]
+    pub match_indices: Vec<u32>,
+}
+
+/// Optional overrides for fuzzy search traversal and scoring.
+/// Every field defaults to the built-in behavior, so callers can omit it.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct SearchOptions {
+    /// Include dotfiles and dot-directories.
+    pub include_hidden: bool,
+    /// Maximum depth below the root (1 = direct children only).
+    pub max_depth: Option<u32>,
+    /// Stop after walking this many entries.
+    pub max_entries: Option<usize>,
+    /// Drop results scoring below this threshold.
+    pub min_score: Option<u32>,
+    /// Directory names to skip in addition to `SKIP_DIRS`.
+    pub extra_skip_dirs: Vec<String>,
+    /// Whitelist mode: when non-empty, only these root-relative directories
+    /// are searched. Listing a skip-listed directory (e.g. `node_modules`)
+    /// here searches inside it.
+    pub allowed_only_dirs: Vec<String>,
+    /// Score multiplier per directory level below the root
+    /// (`score * depth_penalty^depth`). Defaults to 0.95; 1.0 disables it.
+    pub depth_penalty: Option<f32>,
+    /// Score the query against the name, the whole relative path, or both.
+    pub score_against: ScoreTarget,
+    /// Only return files or only directories. With `Directory`, files are
+    /// dropped during the walk and never scored.
+    pub kind: Option<EntryKind>,
+    /// Per-search skip list and hidden-file rule. When set, it takes
+    /// precedence over `include_hidden`.
+    pub scope: Option<SearchScope>,
+}
+
+impl SearchOptions {
+    fn validate(&self) -> Result<(), AppError> {
+        self.scope.as_ref().map_or(Ok(()), SearchScope::validate)
+    }
+}
+
+/// Which directories a search skips, for projects that need to search
+/// inside `build/` or `dist/`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct SearchScope {
+    /// Directory names skipped on top of the base list.
+    pub extra_ignore_dirs: Vec<String>,
+    /// Replaces `SKIP_DIRS` as the base list; `Some(vec![])` skips nothing.
+    /// Entries are directory names, not paths.
+    pub override_ignore_dirs: Option<Vec<String>>,
+    /// Skip dotfiles and dot-directories. Defaults to true.
+    pub ignore_hidden: bool,
+}
+
+impl Default for SearchScope {
+    fn default() -> Self {
+        SearchScope {
+            extra_ignore_dirs: Vec::new(),
+            override_ignore_dirs: None,
+            ignore_hidden: true,
+        }
+    }
+}
+
+impl SearchScope {
+    fn validate(&self) -> Result<(), AppError> {
+        let mut overrides = self.override_ignore_dirs.iter().flatten();
+        match overrides.find(|d| d.contains(['/', '\\'])) {
+            Some(dir) => Err(AppError::InvalidPath(format!(
+                "Ignored directories must be names, not paths: {}",
+                dir
+            ))),
+            None => Ok(()),
+        }
+    }
+}
+
+/// What fuzzy queries are scored against, for `SearchOptions::score_against`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ScoreTarget {
+    #[default]
+    FileName,
+    /// The `/`-separated relative path, with nucleo's path-segment bonuses,
+    /// so `src/main` finds `src/main.rs`.
+    RelativePath,
+    /// Whichever of the name and the relative path scores higher.
+    Both,
+}
+
+/// Which entries a search returns, for `SearchOptions::kind`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EntryKind {
+    File,
+    Directory,
+    #[default]
+    Both,
+}
+
+impl EntryKind {
+    fn includes(self, is_dir: bool) -> bool {
+        match self {
+            EntryKind::File => !is_dir,
+            EntryKind::Directory => is_dir,
+            EntryKind::Both => true,
+        }
+    }
+}
+
+/// Default for `SearchOptions::depth_penalty`.
+const DEFAULT_DEPTH_PENALTY: f32 = 0.95;
+
+/// The scoring part of `SearchOptions`.
+#[derive(Debug, Clone, Copy)]
+struct ScoreConfig {
+    min_score: u32,
+    depth_penalty: f32,
+    target: ScoreTarget,
+}
+
+impl Default for ScoreConfig {
+    fn default() -> Self {
+        ScoreConfig::from(&SearchOptions::default())
+    }
+}
+
+impl From<&SearchOptions> for ScoreConfig {
+    fn from(options: &SearchOptions) -> Self {
+        ScoreConfig {
+            min_score: options.min_score.unwrap_or(0),
+            depth_penalty: options
+                .depth_penalty
+                .filter(|p| p.is_finite())
+                .unwrap_or(DEFAULT_DEPTH_PENALTY)
+                .clamp(0.0, 1.0),
+            target: options.score_against,
+        }
+    }
+}
+
+impl ScoreConfig {
+    /// A matcher tuned for the haystack this config scores.
+    fn matcher(&self) -> Matcher {
+        Matcher::new(matcher_config(self.target == ScoreTarget::RelativePath))
+    }
+}
+
+/// nucleo config for scoring names, or paths with `/` segment bonuses.
+fn matcher_config(match_paths: bool) -> Config {
+    if match_paths {
+        Config::DEFAULT.match_paths()
+    } else {
+        Config::DEFAULT
+    }
+}
+
+/// Score `pattern` against the name and/or `/`-separated path `target`
+/// covers. Returns the best score and whether it came from the path;
+/// `matcher` is left configured for that haystack.
+fn best_match(
+    name: &str,
+    path: &str,
+    pattern: &Pattern,
+    matcher: &mut Matcher,
+    target: ScoreTarget,
+) -> Option<(u32, bool)> {
+    let mut buf = Vec::new();
+    let mut score = |haystack: &str, match_paths: bool| {
+        matcher.config = matcher_config(match_paths);
+        pattern.score(Utf32Str::new(haystack, &mut buf), matcher)
+    };
+    let name_score = match target {
+        ScoreTarget::RelativePath => None,
+        _ => score(name, false),
+    };
+    let path_score = match target {
+        ScoreTarget::FileName => None,
+        _ => score(path, true),
+    };
+    let best = match (name_score, path_score) {
+        (Some(n), Some(p)) if n >= p => Some((n, false)),
+        (_, Some(p)) => Some((p, true)),
+        (n, None) => n.map(|n| (n, false)),
+    };
+    if let Some((_, in_path)) = best {
+        matcher.config = matcher_config(in_path);
+    }
+    best
+}
+
+/// Relative path with `/` separators on every platform, as nucleo's path
+/// scoring expects.
+fn slash_path(relative_path: &str) -> std::borrow::Cow<'_, str> {
+    if std::path::MAIN_SEPARATOR == '/' {
+        std::borrow::Cow::Borrowed(relative_path)
+    } else {
+        std::borrow::Cow::Owned(relative_path.replace(std::path::MAIN_SEPARATOR, "/"))
+    }
 }
 
 /// Search response.
@@ -68,26 +279,149 @@ static SEARCHES: crate::task_registry::TaskRegistry = crate::task_registry::Task
 /// need this cap.
 const WALK_SAFETY_CAP: usize = 500_000;
 
-/// Collect file/directory entries under `root_path` using jwalk.
-/// Returns `(relative_path, name, is_dir)` tuples.
-/// Capped at `WALK_SAFETY_CAP` to bound memory for the non-streaming path.
-fn walk_entries(root_path: &PathBuf) -> Vec<(String, String, bool)> {
-    let mut entries: Vec<(String, String, bool)> = Vec::new();
-
-    let walker = WalkDir::new(root_path)
+/// Build a jwalk walker over `root_path` that skips hidden entries and
+/// does not descend into `SKIP_DIRS`.
+fn build_walker(root_path: &Path) -> WalkDir {
+    WalkDir::new(root_path)
         .skip_hidden(true)
         .process_read_dir(|_depth, _path, _read_dir_state, children| {
-            for entry in children.iter_mut() {
-                if let Ok(e) = entry {
-                    let name = e.file_name().to_string_lossy();
-                    if SKIP_DIRS.contains(&name.as_ref()) {
-                        e.read_children_path = None;
-                    }
+            prune_skip_dirs(children, &[]);
+        })
+}
+
+/// Don't remove skip-listed dirs — they should still appear as search
+/// results. Instead, prevent descent by clearing read_children_path so
+/// their contents aren't walked. Names in `keep` are walked anyway.
+fn prune_skip_dirs(children: &mut [jwalk::Result<WalkEntry>], keep: &[&str]) {
+    for entry in children.iter_mut() {
+        if let Ok(e) = entry {
+            let name = e.file_name().to_string_lossy();
+            if SKIP_DIRS.contains(&name.as_ref()) && !keep.contains(&name.as_ref()) {
+                e.read_children_path = None;
+            }
+        }
+    }
+}
+
+/// Traversal rules derived from `SearchOptions`, shared with jwalk's
+/// read_dir callback and the result loops.
+struct WalkScope {
+    root: PathBuf,
+    include_hidden: bool,
+    skip_dirs: Vec<String>,
+    allowed_only_dirs: Vec<PathBuf>,
+    kind: EntryKind,
+}
+
+impl WalkScope {
+    fn new(root_path: &Path, options: &SearchOptions) -> Self {
+        let scope = options.scope.as_ref();
+        let mut skip_dirs: Vec<String> = match scope.and_then(|s| s.override_ignore_dirs.as_ref()) {
+            Some(dirs) => dirs.clone(),
+            None => SKIP_DIRS.iter().map(|d| d.to_string()).collect(),
+        };
+        skip_dirs.extend(options.extra_skip_dirs.iter().cloned());
+        skip_dirs.extend(scope.into_iter().flat_map(|s| s.extra_ignore_dirs.iter().cloned()));
+        WalkScope {
+            root: root_path.to_path_buf(),
+            include_hidden: scope.map_or(options.include_hidden, |s| !s.ignore_hidden),
+            skip_dirs,
+            allowed_only_dirs: options
+                .allowed_only_dirs
+                .iter()
+                .map(|d| PathBuf::from(d.trim_matches('/')))
+                .filter(|d| !d.as_os_str().is_empty())
+                .collect(),
+            kind: options.kind.unwrap_or_default(),
+        }
+    }
+
+    /// Whether a root-relative path falls inside the whitelist.
+    /// Always true when no whitelist is set.
+    fn includes(&self, relative: &Path) -> bool {
+        self.allowed_only_dirs.is_empty()
+            || self.allowed_only_dirs.iter().any(|d| relative.starts_with(d))
+    }
+
+    /// Whether a root-relative directory is a whitelisted directory or one
+    /// of its ancestors, and so must be walked.
+    fn leads_to_allowed(&self, relative: &Path) -> bool {
+        self.allowed_only_dirs.iter().any(|d| d.starts_with(relative))
+    }
+
+    fn is_skipped_dir(&self, name: &str, relative: &Path) -> bool {
+        let listed = self.skip_dirs.iter().any(|d| d == name);
+        // Whitelisting a directory overrides the skip list
+        listed && !self.leads_to_allowed(relative)
+    }
+
+    /// Drop children outside the whitelist (and files in directory-only
+    /// searches) and stop descent into skipped dirs.
+    /// `depth` is `None` for jwalk's initial call, whose only child is the root.
+    fn prune(
+        &self,
+        depth: Option<usize>,
+        parent: &Path,
+        children: &mut Vec<jwalk::Result<WalkEntry>>,
+    ) {
+        if depth.is_none() {
+            return;
+        }
+        let relative_parent = parent.strip_prefix(&self.root).unwrap_or(parent);
+
+        if !self.allowed_only_dirs.is_empty() {
+            children.retain(|entry| match entry {
+                Ok(e) => {
+                    let relative = relative_parent.join(e.file_name());
+                    self.includes(&relative)
+                        || (e.file_type().is_dir() && self.leads_to_allowed(&relative))
                 }
+                Err(_) => true,
+            });
+        }
+
+        // Directories are still walked for their subdirectories
+        if self.kind == EntryKind::Directory {
+            children.retain(|entry| entry.as_ref().map_or(true, |e| e.file_type().is_dir()));
+        }
+
+        for e in children.iter_mut().flatten() {
+            let name = e.file_name().to_string_lossy();
+            if self.is_skipped_dir(&name, &relative_parent.join(name.as_ref())) {
+                e.read_children_path = None;
             }
+        }
+    }
+}
+
+/// Build a jwalk walker honoring `SearchOptions`. Returns the walk scope
+/// so result loops can apply the same hidden/whitelist rules.
+fn build_scoped_walker(root_path: &Path, options: &SearchOptions) -> (WalkDir, Arc<WalkScope>) {
+    let scope = Arc::new(WalkScope::new(root_path, options));
+    let walk_scope = scope.clone();
+
+    let mut walker = WalkDir::new(root_path)
+        .skip_hidden(!scope.include_hidden)
+        .process_read_dir(move |depth, path, _read_dir_state, children| {
+            walk_scope.prune(depth, path, children);
         });
+    if let Some(max_depth) = options.max_depth {
+        walker = walker.max_depth(max_depth as usize);
+    }
+
+    (walker, scope)
+}
 
-    for entry in walker.into_iter().take(WALK_SAFETY_CAP) {
+/// Collect file/directory entries under `root_path` using jwalk.
+/// Returns `(relative_path, name, is_dir)` tuples.
+/// Capped at `WALK_SAFETY_CAP` (or `options.max_entries`) to bound memory
+/// for the non-streaming path.
+fn walk_entries(root_path: &PathBuf, options: &SearchOptions) -> Vec<(String, String, bool)> {
+    let mut entries: Vec<(String, String, bool)> = Vec::new();
+    let (walker, scope) = build_scoped_walker(root_path, options);
+    let max_entries = options.max_entries.unwrap_or(WALK_SAFETY_CAP);
+
+    for entry in walker.into_iter().take(max_entries) {
         let entry = match entry {
             Ok(e) => e,
             Err(_) => continue,
@@ -100,8 +434,8 @@ fn walk_entries(root_path: &PathBuf) -> Vec<(String, String, bool)> {
 
         let path = entry.path();
         let relative_path = match path.strip_prefix(root_path) {
-            Ok(p) => p.to_string_lossy().to_string(),
-            Err(_) => continue,
+            Ok(p) if scope.includes(p) => p.to_string_lossy().to_string(),
+            _ => continue,
         };
 
         let name = path
@@ -110,11 +444,14 @@ fn walk_entries(root_path: &PathBuf) -> Vec<(String, String, bool)> {
             .unwrap_or_default();
 
         // Skip hidden files not caught by walker
-        if name.starts_with('.') {
+        if !scope.include_hidden && name.starts_with('.') {
             continue;
         }
 
         let is_dir = entry.file_type().is_dir();
+        if !scope.kind.includes(is_dir) {
+            continue;
+        }
         entries.push((relative_path, name, is_dir));
     }
 
@@ -126,9 +463,12 @@ fn walk_entries(root_path: &PathBuf) -> Vec<(String, String, bool)> {
 const DIRECTORY_BONUS: u32 = 30;
 
 /// Score an entry against a query. Returns Some(score) if matched, None otherwise.
-/// Uses nucleo fuzzy matching with a case-insensitive substring fallback.
+/// Uses nucleo fuzzy matching against the name, the relative path or the
+/// better of both (see `ScoreTarget`) with a case-insensitive substring
+/// fallback.
 /// Shallower entries (fewer path components) get a depth bonus so items
-/// closer to the search root rank higher than deeply nested ones.
+/// closer to the search root rank higher than deeply nested ones, and the
+/// total is then scaled by `depth_penalty` once per directory level.
 /// Directories get an additional bonus to rank above files.
 fn score_entry(
     name: &str,
@@ -137,31 +477,78 @@ fn score_entry(
     query_lower: &str,
     pattern: &Pattern,
     matcher: &mut Matcher,
+    config: &ScoreConfig,
 ) -> Option<u32> {
-    let mut buf = Vec::new();
-    let haystack = Utf32Str::new(name, &mut buf);
-    let base_score = if let Some(score) = pattern.score(haystack, matcher) {
+    let path = slash_path(relative_path);
+    let base_score = if let Some((score, _)) = best_match(name, &path, pattern, matcher, config.target) {
         score
-    } else if name.to_lowercase().contains(query_lower)
-        || relative_path.to_lowercase().contains(query_lower)
-    {
+    } else if name.to_lowercase().contains(query_lower) || path.to_lowercase().contains(query_lower) {
         1
     } else {
         return None;
     };
 
+    // Directory levels below the root's direct children
+    let levels = path.matches('/').count() as u32;
+
     // Depth bonus: depth 1 (direct child) gets +50, each extra level reduces by 5.
     // Clamped to 0 so deep items are never penalized below their base score.
-    let depth = relative_path.matches('/').count() + 1;
-    let depth_bonus = (50u32).saturating_sub((depth as u32 - 1) * 5);
+    let depth_bonus = (50u32).saturating_sub(levels * 5);
     let dir_bonus = if is_dir { DIRECTORY_BONUS } else { 0 };
-    Some(base_score.saturating_add(depth_bonus).saturating_add(dir_bonus))
+    let score = base_score.saturating_add(depth_bonus).saturating_add(dir_bonus);
+
+    let penalty = config.depth_penalty.powi(levels as i32);
+    Some((score as f32 * penalty).round() as u32)
+}
+
+/// Byte offsets within `name` of the characters `pattern` matches, sorted.
+/// When the best match is against the relative path, only positions that
+/// fall inside the name are kept.
+/// Empty when the entry only matched through the substring fallback.
+fn match_indices(
+    name: &str,
+    relative_path: &str,
+    pattern: &Pattern,
+    matcher: &mut Matcher,
+    target: ScoreTarget,
+) -> Vec<u32> {
+    let path = slash_path(relative_path);
+    let (haystack, name_start) = match best_match(name, &path, pattern, matcher, target) {
+        Some((_, false)) => (name, 0),
+        Some((_, true)) => (path.as_ref(), path.len().saturating_sub(name.len())),
+        None => return Vec::new(),
+    };
+
+    let mut buf = Vec::new();
+    let mut char_indices = Vec::new();
+    if pattern
+        .indices(Utf32Str::new(haystack, &mut buf), matcher, &mut char_indices)
+        .is_none()
+    {
+        return Vec::new();
+    }
+    char_indices.sort_unstable();
+    char_indices.dedup();
+
+    // nucleo reports character positions; the UI slices by byte offset
+    let byte_offsets: Vec<usize> = haystack.char_indices().map(|(offset, _)| offset).collect();
+    char_indices
+        .into_iter()
+        .filter_map(|i| byte_offsets.get(i as usize).copied())
+        .filter(|&offset| offset >= name_start)
+        .map(|offset| (offset - name_start) as u32)
+        .collect()
 }
 
 /// Fuzzy search for files and directories recursively (non-streaming version).
 /// Uses nucleo for fast fuzzy matching and jwalk for parallel traversal.
 #[tauri::command]
-pub fn fuzzy_search(query: String, root: String, limit: usize) -> Result<SearchResponse, AppError> {
+pub fn fuzzy_search(
+    query: String,
+    root: String,
+    limit: usize,
+    options: Option<SearchOptions>,
+) -> Result<SearchResponse, AppError> {
     let root_path = PathBuf::from(&root);
 
     if !root_path.exists() {
@@ -173,14 +560,17 @@ pub fn fuzzy_search(query: String, root: String, limit: usize) -> Result<SearchR
     }
 
     let limit = limit.min(100).max(1);
-    let entries = walk_entries(&root_path);
+    let options = options.unwrap_or_default();
+    options.validate()?;
+    let config = ScoreConfig::from(&options);
+    let entries = walk_entries(&root_path, &options);
     log::debug!("fuzzy_search: query={:?} entries={}", query, entries.len());
 
     if entries.is_empty() {
         return Ok(SearchResponse { results: vec![] });
     }
 
-    let mut matcher = Matcher::new(Config::DEFAULT);
+    let mut matcher = config.matcher();
     let pattern = Pattern::parse(&query, CaseMatching::Ignore, Normalization::Smart);
     let query_lower = query.to_lowercase();
 
@@ -188,7 +578,8 @@ pub fn fuzzy_search(query: String, root: String, limit: usize) -> Result<SearchR
         .iter()
         .enumerate()
         .filter_map(|(idx, (relative_path, name, is_dir))| {
-            score_entry(name, relative_path, *is_dir, &query_lower, &pattern, &mut matcher)
+            score_entry(name, relative_path, *is_dir, &query_lower, &pattern, &mut matcher, &config)
+                .filter(|score| *score >= config.min_score)
                 .map(|score| (score, idx))
         })
         .collect();
@@ -211,6 +602,8 @@ pub fn fuzzy_search(query: String, root: String, limit: usize) -> Result<SearchR
                 } else {
                     "file".to_string()
                 },
+                modified: None,
+                match_indices: match_indices(name, relative_path, &pattern, &mut matcher, config.target),
             }
         })
         .collect();
@@ -221,6 +614,7 @@ pub fn fuzzy_search(query: String, root: String, limit: usize) -> Result<SearchR
 /// Start a streaming fuzzy search that emits results incrementally.
 /// Returns a search ID that can be used to cancel the search.
 /// `boost_prefix` is an optional path prefix; results under it get a score bonus.
+/// `options` overrides traversal defaults (hidden files, depth, skip list).
 #[tauri::command]
 pub fn start_streaming_search(
     app: AppHandle,
@@ -228,6 +622,7 @@ pub fn start_streaming_search(
     root: String,
     limit: usize,
     boost_prefix: Option<String>,
+    options: Option<SearchOptions>,
 ) -> Result<u64, AppError> {
     let root_path = PathBuf::from(&root);
 
@@ -240,205 +635,1130 @@ pub fn start_streaming_search(
     }
 
     let limit = limit.min(100).max(1);
+    let options = options.unwrap_or_default();
+    options.validate()?;
     log::debug!("start_streaming_search: id=pending query={:?} root={:?}", query, root);
     let (search_id, cancelled) = SEARCHES.start();
 
-    let boost_path = boost_prefix.map(PathBuf::from);
-
+    let query = FuzzyQuery {
+        query,
+        root_path,
+        limit,
+        boost_path: boost_prefix.map(PathBuf::from),
+        options,
+    };
     // Spawn search in background thread
     std::thread::spawn(move || {
-        let mut all_results: Vec<SearchResult> = Vec::new();
-        let mut total_scanned = 0;
-        let batch_size = 500;
+        run_fuzzy_search(search_id, &query, &cancelled, &|event| {
+            let _ = app.emit("search-results", event);
+        });
+        SEARCHES.cleanup(search_id);
+    });
 
-        let query_lower = query.to_lowercase();
-        let mut matcher = Matcher::new(Config::DEFAULT);
-        let pattern = Pattern::parse(&query, CaseMatching::Ignore, Normalization::Smart);
-
-        let walker = WalkDir::new(&root_path)
-            .skip_hidden(true)
-            .process_read_dir(|_depth, _path, _read_dir_state, children| {
-                // Don't remove skip-listed dirs — they should still appear as
-                // search results. Instead, prevent descent by clearing
-                // read_children_path so their contents aren't walked.
-                for entry in children.iter_mut() {
-                    if let Ok(e) = entry {
-                        let name = e.file_name().to_string_lossy();
-                        if SKIP_DIRS.contains(&name.as_ref()) {
-                            e.read_children_path = None;
-                        }
-                    }
-                }
-            });
+    Ok(search_id)
+}
+
+/// Parameters of a streaming fuzzy search, after validation.
+struct FuzzyQuery {
+    query: String,
+    root_path: PathBuf,
+    limit: usize,
+    boost_path: Option<PathBuf>,
+    options: SearchOptions,
+}
 
-        let mut pending_entries: Vec<(String, String, bool)> = Vec::new();
+/// Walk `query.root_path` and score entries in batches, passing the current
+/// top results to `emit` after each batch and once more with `done` set.
+/// Returns early without the final event when `cancelled` is set.
+fn run_fuzzy_search(
+    search_id: u64,
+    query: &FuzzyQuery,
+    cancelled: &AtomicBool,
+    emit: &dyn Fn(SearchResultsEvent),
+) {
+    let FuzzyQuery { root_path, limit, options, .. } = query;
+    let (root_path, limit) = (root_path.as_path(), *limit);
+    let boost_path = query.boost_path.as_ref();
+    let config = ScoreConfig::from(options);
+    let max_entries = options.max_entries.unwrap_or(usize::MAX);
 
-        for entry in walker {
-            // Check for cancellation
-            if cancelled.load(Ordering::Relaxed) {
-                break;
-            }
+    let mut all_results: Vec<SearchResult> = Vec::new();
+    let mut total_scanned = 0;
+    let batch_size = 500;
 
-            let entry = match entry {
-                Ok(e) => e,
-                Err(_) => continue,
-            };
+    let query_lower = query.query.to_lowercase();
+    let mut matcher = config.matcher();
+    let pattern = Pattern::parse(&query.query, CaseMatching::Ignore, Normalization::Smart);
 
-            // Skip the root itself
-            if entry.path() == root_path {
-                continue;
-            }
+    let (walker, scope) = build_scoped_walker(root_path, options);
+
+    let mut pending_entries: Vec<(String, String, bool)> = Vec::new();
+
+    for entry in walker.into_iter().take(max_entries) {
+        // Check for cancellation
+        if cancelled.load(Ordering::Relaxed) {
+            break;
+        }
+
+        let entry = match entry {
+            Ok(e) => e,
+            Err(_) => continue,
+        };
+
+        // Skip the root itself
+        if entry.path() == root_path {
+            continue;
+        }
+
+        let path = entry.path();
+        let relative_path = match path.strip_prefix(root_path) {
+            Ok(p) if scope.includes(p) => p.to_string_lossy().to_string(),
+            _ => continue,
+        };
+
+        let name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+
+        // Skip hidden files
+        if !scope.include_hidden && name.starts_with('.') {
+            continue;
+        }
+
+        let is_dir = entry.file_type().is_dir();
+        if !scope.kind.includes(is_dir) {
+            continue;
+        }
+        pending_entries.push((relative_path, name, is_dir));
+        total_scanned += 1;
+
+        // Process batch and emit results
+        if pending_entries.len() >= batch_size {
+            process_batch(
+                emit,
+                search_id,
+                &mut pending_entries,
+                &mut all_results,
+                root_path,
+                &pattern,
+                &mut matcher,
+                limit,
+                total_scanned,
+                boost_path,
+                &query_lower,
+                &config,
+            );
+        }
+    }
+
+    // Process remaining entries
+    if !pending_entries.is_empty() && !cancelled.load(Ordering::Relaxed) {
+        process_batch(
+            emit,
+            search_id,
+            &mut pending_entries,
+            &mut all_results,
+            root_path,
+            &pattern,
+            &mut matcher,
+            limit,
+            total_scanned,
+            boost_path,
+            &query_lower,
+            &config,
+        );
+    }
+
+    // Emit final results with done=true
+    if !cancelled.load(Ordering::Relaxed) {
+        emit(SearchResultsEvent {
+            search_id,
+            results: all_results.into_iter().take(limit).collect(),
+            done: true,
+            total_scanned,
+        });
+    }
+}
+
+fn process_batch(
+    emit: &dyn Fn(SearchResultsEvent),
+    search_id: u64,
+    pending: &mut Vec<(String, String, bool)>,
+    all_results: &mut Vec<SearchResult>,
+    root_path: &Path,
+    pattern: &Pattern,
+    matcher: &mut Matcher,
+    limit: usize,
+    total_scanned: usize,
+    boost_prefix: Option<&PathBuf>,
+    query_lower: &str,
+    config: &ScoreConfig,
+) {
+    // Score boost for results under the priority prefix (e.g. CWD)
+    const BOOST_SCORE: u32 = 100;
 
-            let path = entry.path();
-            let relative_path = match path.strip_prefix(&root_path) {
-                Ok(p) => p.to_string_lossy().to_string(),
-                Err(_) => continue,
+    let mut new_results: Vec<SearchResult> = pending
+        .iter()
+        .filter_map(|(relative_path, name, is_dir)| {
+            let score = score_entry(name, relative_path, *is_dir, query_lower, pattern, matcher, config)
+                .filter(|score| *score >= config.min_score)?;
+            let full_path = root_path.join(relative_path);
+            // Boost score for results under the priority prefix
+            let boosted_score = if let Some(prefix) = boost_prefix {
+                if full_path.starts_with(prefix) {
+                    score.saturating_add(BOOST_SCORE)
+                } else {
+                    score
+                }
+            } else {
+                score
             };
+            Some(SearchResult {
+                name: name.clone(),
+                path: full_path.to_string_lossy().to_string(),
+                relative_path: relative_path.clone(),
+                score: boosted_score,
+                kind: if *is_dir {
+                    "directory".to_string()
+                } else {
+                    "file".to_string()
+                },
+                modified: None,
+                match_indices: match_indices(name, relative_path, pattern, matcher, config.target),
+            })
+        })
+        .collect();
 
-            let name = path
-                .file_name()
-                .map(|n| n.to_string_lossy().to_string())
-                .unwrap_or_default();
+    pending.clear();
 
-            // Skip hidden files
-            if name.starts_with('.') {
-                continue;
-            }
+    // Merge with existing results — keep a generous buffer so subdirectory
+    // matches from later batches aren't prematurely dropped.
+    all_results.append(&mut new_results);
+    all_results.sort_by(|a, b| b.score.cmp(&a.score));
+    all_results.truncate(limit * 10);
 
-            let is_dir = entry.file_type().is_dir();
-            pending_entries.push((relative_path, name, is_dir));
-            total_scanned += 1;
+    // Emit current top results
+    let current_top: Vec<SearchResult> = all_results.iter().take(limit).cloned().collect();
+
+    if !current_top.is_empty() {
+        emit(SearchResultsEvent {
+            search_id,
+            results: current_top,
+            done: false,
+            total_scanned,
+        });
+    }
+}
+
+/// Cancel an active streaming or combined search.
+#[tauri::command]
+pub fn cancel_search(search_id: u64) -> Result<(), AppError> {
+    SEARCHES.cancel(search_id);
+    Ok(())
+}
+
+/// Save search results to `output_path`. JSON keeps every field; CSV and TSV
+/// have the columns `name,path,relative_path,score,kind`. Returns the number
+/// of results written.
+#[tauri::command]
+pub fn export_search_results(
+    results: Vec<SearchResult>,
+    output_path: String,
+    format: ExportFormat,
+) -> Result<usize, AppError> {
+    let rows: Vec<Vec<String>> = results
+        .iter()
+        .map(|r| {
+            vec![
+                r.name.clone(),
+                r.path.clone(),
+                r.relative_path.clone(),
+                r.score.to_string(),
+                r.kind.clone(),
+            ]
+        })
+        .collect();
+    write_export(
+        &output_path,
+        format,
+        &results,
+        &["name", "path", "relative_path", "score", "kind"],
+        &rows,
+    )
+}
+
+// ===================
+// Combined Search
+// ===================
+
+/// Event payload for `search_combined`: a snapshot of both result lists so
+/// far, so each event replaces the previous one.
+#[derive(Debug, Clone, Serialize)]
+pub struct CombinedSearchEvent {
+    #[serde(rename = "searchId")]
+    pub search_id: u64,
+    /// Current top filename matches, best first.
+    #[serde(rename = "filenameResults")]
+    pub filename_results: Vec<SearchResult>,
+    /// Files with matching lines, in the order they were found.
+    #[serde(rename = "contentResults")]
+    pub content_results: Vec<ContentSearchResult>,
+    /// Both searches have finished.
+    pub done: bool,
+}
+
+/// Results gathered from both halves of a combined search.
+#[derive(Default)]
+struct CombinedProgress {
+    filename_results: Vec<SearchResult>,
+    content_results: Vec<ContentSearchResult>,
+}
+
+/// Search file names (fuzzy) and file contents for `query` at once, on two
+/// threads sharing one search ID. Emits `combined-search-results` with the
+/// merged results whenever either side reports; the last event has `done`
+/// set. `cancel_search` stops both. Content matching is case-insensitive;
+/// `regex_mode` applies to it only.
+#[tauri::command]
+pub fn search_combined(
+    app: AppHandle,
+    query: String,
+    root: String,
+    limit: usize,
+    regex_mode: bool,
+) -> Result<u64, AppError> {
+    let root_path = PathBuf::from(&root);
+    if !root_path.is_dir() {
+        return Err(AppError::NotFound(root));
+    }
+    if query.is_empty() {
+        return Err(AppError::Other("Search query cannot be empty".into()));
+    }
+
+    let limit = limit.clamp(1, 100);
+    let (search_id, cancelled) = SEARCHES.start();
+    let progress = Arc::new(Mutex::new(CombinedProgress::default()));
+    // Holding the lock while emitting keeps events in order
+    let publish = move |progress: &CombinedProgress, done: bool| {
+        let _ = app.emit(
+            "combined-search-results",
+            CombinedSearchEvent {
+                search_id,
+                filename_results: progress.filename_results.clone(),
+                content_results: progress.content_results.clone(),
+                done,
+            },
+        );
+    };
+
+    let content = {
+        let (query, root_path, cancelled) = (query.clone(), root_path.clone(), cancelled.clone());
+        let (progress, publish) = (progress.clone(), publish.clone());
+        std::thread::spawn(move || {
+            let on_event = |event: ContentSearchEvent| {
+                if event.results.is_empty() {
+                    return;
+                }
+                let mut progress = progress.lock().unwrap_or_else(|e| e.into_inner());
+                progress.content_results.extend(event.results);
+                progress.content_results.truncate(limit);
+                publish(&progress, false);
+            };
+            if let Err(e) = run_linked_content_search(query, &root_path, regex_mode, limit, cancelled, &on_event) {
+                log::warn!("Combined search {}: content search failed: {}", search_id, e);
+            }
+        })
+    };
+
+    std::thread::spawn(move || {
+        let fuzzy = FuzzyQuery {
+            query,
+            root_path,
+            limit,
+            boost_path: None,
+            options: SearchOptions::default(),
+        };
+        run_fuzzy_search(search_id, &fuzzy, &cancelled, &|event| {
+            let mut progress = progress.lock().unwrap_or_else(|e| e.into_inner());
+            progress.filename_results = event.results;
+            publish(&progress, false);
+        });
+
+        let _ = content.join();
+        if !cancelled.load(Ordering::Relaxed) {
+            publish(&progress.lock().unwrap_or_else(|e| e.into_inner()), true);
+        }
+        SEARCHES.cleanup(search_id);
+    });
+
+    log::debug!("search_combined: id={} regex={} limit={}", search_id, regex_mode, limit);
+    Ok(search_id)
+}
+
+// ===================
+// Filtered Searches
+// ===================
+
+/// Result cap for filter-style searches (date, extension, glob). These are
+/// exhaustive listings rather than top-N fuzzy rankings, so allow more than
+/// the QuickOpen limit.
+const MAX_FILTER_RESULTS: usize = 1000;
+
+/// Number of walked entries between streaming progress events.
+const FILTER_BATCH_SIZE: usize = 500;
+
+type WalkEntry = jwalk::DirEntry<((), ())>;
+
+/// Validate that `root` exists and is a directory.
+fn resolve_search_root(root: &str) -> Result<PathBuf, AppError> {
+    let root_path = PathBuf::from(root);
+
+    if !root_path.exists() {
+        return Err(AppError::NotFound(root.to_string()));
+    }
+
+    if !root_path.is_dir() {
+        return Err(AppError::InvalidPath(format!("Not a directory: {}", root)));
+    }
+
+    Ok(root_path)
+}
+
+/// How much a filtered walk may collect and scan.
+#[derive(Debug, Clone, Copy)]
+struct FilterBounds {
+    limit: usize,
+    max_entries: usize,
+    /// Unranked searches stop walking as soon as `limit` results are found.
+    ranked: bool,
+}
+
+/// Walk `walker` and collect entries accepted by `accept`, keeping the top
+/// `limit` ordered by the rank `accept` returns (highest first, ties in walk order).
+/// `on_batch` receives the current top results every `FILTER_BATCH_SIZE` entries
+/// so streaming callers can emit progress.
+/// Returns `(results, total_scanned)`.
+fn collect_filtered<F, B>(
+    walker: WalkDir,
+    root_path: &Path,
+    bounds: FilterBounds,
+    cancelled: &AtomicBool,
+    mut accept: F,
+    mut on_batch: B,
+) -> (Vec<SearchResult>, usize)
+where
+    F: FnMut(&WalkEntry, &str, &str) -> Option<(u64, SearchResult)>,
+    B: FnMut(Vec<SearchResult>, usize),
+{
+    let mut ranked: Vec<(u64, SearchResult)> = Vec::new();
+    let mut total_scanned = 0;
+    let mut changed = false;
+
+    for entry in walker.into_iter().take(bounds.max_entries) {
+        if cancelled.load(Ordering::Relaxed) {
+            break;
+        }
+
+        let entry = match entry {
+            Ok(e) => e,
+            Err(_) => continue,
+        };
+
+        // Skip the root itself
+        if entry.depth() == 0 {
+            continue;
+        }
+
+        let path = entry.path();
+        let relative_path = match path.strip_prefix(root_path) {
+            Ok(p) => p.to_string_lossy().to_string(),
+            Err(_) => continue,
+        };
+        let name = entry.file_name().to_string_lossy().to_string();
+        total_scanned += 1;
+
+        if let Some(hit) = accept(&entry, &relative_path, &name) {
+            ranked.push(hit);
+            changed = true;
+
+            if !bounds.ranked && ranked.len() >= bounds.limit {
+                break;
+            }
+        }
+
+        if changed && total_scanned % FILTER_BATCH_SIZE == 0 {
+            truncate_ranked(&mut ranked, bounds.limit);
+            on_batch(ranked.iter().map(|(_, r)| r.clone()).collect(), total_scanned);
+            changed = false;
+        }
+    }
+
+    truncate_ranked(&mut ranked, bounds.limit);
+    (ranked.into_iter().map(|(_, r)| r).collect(), total_scanned)
+}
+
+/// Stable-sort by rank descending and drop everything past `limit`.
+fn truncate_ranked(ranked: &mut Vec<(u64, SearchResult)>, limit: usize) {
+    ranked.sort_by_key(|(rank, _)| std::cmp::Reverse(*rank));
+    ranked.truncate(limit);
+}
+
+/// Run `collect_filtered` on a background thread, streaming progress through
+/// `search-results` events. Cancellable via `cancel_search`.
+fn spawn_filtered_search<F>(
+    app: AppHandle,
+    walker: WalkDir,
+    root_path: PathBuf,
+    limit: usize,
+    ranked: bool,
+    accept: F,
+) -> u64
+where
+    F: FnMut(&WalkEntry, &str, &str) -> Option<(u64, SearchResult)> + Send + 'static,
+{
+    let (search_id, cancelled) = SEARCHES.start();
+
+    std::thread::spawn(move || {
+        let (results, total_scanned) = collect_filtered(
+            walker,
+            &root_path,
+            FilterBounds {
+                limit,
+                max_entries: usize::MAX,
+                ranked,
+            },
+            &cancelled,
+            accept,
+            |results, total_scanned| {
+                let _ = app.emit(
+                    "search-results",
+                    SearchResultsEvent {
+                        search_id,
+                        results,
+                        done: false,
+                        total_scanned,
+                    },
+                );
+            },
+        );
+
+        if !cancelled.load(Ordering::Relaxed) {
+            let _ = app.emit(
+                "search-results",
+                SearchResultsEvent {
+                    search_id,
+                    results,
+                    done: true,
+                    total_scanned,
+                },
+            );
+        }
+
+        SEARCHES.cleanup(search_id);
+    });
+
+    search_id
+}
+
+/// Which entry kinds a filtered search returns.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum KindFilter {
+    Any,
+    File,
+    Directory,
+}
+
+impl KindFilter {
+    /// Parse the frontend's `"file"` / `"directory"` filter; `None` means both.
+    fn parse(kind: Option<&str>) -> Result<Self, AppError> {
+        match kind {
+            None | Some("") => Ok(KindFilter::Any),
+            Some("file") => Ok(KindFilter::File),
+            Some("directory") => Ok(KindFilter::Directory),
+            Some(other) => Err(AppError::Other(format!("Invalid kind filter: {}", other))),
+        }
+    }
+
+    fn accepts(self, is_dir: bool) -> bool {
+        match self {
+            KindFilter::Any => true,
+            KindFilter::File => !is_dir,
+            KindFilter::Directory => is_dir,
+        }
+    }
+}
+
+/// Parse an ISO 8601 date bound. Accepts RFC 3339 timestamps, local
+/// `YYYY-MM-DDTHH:MM:SS` datetimes, and plain `YYYY-MM-DD` dates (local midnight).
+fn parse_date_bound(value: &str) -> Result<SystemTime, AppError> {
+    let value = value.trim();
+
+    if let Ok(dt) = DateTime::parse_from_rfc3339(value) {
+        return Ok(dt.into());
+    }
+
+    let naive = NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S")
+        .ok()
+        .or_else(|| {
+            NaiveDate::parse_from_str(value, "%Y-%m-%d")
+                .ok()
+                .and_then(|d| d.and_hms_opt(0, 0, 0))
+        })
+        .ok_or_else(|| AppError::Other(format!("Invalid date: {}", value)))?;
+
+    Local
+        .from_local_datetime(&naive)
+        .earliest()
+        .map(SystemTime::from)
+        .ok_or_else(|| AppError::Other(format!("Invalid local time: {}", value)))
+}
+
+/// Criteria for a modification-date search, parsed once from command arguments.
+/// `after` is inclusive, `before` is exclusive.
+struct DateCriteria {
+    after: Option<SystemTime>,
+    before: Option<SystemTime>,
+    kind: KindFilter,
+    query: String,
+}
+
+impl DateCriteria {
+    fn parse(
+        modified_after: Option<String>,
+        modified_before: Option<String>,
+        kind_filter: Option<String>,
+        query: Option<String>,
+    ) -> Result<Self, AppError> {
+        Ok(DateCriteria {
+            after: modified_after.as_deref().map(parse_date_bound).transpose()?,
+            before: modified_before.as_deref().map(parse_date_bound).transpose()?,
+            kind: KindFilter::parse(kind_filter.as_deref())?,
+            query: query.unwrap_or_default(),
+        })
+    }
+
+    fn in_range(&self, modified: SystemTime) -> bool {
+        self.after.is_none_or(|after| modified >= after)
+            && self.before.is_none_or(|before| modified < before)
+    }
+
+    /// Build the `collect_filtered` acceptor. Matches are ranked newest first;
+    /// a non-empty query additionally filters names with the fuzzy scorer.
+    fn into_acceptor(
+        self,
+        root_path: PathBuf,
+    ) -> impl FnMut(&WalkEntry, &str, &str) -> Option<(u64, SearchResult)> {
+        let mut matcher = Matcher::new(Config::DEFAULT);
+        let pattern = Pattern::parse(&self.query, CaseMatching::Ignore, Normalization::Smart);
+        let query_lower = self.query.to_lowercase();
+
+        move |entry, relative_path, name| {
+            let is_dir = entry.file_type().is_dir();
+            if !self.kind.accepts(is_dir) {
+                return None;
+            }
+
+            let score = if self.query.is_empty() {
+                0
+            } else {
+                score_entry(
+                    name,
+                    relative_path,
+                    is_dir,
+                    &query_lower,
+                    &pattern,
+                    &mut matcher,
+                    &ScoreConfig::default(),
+                )?
+            };
+
+            let modified = entry.metadata().ok()?.modified().ok()?;
+            if !self.in_range(modified) {
+                return None;
+            }
+
+            let rank = modified
+                .duration_since(SystemTime::UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0);
+
+            Some((
+                rank,
+                SearchResult {
+                    name: name.to_string(),
+                    path: root_path.join(relative_path).to_string_lossy().to_string(),
+                    relative_path: relative_path.to_string(),
+                    score,
+                    kind: if is_dir {
+                        "directory".to_string()
+                    } else {
+                        "file".to_string()
+                    },
+                    modified: Some(
+                        DateTime::<Local>::from(modified)
+                            .format("%Y-%m-%dT%H:%M:%S")
+                            .to_string(),
+                    ),
+                    match_indices: Vec::new(),
+                },
+            ))
+        }
+    }
+}
+
+/// Find entries modified within a date range, newest first.
+/// Dates are ISO 8601 strings; `kind_filter` is `"file"`, `"directory"`, or `None` for both.
+/// A non-empty `query` additionally fuzzy-filters names.
+#[tauri::command]
+pub fn search_by_date(
+    root: String,
+    modified_after: Option<String>,
+    modified_before: Option<String>,
+    kind_filter: Option<String>,
+    query: Option<String>,
+    limit: usize,
+) -> Result<SearchResponse, AppError> {
+    let root_path = resolve_search_root(&root)?;
+    let criteria = DateCriteria::parse(modified_after, modified_before, kind_filter, query)?;
+    let limit = limit.clamp(1, MAX_FILTER_RESULTS);
+
+    let (results, total_scanned) = collect_filtered(
+        build_walker(&root_path),
+        &root_path,
+        FilterBounds {
+            limit,
+            max_entries: WALK_SAFETY_CAP,
+            ranked: true,
+        },
+        &AtomicBool::new(false),
+        criteria.into_acceptor(root_path.clone()),
+        |_, _| {},
+    );
+    log::debug!("search_by_date: scanned={} results={}", total_scanned, results.len());
+
+    Ok(SearchResponse { results })
+}
+
+/// Streaming variant of `search_by_date`. Emits `search-results` events and
+/// returns a search ID that can be passed to `cancel_search`.
+#[tauri::command]
+pub fn start_date_search(
+    app: AppHandle,
+    root: String,
+    modified_after: Option<String>,
+    modified_before: Option<String>,
+    kind_filter: Option<String>,
+    query: Option<String>,
+    limit: usize,
+) -> Result<u64, AppError> {
+    let root_path = resolve_search_root(&root)?;
+    let criteria = DateCriteria::parse(modified_after, modified_before, kind_filter, query)?;
+    let limit = limit.clamp(1, MAX_FILTER_RESULTS);
+
+    let walker = build_walker(&root_path);
+    let acceptor = criteria.into_acceptor(root_path.clone());
+    Ok(spawn_filtered_search(app, walker, root_path, limit, true, acceptor))
+}
+
+/// Normalized extension suffixes for `search_by_extension`.
+struct ExtensionFilter {
+    /// Suffixes including the leading dot (e.g. `.pdf`, `.tar.gz`).
+    suffixes: Vec<String>,
+    case_sensitive: bool,
+}
+
+impl ExtensionFilter {
+    /// Accepts extensions with or without a leading dot. Lowercases them
+    /// unless `case_sensitive` is set.
+    fn new(extensions: Vec<String>, case_sensitive: bool) -> Result<Self, AppError> {
+        let suffixes: Vec<String> = extensions
+            .iter()
+            .map(|ext| ext.trim().trim_start_matches('.'))
+            .filter(|ext| !ext.is_empty())
+            .map(|ext| {
+                let ext = if case_sensitive {
+                    ext.to_string()
+                } else {
+                    ext.to_lowercase()
+                };
+                format!(".{}", ext)
+            })
+            .collect();
+
+        if suffixes.is_empty() {
+            return Err(AppError::Other("At least one extension is required".into()));
+        }
+
+        Ok(ExtensionFilter {
+            suffixes,
+            case_sensitive,
+        })
+    }
+
+    fn matches(&self, name: &str) -> bool {
+        if self.case_sensitive {
+            self.suffixes.iter().any(|s| name.ends_with(s.as_str()))
+        } else {
+            let name = name.to_lowercase();
+            self.suffixes.iter().any(|s| name.ends_with(s.as_str()))
+        }
+    }
+}
+
+/// Walker that drops non-matching files while reading each directory, so they
+/// never reach the result loop. Directories are kept for descent.
+fn build_extension_walker(root_path: &Path, filter: Arc<ExtensionFilter>) -> WalkDir {
+    WalkDir::new(root_path)
+        .skip_hidden(true)
+        .process_read_dir(move |_depth, _path, _read_dir_state, children| {
+            children.retain(|entry| match entry {
+                Ok(e) => e.file_type().is_dir() || filter.matches(&e.file_name().to_string_lossy()),
+                Err(_) => false,
+            });
+            prune_skip_dirs(children, &[]);
+        })
+}
+
+/// Acceptor for extension search: the walker already filtered files, so
+/// only directories need rejecting here.
+fn extension_acceptor(
+    root_path: PathBuf,
+) -> impl FnMut(&WalkEntry, &str, &str) -> Option<(u64, SearchResult)> {
+    move |entry, relative_path, name| {
+        if entry.file_type().is_dir() {
+            return None;
+        }
+
+        Some((
+            0,
+            SearchResult {
+                name: name.to_string(),
+                path: root_path.join(relative_path).to_string_lossy().to_string(),
+                relative_path: relative_path.to_string(),
+                score: 0,
+                kind: "file".to_string(),
+                modified: None,
+                match_indices: Vec::new(),
+            },
+        ))
+    }
+}
+
+/// Find files whose name ends with one of `extensions` (exact suffix match,
+/// not fuzzy). Results are returned in walk order.
+#[tauri::command]
+pub fn search_by_extension(
+    root: String,
+    extensions: Vec<String>,
+    case_sensitive: bool,
+    limit: usize,
+) -> Result<SearchResponse, AppError> {
+    let root_path = resolve_search_root(&root)?;
+    let filter = Arc::new(ExtensionFilter::new(extensions, case_sensitive)?);
+    let limit = limit.clamp(1, MAX_FILTER_RESULTS);
+
+    let (results, total_scanned) = collect_filtered(
+        build_extension_walker(&root_path, filter),
+        &root_path,
+        FilterBounds {
+            limit,
+            max_entries: WALK_SAFETY_CAP,
+            ranked: false,
+        },
+        &AtomicBool::new(false),
+        extension_acceptor(root_path.clone()),
+        |_, _| {},
+    );
+    log::debug!("search_by_extension: scanned={} results={}", total_scanned, results.len());
+
+    Ok(SearchResponse { results })
+}
+
+/// Streaming variant of `search_by_extension`. Emits `search-results` events
+/// and returns a search ID that can be passed to `cancel_search`.
+#[tauri::command]
+pub fn start_extension_search(
+    app: AppHandle,
+    root: String,
+    extensions: Vec<String>,
+    case_sensitive: bool,
+    limit: usize,
+) -> Result<u64, AppError> {
+    let root_path = resolve_search_root(&root)?;
+    let filter = Arc::new(ExtensionFilter::new(extensions, case_sensitive)?);
+    let limit = limit.clamp(1, MAX_FILTER_RESULTS);
+
+    let walker = build_extension_walker(&root_path, filter);
+    let acceptor = extension_acceptor(root_path.clone());
+    Ok(spawn_filtered_search(app, walker, root_path, limit, false, acceptor))
+}
+
+/// Compile a glob relative to the search root. `*` stays within one path
+/// component while `**` crosses directories. Patterns that could escape the
+/// root (absolute paths or `..` components) are rejected.
+fn compile_root_glob(pattern: &str) -> Result<GlobMatcher, AppError> {
+    let pattern = pattern.trim();
+    if pattern.is_empty() {
+        return Err(AppError::Other("Glob pattern cannot be empty".into()));
+    }
+
+    if pattern.starts_with('/')
+        || pattern.starts_with('\\')
+        || pattern.split(['/', '\\']).any(|component| component == "..")
+    {
+        return Err(AppError::InvalidPath(format!(
+            "Glob pattern must stay within the search root: {}",
+            pattern
+        )));
+    }
+
+    GlobBuilder::new(pattern)
+        .literal_separator(true)
+        .build()
+        .map(|glob| glob.compile_matcher())
+        .map_err(|e| AppError::Other(format!("Invalid glob pattern: {}", e)))
+}
+
+/// Walker for glob search. `SKIP_DIRS` are still pruned, except those the
+/// pattern names as a path component (`target/**/*.rs`).
+fn build_glob_walker(root_path: &Path, pattern: &str, include_hidden: bool) -> WalkDir {
+    let components: Vec<&str> = pattern.trim().split(['/', '\\']).collect();
+    let named: Vec<&'static str> =
+        SKIP_DIRS.iter().copied().filter(|dir| components.contains(dir)).collect();
+    WalkDir::new(root_path)
+        .skip_hidden(!include_hidden)
+        .process_read_dir(move |_depth, _path, _read_dir_state, children| {
+            prune_skip_dirs(children, &named);
+        })
+}
+
+/// Acceptor for glob search: matches the glob against the root-relative path.
+fn glob_acceptor(
+    root_path: PathBuf,
+    glob: GlobMatcher,
+) -> impl FnMut(&WalkEntry, &str, &str) -> Option<(u64, SearchResult)> {
+    move |entry, relative_path, name| {
+        if !glob.is_match(relative_path) {
+            return None;
+        }
+
+        let is_dir = entry.file_type().is_dir();
+        Some((
+            0,
+            SearchResult {
+                name: name.to_string(),
+                path: root_path.join(relative_path).to_string_lossy().to_string(),
+                relative_path: relative_path.to_string(),
+                score: 0,
+                kind: if is_dir {
+                    "directory".to_string()
+                } else {
+                    "file".to_string()
+                },
+                modified: None,
+                match_indices: Vec::new(),
+            },
+        ))
+    }
+}
+
+/// Find entries whose root-relative path matches a glob such as `**/*.rs`
+/// or `src/**/*.test.ts`. Results are returned in walk order.
+#[tauri::command]
+pub fn search_with_glob(
+    root: String,
+    pattern: String,
+    include_hidden: bool,
+    limit: usize,
+) -> Result<SearchResponse, AppError> {
+    let root_path = resolve_search_root(&root)?;
+    let glob = compile_root_glob(&pattern)?;
+    let limit = limit.clamp(1, MAX_FILTER_RESULTS);
+
+    let (results, total_scanned) = collect_filtered(
+        build_glob_walker(&root_path, &pattern, include_hidden),
+        &root_path,
+        FilterBounds {
+            limit,
+            max_entries: WALK_SAFETY_CAP,
+            ranked: false,
+        },
+        &AtomicBool::new(false),
+        glob_acceptor(root_path.clone(), glob),
+        |_, _| {},
+    );
+    log::debug!("search_with_glob: scanned={} results={}", total_scanned, results.len());
+
+    Ok(SearchResponse { results })
+}
+
+/// Streaming variant of `search_with_glob`. Emits `search-results` events
+/// with `done: false` until traversal completes, and returns a search ID
+/// that can be passed to `cancel_search`.
+#[tauri::command]
+pub fn start_glob_search(
+    app: AppHandle,
+    root: String,
+    pattern: String,
+    include_hidden: bool,
+    limit: usize,
+) -> Result<u64, AppError> {
+    let root_path = resolve_search_root(&root)?;
+    let glob = compile_root_glob(&pattern)?;
+    let limit = limit.clamp(1, MAX_FILTER_RESULTS);
+
+    let walker = build_glob_walker(&root_path, &pattern, include_hidden);
+    let acceptor = glob_acceptor(root_path.clone(), glob);
+    Ok(spawn_filtered_search(app, walker, root_path, limit, false, acceptor))
+}
+
+// ── Content type scan ───────────────────────────────────────────────────────
+
+/// Files smaller than this can't carry a recognizable magic header.
+const MIN_SNIFF_SIZE: u64 = 16;
+
+/// Matches per `content-type-scan-results` event.
+const CONTENT_TYPE_BATCH_SIZE: usize = 50;
+
+/// Event payload for `content-type-scan-complete`.
+#[derive(Debug, Clone, Serialize)]
+pub struct ContentTypeScanSummary {
+    #[serde(rename = "searchId")]
+    pub search_id: u64,
+    #[serde(rename = "totalScanned")]
+    pub total_scanned: usize,
+    #[serde(rename = "totalMatched")]
+    pub total_matched: usize,
+}
+
+/// Walk `root_path` sniffing every file's magic bytes in jwalk's parallel
+/// read-dir callback, so only matching files (and directories to descend
+/// into) reach the consumer loop. `on_batch` receives new matches only.
+/// Returns `(total_scanned, total_matched)`.
+fn scan_content_type(
+    root_path: &Path,
+    mime_prefix: &str,
+    limit: usize,
+    cancelled: Arc<AtomicBool>,
+    mut on_batch: impl FnMut(Vec<SearchResult>, usize),
+) -> (usize, usize) {
+    let scanned = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+    let walk_scanned = scanned.clone();
+    let walk_cancelled = cancelled.clone();
+    let prefix = mime_prefix.to_string();
+
+    let walker = WalkDir::new(root_path)
+        .skip_hidden(true)
+        .process_read_dir(move |_depth, _path, _read_dir_state, children| {
+            if walk_cancelled.load(Ordering::Relaxed) {
+                children.clear();
+                return;
+            }
+            prune_skip_dirs(children, &[]);
+            children.retain(|entry| match entry {
+                Ok(e) if e.file_type().is_dir() => true,
+                Ok(e) if e.file_type().is_file() => {
+                    walk_scanned.fetch_add(1, Ordering::Relaxed);
+                    e.metadata().is_ok_and(|m| m.len() >= MIN_SNIFF_SIZE)
+                        && crate::files::file_type::detect_file_type(&e.path())
+                            .is_ok_and(|info| info.mime_type.starts_with(&prefix))
+                }
+                _ => false,
+            });
+        });
+
+    let mut batch = Vec::new();
+    let mut total_matched = 0;
+    for entry in walker {
+        if cancelled.load(Ordering::Relaxed) || total_matched >= limit {
+            break;
+        }
+        let Ok(entry) = entry else { continue };
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let path = entry.path();
+        let relative_path = match path.strip_prefix(root_path) {
+            Ok(p) => p.to_string_lossy().to_string(),
+            Err(_) => continue,
+        };
+        batch.push(SearchResult {
+            name: entry.file_name().to_string_lossy().to_string(),
+            path: path.to_string_lossy().to_string(),
+            relative_path,
+            score: 0,
+            kind: "file".to_string(),
+            modified: None,
+            match_indices: Vec::new(),
+        });
+        total_matched += 1;
+        if batch.len() >= CONTENT_TYPE_BATCH_SIZE {
+            on_batch(std::mem::take(&mut batch), scanned.load(Ordering::Relaxed));
+        }
+    }
+    if !batch.is_empty() {
+        on_batch(batch, scanned.load(Ordering::Relaxed));
+    }
+    (scanned.load(Ordering::Relaxed), total_matched)
+}
+
+/// Find files by detected MIME type (`image/`, `video/`, `audio/`, …)
+/// regardless of extension. Streams new matches as
+/// `content-type-scan-results` events, then emits `content-type-scan-complete`
+/// with `totalScanned` and `totalMatched`. Returns a search ID for
+/// `cancel_search`.
+#[tauri::command]
+pub fn find_files_by_content_type(
+    app: AppHandle,
+    root: String,
+    mime_prefix: String,
+    limit: usize,
+) -> Result<u64, AppError> {
+    let root_path = resolve_search_root(&root)?;
+    let mime_prefix = mime_prefix.trim().to_lowercase();
+    if mime_prefix.is_empty() {
+        return Err(AppError::Other("MIME prefix cannot be empty".into()));
+    }
+    let limit = limit.clamp(1, MAX_FILTER_RESULTS);
+    let (search_id, cancelled) = SEARCHES.start();
 
-            // Process batch and emit results
-            if pending_entries.len() >= batch_size {
-                process_batch(
-                    &app,
-                    search_id,
-                    &mut pending_entries,
-                    &mut all_results,
-                    &root_path,
-                    &pattern,
-                    &mut matcher,
-                    limit,
-                    total_scanned,
-                    boost_path.as_ref(),
-                    &query_lower,
+    std::thread::spawn(move || {
+        let (total_scanned, total_matched) =
+            scan_content_type(&root_path, &mime_prefix, limit, cancelled.clone(), |results, total_scanned| {
+                let _ = app.emit(
+                    "content-type-scan-results",
+                    SearchResultsEvent {
+                        search_id,
+                        results,
+                        done: false,
+                        total_scanned,
+                    },
                 );
-            }
-        }
-
-        // Process remaining entries
-        if !pending_entries.is_empty() && !cancelled.load(Ordering::Relaxed) {
-            process_batch(
-                &app,
-                search_id,
-                &mut pending_entries,
-                &mut all_results,
-                &root_path,
-                &pattern,
-                &mut matcher,
-                limit,
-                total_scanned,
-                boost_path.as_ref(),
-                    &query_lower,
-            );
-        }
+            });
+        log::debug!(
+            "find_files_by_content_type({}): scanned={} matched={}",
+            mime_prefix,
+            total_scanned,
+            total_matched
+        );
 
-        // Emit final results with done=true
         if !cancelled.load(Ordering::Relaxed) {
             let _ = app.emit(
-                "search-results",
-                SearchResultsEvent {
+                "content-type-scan-complete",
+                ContentTypeScanSummary {
                     search_id,
-                    results: all_results.into_iter().take(limit).collect(),
-                    done: true,
                     total_scanned,
+                    total_matched,
                 },
             );
         }
-
         SEARCHES.cleanup(search_id);
     });
 
     Ok(search_id)
 }
 
-fn process_batch(
-    app: &AppHandle,
-    search_id: u64,
-    pending: &mut Vec<(String, String, bool)>,
-    all_results: &mut Vec<SearchResult>,
-    root_path: &PathBuf,
-    pattern: &Pattern,
-    matcher: &mut Matcher,
-    limit: usize,
-    total_scanned: usize,
-    boost_prefix: Option<&PathBuf>,
-    query_lower: &str,
-) {
-    // Score boost for results under the priority prefix (e.g. CWD)
-    const BOOST_SCORE: u32 = 100;
-
-    let mut new_results: Vec<SearchResult> = pending
-        .iter()
-        .filter_map(|(relative_path, name, is_dir)| {
-            let score = score_entry(name, relative_path, *is_dir, query_lower, pattern, matcher)?;
-            let full_path = root_path.join(relative_path);
-            // Boost score for results under the priority prefix
-            let boosted_score = if let Some(prefix) = boost_prefix {
-                if full_path.starts_with(prefix) {
-                    score.saturating_add(BOOST_SCORE)
-                } else {
-                    score
-                }
-            } else {
-                score
-            };
-            Some(SearchResult {
-                name: name.clone(),
-                path: full_path.to_string_lossy().to_string(),
-                relative_path: relative_path.clone(),
-                score: boosted_score,
-                kind: if *is_dir {
-                    "directory".to_string()
-                } else {
-                    "file".to_string()
-                },
-            })
-        })
-        .collect();
-
-    pending.clear();
-
-    // Merge with existing results — keep a generous buffer so subdirectory
-    // matches from later batches aren't prematurely dropped.
-    all_results.append(&mut new_results);
-    all_results.sort_by(|a, b| b.score.cmp(&a.score));
-    all_results.truncate(limit * 10);
-
-    // Emit current top results
-    let current_top: Vec<SearchResult> = all_results.iter().take(limit).cloned().collect();
-
-    if !current_top.is_empty() {
-        let _ = app.emit(
-            "search-results",
-            SearchResultsEvent {
-                search_id,
-                results: current_top,
-                done: false,
-                total_scanned,
-            },
-        );
-    }
-}
-
-/// Cancel an active streaming search.
-#[tauri::command]
-pub fn cancel_search(search_id: u64) -> Result<(), AppError> {
-    SEARCHES.cancel(search_id);
-    Ok(())
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -469,7 +1789,7 @@ mod tests {
         let root = visible_root(&dir);
         build_project_tree(&root);
 
-        let entries = walk_entries(&PathBuf::from(&root));
+        let entries = walk_entries(&PathBuf::from(&root), &SearchOptions::default());
 
         // Verify we collected entries from all depths
         let names: Vec<&str> = entries.iter().map(|e| e.1.as_str()).collect();
@@ -511,7 +1831,7 @@ mod tests {
         fs::create_dir(root.join("folder2")).unwrap();
         File::create(root.join("folder2/abc.txt")).unwrap();
 
-        let entries = walk_entries(&PathBuf::from(&root));
+        let entries = walk_entries(&PathBuf::from(&root), &SearchOptions::default());
 
         // Count "abc" directories
         let abc_dirs: Vec<&(String, String, bool)> = entries
@@ -546,7 +1866,7 @@ mod tests {
         fs::create_dir_all(root.join("a/b/c/d/target_folder")).unwrap();
         File::create(root.join("a/b/c/d/target_folder/payload.txt")).unwrap();
 
-        let entries = walk_entries(&PathBuf::from(&root));
+        let entries = walk_entries(&PathBuf::from(&root), &SearchOptions::default());
 
         // Must find the deeply nested folder despite 200 root siblings
         assert!(
@@ -567,7 +1887,7 @@ mod tests {
         File::create(root.join("goodbye.txt")).unwrap();
         fs::create_dir(root.join("hello_folder")).unwrap();
 
-        let result = fuzzy_search("hello".into(), root.to_string_lossy().into(), 10).unwrap();
+        let result = fuzzy_search("hello".into(), root.to_string_lossy().into(), 10, None).unwrap();
 
         assert!(
             result.results.iter().any(|r| r.name.contains("hello")),
@@ -576,6 +1896,46 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_run_fuzzy_search_and_linked_content_search() {
+        let dir = tempdir().unwrap();
+        let root = visible_root(&dir);
+        fs::write(root.join("notes.txt"), "todo: write more notes\n").unwrap();
+        fs::write(root.join("other.md"), "nothing here\n").unwrap();
+
+        let query = FuzzyQuery {
+            query: "notes".into(),
+            root_path: root.clone(),
+            limit: 10,
+            boost_path: None,
+            options: SearchOptions::default(),
+        };
+        let events = Mutex::new(Vec::new());
+        run_fuzzy_search(7, &query, &AtomicBool::new(false), &|event| events.lock().unwrap().push(event));
+        let events = events.into_inner().unwrap();
+        let last = events.last().unwrap();
+        assert!(last.done && last.search_id == 7);
+        assert_eq!(fmt_results(&last.results).len(), 1, "{:?}", fmt_results(&last.results));
+
+        let cancelled = Arc::new(AtomicBool::new(false));
+        let found = Mutex::new(Vec::new());
+        run_linked_content_search("NOTES".into(), &root, false, 10, cancelled.clone(), &|event| {
+            found.lock().unwrap().extend(event.results.into_iter().map(|r| r.relative_path));
+        })
+        .unwrap();
+        assert_eq!(found.into_inner().unwrap(), ["notes.txt"]);
+
+        // Cancelled up front: no final event from either side
+        cancelled.store(true, Ordering::Relaxed);
+        let emitted = Mutex::new(0);
+        run_fuzzy_search(8, &query, &cancelled, &|_| *emitted.lock().unwrap() += 1);
+        run_linked_content_search("notes".into(), &root, false, 10, cancelled, &|event| {
+            assert!(event.results.is_empty());
+        })
+        .unwrap();
+        assert_eq!(*emitted.lock().unwrap(), 0);
+    }
+
     #[test]
     fn test_fuzzy_search_no_match() {
         let dir = tempdir().unwrap();
@@ -583,7 +1943,7 @@ mod tests {
         File::create(root.join("test.txt")).unwrap();
 
         let result =
-            fuzzy_search("zzzzzznotfound".into(), root.to_string_lossy().into(), 10).unwrap();
+            fuzzy_search("zzzzzznotfound".into(), root.to_string_lossy().into(), 10, None).unwrap();
         assert!(result.results.is_empty());
     }
 
@@ -595,7 +1955,7 @@ mod tests {
         File::create(root.join(".git").join("config")).unwrap();
         File::create(root.join("visible.txt")).unwrap();
 
-        let result = fuzzy_search("config".into(), root.to_string_lossy().into(), 10).unwrap();
+        let result = fuzzy_search("config".into(), root.to_string_lossy().into(), 10, None).unwrap();
         assert!(result.results.iter().all(|r| !r.path.contains(".git")));
     }
 
@@ -609,20 +1969,43 @@ mod tests {
         File::create(root.join("src").join("utils.ts")).unwrap();
 
         let result =
-            fuzzy_search("component".into(), root.to_string_lossy().into(), 10).unwrap();
+            fuzzy_search("component".into(), root.to_string_lossy().into(), 10, None).unwrap();
         assert!(
             result.results.iter().any(|r| r.name == "my-component.test.tsx"),
             "Substring match should work, got: {:?}",
             fmt_results(&result.results)
         );
 
-        let result = fuzzy_search("readme".into(), root.to_string_lossy().into(), 10).unwrap();
+        let result = fuzzy_search("readme".into(), root.to_string_lossy().into(), 10, None).unwrap();
         assert!(
             result.results.iter().any(|r| r.name == "README.md"),
             "Case-insensitive substring match should work"
         );
     }
 
+    #[test]
+    fn test_match_indices() {
+        let mut matcher = Matcher::new(Config::DEFAULT);
+        let pattern = Pattern::parse("hw", CaseMatching::Ignore, Normalization::Smart);
+        assert_eq!(
+            match_indices("hello_world", "hello_world", &pattern, &mut matcher, ScoreTarget::FileName),
+            vec![0, 6]
+        );
+
+        // Offsets are bytes: "é" takes two
+        let pattern = Pattern::parse("ef", CaseMatching::Ignore, Normalization::Smart);
+        assert_eq!(
+            match_indices("é_f", "é_f", &pattern, &mut matcher, ScoreTarget::FileName),
+            vec![0, 3]
+        );
+
+        let dir = tempdir().unwrap();
+        let root = visible_root(&dir);
+        File::create(root.join("hello_world")).unwrap();
+        let result = fuzzy_search("hw".into(), root.to_string_lossy().into(), 10, None).unwrap();
+        assert_eq!(result.results[0].match_indices, vec![0, 6]);
+    }
+
     // ── Larger tree with subdirectories ──────────────────────────────────
 
     /// Build a realistic project tree:
@@ -695,7 +2078,7 @@ mod tests {
         build_project_tree(&root);
 
         // Deeply nested folder
-        let result = fuzzy_search("Button".into(), root.to_string_lossy().into(), 20).unwrap();
+        let result = fuzzy_search("Button".into(), root.to_string_lossy().into(), 20, None).unwrap();
         assert!(
             result.results.iter().any(|r| r.name == "Button" && r.kind == "directory"),
             "Should find folder 'Button' in subdirectory, got: {:?}",
@@ -703,7 +2086,7 @@ mod tests {
         );
 
         // Another nested folder
-        let result = fuzzy_search("core".into(), root.to_string_lossy().into(), 20).unwrap();
+        let result = fuzzy_search("core".into(), root.to_string_lossy().into(), 20, None).unwrap();
         assert!(
             result.results.iter().any(|r| r.name == "core" && r.kind == "directory"),
             "Should find folder 'core' in subdirectory, got: {:?}",
@@ -712,7 +2095,7 @@ mod tests {
 
         // Nested folder + file that share the name
         let result =
-            fuzzy_search("integration".into(), root.to_string_lossy().into(), 20).unwrap();
+            fuzzy_search("integration".into(), root.to_string_lossy().into(), 20, None).unwrap();
         assert!(
             result
                 .results
@@ -741,7 +2124,7 @@ mod tests {
         fs::create_dir(root.join("folder2")).unwrap();
         File::create(root.join("folder2/abc.txt")).unwrap();
 
-        let result = fuzzy_search("abc".into(), root.to_string_lossy().into(), 20).unwrap();
+        let result = fuzzy_search("abc".into(), root.to_string_lossy().into(), 20, None).unwrap();
 
         // Should find ALL instances of "abc"
         let abc_dirs: Vec<&SearchResult> = result
@@ -800,7 +2183,7 @@ mod tests {
         File::create(root.join("a/target_folder/other.txt")).unwrap();
 
         let result =
-            fuzzy_search("target_folder".into(), root.to_string_lossy().into(), 20).unwrap();
+            fuzzy_search("target_folder".into(), root.to_string_lossy().into(), 20, None).unwrap();
 
         let target_dirs: Vec<&SearchResult> = result
             .results
@@ -823,7 +2206,7 @@ mod tests {
 
         // Search for a file that only exists deep in the tree
         let result =
-            fuzzy_search("api.test.ts".into(), root.to_string_lossy().into(), 20).unwrap();
+            fuzzy_search("api.test.ts".into(), root.to_string_lossy().into(), 20, None).unwrap();
         assert!(
             result.results.iter().any(|r| r.name == "api.test.ts"),
             "Should find deeply nested file, got: {:?}",
@@ -831,7 +2214,7 @@ mod tests {
         );
 
         // Search for "deploy" — only scripts/deploy.sh matches
-        let result = fuzzy_search("deploy".into(), root.to_string_lossy().into(), 20).unwrap();
+        let result = fuzzy_search("deploy".into(), root.to_string_lossy().into(), 20, None).unwrap();
         assert!(
             result.results.iter().any(|r| r.name == "deploy.sh"),
             "Should find file in subdirectory, got: {:?}",
@@ -849,7 +2232,7 @@ mod tests {
         fs::create_dir_all(root.join("a/b/config")).unwrap();
         fs::create_dir_all(root.join("a/b/c/d/config")).unwrap();
 
-        let result = fuzzy_search("config".into(), root.to_string_lossy().into(), 20).unwrap();
+        let result = fuzzy_search("config".into(), root.to_string_lossy().into(), 20, None).unwrap();
 
         let configs: Vec<&SearchResult> = result
             .results
@@ -868,4 +2251,455 @@ mod tests {
         // Shallowest should be first
         assert_eq!(configs[0].relative_path, "config");
     }
+
+    // ── Date search ─────────────────────────────────────────────────────
+
+    /// Helper: set a file's modification time to local midnight on `date`.
+    fn set_mtime(path: &std::path::Path, date: &str) {
+        let time = parse_date_bound(date).unwrap();
+        File::options().write(true).open(path).unwrap().set_modified(time).unwrap();
+    }
+
+    #[test]
+    fn test_parse_date_bound_formats() {
+        let date = parse_date_bound("2023-06-01").unwrap();
+        let datetime = parse_date_bound("2023-06-01T00:00:00").unwrap();
+        assert_eq!(date, datetime);
+
+        let rfc = parse_date_bound("2023-06-01T12:00:00Z").unwrap();
+        let expected = SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(1_685_620_800);
+        assert_eq!(rfc, expected);
+
+        assert!(parse_date_bound("last tuesday").is_err());
+    }
+
+    #[test]
+    fn test_search_by_date_range() {
+        let dir = tempdir().unwrap();
+        let root = visible_root(&dir);
+        let files = [
+            ("old.txt", "2021-03-01"),
+            ("mid.txt", "2023-05-10"),
+            ("new.txt", "2023-11-20"),
+        ];
+        for (name, date) in files {
+            let path = root.join(name);
+            File::create(&path).unwrap();
+            set_mtime(&path, date);
+        }
+
+        let result = search_by_date(
+            root.to_string_lossy().into(),
+            Some("2023-01-01".into()),
+            Some("2024-01-01".into()),
+            Some("file".into()),
+            None,
+            10,
+        )
+        .unwrap();
+
+        let names: Vec<&str> = result.results.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec!["new.txt", "mid.txt"],
+            "Should be newest first, got: {:?}",
+            fmt_results(&result.results)
+        );
+        assert_eq!(result.results[0].modified.as_deref(), Some("2023-11-20T00:00:00"));
+    }
+
+    #[test]
+    fn test_search_by_date_kind_and_query() {
+        let dir = tempdir().unwrap();
+        let root = visible_root(&dir);
+        fs::create_dir(root.join("reports")).unwrap();
+        File::create(root.join("reports/report.pdf")).unwrap();
+        File::create(root.join("notes.txt")).unwrap();
+
+        let dirs_only = search_by_date(
+            root.to_string_lossy().into(),
+            None,
+            None,
+            Some("directory".into()),
+            None,
+            10,
+        )
+        .unwrap();
+        assert_eq!(dirs_only.results.len(), 1);
+        assert_eq!(dirs_only.results[0].name, "reports");
+
+        let queried =
+            search_by_date(root.to_string_lossy().into(), None, None, None, Some("report".into()), 10)
+                .unwrap();
+        assert!(
+            queried.results.iter().all(|r| r.name.contains("report")),
+            "Query should filter names, got: {:?}",
+            fmt_results(&queried.results)
+        );
+        assert_eq!(queried.results.len(), 2);
+
+        let invalid =
+            search_by_date(root.to_string_lossy().into(), None, None, Some("symlink".into()), None, 10);
+        assert!(invalid.is_err());
+    }
+
+    // ── Extension search ────────────────────────────────────────────────
+
+    #[test]
+    fn test_extension_filter_normalization() {
+        let filter = ExtensionFilter::new(vec![".PDF".into(), "tar.gz".into()], false).unwrap();
+        assert!(filter.matches("report.pdf"));
+        assert!(filter.matches("SCAN.Pdf"));
+        assert!(filter.matches("backup.tar.gz"));
+        assert!(!filter.matches("archive.gz"));
+        assert!(!filter.matches("pdf"));
+
+        let strict = ExtensionFilter::new(vec!["pdf".into()], true).unwrap();
+        assert!(strict.matches("report.pdf"));
+        assert!(!strict.matches("SCAN.PDF"));
+
+        assert!(ExtensionFilter::new(vec![".".into(), "".into()], false).is_err());
+    }
+
+    #[test]
+    fn test_search_by_extension() {
+        let dir = tempdir().unwrap();
+        let root = visible_root(&dir);
+        build_project_tree(&root);
+        File::create(root.join("docs/Manual.MD")).unwrap();
+
+        let result = search_by_extension(
+            root.to_string_lossy().into(),
+            vec![".md".into(), "sh".into()],
+            false,
+            50,
+        )
+        .unwrap();
+
+        let mut names: Vec<&str> = result.results.iter().map(|r| r.name.as_str()).collect();
+        names.sort();
+        assert_eq!(
+            names,
+            vec!["Manual.MD", "README.md", "deploy.sh", "guide.md", "setup.sh"],
+            "got: {:?}",
+            fmt_results(&result.results)
+        );
+        assert!(result.results.iter().all(|r| r.kind == "file"));
+
+        let limited =
+            search_by_extension(root.to_string_lossy().into(), vec!["ts".into()], true, 2).unwrap();
+        assert_eq!(limited.results.len(), 2);
+    }
+
+    // ── Glob search ─────────────────────────────────────────────────────
+
+    #[test]
+    fn test_compile_root_glob_rejects_escapes() {
+        assert!(compile_root_glob("../*.rs").is_err());
+        assert!(compile_root_glob("src/../../etc/*").is_err());
+        assert!(compile_root_glob("/etc/*").is_err());
+        assert!(compile_root_glob("").is_err());
+        assert!(compile_root_glob("src/**/*.ts").is_ok());
+    }
+
+    #[test]
+    fn test_search_with_glob() {
+        let dir = tempdir().unwrap();
+        let root = visible_root(&dir);
+        build_project_tree(&root);
+        File::create(root.join(".hidden.test.ts")).unwrap();
+
+        let result = search_with_glob(
+            root.to_string_lossy().into(),
+            "**/*.test.ts".into(),
+            false,
+            50,
+        )
+        .unwrap();
+        let mut paths: Vec<&str> = result.results.iter().map(|r| r.relative_path.as_str()).collect();
+        paths.sort();
+        assert_eq!(
+            paths,
+            vec![
+                "packages/core/core.test.ts",
+                "src/components/Button/Button.test.ts",
+                "tests/integration/api.test.ts",
+            ]
+        );
+
+        // `*` does not cross directory boundaries
+        let result =
+            search_with_glob(root.to_string_lossy().into(), "src/*".into(), false, 50).unwrap();
+        let mut names: Vec<&str> = result.results.iter().map(|r| r.name.as_str()).collect();
+        names.sort();
+        assert_eq!(names, vec!["components", "utils"]);
+
+        let hidden =
+            search_with_glob(root.to_string_lossy().into(), "*.test.ts".into(), true, 50).unwrap();
+        assert_eq!(hidden.results.len(), 1);
+        assert_eq!(hidden.results[0].name, ".hidden.test.ts");
+    }
+
+    #[test]
+    fn test_search_with_glob_into_skip_dirs() {
+        let dir = tempdir().unwrap();
+        let root = visible_root(&dir);
+        fs::create_dir_all(root.join("target/debug")).unwrap();
+        File::create(root.join("target/debug/build.rs")).unwrap();
+        File::create(root.join("main.rs")).unwrap();
+        let root_str = root.to_string_lossy().to_string();
+
+        // Named explicitly, a skipped directory is searched
+        let named = search_with_glob(root_str.clone(), "target/**/*.rs".into(), false, 50).unwrap();
+        let paths: Vec<&str> = named.results.iter().map(|r| r.relative_path.as_str()).collect();
+        assert_eq!(paths, vec!["target/debug/build.rs"]);
+
+        // Otherwise it is still pruned
+        let broad = search_with_glob(root_str, "**/*.rs".into(), false, 50).unwrap();
+        let paths: Vec<&str> = broad.results.iter().map(|r| r.relative_path.as_str()).collect();
+        assert_eq!(paths, vec!["main.rs"]);
+    }
+
+    // ── Search options ──────────────────────────────────────────────────
+
+    #[test]
+    fn test_search_options_defaults_deserialize() {
+        let options: SearchOptions = serde_json::from_str("{}").unwrap();
+        assert!(!options.include_hidden);
+        assert!(options.max_depth.is_none());
+        assert!(options.allowed_only_dirs.is_empty());
+
+        let options: SearchOptions =
+            serde_json::from_str(r#"{"includeHidden":true,"maxDepth":2,"extraSkipDirs":["docs"]}"#)
+                .unwrap();
+        assert!(options.include_hidden);
+        assert_eq!(options.max_depth, Some(2));
+        assert_eq!(options.extra_skip_dirs, vec!["docs"]);
+    }
+
+    #[test]
+    fn test_search_options_hidden_and_depth() {
+        let dir = tempdir().unwrap();
+        let root = visible_root(&dir);
+        build_project_tree(&root);
+        File::create(root.join(".envrc")).unwrap();
+
+        let default = fuzzy_search("envrc".into(), root.to_string_lossy().into(), 10, None).unwrap();
+        assert!(default.results.is_empty());
+
+        let hidden = SearchOptions {
+            include_hidden: true,
+            ..Default::default()
+        };
+        let result =
+            fuzzy_search("envrc".into(), root.to_string_lossy().into(), 10, Some(hidden)).unwrap();
+        assert!(result.results.iter().any(|r| r.name == ".envrc"));
+
+        let shallow = SearchOptions {
+            max_depth: Some(1),
+            ..Default::default()
+        };
+        let result =
+            fuzzy_search("index".into(), root.to_string_lossy().into(), 10, Some(shallow)).unwrap();
+        assert!(result.results.is_empty(), "got: {:?}", fmt_results(&result.results));
+
+        let strict = SearchOptions {
+            min_score: Some(u32::MAX),
+            ..Default::default()
+        };
+        let result =
+            fuzzy_search("index".into(), root.to_string_lossy().into(), 10, Some(strict)).unwrap();
+        assert!(result.results.is_empty());
+    }
+
+    #[test]
+    fn test_search_options_depth_penalty_and_path_mode() {
+        let dir = tempdir().unwrap();
+        let root = visible_root(&dir);
+        fs::create_dir_all(root.join("a/b/c")).unwrap();
+        File::create(root.join("a/notes.md")).unwrap();
+        File::create(root.join("a/b/c/notes.md")).unwrap();
+
+        let flat = SearchOptions {
+            depth_penalty: Some(1.0),
+            ..Default::default()
+        };
+        let steep = SearchOptions {
+            depth_penalty: Some(0.5),
+            ..Default::default()
+        };
+        let flat = fuzzy_search("notes".into(), root.to_string_lossy().into(), 10, Some(flat)).unwrap();
+        let steep = fuzzy_search("notes".into(), root.to_string_lossy().into(), 10, Some(steep)).unwrap();
+        let gap = |results: &[SearchResult]| results[0].score - results[1].score;
+        assert_eq!(steep.results[0].relative_path, format!("a{}notes.md", std::path::MAIN_SEPARATOR));
+        assert!(gap(&steep.results) > gap(&flat.results));
+
+        // "bc" only matches across the directory components
+        let by_name = fuzzy_search("abcnotes".into(), root.to_string_lossy().into(), 10, None).unwrap();
+        assert!(by_name.results.is_empty(), "got: {:?}", fmt_results(&by_name.results));
+        let by_path = SearchOptions {
+            score_against: ScoreTarget::RelativePath,
+            ..Default::default()
+        };
+        let result =
+            fuzzy_search("abcnotes".into(), root.to_string_lossy().into(), 10, Some(by_path)).unwrap();
+        assert_eq!(result.results.len(), 1, "got: {:?}", fmt_results(&result.results));
+        assert_eq!(result.results[0].match_indices, vec![0, 1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_search_options_kind() {
+        let dir = tempdir().unwrap();
+        let root = visible_root(&dir);
+        fs::create_dir_all(root.join("docs/drafts")).unwrap();
+        File::create(root.join("docs/draft.md")).unwrap();
+        File::create(root.join("docs/drafts/doc.md")).unwrap();
+        let search = |kind: EntryKind| {
+            let options = SearchOptions {
+                kind: Some(kind),
+                ..Default::default()
+            };
+            let response = fuzzy_search("d".into(), root.to_string_lossy().into(), 10, Some(options)).unwrap();
+            let mut names: Vec<String> = response.results.into_iter().map(|r| r.name).collect();
+            names.sort();
+            names
+        };
+
+        assert_eq!(search(EntryKind::Directory), ["docs", "drafts"]);
+        assert_eq!(search(EntryKind::File), ["doc.md", "draft.md"]);
+        assert_eq!(search(EntryKind::Both).len(), 4);
+    }
+
+    #[test]
+    fn test_search_options_score_against() {
+        let dir = tempdir().unwrap();
+        let root = visible_root(&dir);
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::create_dir_all(root.join("docs")).unwrap();
+        File::create(root.join("src/main.rs")).unwrap();
+        File::create(root.join("docs/readme.md")).unwrap();
+        let search = |query: &str, score_against: ScoreTarget| {
+            let options = SearchOptions {
+                score_against,
+                ..Default::default()
+            };
+            fuzzy_search(query.into(), root.to_string_lossy().into(), 10, Some(options)).unwrap().results
+        };
+
+        // The name alone only matches through the substring fallback
+        let by_name = search("src/main", ScoreTarget::FileName);
+        assert!(by_name[0].match_indices.is_empty());
+        let by_path = search("src/main", ScoreTarget::RelativePath);
+        assert_eq!(by_path.len(), 1, "got: {:?}", fmt_results(&by_path));
+        assert!(by_path[0].score > by_name[0].score);
+        assert_eq!(by_path[0].name, "main.rs");
+        assert_eq!(by_path[0].match_indices, vec![0, 1, 2, 3]);
+
+        let both = search("src/main", ScoreTarget::Both);
+        assert_eq!(both.len(), 1);
+        assert_eq!(both[0].score, by_path[0].score);
+
+        // A name match outscores the same query against the longer path
+        let by_name = search("readme", ScoreTarget::FileName);
+        let both = search("readme", ScoreTarget::Both);
+        assert_eq!(both[0].score, by_name[0].score);
+        assert_eq!(both[0].match_indices, by_name[0].match_indices);
+    }
+
+    #[test]
+    fn test_search_options_skip_and_whitelist() {
+        let dir = tempdir().unwrap();
+        let root = visible_root(&dir);
+        build_project_tree(&root);
+        fs::create_dir_all(root.join("node_modules/react")).unwrap();
+        File::create(root.join("node_modules/react/index.js")).unwrap();
+
+        let skip_docs = SearchOptions {
+            extra_skip_dirs: vec!["docs".into()],
+            ..Default::default()
+        };
+        let result =
+            fuzzy_search("guide".into(), root.to_string_lossy().into(), 10, Some(skip_docs)).unwrap();
+        assert!(result.results.is_empty(), "got: {:?}", fmt_results(&result.results));
+
+        let entries = walk_entries(
+            &root,
+            &SearchOptions {
+                allowed_only_dirs: vec!["node_modules".into(), "src/utils".into()],
+                ..Default::default()
+            },
+        );
+        let mut paths: Vec<&str> = entries.iter().map(|e| e.0.as_str()).collect();
+        paths.sort();
+        assert_eq!(
+            paths,
+            vec![
+                "node_modules",
+                "node_modules/react",
+                "node_modules/react/index.js",
+                "src/utils",
+                "src/utils/format.ts",
+                "src/utils/helpers.ts",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_search_scope() {
+        let dir = tempdir().unwrap();
+        let root = visible_root(&dir);
+        build_project_tree(&root);
+        fs::create_dir_all(root.join("build")).unwrap();
+        File::create(root.join("build/bundle.js")).unwrap();
+        File::create(root.join(".envrc")).unwrap();
+        let search = |query: &str, scope: SearchScope| {
+            let options = SearchOptions { scope: Some(scope), ..Default::default() };
+            fuzzy_search(query.into(), root.to_string_lossy().into(), 10, Some(options))
+        };
+
+        assert!(fuzzy_search("bundle".into(), root.to_string_lossy().into(), 10, None).unwrap().results.is_empty());
+        let open_build = SearchScope { override_ignore_dirs: Some(vec![".git".into()]), ..Default::default() };
+        assert_eq!(search("bundle", open_build).unwrap().results[0].name, "bundle.js");
+
+        let skip_docs = SearchScope { extra_ignore_dirs: vec!["docs".into()], ..Default::default() };
+        assert!(search("guide", skip_docs).unwrap().results.is_empty());
+
+        assert!(search("envrc", SearchScope::default()).unwrap().results.is_empty());
+        let hidden = SearchScope { ignore_hidden: false, ..Default::default() };
+        assert!(search("envrc", hidden).unwrap().results.iter().any(|r| r.name == ".envrc"));
+
+        let by_path = SearchScope { override_ignore_dirs: Some(vec!["src/utils".into()]), ..Default::default() };
+        assert!(matches!(search("guide", by_path), Err(AppError::InvalidPath(_))));
+        let scope: SearchScope = serde_json::from_str(r#"{"extraIgnoreDirs":["docs"]}"#).unwrap();
+        assert!(scope.ignore_hidden && scope.override_ignore_dirs.is_none());
+    }
+
+    // ── Content type scan ───────────────────────────────────────────────────
+
+    #[test]
+    fn test_scan_content_type_ignores_extensions() {
+        let dir = tempdir().unwrap();
+        let root = visible_root(&dir);
+        let png = b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR\0\0\0\x01\0\0\0\x01";
+        fs::create_dir(root.join("photos")).unwrap();
+        fs::write(root.join("photos/holiday.dat"), png).unwrap();
+        fs::write(root.join("cover.txt"), png).unwrap();
+        fs::write(root.join("fake.png"), "not really an image").unwrap();
+        fs::write(root.join("tiny.png"), &png[..8]).unwrap();
+        fs::create_dir(root.join("node_modules")).unwrap();
+        fs::write(root.join("node_modules/icon.png"), png).unwrap();
+
+        let mut found = Vec::new();
+        let (scanned, matched) = scan_content_type(&root, "image/", 100, Arc::new(AtomicBool::new(false)), |batch, _| {
+            found.extend(batch.into_iter().map(|r| r.relative_path))
+        });
+        found.sort();
+        assert_eq!(found, vec!["cover.txt", "photos/holiday.dat"]);
+        assert_eq!((scanned, matched), (4, 2));
+
+        let (_, limited) = scan_content_type(&root, "image/", 1, Arc::new(AtomicBool::new(false)), |_, _| {});
+        assert_eq!(limited, 1);
+        let (_, audio) = scan_content_type(&root, "audio/", 100, Arc::new(AtomicBool::new(false)), |_, _| {});
+        assert_eq!(audio, 0);
+    }
 }
@@ -0,0 +1,244 @@
+//! Past search queries, for autocomplete in the search box.
+//!
+//! Stored most-recent-first as a JSON array in the app's local data directory
+//! (~/.local/share/tauri-explorer/search_history.json on Linux), one entry
+//! per (query, root) pair.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+
+use crate::error::AppError;
+
+/// Entries kept in the history; the least recently used fall off.
+const MAX_HISTORY_ENTRIES: usize = 500;
+
+/// Serializes read-modify-write cycles on the history file.
+static HISTORY_LOCK: Mutex<()> = Mutex::new(());
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct HistoryEntry {
+    query: String,
+    root: String,
+    /// ISO 8601, local time.
+    #[serde(rename = "lastUsed")]
+    last_used: String,
+    #[serde(rename = "useCount")]
+    use_count: u32,
+    /// Results found the last time this search ran.
+    #[serde(rename = "resultCount")]
+    result_count: usize,
+}
+
+/// An autocomplete candidate: one past query, merged across roots.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SearchSuggestion {
+    pub query: String,
+    /// ISO 8601, local time.
+    #[serde(rename = "lastUsed")]
+    pub last_used: String,
+    /// Uses across all roots.
+    #[serde(rename = "useCount")]
+    pub use_count: u32,
+    /// The root this query was run in most often.
+    #[serde(rename = "typicalRoot")]
+    pub typical_root: String,
+}
+
+/// Path of the history file.
+fn history_file() -> Result<PathBuf, AppError> {
+    let base = dirs::data_local_dir()
+        .ok_or_else(|| AppError::Other("Could not determine data directory".into()))?;
+    Ok(base.join("tauri-explorer").join("search_history.json"))
+}
+
+fn load(file: &Path) -> Result<Vec<HistoryEntry>, AppError> {
+    if !file.exists() {
+        return Ok(Vec::new());
+    }
+    let data = fs::read_to_string(file)?;
+    if data.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+    serde_json::from_str(&data)
+        .map_err(|e| AppError::Other(format!("Corrupt search history file: {}", e)))
+}
+
+fn save(file: &Path, entries: &[HistoryEntry]) -> Result<(), AppError> {
+    if let Some(parent) = file.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let data = serde_json::to_string(entries)
+        .map_err(|e| AppError::Other(format!("Failed to serialize search history: {}", e)))?;
+    let tmp = file.with_extension("json.tmp");
+    fs::write(&tmp, data)?;
+    fs::rename(&tmp, file)?;
+    Ok(())
+}
+
+fn record_search_in(file: &Path, query: &str, root: &str, result_count: usize) -> Result<(), AppError> {
+    let query = query.trim();
+    if query.is_empty() {
+        return Ok(());
+    }
+
+    let _guard = HISTORY_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut entries = load(file)?;
+    let use_count = match entries.iter().position(|e| e.query == query && e.root == root) {
+        Some(i) => entries.remove(i).use_count.saturating_add(1),
+        None => 1,
+    };
+    entries.insert(
+        0,
+        HistoryEntry {
+            query: query.to_string(),
+            root: root.to_string(),
+            last_used: Local::now().format("%Y-%m-%dT%H:%M:%S").to_string(),
+            use_count,
+            result_count,
+        },
+    );
+    entries.truncate(MAX_HISTORY_ENTRIES);
+    save(file, &entries)
+}
+
+fn get_suggestions_in(file: &Path, prefix: &str, limit: usize) -> Result<Vec<SearchSuggestion>, AppError> {
+    let prefix = prefix.trim().to_lowercase();
+    let entries = load(file)?;
+
+    // Entries are most recent first, so each query's first entry carries its
+    // latest use and suggestions come out in recency order
+    let mut suggestions: Vec<SearchSuggestion> = Vec::new();
+    let mut root_uses: Vec<u32> = Vec::new();
+    for entry in entries.iter().filter(|e| e.query.to_lowercase().starts_with(&prefix)) {
+        match suggestions.iter().position(|s| s.query == entry.query) {
+            Some(i) => {
+                let suggestion = &mut suggestions[i];
+                suggestion.use_count = suggestion.use_count.saturating_add(entry.use_count);
+                if entry.use_count > root_uses[i] {
+                    suggestion.typical_root = entry.root.clone();
+                    root_uses[i] = entry.use_count;
+                }
+            }
+            None => {
+                suggestions.push(SearchSuggestion {
+                    query: entry.query.clone(),
+                    last_used: entry.last_used.clone(),
+                    use_count: entry.use_count,
+                    typical_root: entry.root.clone(),
+                });
+                root_uses.push(entry.use_count);
+            }
+        }
+    }
+
+    // Timestamps have one-second resolution; break ties by frequency
+    suggestions.sort_by(|a, b| b.last_used.cmp(&a.last_used).then(b.use_count.cmp(&a.use_count)));
+    suggestions.truncate(limit);
+    Ok(suggestions)
+}
+
+/// Remember that `query` was searched in `root`. Blank queries are ignored.
+#[tauri::command]
+pub fn record_search(query: String, root: String, result_count: usize) -> Result<(), AppError> {
+    record_search_in(&history_file()?, &query, &root, result_count)
+}
+
+/// Past queries starting with `prefix` (case-insensitive), most recently
+/// used first, then most used.
+#[tauri::command]
+pub fn get_search_suggestions(prefix: String, limit: usize) -> Result<Vec<SearchSuggestion>, AppError> {
+    get_suggestions_in(&history_file()?, &prefix, limit)
+}
+
+/// Forget all past searches.
+#[tauri::command]
+pub fn clear_search_history() -> Result<(), AppError> {
+    let file = history_file()?;
+    let _guard = HISTORY_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    if file.exists() {
+        fs::remove_file(&file)?;
+    }
+    log::info!("Cleared search history");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_suggestions_merge_roots_and_filter_by_prefix() {
+        let dir = tempdir().unwrap();
+        let file = dir.path().join("search_history.json");
+        record_search_in(&file, "config", "/home", 3).unwrap();
+        record_search_in(&file, "config", "/work", 1).unwrap();
+        record_search_in(&file, "config", "/work", 2).unwrap();
+        record_search_in(&file, "Cargo.toml", "/work", 1).unwrap();
+        record_search_in(&file, "readme", "/home", 5).unwrap();
+        record_search_in(&file, "   ", "/home", 0).unwrap();
+
+        let suggestions = get_suggestions_in(&file, "c", 10).unwrap();
+        let queries: Vec<&str> = suggestions.iter().map(|s| s.query.as_str()).collect();
+        assert_eq!(queries.len(), 2);
+        assert!(queries.contains(&"config") && queries.contains(&"Cargo.toml"));
+
+        let config = suggestions.iter().find(|s| s.query == "config").unwrap();
+        assert_eq!(config.use_count, 3);
+        assert_eq!(config.typical_root, "/work");
+
+        assert_eq!(get_suggestions_in(&file, "", 10).unwrap().len(), 3);
+        assert_eq!(get_suggestions_in(&file, "", 1).unwrap().len(), 1);
+        assert_eq!(load(&file).unwrap().len(), 4);
+    }
+
+    #[test]
+    fn test_suggestions_order_by_recency_then_frequency() {
+        let dir = tempdir().unwrap();
+        let file = dir.path().join("search_history.json");
+        let entry = |query: &str, last_used: &str, use_count| HistoryEntry {
+            query: query.into(),
+            root: "/".into(),
+            last_used: last_used.into(),
+            use_count,
+            result_count: 0,
+        };
+        save(
+            &file,
+            &[
+                entry("new", "2026-01-02T00:00:00", 1),
+                entry("tie-often", "2026-01-01T00:00:00", 9),
+                entry("tie-rare", "2026-01-01T00:00:00", 2),
+            ],
+        )
+        .unwrap();
+        // A stored order that disagrees with the timestamps still sorts by them
+        let mut entries = load(&file).unwrap();
+        entries.swap(1, 2);
+        entries.insert(0, entry("old", "2025-12-31T00:00:00", 50));
+        save(&file, &entries).unwrap();
+
+        let queries: Vec<String> = get_suggestions_in(&file, "", 10)
+            .unwrap()
+            .into_iter()
+            .map(|s| s.query)
+            .collect();
+        assert_eq!(queries, ["new", "tie-often", "tie-rare", "old"]);
+    }
+
+    #[test]
+    fn test_history_is_capped() {
+        let dir = tempdir().unwrap();
+        let file = dir.path().join("search_history.json");
+        for i in 0..MAX_HISTORY_ENTRIES + 5 {
+            record_search_in(&file, &format!("q{}", i), "/", 0).unwrap();
+        }
+        let entries = load(&file).unwrap();
+        assert_eq!(entries.len(), MAX_HISTORY_ENTRIES);
+        assert_eq!(entries[0].query, format!("q{}", MAX_HISTORY_ENTRIES + 4));
+    }
+}
@@ -0,0 +1,188 @@
+//! Keyword tags on files and folders.
+//!
+//! Stored as a path → tags JSON object in the app's local data directory
+//! (~/.local/share/tauri-explorer/tags.json on Linux). Tags are lowercase
+//! and trimmed.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use crate::error::AppError;
+
+type TagMap = HashMap<String, Vec<String>>;
+
+/// Serializes read-modify-write cycles on the tags file.
+static TAGS_LOCK: Mutex<()> = Mutex::new(());
+
+/// Path of the tags file.
+fn tags_file() -> Result<PathBuf, AppError> {
+    let base = dirs::data_local_dir()
+        .ok_or_else(|| AppError::Other("Could not determine data directory".into()))?;
+    Ok(base.join("tauri-explorer").join("tags.json"))
+}
+
+fn normalize_tag(tag: &str) -> Result<String, AppError> {
+    let tag = tag.trim().to_lowercase();
+    if tag.is_empty() {
+        return Err(AppError::Other("Tag is empty".into()));
+    }
+    Ok(tag)
+}
+
+fn load(file: &Path) -> Result<TagMap, AppError> {
+    if !file.exists() {
+        return Ok(TagMap::new());
+    }
+    let data = fs::read_to_string(file)?;
+    if data.trim().is_empty() {
+        return Ok(TagMap::new());
+    }
+    serde_json::from_str(&data).map_err(|e| AppError::Other(format!("Corrupt tags file: {}", e)))
+}
+
+fn save(file: &Path, tags: &TagMap) -> Result<(), AppError> {
+    if let Some(parent) = file.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let data = serde_json::to_string(tags)
+        .map_err(|e| AppError::Other(format!("Failed to serialize tags: {}", e)))?;
+    let tmp = file.with_extension("json.tmp");
+    fs::write(&tmp, data)?;
+    fs::rename(&tmp, file)?;
+    Ok(())
+}
+
+fn tag_file_in(file: &Path, path: &str, tag: &str) -> Result<(), AppError> {
+    let tag = normalize_tag(tag)?;
+    if !Path::new(path).exists() {
+        return Err(AppError::NotFound(path.to_string()));
+    }
+
+    let _guard = TAGS_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut tags = load(file)?;
+    let file_tags = tags.entry(path.to_string()).or_default();
+    if let Err(pos) = file_tags.binary_search(&tag) {
+        file_tags.insert(pos, tag);
+        save(file, &tags)?;
+    }
+    Ok(())
+}
+
+fn untag_file_in(file: &Path, path: &str, tag: &str) -> Result<(), AppError> {
+    let tag = normalize_tag(tag)?;
+    let _guard = TAGS_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut tags = load(file)?;
+    let Some(file_tags) = tags.get_mut(path) else {
+        return Ok(());
+    };
+    let before = file_tags.len();
+    file_tags.retain(|t| *t != tag);
+    if file_tags.len() == before {
+        return Ok(());
+    }
+    if file_tags.is_empty() {
+        tags.remove(path);
+    }
+    save(file, &tags)
+}
+
+fn tag_index(tags: &TagMap) -> HashMap<String, Vec<String>> {
+    let mut index: HashMap<String, Vec<String>> = HashMap::new();
+    for (path, file_tags) in tags {
+        for tag in file_tags {
+            index.entry(tag.clone()).or_default().push(path.clone());
+        }
+    }
+    for paths in index.values_mut() {
+        paths.sort();
+    }
+    index
+}
+
+fn search_by_tag_in(file: &Path, tag: &str) -> Result<Vec<String>, AppError> {
+    let tag = normalize_tag(tag)?;
+    let mut paths: Vec<String> = load(file)?
+        .into_iter()
+        .filter(|(_, file_tags)| file_tags.contains(&tag))
+        .map(|(path, _)| path)
+        .collect();
+    paths.sort();
+    Ok(paths)
+}
+
+/// Add `tag` to `path`. Adding a tag the path already has is a no-op.
+#[tauri::command]
+pub fn tag_file(path: String, tag: String) -> Result<(), AppError> {
+    tag_file_in(&tags_file()?, &path, &tag)
+}
+
+/// Remove `tag` from `path`. Removing a tag the path doesn't have is a no-op.
+#[tauri::command]
+pub fn untag_file(path: String, tag: String) -> Result<(), AppError> {
+    untag_file_in(&tags_file()?, &path, &tag)
+}
+
+/// Tags on `path`, sorted.
+#[tauri::command]
+pub fn get_tags(path: String) -> Result<Vec<String>, AppError> {
+    Ok(load(&tags_file()?)?.remove(&path).unwrap_or_default())
+}
+
+/// Every tag in use → the paths carrying it, sorted.
+#[tauri::command]
+pub fn list_all_tags() -> Result<HashMap<String, Vec<String>>, AppError> {
+    Ok(tag_index(&load(&tags_file()?)?))
+}
+
+/// Paths carrying `tag`, sorted.
+#[tauri::command]
+pub fn search_by_tag(tag: String) -> Result<Vec<String>, AppError> {
+    search_by_tag_in(&tags_file()?, &tag)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_tag_untag_and_search() {
+        let dir = tempdir().unwrap();
+        let file = dir.path().join("tags.json");
+        let a = dir.path().join("a.txt");
+        let b = dir.path().join("b.txt");
+        fs::write(&a, "").unwrap();
+        fs::write(&b, "").unwrap();
+        let (a, b) = (a.to_string_lossy().to_string(), b.to_string_lossy().to_string());
+
+        tag_file_in(&file, &a, " Work ").unwrap();
+        tag_file_in(&file, &a, "work").unwrap();
+        tag_file_in(&file, &a, "Urgent").unwrap();
+        tag_file_in(&file, &b, "WORK").unwrap();
+
+        assert_eq!(load(&file).unwrap()[&a], vec!["urgent", "work"]);
+        assert_eq!(search_by_tag_in(&file, "Work").unwrap(), vec![a.clone(), b.clone()]);
+
+        let index = tag_index(&load(&file).unwrap());
+        assert_eq!(index["urgent"], vec![a.clone()]);
+        assert_eq!(index["work"].len(), 2);
+
+        untag_file_in(&file, &b, "work").unwrap();
+        untag_file_in(&file, &b, "work").unwrap();
+        assert!(!load(&file).unwrap().contains_key(&b));
+        assert_eq!(search_by_tag_in(&file, "work").unwrap(), vec![a]);
+    }
+
+    #[test]
+    fn test_tag_rejects_empty_tag_and_missing_path() {
+        let dir = tempdir().unwrap();
+        let file = dir.path().join("tags.json");
+        let path = dir.path().to_string_lossy().to_string();
+        assert!(tag_file_in(&file, &path, "   ").is_err());
+
+        let missing = dir.path().join("missing").to_string_lossy().to_string();
+        assert!(matches!(tag_file_in(&file, &missing, "x"), Err(AppError::NotFound(_))));
+    }
+}
@@ -47,4 +47,9 @@ impl TaskRegistry {
     pub fn cleanup(&self, id: u64) {
         self.active_map().lock().unwrap().remove(&id);
     }
+
+    /// Whether a task is still registered (started and not yet cleaned up).
+    pub fn is_active(&self, id: u64) -> bool {
+        self.active_map().lock().unwrap().contains_key(&id)
+    }
 }
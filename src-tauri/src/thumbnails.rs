@@ -9,10 +9,16 @@ use base64::Engine as _;
 use crate::error::AppError;
 use log;
 use image::{ImageFormat, ImageReader};
+use rayon::prelude::*;
 use sha2::{Sha256, Digest};
 use std::fs;
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 use std::io::Cursor;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Mutex, OnceLock};
+use tauri::{AppHandle, Emitter};
+use crate::task_registry::TaskRegistry;
 
 /// Default thumbnail size (width and height in pixels)
 const THUMBNAIL_SIZE: u32 = 128;
@@ -20,18 +26,188 @@ const THUMBNAIL_SIZE: u32 = 128;
 /// Micro thumbnail size for progressive loading preview
 const MICRO_SIZE: u32 = 16;
 
+/// Maximum paths accepted by a single `get_thumbnail_batch` call
+const MAX_BATCH_SIZE: usize = 200;
+
+/// Upper bound on batch worker threads, so decoding a photo folder
+/// doesn't starve searches and listings running at the same time
+const MAX_BATCH_THREADS: usize = 4;
+
+/// Parallel decoders used by `preload_thumbnails`
+const PRELOAD_WORKERS: usize = 4;
+
+/// Running `preload_thumbnails` tasks
+static PRELOADS: TaskRegistry = TaskRegistry::new();
+
 /// Supported image extensions for thumbnail generation
+#[cfg(not(target_os = "macos"))]
 const SUPPORTED_EXTENSIONS: &[&str] = &["jpg", "jpeg", "png", "gif", "webp", "bmp"];
 
+/// Supported image extensions for thumbnail generation; HEIC/HEIF are
+/// converted with `sips`
+#[cfg(target_os = "macos")]
+const SUPPORTED_EXTENSIONS: &[&str] = &["jpg", "jpeg", "png", "gif", "webp", "bmp", "heic", "heif"];
+
+/// HEIC/HEIF extensions, which the `image` crate can't decode
+const HEIC_EXTENSIONS: &[&str] = &["heic", "heif"];
+
+/// Vector formats rasterized with resvg
+const VECTOR_EXTENSIONS: &[&str] = &["svg"];
+
+/// Video extensions handled via `ffmpeg`
+const VIDEO_EXTENSIONS: &[&str] = &["mp4", "mkv", "avi", "mov", "webm", "m4v", "wmv", "flv", "mpg", "mpeg"];
+
+/// Document extensions rendered from their first page
+const DOCUMENT_EXTENSIONS: &[&str] = &["pdf"];
+
+/// Default seek position for video thumbnails, past most black intro frames
+const VIDEO_OFFSET_SECS: f64 = 1.0;
+
 /// Get the cache directory for thumbnails
 fn get_cache_dir() -> Option<PathBuf> {
     dirs::cache_dir().map(|p| p.join("tauri-explorer").join("thumbnails"))
 }
 
+/// Encoding of generated thumbnails. Each format is cached in its own
+/// subdirectory, so one source can be cached in several.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ThumbnailFormat {
+    #[default]
+    Jpeg,
+    Png,
+    /// Lossy, at the same quality as the JPEG thumbnails.
+    WebP,
+}
+
+impl ThumbnailFormat {
+    /// Cache subdirectory name.
+    fn dir_name(self) -> &'static str {
+        match self {
+            ThumbnailFormat::Jpeg => "jpeg",
+            ThumbnailFormat::Png => "png",
+            ThumbnailFormat::WebP => "webp",
+        }
+    }
+
+    fn extension(self) -> &'static str {
+        match self {
+            ThumbnailFormat::Jpeg => "jpg",
+            ThumbnailFormat::Png => "png",
+            ThumbnailFormat::WebP => "webp",
+        }
+    }
+
+    fn mime_type(self) -> &'static str {
+        match self {
+            ThumbnailFormat::Jpeg => "image/jpeg",
+            ThumbnailFormat::Png => "image/png",
+            ThumbnailFormat::WebP => "image/webp",
+        }
+    }
+
+    fn has_alpha(self) -> bool {
+        self != ThumbnailFormat::Jpeg
+    }
+}
+
+/// Where the `format` thumbnail for `cache_key` lives.
+fn cache_file_path(cache_key: &str, format: ThumbnailFormat) -> Option<PathBuf> {
+    let file_name = format!("{}.{}", cache_key, format.extension());
+    get_cache_dir().map(|dir| dir.join(format.dir_name()).join(file_name))
+}
+
+/// Every file in the cache: the per-format subdirectories, plus loose files
+/// written before thumbnails were split by format. The directory index is
+/// not a thumbnail and is left out.
+fn cache_files(cache_dir: &Path) -> Vec<(PathBuf, fs::Metadata)> {
+    let mut files = Vec::new();
+    let Ok(entries) = fs::read_dir(cache_dir) else { return files };
+    for entry in entries.flatten() {
+        let Ok(metadata) = entry.metadata() else { continue };
+        if metadata.is_dir() {
+            let Ok(format_entries) = fs::read_dir(entry.path()) else { continue };
+            files.extend(format_entries.flatten().filter_map(|entry| {
+                let metadata = entry.metadata().ok().filter(|m| m.is_file())?;
+                Some((entry.path(), metadata))
+            }));
+        } else if metadata.is_file() && entry.file_name() != DIRECTORY_INDEX_FILE {
+            files.push((entry.path(), metadata));
+        }
+    }
+    files
+}
+
+/// Cache size limit in bytes (0 = unlimited), set by `set_thumbnail_cache_limit`
+static CACHE_LIMIT: OnceLock<AtomicU64> = OnceLock::new();
+
+/// Set while a thread is trimming the cache, so parallel writers don't all rescan it
+static ENFORCING_LIMIT: AtomicBool = AtomicBool::new(false);
+
+/// Bytes in the cache as of the last scan, plus writes and minus removals
+/// since; None until the first scan (the first write with a limit set).
+static CACHE_SIZE: Mutex<Option<u64>> = Mutex::new(None);
+
+fn cache_limit() -> Option<u64> {
+    CACHE_LIMIT
+        .get()
+        .map(|limit| limit.load(Ordering::Relaxed))
+        .filter(|&limit| limit > 0)
+}
+
 /// Cache version - bump when thumbnail generation logic changes to invalidate stale cache
-const CACHE_VERSION: u8 = 2;
+const CACHE_VERSION: u8 = 3;
+
+/// Hex characters of the source directory hash embedded in cache keys
+const DIRECTORY_HASH_LEN: usize = 8;
+
+/// Short hash of a directory path, embedded in cache keys so one
+/// directory's thumbnails can be found without reading the cache.
+fn directory_hash(directory: &Path) -> String {
+    // Normalized so `/photos/` and `/photos` hash the same
+    let normalized: PathBuf = directory.components().collect();
+    let digest = Sha256::digest(normalized.to_string_lossy().as_bytes());
+    hex::encode(digest)[..DIRECTORY_HASH_LEN].to_string()
+}
 
-/// Generate a cache key (hash) for a file path + modification time + size + cache version
+/// File in the cache directory mapping directory hashes back to the
+/// directories they came from
+const DIRECTORY_INDEX_FILE: &str = "index.json";
+
+/// In-memory copy of the directory index, loaded on first use
+static DIRECTORY_INDEX: Mutex<Option<HashMap<String, PathBuf>>> = Mutex::new(None);
+
+fn load_directory_index(cache_dir: &Path) -> HashMap<String, PathBuf> {
+    fs::read(cache_dir.join(DIRECTORY_INDEX_FILE))
+        .ok()
+        .and_then(|data| serde_json::from_slice(&data).ok())
+        .unwrap_or_default()
+}
+
+/// Remember which directory `source_path`'s cache keys point at, so
+/// `clear_thumbnail_cache_for_directory` can match it by path prefix.
+/// The index is only rewritten when a new directory shows up.
+fn record_source_directory(source_path: &Path) {
+    let Some(cache_dir) = get_cache_dir() else { return };
+    let directory: PathBuf = source_path.parent().unwrap_or(Path::new("")).components().collect();
+    let dir_hash = directory_hash(&directory);
+
+    let mut guard = DIRECTORY_INDEX.lock().unwrap_or_else(|e| e.into_inner());
+    let index = guard.get_or_insert_with(|| load_directory_index(&cache_dir));
+    if index.contains_key(&dir_hash) {
+        return;
+    }
+    index.insert(dir_hash, directory);
+    if let Ok(json) = serde_json::to_vec(index) {
+        let _ = fs::create_dir_all(&cache_dir);
+        let _ = fs::write(cache_dir.join(DIRECTORY_INDEX_FILE), json);
+    }
+}
+
+/// Generate a cache key (hash) for a file path + modification time + size + cache version.
+/// The key is prefixed with the source extension and a hash of the source
+/// directory (`png-<dir hash>-<hash>`) so cache stats can be broken down by
+/// file type and a directory's thumbnails cleared on their own.
 fn generate_cache_key(path: &Path, size: u32) -> Option<String> {
     let metadata = fs::metadata(path).ok()?;
     let modified = metadata.modified().ok()?;
@@ -46,35 +222,167 @@ fn generate_cache_key(path: &Path, size: u32) -> Option<String> {
     hasher.update(size.to_le_bytes());
     hasher.update([CACHE_VERSION]);
 
-    Some(hex::encode(hasher.finalize()))
+    let ext = path
+        .extension()
+        .map(|e| e.to_string_lossy().to_lowercase())
+        .unwrap_or_else(|| "none".to_string());
+    let dir_hash = directory_hash(path.parent().unwrap_or(Path::new("")));
+    Some(format!("{}-{}-{}", ext, dir_hash, hex::encode(hasher.finalize())))
 }
 
 /// Check if a file is a supported image type
 pub fn is_supported_image(path: &Path) -> bool {
     path.extension()
         .and_then(|e| e.to_str())
-        .map(|e| SUPPORTED_EXTENSIONS.contains(&e.to_lowercase().as_str()))
+        .map(|e| {
+            let ext = e.to_lowercase();
+            SUPPORTED_EXTENSIONS.contains(&ext.as_str()) || VECTOR_EXTENSIONS.contains(&ext.as_str())
+        })
         .unwrap_or(false)
 }
 
+/// Check if a file is a vector image (rasterized instead of decoded)
+pub fn is_vector_image(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .map(|e| VECTOR_EXTENSIONS.contains(&e.to_lowercase().as_str()))
+        .unwrap_or(false)
+}
+
+/// Check if a file is a HEIC/HEIF image (thumbnailed on macOS only)
+pub fn is_heic_image(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .map(|e| HEIC_EXTENSIONS.contains(&e.to_lowercase().as_str()))
+        .unwrap_or(false)
+}
+
+/// Check if a file is a video that `ffmpeg` can thumbnail
+pub fn is_video_file(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .map(|e| VIDEO_EXTENSIONS.contains(&e.to_lowercase().as_str()))
+        .unwrap_or(false)
+}
+
+/// Check if a file is a PDF
+pub fn is_pdf_file(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .map(|e| DOCUMENT_EXTENSIONS.contains(&e.to_lowercase().as_str()))
+        .unwrap_or(false)
+}
+
+/// Check if any thumbnail command handles this file (images, PDFs, videos),
+/// so callers can skip requesting thumbnails for everything else.
+#[tauri::command]
+pub fn is_supported_format(path: String) -> bool {
+    let path = Path::new(&path);
+    is_supported_image(path) || is_pdf_file(path) || is_video_file(path)
+}
+
 /// Get cached thumbnail path if it exists
-fn get_cached_thumbnail(cache_key: &str) -> Option<PathBuf> {
-    let cache_dir = get_cache_dir()?;
-    let cache_path = cache_dir.join(format!("{}.jpg", cache_key));
+fn get_cached_thumbnail(cache_key: &str, format: ThumbnailFormat) -> Option<PathBuf> {
+    let cache_path = cache_file_path(cache_key, format)?;
 
     if cache_path.exists() {
+        // Bump mtime on hit so eviction drops the least recently used files
+        if cache_limit().is_some() {
+            let _ = fs::File::options()
+                .write(true)
+                .open(&cache_path)
+                .and_then(|f| f.set_modified(std::time::SystemTime::now()));
+        }
         Some(cache_path)
     } else {
         None
     }
 }
 
-/// Save raw JPEG bytes to cache
-fn save_to_cache(cache_key: &str, data: &[u8]) {
-    if let Some(cache_dir) = get_cache_dir() {
-        let _ = fs::create_dir_all(&cache_dir);
-        let cache_path = cache_dir.join(format!("{}.jpg", cache_key));
-        let _ = fs::write(&cache_path, data);
+/// Write a thumbnail into the cache and account for it in `CACHE_SIZE`.
+/// If that takes the cache over the limit it is rescanned and trimmed to
+/// 90% of the limit, so the writes that follow don't each rescan it.
+fn write_cache_file(cache_path: &Path, data: &[u8]) -> std::io::Result<()> {
+    let replaced = fs::metadata(cache_path).map_or(0, |m| m.len());
+    fs::write(cache_path, data)?;
+
+    let over_limit = {
+        let mut size = CACHE_SIZE.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(total) = size.as_mut() {
+            *total = (*total + data.len() as u64).saturating_sub(replaced);
+        }
+        cache_limit().filter(|&limit| size.is_none_or(|total| total > limit))
+    };
+    if let Some(limit) = over_limit {
+        if !ENFORCING_LIMIT.swap(true, Ordering::Acquire) {
+            if let Err(e) = enforce_cache_limit(limit - limit / 10) {
+                log::warn!("Failed to enforce thumbnail cache limit: {}", e);
+            }
+            ENFORCING_LIMIT.store(false, Ordering::Release);
+        }
+    }
+    Ok(())
+}
+
+/// Take `bytes` removed from the cache off `CACHE_SIZE`.
+fn cache_size_removed(bytes: u64) {
+    if let Some(total) = CACHE_SIZE.lock().unwrap_or_else(|e| e.into_inner()).as_mut() {
+        *total = total.saturating_sub(bytes);
+    }
+}
+
+/// Delete least recently used thumbnails until the cache is at most `max_bytes`.
+/// Returns the number of bytes freed.
+pub fn enforce_cache_limit(max_bytes: u64) -> Result<u64, AppError> {
+    match get_cache_dir() {
+        Some(cache_dir) if cache_dir.exists() => {
+            let (freed, remaining) = evict_lru(&cache_dir, max_bytes)?;
+            *CACHE_SIZE.lock().unwrap_or_else(|e| e.into_inner()) = Some(remaining);
+            Ok(freed)
+        }
+        _ => Ok(0),
+    }
+}
+
+/// Returns the bytes freed and the bytes left in the cache.
+fn evict_lru(cache_dir: &Path, max_bytes: u64) -> Result<(u64, u64), AppError> {
+    let mut files: Vec<(std::time::SystemTime, u64, PathBuf)> = cache_files(cache_dir)
+        .into_iter()
+        .map(|(path, metadata)| {
+            let modified = metadata.modified().unwrap_or(std::time::UNIX_EPOCH);
+            (modified, metadata.len(), path)
+        })
+        .collect();
+
+    let mut total: u64 = files.iter().map(|(_, len, _)| len).sum();
+    if total <= max_bytes {
+        return Ok((0, total));
+    }
+
+    files.sort_by_key(|(modified, _, _)| *modified);
+    let mut freed = 0u64;
+    for (_, len, path) in files {
+        if total <= max_bytes {
+            break;
+        }
+        if fs::remove_file(&path).is_ok() {
+            total -= len;
+            freed += len;
+        }
+    }
+
+    log::info!("Thumbnail cache trimmed: {} bytes freed", freed);
+    Ok((freed, total))
+}
+
+/// Save encoded thumbnail bytes for `source_path` to cache
+fn save_to_cache(source_path: &Path, cache_key: &str, format: ThumbnailFormat, data: &[u8]) {
+    if let Some(cache_path) = cache_file_path(cache_key, format) {
+        if let Some(parent) = cache_path.parent() {
+            let _ = fs::create_dir_all(parent);
+        }
+        let _ = write_cache_file(&cache_path, data);
+        record_source_directory(source_path);
     }
 }
 
@@ -91,10 +399,34 @@ fn encode_jpeg(img: &image::RgbImage, quality: u8) -> Result<Vec<u8>, AppError>
     Ok(buffer.into_inner())
 }
 
+/// Encode an RGBA8 image to lossy WebP bytes at the given quality (0-100)
+fn encode_webp(img: &image::RgbaImage, quality: f32) -> Vec<u8> {
+    webp::Encoder::from_rgba(img.as_raw(), img.width(), img.height())
+        .encode(quality)
+        .to_vec()
+}
+
+/// Encode a thumbnail in `format`: JPEG and lossy WebP at quality 80,
+/// PNG losslessly. JPEG drops alpha; PNG and WebP keep it.
+fn encode_thumbnail(img: &image::DynamicImage, format: ThumbnailFormat) -> Result<Vec<u8>, AppError> {
+    match format {
+        ThumbnailFormat::Jpeg => encode_jpeg(&img.to_rgb8(), 80),
+        ThumbnailFormat::WebP => Ok(encode_webp(&img.to_rgba8(), 80.0)),
+        ThumbnailFormat::Png => {
+            let mut buffer = Cursor::new(Vec::new());
+            image::DynamicImage::ImageRgba8(img.to_rgba8())
+                .write_to(&mut buffer, ImageFormat::Png)
+                .map_err(|e| AppError::Other(format!("Failed to encode PNG: {}", e)))?;
+            Ok(buffer.into_inner())
+        }
+    }
+}
+
 /// Format raw bytes as a data URI
-fn to_data_uri(data: &[u8]) -> String {
+fn to_data_uri(data: &[u8], format: ThumbnailFormat) -> String {
     format!(
-        "data:image/jpeg;base64,{}",
+        "data:{};base64,{}",
+        format.mime_type(),
         base64::engine::general_purpose::STANDARD.encode(data)
     )
 }
@@ -104,45 +436,171 @@ fn validate_thumbnail_path(path: &Path, path_str: &str) -> Result<(), AppError>
     if !path.exists() {
         return Err(AppError::NotFound(path_str.to_string()));
     }
+    if !is_supported_image(path) && is_heic_image(path) {
+        return Err(AppError::Other(format!("HEIC not supported on this platform: {}", path_str)));
+    }
     if !is_supported_image(path) {
         return Err(AppError::InvalidPath(format!("Unsupported image format: {}", path_str)));
     }
     Ok(())
 }
 
+/// Read the EXIF orientation tag (1-8), if the file has one.
+fn read_exif_orientation(path: &Path) -> Option<u32> {
+    let file = fs::File::open(path).ok()?;
+    let exif = exif::Reader::new()
+        .read_from_container(&mut std::io::BufReader::new(file))
+        .ok()?;
+    exif.get_field(exif::Tag::Orientation, exif::In::PRIMARY)?
+        .value
+        .get_uint(0)
+}
+
+/// Whether the file's leading bytes carry an XMP orientation, which we don't apply.
+fn has_xmp_orientation(path: &Path) -> bool {
+    use std::io::Read;
+    let mut head = Vec::with_capacity(64 * 1024);
+    fs::File::open(path)
+        .and_then(|f| f.take(64 * 1024).read_to_end(&mut head))
+        .is_ok_and(|_| head.windows(16).any(|w| w == b"tiff:Orientation"))
+}
+
+/// Decode an image and rotate/flip it upright according to its EXIF orientation.
+fn load_oriented_image(path: &Path) -> Result<image::DynamicImage, AppError> {
+    let reader = ImageReader::open(path)?.with_guessed_format()?;
+    let format = reader.format();
+    let mut img = reader
+        .decode()
+        .map_err(|e| AppError::Other(format!("Failed to decode image: {}", e)))?;
+
+    match read_exif_orientation(path) {
+        Some(value) => {
+            if let Some(orientation) = image::metadata::Orientation::from_exif(value as u8) {
+                img.apply_orientation(orientation);
+            }
+        }
+        None if format != Some(ImageFormat::Jpeg) && has_xmp_orientation(path) => {
+            log::warn!("Ignoring XMP orientation for thumbnail: {}", path.display());
+        }
+        None => {}
+    }
+    Ok(img)
+}
+
+/// Render an SVG to fit within `size`×`size`, preserving aspect ratio.
+/// `opaque` composites onto white (for JPEG output); otherwise alpha is kept.
+pub(crate) fn render_svg(path: &Path, size: u32, opaque: bool) -> Result<image::DynamicImage, AppError> {
+    use resvg::{tiny_skia, usvg};
+
+    let data = fs::read(path)?;
+    let tree = usvg::Tree::from_data(&data, &usvg::Options::default())
+        .map_err(|e| AppError::Other(format!("Failed to parse SVG: {}", e)))?;
+
+    let svg_size = tree.size();
+    let scale = (size as f32 / svg_size.width()).min(size as f32 / svg_size.height());
+    let width = ((svg_size.width() * scale).round() as u32).max(1);
+    let height = ((svg_size.height() * scale).round() as u32).max(1);
+
+    let mut pixmap = tiny_skia::Pixmap::new(width, height)
+        .ok_or_else(|| AppError::Other(format!("Invalid SVG size: {}x{}", width, height)))?;
+    if opaque {
+        pixmap.fill(tiny_skia::Color::WHITE);
+    }
+    resvg::render(&tree, tiny_skia::Transform::from_scale(scale, scale), &mut pixmap.as_mut());
+
+    // tiny-skia stores premultiplied alpha; image expects straight alpha
+    let pixels: Vec<u8> = pixmap
+        .pixels()
+        .iter()
+        .flat_map(|p| {
+            let c = p.demultiply();
+            [c.red(), c.green(), c.blue(), c.alpha()]
+        })
+        .collect();
+    let rgba = image::RgbaImage::from_raw(width, height, pixels)
+        .ok_or_else(|| AppError::Other("SVG render buffer size mismatch".into()))?;
+    Ok(image::DynamicImage::ImageRgba8(rgba))
+}
+
+/// Rasterize an SVG into a `size`×`size`-bounded JPEG thumbnail.
+pub fn rasterize_svg_thumbnail(path: &Path, size: u32) -> Result<Vec<u8>, AppError> {
+    encode_jpeg(&render_svg(path, size, true)?.to_rgb8(), 80)
+}
+
+/// Convert a HEIC/HEIF image to a JPEG fitting `size`×`size` with `sips`
+/// (aspect ratio kept), then decode it upright.
+#[cfg(target_os = "macos")]
+fn convert_heic_with_sips(path: &Path, size: u32) -> Result<image::DynamicImage, AppError> {
+    static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+    let output_path = std::env::temp_dir().join(format!(
+        "tauri-explorer-heic-{}-{}.jpg",
+        std::process::id(),
+        NEXT_ID.fetch_add(1, Ordering::Relaxed)
+    ));
+
+    let output = std::process::Command::new("sips")
+        .args(["-s", "format", "jpeg", "-Z", &size.to_string()])
+        .arg(path)
+        .arg("--out")
+        .arg(&output_path)
+        .stdin(std::process::Stdio::null())
+        .output()?;
+    let result = if output.status.success() {
+        load_oriented_image(&output_path)
+    } else {
+        Err(AppError::Other(format!(
+            "sips failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )))
+    };
+    let _ = fs::remove_file(&output_path);
+    result
+}
+
+/// Decode any supported image for thumbnailing: SVGs are rasterized at
+/// `size` (onto white when `opaque`), HEIC is converted by `sips` on macOS,
+/// raster images are decoded and oriented.
+fn decode_for_thumbnail(path: &Path, size: u32, opaque: bool) -> Result<image::DynamicImage, AppError> {
+    if is_vector_image(path) {
+        return render_svg(path, size, opaque);
+    }
+    #[cfg(target_os = "macos")]
+    if is_heic_image(path) {
+        return convert_heic_with_sips(path, size);
+    }
+    load_oriented_image(path)
+}
+
 /// Generate thumbnail and save to cache
 fn generate_and_cache_thumbnail(
     source_path: &Path,
     cache_key: &str,
     size: u32,
+    format: ThumbnailFormat,
 ) -> Result<PathBuf, AppError> {
-    let cache_dir = get_cache_dir().ok_or(AppError::Other("Failed to get cache directory".into()))?;
+    let cache_path = cache_file_path(cache_key, format)
+        .ok_or(AppError::Other("Failed to get cache directory".into()))?;
 
     // Create cache directory if it doesn't exist
-    fs::create_dir_all(&cache_dir)?;
+    if let Some(parent) = cache_path.parent() {
+        fs::create_dir_all(parent)?;
+    }
 
-    // Load and decode image (with_guessed_format for robust format detection)
-    let img = ImageReader::open(source_path)?
-        .with_guessed_format()?
-        .decode()
-        .map_err(|e| AppError::Other(format!("Failed to decode image: {}", e)))?;
+    // Load, decode and orient upright (with_guessed_format for robust format detection)
+    let img = decode_for_thumbnail(source_path, size, !format.has_alpha())?;
 
     // Generate thumbnail using fast Lanczos3 sampling
-    // Always convert to RGB8 for JPEG output (PNG/GIF may have alpha channels)
-    let thumbnail = img.thumbnail(size, size).to_rgb8();
-
-    // Save to cache as JPEG for smaller size and fast loading
-    let cache_path = cache_dir.join(format!("{}.jpg", cache_key));
-    thumbnail
-        .save_with_format(&cache_path, ImageFormat::Jpeg)
+    let data = encode_thumbnail(&img.thumbnail(size, size), format)?;
+    write_cache_file(&cache_path, &data)
         .map_err(|e| AppError::Other(format!("Failed to save thumbnail: {}", e)))?;
+    record_source_directory(source_path);
 
     Ok(cache_path)
 }
 
 // ─── Sync implementations ───────────────────────────────────────────────────
 
-fn get_thumbnail_sync(path: String, size: Option<u32>) -> Result<String, AppError> {
+fn get_thumbnail_sync(path: String, size: Option<u32>, format: ThumbnailFormat) -> Result<String, AppError> {
     let source_path = PathBuf::from(&path);
     let size = size.unwrap_or(THUMBNAIL_SIZE);
     validate_thumbnail_path(&source_path, &path)?;
@@ -150,15 +608,15 @@ fn get_thumbnail_sync(path: String, size: Option<u32>) -> Result<String, AppErro
     let cache_key = generate_cache_key(&source_path, size)
         .ok_or_else(|| AppError::Other(format!("Failed to generate cache key for: {}", path)))?;
 
-    if let Some(cached_path) = get_cached_thumbnail(&cache_key) {
+    if let Some(cached_path) = get_cached_thumbnail(&cache_key, format) {
         return Ok(cached_path.to_string_lossy().to_string());
     }
 
-    let thumb_path = generate_and_cache_thumbnail(&source_path, &cache_key, size)?;
+    let thumb_path = generate_and_cache_thumbnail(&source_path, &cache_key, size, format)?;
     Ok(thumb_path.to_string_lossy().to_string())
 }
 
-fn get_thumbnail_data_sync(path: String, size: Option<u32>) -> Result<String, AppError> {
+fn get_thumbnail_data_sync(path: String, size: Option<u32>, format: ThumbnailFormat) -> Result<String, AppError> {
     let source_path = PathBuf::from(&path);
     let size = size.unwrap_or(THUMBNAIL_SIZE);
     validate_thumbnail_path(&source_path, &path)?;
@@ -167,23 +625,16 @@ fn get_thumbnail_data_sync(path: String, size: Option<u32>) -> Result<String, Ap
         .ok_or_else(|| AppError::Other(format!("Failed to generate cache key for: {}", path)))?;
 
     // Check cache first
-    if let Some(cached_path) = get_cached_thumbnail(&cache_key) {
+    if let Some(cached_path) = get_cached_thumbnail(&cache_key, format) {
         let data = fs::read(&cached_path)?;
-        return Ok(to_data_uri(&data));
+        return Ok(to_data_uri(&data, format));
     }
 
-    // Decode image
-    let img = ImageReader::open(&source_path)?
-        .with_guessed_format()?
-        .decode()
-        .map_err(|e| AppError::Other(format!("Failed to decode image: {}", e)))?;
-
-    let thumbnail = img.thumbnail(size, size).to_rgb8();
-
-    let data = encode_jpeg(&thumbnail, 80)?;
-    save_to_cache(&cache_key, &data);
+    let img = decode_for_thumbnail(&source_path, size, !format.has_alpha())?;
+    let data = encode_thumbnail(&img.thumbnail(size, size), format)?;
+    save_to_cache(&source_path, &cache_key, format, &data);
 
-    Ok(to_data_uri(&data))
+    Ok(to_data_uri(&data, format))
 }
 
 fn get_micro_thumbnail_sync(path: String) -> Result<String, AppError> {
@@ -195,53 +646,299 @@ fn get_micro_thumbnail_sync(path: String) -> Result<String, AppError> {
         .ok_or_else(|| AppError::Other(format!("Failed to generate cache key for: {}", path)))?;
 
     // Check micro cache first
-    if let Some(cached_path) = get_cached_thumbnail(&micro_cache_key) {
+    if let Some(cached_path) = get_cached_thumbnail(&micro_cache_key, ThumbnailFormat::Jpeg) {
         let data = fs::read(&cached_path)?;
-        return Ok(to_data_uri(&data));
+        return Ok(to_data_uri(&data, ThumbnailFormat::Jpeg));
     }
 
     // Decode image once (the expensive part)
-    let img = ImageReader::open(&source_path)?
-        .with_guessed_format()?
-        .decode()
-        .map_err(|e| AppError::Other(format!("Failed to decode image: {}", e)))?;
+    let img = decode_for_thumbnail(&source_path, THUMBNAIL_SIZE, true)?;
 
     // Generate micro thumbnail (Nearest = fastest resize algorithm)
     let micro = img.resize(MICRO_SIZE, MICRO_SIZE, image::imageops::FilterType::Nearest).to_rgb8();
     let micro_data = encode_jpeg(&micro, 50)?;
-    save_to_cache(&micro_cache_key, &micro_data);
+    save_to_cache(&source_path, &micro_cache_key, ThumbnailFormat::Jpeg, &micro_data);
 
     // Pre-warm full thumbnail cache if not already present.
     // Since the image is already decoded in memory, this is nearly free.
     let full_cache_key = generate_cache_key(&source_path, THUMBNAIL_SIZE);
     if let Some(ref key) = full_cache_key {
-        if get_cached_thumbnail(key).is_none() {
+        if get_cached_thumbnail(key, ThumbnailFormat::Jpeg).is_none() {
             let full = img.thumbnail(THUMBNAIL_SIZE, THUMBNAIL_SIZE).to_rgb8();
             if let Ok(full_data) = encode_jpeg(&full, 80) {
-                save_to_cache(key, &full_data);
+                save_to_cache(&source_path, key, ThumbnailFormat::Jpeg, &full_data);
             }
         }
     }
 
-    Ok(to_data_uri(&micro_data))
+    Ok(to_data_uri(&micro_data, ThumbnailFormat::Jpeg))
+}
+
+/// Cached JPEG data URI for a thumbnail, without generating anything.
+fn cached_thumbnail_data(source_path: &Path, size: u32) -> Option<String> {
+    if !is_supported_image(source_path) {
+        return None;
+    }
+    let cache_key = generate_cache_key(source_path, size)?;
+    let data = fs::read(get_cached_thumbnail(&cache_key, ThumbnailFormat::Jpeg)?).ok()?;
+    Some(to_data_uri(&data, ThumbnailFormat::Jpeg))
+}
+
+/// Dedicated pool for batch generation (None if the pool failed to start).
+fn batch_pool() -> Option<&'static rayon::ThreadPool> {
+    static POOL: OnceLock<Option<rayon::ThreadPool>> = OnceLock::new();
+    POOL.get_or_init(|| {
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(num_cpus::get().saturating_sub(1).clamp(1, MAX_BATCH_THREADS))
+            .thread_name(|i| format!("thumbnail-{}", i))
+            .build()
+            .map_err(|e| log::warn!("Failed to start thumbnail pool: {}", e))
+            .ok()
+    })
+    .as_ref()
+}
+
+/// Grab one frame at `offset_secs` with ffmpeg, scaled to fit `size`, as JPEG bytes.
+fn extract_video_frame(path: &Path, size: u32, offset_secs: f64) -> Result<Vec<u8>, AppError> {
+    let output = std::process::Command::new("ffmpeg")
+        .args(["-v", "error", "-ss", &format!("{:.3}", offset_secs), "-i"])
+        .arg(path)
+        .args(["-vframes", "1", "-vf"])
+        .arg(format!("scale={0}:{0}:force_original_aspect_ratio=decrease", size))
+        .args(["-f", "image2pipe", "-vcodec", "mjpeg", "-"])
+        .stdin(std::process::Stdio::null())
+        .output()
+        .map_err(|e| match e.kind() {
+            std::io::ErrorKind::NotFound => {
+                AppError::Other("ffmpeg not found: install ffmpeg to enable video thumbnails".into())
+            }
+            _ => AppError::Io(e),
+        })?;
+
+    if !output.status.success() {
+        return Err(AppError::Other(format!(
+            "ffmpeg failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(output.stdout)
+}
+
+fn get_video_thumbnail_sync(path: String, size: Option<u32>, time_offset_secs: Option<f64>) -> Result<String, AppError> {
+    let source_path = PathBuf::from(&path);
+    let size = size.unwrap_or(THUMBNAIL_SIZE);
+    let offset = time_offset_secs.unwrap_or(VIDEO_OFFSET_SECS).max(0.0);
+
+    if !source_path.exists() {
+        return Err(AppError::NotFound(path));
+    }
+    if !is_video_file(&source_path) {
+        return Err(AppError::InvalidPath(format!("Unsupported video format: {}", path)));
+    }
+
+    let cache_key = generate_cache_key(&source_path, size)
+        .map(|k| format!("{}_video_{}", k, (offset * 1000.0) as u64))
+        .ok_or_else(|| AppError::Other(format!("Failed to generate cache key for: {}", path)))?;
+
+    if let Some(cached_path) = get_cached_thumbnail(&cache_key, ThumbnailFormat::Jpeg) {
+        let data = fs::read(&cached_path)?;
+        return Ok(to_data_uri(&data, ThumbnailFormat::Jpeg));
+    }
+
+    let mut data = extract_video_frame(&source_path, size, offset)?;
+    // Seeking past the end yields no frame; clips shorter than the offset
+    // still get a thumbnail from their first frame.
+    if data.is_empty() && offset > 0.0 {
+        data = extract_video_frame(&source_path, size, 0.0)?;
+    }
+    if data.is_empty() {
+        return Err(AppError::Other(format!("No video frame decoded from: {}", path)));
+    }
+
+    save_to_cache(&source_path, &cache_key, ThumbnailFormat::Jpeg, &data);
+    Ok(to_data_uri(&data, ThumbnailFormat::Jpeg))
+}
+
+/// Render the first PDF page at 72 DPI with poppler's `pdftoppm`.
+fn render_pdf_with_pdftoppm(path: &Path) -> Result<image::DynamicImage, AppError> {
+    let output = std::process::Command::new("pdftoppm")
+        .args(["-jpeg", "-r", "72", "-f", "1", "-l", "1"])
+        .arg(path)
+        .arg("-")
+        .stdin(std::process::Stdio::null())
+        .output()
+        .map_err(|e| match e.kind() {
+            std::io::ErrorKind::NotFound => {
+                AppError::Other("pdftoppm not found: install poppler-utils to enable PDF thumbnails".into())
+            }
+            _ => AppError::Io(e),
+        })?;
+
+    if !output.status.success() || output.stdout.is_empty() {
+        return Err(AppError::Other(format!(
+            "pdftoppm failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    image::load_from_memory_with_format(&output.stdout, ImageFormat::Jpeg)
+        .map_err(|e| AppError::Other(format!("Failed to decode PDF page: {}", e)))
+}
+
+/// Render the first PDF page in-process with pdfium, sized to fit `size`.
+#[cfg(feature = "pdfium")]
+fn render_pdf_with_pdfium(path: &Path, size: u32) -> Result<image::DynamicImage, AppError> {
+    use pdfium_render::prelude::*;
+
+    let err = |e: PdfiumError| AppError::Other(format!("pdfium: {}", e));
+    let pdfium = Pdfium::new(Pdfium::bind_to_system_library().map_err(err)?);
+    let document = pdfium.load_pdf_from_file(path, None).map_err(err)?;
+    let page = document.pages().first().map_err(err)?;
+    let config = PdfRenderConfig::new()
+        .set_target_width(size as i32)
+        .set_maximum_height(size as i32);
+    let image = page.render_with_config(&config).map_err(err)?.as_image();
+    Ok(image)
+}
+
+fn render_pdf_first_page(path: &Path, _size: u32) -> Result<image::DynamicImage, AppError> {
+    #[cfg(feature = "pdfium")]
+    match render_pdf_with_pdfium(path, _size) {
+        Ok(img) => return Ok(img),
+        Err(e) => log::warn!("{}, falling back to pdftoppm", e),
+    }
+    render_pdf_with_pdftoppm(path)
+}
+
+fn get_pdf_thumbnail_sync(path: String, size: Option<u32>) -> Result<String, AppError> {
+    let source_path = PathBuf::from(&path);
+    let size = size.unwrap_or(THUMBNAIL_SIZE);
+
+    if !source_path.exists() {
+        return Err(AppError::NotFound(path));
+    }
+    if !is_pdf_file(&source_path) {
+        return Err(AppError::InvalidPath(format!("Not a PDF file: {}", path)));
+    }
+
+    let cache_key = generate_cache_key(&source_path, size)
+        .ok_or_else(|| AppError::Other(format!("Failed to generate cache key for: {}", path)))?;
+
+    if let Some(cached_path) = get_cached_thumbnail(&cache_key, ThumbnailFormat::Jpeg) {
+        let data = fs::read(&cached_path)?;
+        return Ok(to_data_uri(&data, ThumbnailFormat::Jpeg));
+    }
+
+    let page = render_pdf_first_page(&source_path, size)?;
+    let data = encode_jpeg(&page.thumbnail(size, size).to_rgb8(), 80)?;
+    save_to_cache(&source_path, &cache_key, ThumbnailFormat::Jpeg, &data);
+
+    Ok(to_data_uri(&data, ThumbnailFormat::Jpeg))
+}
+
+fn get_thumbnail_batch_sync(paths: Vec<String>, size: Option<u32>) -> Result<Vec<ThumbnailResult>, AppError> {
+    if paths.len() > MAX_BATCH_SIZE {
+        return Err(AppError::Other(format!(
+            "Thumbnail batch too large: {} paths (max {})",
+            paths.len(),
+            MAX_BATCH_SIZE
+        )));
+    }
+    let size = size.unwrap_or(THUMBNAIL_SIZE);
+
+    // Serve cache hits inline; only misses go to the pool
+    let mut results: Vec<ThumbnailResult> = paths
+        .into_iter()
+        .map(|path| ThumbnailResult {
+            data: cached_thumbnail_data(Path::new(&path), size),
+            path,
+            error: None,
+        })
+        .collect();
+
+    let misses: Vec<&mut ThumbnailResult> = results.iter_mut().filter(|r| r.data.is_none()).collect();
+    let generate = |result: &mut ThumbnailResult| match get_thumbnail_data_sync(result.path.clone(), Some(size), ThumbnailFormat::Jpeg) {
+        Ok(data) => result.data = Some(data),
+        Err(e) => result.error = Some(e.to_string()),
+    };
+
+    match batch_pool() {
+        Some(pool) if misses.len() > 1 => pool.install(|| misses.into_par_iter().for_each(generate)),
+        _ => misses.into_iter().for_each(generate),
+    }
+
+    Ok(results)
+}
+
+/// Supported images directly in `directory` whose `size` thumbnail isn't
+/// cached yet, with their cache keys.
+fn uncached_images(directory: &Path, size: u32) -> Vec<(PathBuf, String)> {
+    let Ok(entries) = fs::read_dir(directory) else { return Vec::new() };
+    entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| is_supported_image(path) && path.is_file())
+        .filter_map(|path| {
+            let cache_key = generate_cache_key(&path, size)?;
+            get_cached_thumbnail(&cache_key, ThumbnailFormat::Jpeg).is_none().then_some((path, cache_key))
+        })
+        .collect()
+}
+
+/// Generate thumbnails for `pending` on `PRELOAD_WORKERS` threads until done
+/// or cancelled. `progress` gets the running count after each file, failed
+/// ones included. Returns how many were processed.
+fn preload_images(
+    pending: &[(PathBuf, String)],
+    size: u32,
+    cancelled: &AtomicBool,
+    progress: &(dyn Fn(u64) + Sync),
+) -> u64 {
+    let next = AtomicUsize::new(0);
+    let completed = AtomicU64::new(0);
+    std::thread::scope(|scope| {
+        for _ in 0..PRELOAD_WORKERS.min(pending.len()) {
+            scope.spawn(|| {
+                while !cancelled.load(Ordering::Relaxed) {
+                    let Some((path, cache_key)) = pending.get(next.fetch_add(1, Ordering::Relaxed)) else {
+                        break;
+                    };
+                    if let Err(e) = generate_and_cache_thumbnail(path, cache_key, size, ThumbnailFormat::Jpeg) {
+                        log::debug!("Thumbnail preload failed for {}: {}", path.display(), e);
+                    }
+                    progress(completed.fetch_add(1, Ordering::Relaxed) + 1);
+                }
+            });
+        }
+    });
+    completed.into_inner()
 }
 
 // ─── Async Tauri commands ───────────────────────────────────────────────────
 
-/// Get or generate thumbnail for an image file.
-/// Returns the path to the cached thumbnail.
+/// Get or generate thumbnail for an image file, JPEG unless `output_format`
+/// says otherwise. Returns the path to the cached thumbnail.
 #[tauri::command]
-pub async fn get_thumbnail(path: String, size: Option<u32>) -> Result<String, AppError> {
-    tokio::task::spawn_blocking(move || get_thumbnail_sync(path, size))
+pub async fn get_thumbnail(
+    path: String,
+    size: Option<u32>,
+    output_format: Option<ThumbnailFormat>,
+) -> Result<String, AppError> {
+    let format = output_format.unwrap_or_default();
+    tokio::task::spawn_blocking(move || get_thumbnail_sync(path, size, format))
         .await
         .map_err(|e| AppError::Other(format!("Task join error: {}", e)))?
 }
 
-/// Get thumbnail as base64-encoded data URI.
-/// More efficient for small thumbnails as it avoids file I/O.
+/// Get thumbnail as base64-encoded data URI, JPEG unless `output_format`
+/// says otherwise. More efficient for small thumbnails as it avoids file I/O.
 #[tauri::command]
-pub async fn get_thumbnail_data(path: String, size: Option<u32>) -> Result<String, AppError> {
-    tokio::task::spawn_blocking(move || get_thumbnail_data_sync(path, size))
+pub async fn get_thumbnail_data(
+    path: String,
+    size: Option<u32>,
+    output_format: Option<ThumbnailFormat>,
+) -> Result<String, AppError> {
+    let format = output_format.unwrap_or_default();
+    tokio::task::spawn_blocking(move || get_thumbnail_data_sync(path, size, format))
         .await
         .map_err(|e| AppError::Other(format!("Task join error: {}", e)))?
 }
@@ -255,6 +952,128 @@ pub async fn get_micro_thumbnail(path: String) -> Result<String, AppError> {
         .map_err(|e| AppError::Other(format!("Task join error: {}", e)))?
 }
 
+/// Get a thumbnail for a video file by extracting a frame with `ffmpeg`.
+/// `time_offset_secs` defaults to 1s. Returns a JPEG data URI.
+#[tauri::command]
+pub async fn get_video_thumbnail(
+    path: String,
+    size: Option<u32>,
+    time_offset_secs: Option<f64>,
+) -> Result<String, AppError> {
+    tokio::task::spawn_blocking(move || get_video_thumbnail_sync(path, size, time_offset_secs))
+        .await
+        .map_err(|e| AppError::Other(format!("Task join error: {}", e)))?
+}
+
+/// Get a thumbnail of a PDF's first page as a JPEG data URI.
+/// Uses pdfium when built with the `pdfium` feature, otherwise `pdftoppm`.
+#[tauri::command]
+pub async fn get_pdf_thumbnail(path: String, size: Option<u32>) -> Result<String, AppError> {
+    tokio::task::spawn_blocking(move || get_pdf_thumbnail_sync(path, size))
+        .await
+        .map_err(|e| AppError::Other(format!("Task join error: {}", e)))?
+}
+
+/// Get thumbnails for many files in one call, in the order requested.
+/// Per-file failures are reported in `error` rather than failing the batch.
+#[tauri::command]
+pub async fn get_thumbnail_batch(paths: Vec<String>, size: Option<u32>) -> Result<Vec<ThumbnailResult>, AppError> {
+    tokio::task::spawn_blocking(move || get_thumbnail_batch_sync(paths, size))
+        .await
+        .map_err(|e| AppError::Other(format!("Task join error: {}", e)))?
+}
+
+/// Generate thumbnails in the background for the images directly in
+/// `directory` that aren't cached yet, 4 at a time. Returns a preload ID at
+/// once; progress arrives as `thumbnail-preload-progress` events
+/// (`{ preloadId, completed, total }`, where `total` counts only uncached
+/// images). Pass the ID to `cancel_preload` to stop early.
+#[tauri::command]
+pub fn preload_thumbnails(app: AppHandle, directory: String, size: Option<u32>) -> Result<u64, AppError> {
+    let dir_path = PathBuf::from(&directory);
+    if !dir_path.is_dir() {
+        return Err(AppError::NotFound(directory));
+    }
+    let size = size.unwrap_or(THUMBNAIL_SIZE);
+    let (preload_id, cancelled) = PRELOADS.start();
+
+    std::thread::spawn(move || {
+        let pending = uncached_images(&dir_path, size);
+        let total = pending.len() as u64;
+        let emit = |completed| {
+            let _ = app.emit(
+                "thumbnail-preload-progress",
+                PreloadProgress { preload_id, completed, total },
+            );
+        };
+        emit(0);
+        let completed = preload_images(&pending, size, &cancelled, &emit);
+        log::debug!("Preloaded {}/{} thumbnails in {:?}", completed, total, dir_path);
+        PRELOADS.cleanup(preload_id);
+    });
+
+    Ok(preload_id)
+}
+
+/// Stop a running `preload_thumbnails`; thumbnails already being generated finish.
+#[tauri::command]
+pub fn cancel_preload(preload_id: u64) -> Result<(), AppError> {
+    PRELOADS.cancel(preload_id);
+    Ok(())
+}
+
+/// Source directory hash encoded in a cache file name (see `generate_cache_key`);
+/// None for files written before keys carried it.
+fn cached_directory_hash(file_name: &str) -> Option<&str> {
+    let mut parts = file_name.rsplitn(3, '-');
+    let _hash = parts.next()?;
+    let dir_hash = parts.next()?;
+    parts.next()?;
+    Some(dir_hash)
+}
+
+/// Hashes of `directory` and every indexed directory below it.
+fn subtree_hashes(index: &HashMap<String, PathBuf>, directory: &Path) -> HashSet<String> {
+    let mut hashes: HashSet<String> = index
+        .iter()
+        .filter(|(_, source_dir)| source_dir.starts_with(directory))
+        .map(|(dir_hash, _)| dir_hash.clone())
+        .collect();
+    // Covers thumbnails written before the index existed, for the top level at least
+    hashes.insert(directory_hash(directory));
+    hashes
+}
+
+/// Remove cached thumbnails whose source directory hash is in `dir_hashes`.
+/// Returns the number of bytes freed.
+fn remove_directory_entries(cache_dir: &Path, dir_hashes: &HashSet<String>) -> u64 {
+    let mut freed = 0u64;
+    for (path, metadata) in cache_files(cache_dir) {
+        let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+        let matches = cached_directory_hash(&file_name).is_some_and(|hash| dir_hashes.contains(hash));
+        if matches && fs::remove_file(&path).is_ok() {
+            freed += metadata.len();
+        }
+    }
+    freed
+}
+
+/// Remove the cached thumbnails (all sizes and formats) of every file under
+/// `directory`, subdirectories included. Returns bytes freed.
+#[tauri::command]
+pub fn clear_thumbnail_cache_for_directory(directory: String) -> Result<u64, AppError> {
+    let cache_dir = get_cache_dir().ok_or(AppError::Other("Failed to get cache directory".into()))?;
+    let hashes = {
+        let mut guard = DIRECTORY_INDEX.lock().unwrap_or_else(|e| e.into_inner());
+        let index = guard.get_or_insert_with(|| load_directory_index(&cache_dir));
+        subtree_hashes(index, Path::new(&directory))
+    };
+    let freed = remove_directory_entries(&cache_dir, &hashes);
+    cache_size_removed(freed);
+    log::info!("Cleared thumbnails for {}: {} bytes freed", directory, freed);
+    Ok(freed)
+}
+
 /// Clear the thumbnail cache
 #[tauri::command]
 pub fn clear_thumbnail_cache() -> Result<u64, AppError> {
@@ -267,53 +1086,112 @@ pub fn clear_thumbnail_cache() -> Result<u64, AppError> {
     log::info!("Clearing thumbnail cache");
     let mut cleared = 0u64;
 
-    for entry in fs::read_dir(&cache_dir).map_err(AppError::Io)? {
-        if let Ok(entry) = entry {
-            if let Ok(metadata) = entry.metadata() {
-                if metadata.is_file() {
-                    if fs::remove_file(entry.path()).is_ok() {
-                        cleared += metadata.len();
-                    }
-                }
-            }
+    for (path, metadata) in cache_files(&cache_dir) {
+        if fs::remove_file(path).is_ok() {
+            cleared += metadata.len();
         }
     }
+    cache_size_removed(cleared);
 
     Ok(cleared)
 }
 
+/// Cap the thumbnail cache at `max_bytes` (0 removes the limit), evicting
+/// least recently used thumbnails now and whenever a new thumbnail takes
+/// the cache over it. Returns the number of bytes freed immediately.
+#[tauri::command]
+pub fn set_thumbnail_cache_limit(max_bytes: u64) -> Result<u64, AppError> {
+    CACHE_LIMIT
+        .get_or_init(|| AtomicU64::new(0))
+        .store(max_bytes, Ordering::Relaxed);
+    if max_bytes == 0 {
+        return Ok(0);
+    }
+    log::info!("Thumbnail cache limit set to {} bytes", max_bytes);
+    enforce_cache_limit(max_bytes)
+}
+
 /// Get cache statistics
 #[tauri::command]
 pub fn get_thumbnail_cache_stats() -> Result<ThumbnailCacheStats, AppError> {
     let cache_dir = get_cache_dir().ok_or(AppError::Other("Failed to get cache directory".into()))?;
+    collect_cache_stats(&cache_dir, std::time::SystemTime::now())
+}
+
+/// Upper bounds (in seconds of age) of the first three age buckets:
+/// last day, last week, last month. Everything older goes in the fourth.
+const AGE_BUCKET_LIMITS: [u64; 3] = [86_400, 7 * 86_400, 30 * 86_400];
+
+fn age_bucket(age_secs: u64) -> usize {
+    AGE_BUCKET_LIMITS
+        .iter()
+        .position(|&limit| age_secs < limit)
+        .unwrap_or(AGE_BUCKET_LIMITS.len())
+}
+
+/// Source extension encoded in a cache file name (see `generate_cache_key`).
+fn cached_source_extension(file_name: &str) -> &str {
+    match file_name.split_once('-') {
+        Some((ext, _)) if !ext.is_empty() => ext,
+        _ => "unknown", // files written before keys carried the extension
+    }
+}
+
+fn collect_cache_stats(cache_dir: &Path, now: std::time::SystemTime) -> Result<ThumbnailCacheStats, AppError> {
+    let mut stats = ThumbnailCacheStats {
+        count: 0,
+        total_size: 0,
+        path: cache_dir.to_string_lossy().to_string(),
+        limit_bytes: cache_limit(),
+        by_extension: HashMap::new(),
+        oldest_entry_secs: None,
+        newest_entry_secs: None,
+        size_by_age_bucket: [0; 4],
+    };
 
     if !cache_dir.exists() {
-        return Ok(ThumbnailCacheStats {
-            count: 0,
-            total_size: 0,
-            path: cache_dir.to_string_lossy().to_string(),
-        });
+        return Ok(stats);
     }
 
-    let mut count = 0;
-    let mut total_size = 0u64;
+    for (path, metadata) in cache_files(cache_dir) {
+        stats.count += 1;
+        stats.total_size += metadata.len();
 
-    for entry in fs::read_dir(&cache_dir).map_err(AppError::Io)? {
-        if let Ok(entry) = entry {
-            if let Ok(metadata) = entry.metadata() {
-                if metadata.is_file() {
-                    count += 1;
-                    total_size += metadata.len();
-                }
-            }
-        }
+        let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+        let ext = cached_source_extension(&file_name).to_string();
+        *stats.by_extension.entry(ext).or_insert(0) += 1;
+
+        let modified = metadata.modified().unwrap_or(std::time::UNIX_EPOCH);
+        let modified_secs = modified
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        stats.oldest_entry_secs = Some(stats.oldest_entry_secs.map_or(modified_secs, |s| s.min(modified_secs)));
+        stats.newest_entry_secs = Some(stats.newest_entry_secs.map_or(modified_secs, |s| s.max(modified_secs)));
+
+        let age_secs = now.duration_since(modified).map(|d| d.as_secs()).unwrap_or(0);
+        stats.size_by_age_bucket[age_bucket(age_secs)] += metadata.len();
     }
 
-    Ok(ThumbnailCacheStats {
-        count,
-        total_size,
-        path: cache_dir.to_string_lossy().to_string(),
-    })
+    Ok(stats)
+}
+
+/// One entry of a `get_thumbnail_batch` response.
+#[derive(Debug, serde::Serialize)]
+pub struct ThumbnailResult {
+    path: String,
+    /// Data URI, if the thumbnail was available or generated.
+    data: Option<String>,
+    error: Option<String>,
+}
+
+/// Event payload for `thumbnail-preload-progress`.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct PreloadProgress {
+    #[serde(rename = "preloadId")]
+    preload_id: u64,
+    completed: u64,
+    total: u64,
 }
 
 #[derive(Debug, serde::Serialize)]
@@ -322,6 +1200,20 @@ pub struct ThumbnailCacheStats {
     #[serde(rename = "totalSize")]
     total_size: u64,
     path: String,
+    #[serde(rename = "limitBytes")]
+    limit_bytes: Option<u64>,
+    /// Thumbnail count per source file extension.
+    #[serde(rename = "byExtension")]
+    by_extension: HashMap<String, usize>,
+    /// Oldest cache file mtime, seconds since the Unix epoch.
+    #[serde(rename = "oldestEntrySecs")]
+    oldest_entry_secs: Option<u64>,
+    /// Newest cache file mtime, seconds since the Unix epoch.
+    #[serde(rename = "newestEntrySecs")]
+    newest_entry_secs: Option<u64>,
+    /// Bytes by age: last day, last week, last month, older.
+    #[serde(rename = "sizeByAgeBucket")]
+    size_by_age_bucket: [u64; 4],
 }
 
 #[cfg(test)]
@@ -340,6 +1232,21 @@ mod tests {
         assert!(is_supported_image(Path::new("test.bmp")));
         assert!(!is_supported_image(Path::new("test.txt")));
         assert!(!is_supported_image(Path::new("test.pdf")));
+        assert!(is_supported_image(Path::new("icon.SVG")));
+        assert!(is_vector_image(Path::new("icon.svg")));
+        assert!(!is_vector_image(Path::new("photo.png")));
+        assert!(is_heic_image(Path::new("IMG_0001.HEIC")));
+        assert_eq!(is_supported_image(Path::new("IMG_0001.heif")), cfg!(target_os = "macos"));
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    #[test]
+    fn test_heic_unsupported_off_macos() {
+        let dir = tempdir().unwrap();
+        let file_path = dir.path().join("IMG_0001.heic");
+        File::create(&file_path).unwrap();
+        let err = get_thumbnail_sync(file_path.to_string_lossy().to_string(), None, ThumbnailFormat::Jpeg);
+        assert!(matches!(err, Err(AppError::Other(msg)) if msg.starts_with("HEIC not supported")));
     }
 
     #[test]
@@ -348,9 +1255,71 @@ mod tests {
         let file_path = dir.path().join("test.jpg");
         File::create(&file_path).unwrap();
 
-        let key = generate_cache_key(&file_path, THUMBNAIL_SIZE);
-        assert!(key.is_some());
-        assert_eq!(key.unwrap().len(), 64); // SHA256 hex is 64 chars
+        let key = generate_cache_key(&file_path, THUMBNAIL_SIZE).unwrap();
+        let (ext, rest) = key.split_once('-').unwrap();
+        let (dir_hash, hash) = rest.split_once('-').unwrap();
+        assert_eq!(ext, "jpg");
+        assert_eq!(dir_hash, directory_hash(dir.path()));
+        assert_eq!(hash.len(), 64); // SHA256 hex is 64 chars
+    }
+
+    #[test]
+    fn test_remove_directory_entries() {
+        let photos = Path::new("/home/user/photos");
+        let photos_hash = directory_hash(photos);
+        assert_eq!(photos_hash.len(), DIRECTORY_HASH_LEN);
+        assert_eq!(directory_hash(Path::new("/home/user/photos/")), photos_hash);
+        let other_hash = directory_hash(Path::new("/home/user/other"));
+        assert_ne!(other_hash, photos_hash);
+
+        let cache = tempdir().unwrap();
+        fs::create_dir(cache.path().join("jpeg")).unwrap();
+        fs::create_dir(cache.path().join("webp")).unwrap();
+        let files = [
+            (format!("jpeg/png-{}-aaa.jpg", photos_hash), 10),
+            (format!("jpeg/png-{}-bbb_micro.jpg", photos_hash), 20),
+            (format!("webp/png-{}-aaa.webp", photos_hash), 30),
+            (format!("jpeg/png-{}-ccc.jpg", other_hash), 40),
+            ("png-legacyhash.jpg".to_string(), 50),
+        ];
+        for (name, len) in &files {
+            fs::write(cache.path().join(name), vec![0u8; *len]).unwrap();
+        }
+
+        let hashes = HashSet::from([photos_hash.clone()]);
+        assert_eq!(remove_directory_entries(cache.path(), &hashes), 60);
+        let remaining: Vec<_> = files.iter().filter(|(name, _)| cache.path().join(name).exists()).collect();
+        assert_eq!(remaining.len(), 2);
+        assert_eq!(remove_directory_entries(cache.path(), &hashes), 0);
+    }
+
+    #[test]
+    fn test_clear_directory_includes_subdirectories() {
+        let photos = Path::new("/home/user/photos");
+        let nested = Path::new("/home/user/photos/2024/summer");
+        let sibling = Path::new("/home/user/photos-old");
+        let index: HashMap<String, PathBuf> = [photos, nested, sibling]
+            .iter()
+            .map(|dir| (directory_hash(dir), dir.to_path_buf()))
+            .collect();
+
+        let cache = tempdir().unwrap();
+        fs::create_dir(cache.path().join("jpeg")).unwrap();
+        fs::write(cache.path().join(DIRECTORY_INDEX_FILE), serde_json::to_vec(&index).unwrap()).unwrap();
+        let top = format!("jpeg/jpg-{}-aaa.jpg", directory_hash(photos));
+        let deep = format!("jpeg/jpg-{}-bbb.jpg", directory_hash(nested));
+        let other = format!("jpeg/jpg-{}-ccc.jpg", directory_hash(sibling));
+        for name in [&top, &deep, &other] {
+            fs::write(cache.path().join(name), vec![0u8; 10]).unwrap();
+        }
+
+        let hashes = subtree_hashes(&load_directory_index(cache.path()), Path::new("/home/user/photos/"));
+        assert_eq!(remove_directory_entries(cache.path(), &hashes), 20);
+        assert!(!cache.path().join(&top).exists());
+        assert!(!cache.path().join(&deep).exists());
+        assert!(cache.path().join(&other).exists());
+        // The index itself is not counted as a thumbnail
+        assert_eq!(cache_files(cache.path()).len(), 1);
     }
 
     #[test]
@@ -378,7 +1347,7 @@ mod tests {
         img.save(&png_path).unwrap();
 
         // Test get_thumbnail_data_sync succeeds for PNG
-        let result = get_thumbnail_data_sync(png_path.to_string_lossy().to_string(), Some(64));
+        let result = get_thumbnail_data_sync(png_path.to_string_lossy().to_string(), Some(64), ThumbnailFormat::Jpeg);
         assert!(result.is_ok(), "PNG thumbnail generation failed: {:?}", result.err());
         let data_uri = result.unwrap();
         assert!(data_uri.starts_with("data:image/jpeg;base64,"), "Expected JPEG data URI, got: {}", &data_uri[..50]);
@@ -392,7 +1361,7 @@ mod tests {
             return; // Skip if not available
         }
 
-        let result = get_thumbnail_data_sync(icon_path.to_string_lossy().to_string(), Some(64));
+        let result = get_thumbnail_data_sync(icon_path.to_string_lossy().to_string(), Some(64), ThumbnailFormat::Jpeg);
         assert!(result.is_ok(), "Real PNG thumbnail failed: {:?}", result.err());
     }
 
@@ -407,7 +1376,7 @@ mod tests {
         });
         img.save(&jpg_path).unwrap();
 
-        let result = get_thumbnail_data_sync(jpg_path.to_string_lossy().to_string(), Some(64));
+        let result = get_thumbnail_data_sync(jpg_path.to_string_lossy().to_string(), Some(64), ThumbnailFormat::Jpeg);
         assert!(result.is_ok(), "JPEG thumbnail generation failed: {:?}", result.err());
         let data_uri = result.unwrap();
         assert!(data_uri.starts_with("data:image/jpeg;base64,"));
@@ -432,8 +1401,294 @@ mod tests {
         // Verify that full thumbnail cache was pre-warmed
         let cache_key = generate_cache_key(&img_path, THUMBNAIL_SIZE).unwrap();
         assert!(
-            get_cached_thumbnail(&cache_key).is_some(),
+            get_cached_thumbnail(&cache_key, ThumbnailFormat::Jpeg).is_some(),
             "Full thumbnail cache should be pre-warmed by micro thumbnail"
         );
     }
+
+    #[test]
+    fn test_thumbnail_batch_mixed_results() {
+        let dir = tempdir().unwrap();
+        let png_path = dir.path().join("batch.png");
+        image::RgbImage::from_fn(40, 20, |_, _| image::Rgb([10, 200, 30]))
+            .save(&png_path)
+            .unwrap();
+        let missing = dir.path().join("missing.png");
+        let text = dir.path().join("notes.txt");
+        fs::write(&text, "hi").unwrap();
+
+        let paths: Vec<String> = [&png_path, &missing, &text]
+            .iter()
+            .map(|p| p.to_string_lossy().to_string())
+            .collect();
+        let results = get_thumbnail_batch_sync(paths.clone(), Some(32)).unwrap();
+
+        assert_eq!(results.iter().map(|r| r.path.clone()).collect::<Vec<_>>(), paths);
+        assert!(results[0].data.as_ref().unwrap().starts_with("data:image/jpeg;base64,"));
+        assert!(results[0].error.is_none());
+        assert!(results[1].data.is_none() && results[1].error.is_some());
+        assert!(results[2].data.is_none() && results[2].error.is_some());
+
+        // Second call is served from cache
+        let cached = get_thumbnail_batch_sync(paths[..1].to_vec(), Some(32)).unwrap();
+        assert_eq!(cached[0].data, results[0].data);
+    }
+
+    #[test]
+    fn test_thumbnail_batch_size_cap() {
+        let paths = vec!["/nonexistent.png".to_string(); MAX_BATCH_SIZE + 1];
+        assert!(get_thumbnail_batch_sync(paths, None).is_err());
+
+        let paths = vec!["/nonexistent.png".to_string(); MAX_BATCH_SIZE];
+        assert_eq!(get_thumbnail_batch_sync(paths, None).unwrap().len(), MAX_BATCH_SIZE);
+    }
+
+    #[test]
+    fn test_preload_generates_uncached_thumbnails() {
+        let dir = tempdir().unwrap();
+        for i in 0..6 {
+            image::RgbImage::from_fn(30, 20, |_, _| image::Rgb([i * 40, 0, 0]))
+                .save(dir.path().join(format!("p{}.png", i)))
+                .unwrap();
+        }
+        fs::write(dir.path().join("notes.txt"), "not an image").unwrap();
+        fs::write(dir.path().join("broken.jpg"), "not a jpeg").unwrap();
+
+        let pending = uncached_images(dir.path(), 48);
+        assert_eq!(pending.len(), 7);
+
+        let reported = std::sync::Mutex::new(Vec::new());
+        let completed = preload_images(&pending, 48, &AtomicBool::new(false), &|n| {
+            reported.lock().unwrap().push(n)
+        });
+        assert_eq!(completed, 7, "failures count as processed");
+        let mut reported = reported.into_inner().unwrap();
+        reported.sort();
+        assert_eq!(reported, (1..=7).collect::<Vec<u64>>());
+
+        let still_pending = uncached_images(dir.path(), 48);
+        assert_eq!(still_pending.len(), 1);
+        assert!(still_pending[0].0.ends_with("broken.jpg"));
+
+        assert_eq!(preload_images(&still_pending, 48, &AtomicBool::new(true), &|_| {}), 0);
+    }
+
+    #[test]
+    fn test_is_video_file() {
+        assert!(is_video_file(Path::new("clip.mp4")));
+        assert!(is_video_file(Path::new("Clip.MKV")));
+        assert!(is_video_file(Path::new("movie.webm")));
+        assert!(!is_video_file(Path::new("photo.jpg")));
+        assert!(!is_video_file(Path::new("mp4")));
+    }
+
+    #[test]
+    fn test_video_thumbnail_rejects_bad_input() {
+        let dir = tempdir().unwrap();
+        let missing = dir.path().join("missing.mp4").to_string_lossy().to_string();
+        assert!(matches!(get_video_thumbnail_sync(missing, None, None), Err(AppError::NotFound(_))));
+
+        let image = dir.path().join("still.png");
+        File::create(&image).unwrap();
+        let result = get_video_thumbnail_sync(image.to_string_lossy().to_string(), None, None);
+        assert!(matches!(result, Err(AppError::InvalidPath(_))));
+    }
+
+    #[test]
+    fn test_is_supported_format() {
+        assert!(is_pdf_file(Path::new("report.PDF")));
+        assert!(!is_pdf_file(Path::new("report.txt")));
+        assert!(is_supported_format("photo.jpg".into()));
+        assert!(is_supported_format("report.pdf".into()));
+        assert!(is_supported_format("clip.mp4".into()));
+        assert!(!is_supported_format("notes.txt".into()));
+    }
+
+    #[test]
+    fn test_pdf_thumbnail_rejects_bad_input() {
+        let dir = tempdir().unwrap();
+        let missing = dir.path().join("missing.pdf").to_string_lossy().to_string();
+        assert!(matches!(get_pdf_thumbnail_sync(missing, None), Err(AppError::NotFound(_))));
+
+        let image = dir.path().join("still.png");
+        File::create(&image).unwrap();
+        let result = get_pdf_thumbnail_sync(image.to_string_lossy().to_string(), None);
+        assert!(matches!(result, Err(AppError::InvalidPath(_))));
+    }
+
+    /// Insert a minimal EXIF APP1 segment with the given orientation after the JPEG SOI marker.
+    fn with_exif_orientation(jpeg: &[u8], orientation: u8) -> Vec<u8> {
+        let mut tiff = vec![b'I', b'I', 0x2A, 0x00, 0x08, 0x00, 0x00, 0x00]; // little-endian header, IFD at 8
+        tiff.extend_from_slice(&[0x01, 0x00]); // one entry
+        tiff.extend_from_slice(&[0x12, 0x01, 0x03, 0x00, 0x01, 0x00, 0x00, 0x00]); // Orientation, SHORT, count 1
+        tiff.extend_from_slice(&[orientation, 0x00, 0x00, 0x00]);
+        tiff.extend_from_slice(&[0x00, 0x00, 0x00, 0x00]); // no next IFD
+
+        let mut app1 = b"Exif\0\0".to_vec();
+        app1.extend_from_slice(&tiff);
+        let len = (app1.len() + 2) as u16;
+
+        let mut out = jpeg[..2].to_vec();
+        out.extend_from_slice(&[0xFF, 0xE1]);
+        out.extend_from_slice(&len.to_be_bytes());
+        out.extend_from_slice(&app1);
+        out.extend_from_slice(&jpeg[2..]);
+        out
+    }
+
+    #[test]
+    fn test_exif_orientation_rotates_thumbnail() {
+        let dir = tempdir().unwrap();
+        let jpg_path = dir.path().join("rotated.jpg");
+
+        // Landscape pixels, tagged "rotate 90° CW to display" (orientation 6)
+        let img = image::RgbImage::from_fn(80, 40, |x, _| image::Rgb([(x * 3) as u8, 90, 30]));
+        let jpeg = encode_jpeg(&img, 90).unwrap();
+        fs::write(&jpg_path, with_exif_orientation(&jpeg, 6)).unwrap();
+        assert_eq!(read_exif_orientation(&jpg_path), Some(6));
+
+        let key = generate_cache_key(&jpg_path, 64).unwrap();
+        let thumb_path = generate_and_cache_thumbnail(&jpg_path, &key, 64, ThumbnailFormat::Jpeg).unwrap();
+        let (width, height) = image::image_dimensions(&thumb_path).unwrap();
+        assert!(height > width, "expected portrait thumbnail, got {}x{}", width, height);
+        let _ = fs::remove_file(thumb_path);
+    }
+
+    #[test]
+    fn test_no_exif_keeps_orientation() {
+        let dir = tempdir().unwrap();
+        let png_path = dir.path().join("plain.png");
+        image::RgbImage::from_fn(80, 40, |_, _| image::Rgb([1, 2, 3])).save(&png_path).unwrap();
+
+        assert_eq!(read_exif_orientation(&png_path), None);
+        let img = load_oriented_image(&png_path).unwrap();
+        assert_eq!((img.width(), img.height()), (80, 40));
+    }
+
+    #[test]
+    fn test_evict_lru_removes_oldest_first() {
+        let dir = tempdir().unwrap();
+        let now = std::time::SystemTime::now();
+        for (i, name) in ["old.jpg", "mid.jpg", "new.jpg"].iter().enumerate() {
+            let path = dir.path().join(name);
+            fs::write(&path, vec![0u8; 100]).unwrap();
+            let age = std::time::Duration::from_secs(3600 * (3 - i as u64));
+            File::options().write(true).open(&path).unwrap().set_modified(now - age).unwrap();
+        }
+
+        assert_eq!(evict_lru(dir.path(), 300).unwrap(), (0, 300));
+        assert_eq!(evict_lru(dir.path(), 250).unwrap(), (100, 200));
+        assert!(!dir.path().join("old.jpg").exists());
+        assert!(dir.path().join("mid.jpg").exists());
+
+        assert_eq!(evict_lru(dir.path(), 0).unwrap(), (200, 0));
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn test_svg_thumbnail_preserves_aspect_ratio() {
+        let dir = tempdir().unwrap();
+        let svg_path = dir.path().join("wide.svg");
+        fs::write(
+            &svg_path,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="200" height="100"><rect width="200" height="100" fill="red"/></svg>"#,
+        )
+        .unwrap();
+
+        let jpeg = rasterize_svg_thumbnail(&svg_path, 64).unwrap();
+        let img = image::load_from_memory(&jpeg).unwrap().to_rgb8();
+        assert_eq!((img.width(), img.height()), (64, 32));
+        let center = img.get_pixel(32, 16);
+        assert!(center[0] > 200 && center[1] < 60, "expected red, got {:?}", center);
+
+        let data_uri = get_thumbnail_data_sync(svg_path.to_string_lossy().to_string(), Some(64), ThumbnailFormat::Jpeg).unwrap();
+        assert!(data_uri.starts_with("data:image/jpeg;base64,"));
+    }
+
+    #[test]
+    fn test_thumbnail_output_formats() {
+        let dir = tempdir().unwrap();
+        let png_path = dir.path().join("formats.png");
+        image::RgbaImage::from_fn(60, 30, |x, _| image::Rgba([200, 10, 10, (x * 4) as u8]))
+            .save(&png_path)
+            .unwrap();
+        let path = png_path.to_string_lossy().to_string();
+
+        for (format, mime, dir_name) in [
+            (ThumbnailFormat::Jpeg, "image/jpeg", "jpeg"),
+            (ThumbnailFormat::Png, "image/png", "png"),
+            (ThumbnailFormat::WebP, "image/webp", "webp"),
+        ] {
+            let data_uri = get_thumbnail_data_sync(path.clone(), Some(40), format).unwrap();
+            let encoded = data_uri.strip_prefix(&format!("data:{};base64,", mime)).unwrap();
+            let bytes = base64::engine::general_purpose::STANDARD.decode(encoded).unwrap();
+            let img = image::load_from_memory(&bytes).unwrap();
+            assert_eq!((img.width(), img.height()), (40, 20));
+            assert_eq!(img.color().has_alpha(), format.has_alpha());
+            if format == ThumbnailFormat::WebP {
+                // Lossy bitstream, not VP8L
+                assert!(bytes.windows(4).any(|chunk| chunk == b"VP8 "));
+            }
+
+            let cached = PathBuf::from(get_thumbnail_sync(path.clone(), Some(40), format).unwrap());
+            assert_eq!(cached.parent().unwrap().file_name().unwrap(), dir_name);
+            assert_eq!(cached.extension().unwrap(), format.extension());
+        }
+
+        let format: ThumbnailFormat = serde_json::from_str("\"webp\"").unwrap();
+        assert_eq!(format, ThumbnailFormat::WebP);
+    }
+
+    #[test]
+    fn test_invalid_svg_errors() {
+        let dir = tempdir().unwrap();
+        let svg_path = dir.path().join("broken.svg");
+        fs::write(&svg_path, "not svg").unwrap();
+
+        assert!(rasterize_svg_thumbnail(&svg_path, 64).is_err());
+    }
+
+    #[test]
+    fn test_cache_stats_breakdown() {
+        let dir = tempdir().unwrap();
+        let now = std::time::SystemTime::now();
+        let day = 86_400;
+        let files = [
+            ("png-aaa.jpg", 10, 60),           // last day
+            ("png-bbb_micro.jpg", 20, 3 * day), // last week
+            ("svg-ccc.jpg", 30, 10 * day),      // last month
+            ("legacyhash.jpg", 40, 90 * day),   // older
+        ];
+        for (name, len, age) in files {
+            let path = dir.path().join(name);
+            fs::write(&path, vec![0u8; len]).unwrap();
+            let mtime = now - std::time::Duration::from_secs(age);
+            File::options().write(true).open(&path).unwrap().set_modified(mtime).unwrap();
+        }
+
+        let stats = collect_cache_stats(dir.path(), now).unwrap();
+
+        assert_eq!(stats.count, 4);
+        assert_eq!(stats.total_size, 100);
+        assert_eq!(stats.by_extension.get("png"), Some(&2));
+        assert_eq!(stats.by_extension.get("svg"), Some(&1));
+        assert_eq!(stats.by_extension.get("unknown"), Some(&1));
+        assert_eq!(stats.size_by_age_bucket, [10, 20, 30, 40]);
+        let now_secs = now.duration_since(std::time::UNIX_EPOCH).unwrap().as_secs();
+        assert_eq!(stats.newest_entry_secs, Some(now_secs - 60));
+        assert_eq!(stats.oldest_entry_secs, Some(now_secs - 90 * day));
+
+        let json = serde_json::to_string(&stats).unwrap();
+        assert!(json.contains("\"sizeByAgeBucket\":[10,20,30,40]"));
+        assert!(json.contains("\"byExtension\""));
+    }
+
+    #[test]
+    fn test_cache_stats_missing_dir() {
+        let dir = tempdir().unwrap();
+        let stats = collect_cache_stats(&dir.path().join("nope"), std::time::SystemTime::now()).unwrap();
+        assert_eq!(stats.count, 0);
+        assert_eq!(stats.oldest_entry_secs, None);
+        assert_eq!(stats.size_by_age_bucket, [0; 4]);
+    }
 }
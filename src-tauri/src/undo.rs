@@ -0,0 +1,435 @@
+//! In-memory log of file operations, for undo.
+//!
+//! The mutating commands in `files::file_ops` (and the trash commands) record
+//! what they did here; `merge_directories` and `flatten_directory` record
+//! all their moves as one batch. Copies, moves, renames and creations can be
+//! reversed; permanent deletes and trash operations are logged for the
+//! history view but can't be undone from here. The background batch
+//! copy/move commands in `files::batch` and the archive commands are not
+//! logged. The log lives in Tauri managed state and is lost when the app
+//! exits.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+
+use chrono::Local;
+use serde::Serialize;
+use tauri::{AppHandle, Manager, State};
+
+use crate::error::AppError;
+use crate::files::file_ops;
+
+/// Operations kept in the log; older ones fall off and can't be undone.
+const MAX_OPERATIONS: usize = 50;
+
+/// A completed file operation, with what's needed to reverse it.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Operation {
+    /// `snapshot` is the copy as it was made; see `Operation::copy`.
+    Copy { source: PathBuf, target: PathBuf, snapshot: Option<Snapshot> },
+    Move { source: PathBuf, target: PathBuf },
+    Rename { source: PathBuf, target: PathBuf },
+    /// New files or directories, outermost first.
+    Create { paths: Vec<PathBuf> },
+    Delete { paths: Vec<PathBuf> },
+    Trash { paths: Vec<PathBuf> },
+    /// Several operations done by one command into `destination`, undone
+    /// together, last first.
+    Batch { kind: OperationKind, destination: PathBuf, operations: Vec<Operation> },
+}
+
+/// Entry count, total size and latest mtime of everything under a path,
+/// to tell whether it has been touched since it was recorded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Snapshot {
+    entries: u64,
+    bytes: u64,
+    modified: Option<SystemTime>,
+}
+
+impl Snapshot {
+    /// None if `path` or anything below it can't be read.
+    fn of(path: &Path) -> Option<Snapshot> {
+        let mut snapshot = Snapshot { entries: 0, bytes: 0, modified: None };
+        snapshot.add(path).ok()?;
+        Some(snapshot)
+    }
+
+    fn add(&mut self, path: &Path) -> std::io::Result<()> {
+        let metadata = fs::symlink_metadata(path)?;
+        self.entries += 1;
+        self.bytes += metadata.len();
+        self.modified = self.modified.max(metadata.modified().ok());
+        if metadata.is_dir() {
+            for entry in fs::read_dir(path)? {
+                self.add(&entry?.path())?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OperationKind {
+    Copy,
+    Move,
+    Rename,
+    Create,
+    Delete,
+    Trash,
+}
+
+impl Operation {
+    /// A copy of `source` at `target`, snapshotted so undo can refuse to
+    /// delete it once it has been changed.
+    pub(crate) fn copy(source: PathBuf, target: PathBuf) -> Self {
+        let snapshot = Snapshot::of(&target);
+        Operation::Copy { source, target, snapshot }
+    }
+
+    fn kind(&self) -> OperationKind {
+        match self {
+            Operation::Copy { .. } => OperationKind::Copy,
+            Operation::Move { .. } => OperationKind::Move,
+            Operation::Rename { .. } => OperationKind::Rename,
+            Operation::Create { .. } => OperationKind::Create,
+            Operation::Delete { .. } => OperationKind::Delete,
+            Operation::Trash { .. } => OperationKind::Trash,
+            Operation::Batch { kind, .. } => *kind,
+        }
+    }
+
+    fn is_undoable(&self) -> bool {
+        match self {
+            Operation::Delete { .. } | Operation::Trash { .. } => false,
+            Operation::Batch { operations, .. } => operations.iter().all(Operation::is_undoable),
+            _ => true,
+        }
+    }
+
+    /// Put things back the way they were before the operation. Undoing an
+    /// overwriting copy or move can't bring back the entry it replaced.
+    /// A batch that fails part way keeps only the operations not yet undone.
+    fn reverse(&mut self) -> Result<(), AppError> {
+        match self {
+            Operation::Copy { target, snapshot, .. } => {
+                if fs::symlink_metadata(&*target).is_err() {
+                    return Err(AppError::NotFound(target.to_string_lossy().to_string()));
+                }
+                if snapshot.is_none() || Snapshot::of(target) != *snapshot {
+                    return Err(AppError::Other(format!(
+                        "{} has changed since it was copied; not deleting it",
+                        target.display()
+                    )));
+                }
+                remove_entry(target)
+            }
+            Operation::Move { source, target } => {
+                ensure_free(source)?;
+                // The source's folder may have been removed since (merge, flatten)
+                if let Some(parent) = source.parent() {
+                    fs::create_dir_all(parent)?;
+                }
+                file_ops::move_to(target, source)
+            }
+            Operation::Rename { source, target } => {
+                file_ops::rename_noreplace(target, source).map_err(|e| match e.kind() {
+                    std::io::ErrorKind::AlreadyExists => {
+                        AppError::AlreadyExists(source.to_string_lossy().to_string())
+                    }
+                    std::io::ErrorKind::NotFound => AppError::NotFound(target.to_string_lossy().to_string()),
+                    _ => AppError::Io(e),
+                })
+            }
+            Operation::Create { paths } => {
+                // Innermost first; a directory that has gained contents since
+                // is left alone rather than deleted with them, and paths
+                // already gone (from an earlier, partly failed undo) are skipped
+                for path in paths.iter().rev() {
+                    let Ok(metadata) = fs::symlink_metadata(path) else {
+                        continue;
+                    };
+                    if metadata.is_dir() {
+                        fs::remove_dir(path)?;
+                    } else {
+                        fs::remove_file(path)?;
+                    }
+                }
+                Ok(())
+            }
+            Operation::Batch { operations, .. } => {
+                while let Some(operation) = operations.last_mut() {
+                    operation.reverse()?;
+                    operations.pop();
+                }
+                Ok(())
+            }
+            Operation::Delete { .. } | Operation::Trash { .. } => {
+                Err(AppError::Other(format!("A {:?} operation can't be undone", self.kind())))
+            }
+        }
+    }
+
+    /// Source paths (or the created/deleted paths) and the destination.
+    fn paths(&self) -> (Vec<&Path>, Option<&Path>) {
+        match self {
+            Operation::Copy { source, target, .. }
+            | Operation::Move { source, target }
+            | Operation::Rename { source, target } => (vec![source.as_path()], Some(target.as_path())),
+            Operation::Create { paths } | Operation::Delete { paths } | Operation::Trash { paths } => {
+                (paths.iter().map(PathBuf::as_path).collect(), None)
+            }
+            Operation::Batch { destination, operations, .. } => {
+                let sources = operations.iter().flat_map(|operation| operation.paths().0).collect();
+                (sources, Some(destination.as_path()))
+            }
+        }
+    }
+}
+
+fn ensure_free(path: &Path) -> Result<(), AppError> {
+    if fs::symlink_metadata(path).is_ok() {
+        return Err(AppError::AlreadyExists(path.to_string_lossy().to_string()));
+    }
+    Ok(())
+}
+
+fn remove_entry(path: &Path) -> Result<(), AppError> {
+    let metadata =
+        fs::symlink_metadata(path).map_err(|_| AppError::NotFound(path.to_string_lossy().to_string()))?;
+    if metadata.is_dir() {
+        fs::remove_dir_all(path)?;
+    } else {
+        fs::remove_file(path)?;
+    }
+    Ok(())
+}
+
+/// One logged operation, as shown in the undo history.
+#[derive(Debug, Clone, Serialize)]
+pub struct OperationSummary {
+    pub id: u64,
+    pub kind: OperationKind,
+    /// Sources for copy/move/rename; the affected paths otherwise.
+    pub paths: Vec<String>,
+    pub destination: Option<String>,
+    /// ISO 8601, local time.
+    pub timestamp: String,
+    #[serde(rename = "isUndoable")]
+    pub is_undoable: bool,
+}
+
+#[derive(Debug)]
+struct LoggedOperation {
+    id: u64,
+    operation: Operation,
+    timestamp: String,
+}
+
+impl LoggedOperation {
+    fn summary(&self) -> OperationSummary {
+        let (paths, destination) = self.operation.paths();
+        OperationSummary {
+            id: self.id,
+            kind: self.operation.kind(),
+            paths: paths.iter().map(|p| p.to_string_lossy().to_string()).collect(),
+            destination: destination.map(|p| p.to_string_lossy().to_string()),
+            timestamp: self.timestamp.clone(),
+            is_undoable: self.operation.is_undoable(),
+        }
+    }
+}
+
+/// Managed state holding the operation log, oldest first.
+#[derive(Debug, Default)]
+pub struct UndoLog {
+    operations: Arc<Mutex<Vec<LoggedOperation>>>,
+    next_id: AtomicU64,
+}
+
+impl UndoLog {
+    pub(crate) fn push(&self, operation: Operation) {
+        let logged = LoggedOperation {
+            id: self.next_id.fetch_add(1, Ordering::Relaxed),
+            operation,
+            timestamp: Local::now().format("%Y-%m-%dT%H:%M:%S").to_string(),
+        };
+        let mut operations = self.operations.lock().unwrap_or_else(|e| e.into_inner());
+        operations.push(logged);
+        let excess = operations.len().saturating_sub(MAX_OPERATIONS);
+        operations.drain(..excess);
+    }
+
+    /// Most recent first.
+    fn summaries(&self) -> Vec<OperationSummary> {
+        let operations = self.operations.lock().unwrap_or_else(|e| e.into_inner());
+        operations.iter().rev().map(LoggedOperation::summary).collect()
+    }
+
+    /// Reverse the most recent undoable operation and drop it from the log.
+    /// It stays logged if reversing fails.
+    fn undo_last(&self) -> Result<OperationSummary, AppError> {
+        // Held across the reversal so two undos can't pick the same operation
+        let mut operations = self.operations.lock().unwrap_or_else(|e| e.into_inner());
+        let index = operations
+            .iter()
+            .rposition(|logged| logged.operation.is_undoable())
+            .ok_or_else(|| AppError::Other("Nothing to undo".into()))?;
+        operations[index].operation.reverse()?;
+        let undone = operations.remove(index);
+        log::info!("Undid {:?} operation {}", undone.operation.kind(), undone.id);
+        Ok(undone.summary())
+    }
+}
+
+/// Log `operation` in the app's undo log.
+pub(crate) fn record(app: &AppHandle, operation: Operation) {
+    app.state::<UndoLog>().push(operation);
+}
+
+/// The last 50 file operations, most recent first, including ones that
+/// can't be undone.
+#[tauri::command]
+pub fn get_undo_stack(undo: State<'_, UndoLog>) -> Result<Vec<OperationSummary>, AppError> {
+    Ok(undo.summaries())
+}
+
+/// Reverse the most recent copy, move, rename, create or batch of those,
+/// skipping over deletes and trash operations. A copy that has been
+/// changed since is not deleted. Returns the operation that was undone.
+#[tauri::command]
+pub fn undo_last_operation(undo: State<'_, UndoLog>) -> Result<OperationSummary, AppError> {
+    undo.undo_last()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_undo_reverses_most_recent_undoable_operation() {
+        let dir = tempdir().unwrap();
+        let original = dir.path().join("a.txt");
+        let renamed = dir.path().join("b.txt");
+        let moved = dir.path().join("sub").join("b.txt");
+        let copy = dir.path().join("b - Copy.txt");
+        fs::create_dir(dir.path().join("sub")).unwrap();
+        fs::write(&renamed, "data").unwrap();
+
+        let undo = UndoLog::default();
+        undo.push(Operation::Rename { source: original.clone(), target: renamed.clone() });
+        fs::copy(&renamed, &copy).unwrap();
+        undo.push(Operation::copy(renamed.clone(), copy.clone()));
+        fs::rename(&renamed, &moved).unwrap();
+        undo.push(Operation::Move { source: renamed.clone(), target: moved.clone() });
+        undo.push(Operation::Delete { paths: vec![dir.path().join("gone")] });
+
+        let stack = undo.summaries();
+        assert_eq!(stack.len(), 4);
+        assert_eq!(stack[0].kind, OperationKind::Delete);
+        assert!(!stack[0].is_undoable);
+
+        assert_eq!(undo.undo_last().unwrap().kind, OperationKind::Move);
+        assert!(renamed.exists() && !moved.exists());
+        assert_eq!(undo.undo_last().unwrap().kind, OperationKind::Copy);
+        assert!(!copy.exists());
+        assert_eq!(undo.undo_last().unwrap().kind, OperationKind::Rename);
+        assert_eq!(fs::read_to_string(&original).unwrap(), "data");
+
+        assert!(matches!(undo.undo_last(), Err(AppError::Other(_))));
+        assert_eq!(undo.summaries().len(), 1, "the delete stays in the history");
+    }
+
+    #[test]
+    fn test_undo_create_and_failed_undo_stays_logged() {
+        let dir = tempdir().unwrap();
+        let outer = dir.path().join("x");
+        let inner = outer.join("y");
+        fs::create_dir_all(&inner).unwrap();
+        fs::write(inner.join("added-later.txt"), "").unwrap();
+
+        let undo = UndoLog::default();
+        undo.push(Operation::Create { paths: vec![outer.clone(), inner.clone()] });
+        assert!(undo.undo_last().is_err(), "non-empty directories are not deleted");
+        assert!(inner.join("added-later.txt").exists());
+        assert_eq!(undo.summaries().len(), 1);
+
+        fs::remove_file(inner.join("added-later.txt")).unwrap();
+        undo.undo_last().unwrap();
+        assert!(!outer.exists());
+        assert!(undo.summaries().is_empty());
+    }
+
+    #[test]
+    fn test_undo_copy_refuses_changed_copy() {
+        let dir = tempdir().unwrap();
+        let source = dir.path().join("src");
+        let copy = dir.path().join("src - Copy");
+        fs::create_dir(&source).unwrap();
+        fs::create_dir(&copy).unwrap();
+        fs::write(copy.join("a.txt"), "a").unwrap();
+
+        let undo = UndoLog::default();
+        undo.push(Operation::copy(source.clone(), copy.clone()));
+        fs::write(copy.join("notes.txt"), "added after the copy").unwrap();
+        assert!(undo.undo_last().is_err());
+        assert!(copy.join("notes.txt").exists());
+
+        fs::remove_file(copy.join("notes.txt")).unwrap();
+        // Removing the file touched the directory's mtime
+        let undo = UndoLog::default();
+        undo.push(Operation::copy(source, copy.clone()));
+        undo.undo_last().unwrap();
+        assert!(!copy.exists());
+    }
+
+    #[test]
+    fn test_undo_batch_restores_moves_and_keeps_remainder_on_failure() {
+        let dir = tempdir().unwrap();
+        let source = dir.path().join("src");
+        let dest = dir.path().join("dest");
+        fs::create_dir_all(&dest).unwrap();
+        for name in ["a.txt", "b.txt"] {
+            fs::write(dest.join(name), name).unwrap();
+        }
+        let moves = ["a.txt", "b.txt"]
+            .iter()
+            .map(|name| Operation::Move { source: source.join("sub").join(name), target: dest.join(name) })
+            .collect();
+
+        let undo = UndoLog::default();
+        undo.push(Operation::Batch { kind: OperationKind::Move, destination: dest.clone(), operations: moves });
+        let stack = undo.summaries();
+        assert_eq!(stack[0].kind, OperationKind::Move);
+        assert_eq!(stack[0].paths.len(), 2);
+
+        // `a.txt` has been taken back at the source in the meantime
+        fs::create_dir_all(source.join("sub")).unwrap();
+        fs::write(source.join("sub").join("a.txt"), "new").unwrap();
+        assert!(matches!(undo.undo_last(), Err(AppError::AlreadyExists(_))));
+        assert_eq!(fs::read_to_string(source.join("sub").join("b.txt")).unwrap(), "b.txt");
+        assert_eq!(undo.summaries()[0].paths.len(), 1, "only the failed move is left");
+
+        fs::remove_file(source.join("sub").join("a.txt")).unwrap();
+        undo.undo_last().unwrap();
+        assert_eq!(fs::read_to_string(source.join("sub").join("a.txt")).unwrap(), "a.txt");
+        assert!(undo.summaries().is_empty());
+    }
+
+    #[test]
+    fn test_undo_log_is_capped() {
+        let undo = UndoLog::default();
+        for i in 0..MAX_OPERATIONS + 3 {
+            undo.push(Operation::Trash { paths: vec![PathBuf::from(format!("/t{}", i))] });
+        }
+        let stack = undo.summaries();
+        assert_eq!(stack.len(), MAX_OPERATIONS);
+        assert_eq!(stack[0].paths, [format!("/t{}", MAX_OPERATIONS + 2)]);
+        assert_eq!(serde_json::to_value(&stack[0]).unwrap()["kind"], "trash");
+    }
+}
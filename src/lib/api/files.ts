@@ -24,9 +24,20 @@ async function invoke<T>(cmd: string, args?: Record<string, unknown>): Promise<T
 /** Structured error from Tauri backend */
 export type AppErrorKind = "not_found" | "permission_denied" | "already_exists" | "invalid_path" | "io" | "other";
 
+/** Stable, language-independent error code */
+export type AppErrorCode =
+  | "NOT_FOUND"
+  | "PERMISSION_DENIED"
+  | "ALREADY_EXISTS"
+  | "INVALID_PATH"
+  | "IO"
+  | "OTHER";
+
 export interface AppError {
+  code: AppErrorCode;
   kind: AppErrorKind;
   message: string;
+  path: string | null;
 }
 
 /** Extract error message from Tauri command error (structured or string) */
@@ -127,21 +138,41 @@ export async function deleteEntry(path: string): Promise<ApiResult<void>> {
   }
 }
 
+/** Per-path outcome of a batch trash. */
+export interface TrashResult {
+  trashed: string[];
+  /** Paths that no longer existed */
+  notFound: string[];
+  /** [path, error message] */
+  failed: [string, string][];
+}
+
 /**
  * Move multiple files/directories to the system trash.
  *
+ * A batch where some paths fail still resolves `ok`; check `data.failed`
+ * (see `trashFailureMessage`).
+ *
  * @param paths - Array of full paths to delete
- * @returns Result indicating success or error message
+ * @returns Result with the per-path outcome or error message
  */
-export async function deleteMultipleEntries(paths: string[]): Promise<ApiResult<void>> {
+export async function deleteMultipleEntries(paths: string[]): Promise<ApiResult<TrashResult>> {
   try {
-    await invoke("move_multiple_to_trash", { paths });
-    return { ok: true, data: undefined };
+    const data = await invoke<TrashResult>("move_multiple_to_trash", { paths });
+    return { ok: true, data };
   } catch (err) {
     return { ok: false, error: extractError(err) };
   }
 }
 
+/** Error message for the paths a batch trash failed on, or null if none failed. */
+export function trashFailureMessage(result: TrashResult): string | null {
+  if (result.failed.length === 0) return null;
+  const details = result.failed.map(([path, error]) => `${path}: ${error}`).join("; ");
+  const count = result.failed.length;
+  return `Failed to move ${count} ${count === 1 ? "item" : "items"} to trash: ${details}`;
+}
+
 /** Permanently delete a file or directory (bypasses trash). */
 export async function deleteEntryPermanent(path: string): Promise<ApiResult<void>> {
   try {
@@ -770,20 +801,33 @@ export async function compressToZip(paths: string[]): Promise<ApiResult<string>>
   }
 }
 
+/** Summary of an archive extraction. */
+export interface ExtractResult {
+  /** Directory the archive was extracted into */
+  destination: string;
+  filesExtracted: number;
+  bytesExtracted: number;
+  /** Archive paths left alone because the file already existed */
+  skipped: string[];
+}
+
 /**
- * Extract a ZIP archive.
+ * Extract a ZIP or tar archive.
  *
  * @param archivePath - Path to the archive file
  * @param extractHere - If true, extract to archive's directory; if false, extract to new folder
- * @returns Result with extraction destination path or error
+ * @returns Result with the extraction summary or error
  */
 export async function extractArchive(
   archivePath: string,
   extractHere: boolean = false
-): Promise<ApiResult<string>> {
+): Promise<ApiResult<ExtractResult>> {
   try {
-    const destPath = await invoke<string>("extract_archive", { archivePath, extractHere });
-    return { ok: true, data: destPath };
+    const data = await invoke<ExtractResult>("extract_archive", {
+      archivePath,
+      extractHere,
+    });
+    return { ok: true, data };
   } catch (err) {
     return { ok: false, error: extractError(err) };
   }
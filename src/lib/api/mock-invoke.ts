@@ -337,15 +337,21 @@ const mockCommands: Record<string, CommandHandler> = {
 
   move_multiple_to_trash: (args) => {
     const paths = args.paths as string[];
+    const trashed: string[] = [];
+    const notFound: string[] = [];
     for (const path of paths) {
       const parentPath = path.substring(0, path.lastIndexOf("/"));
       const entries = mockFiles[parentPath] || [];
       const entryIndex = entries.findIndex((e) => e.path === path);
       if (entryIndex >= 0) {
         entries.splice(entryIndex, 1);
+        trashed.push(path);
+      } else {
+        notFound.push(path);
       }
       delete mockFiles[path];
     }
+    return { trashed, notFound, failed: [] };
   },
 
   restore_from_trash: () => {
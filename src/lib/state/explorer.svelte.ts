@@ -18,6 +18,7 @@ import {
   renameEntry as apiRenameEntry,
   deleteEntry,
   deleteMultipleEntries,
+  trashFailureMessage,
   deleteEntryPermanent,
   clipboardHasImage,
   clipboardPasteImage,
@@ -317,25 +318,45 @@ function createExplorerState() {
     }
   }
 
+  async function removeDeletedEntries(deletedPaths: Set<string>): Promise<void> {
+    coreState.entries = coreState.entries.filter((e) => !deletedPaths.has(e.path));
+    coreState.selectedPaths = new Set(
+      [...coreState.selectedPaths].filter((p) => !deletedPaths.has(p))
+    );
+    await navigateAwayIfNeeded(deletedPaths);
+  }
+
+  /**
+   * Move paths to trash. A batch can partly fail: `trashed` is what can be
+   * undone, `gone` what to drop from the view (including paths that no longer
+   * existed), and `error` describes the paths left in place.
+   */
+  async function trashPaths(
+    paths: string[]
+  ): Promise<{ trashed: string[]; gone: string[]; error: string | null }> {
+    if (paths.length === 1) {
+      const result = await deleteEntry(paths[0]);
+      return result.ok
+        ? { trashed: paths, gone: paths, error: null }
+        : { trashed: [], gone: [], error: result.error };
+    }
+    const result = await deleteMultipleEntries(paths);
+    if (!result.ok) return { trashed: [], gone: [], error: result.error };
+    const { trashed, notFound } = result.data;
+    return { trashed, gone: [...trashed, ...notFound], error: trashFailureMessage(result.data) };
+  }
+
   async function startDelete(entries: FileEntry | FileEntry[]) {
     const arr = Array.isArray(entries) ? entries : [entries];
     if (arr.length === 0) return;
 
     if (!settingsStore.confirmDelete) {
-      const paths = arr.map((e) => e.path);
-      const result = arr.length === 1
-        ? await deleteEntry(paths[0])
-        : await deleteMultipleEntries(paths);
-
-      if (result.ok) {
-        undoStore.push({ type: "delete", paths, parentDir: coreState.currentPath });
-        const deletedPaths = new Set(paths);
-        coreState.entries = coreState.entries.filter((e) => !deletedPaths.has(e.path));
-        coreState.selectedPaths = new Set(
-          [...coreState.selectedPaths].filter((p) => !deletedPaths.has(p))
-        );
-        await navigateAwayIfNeeded(deletedPaths);
+      const { trashed, gone, error } = await trashPaths(arr.map((e) => e.path));
+      if (trashed.length > 0) {
+        undoStore.push({ type: "delete", paths: trashed, parentDir: coreState.currentPath });
       }
+      if (gone.length > 0) await removeDeletedEntries(new Set(gone));
+      if (error) toastStore.error(error);
       return;
     }
 
@@ -414,7 +435,6 @@ function createExplorerState() {
     const isPermanent = dialogStore.isPermanentDelete;
 
     const paths = entries.map((e) => e.path);
-    let result: { ok: boolean; error?: string };
 
     if (isPermanent) {
       // Permanent delete: delete each entry one by one (no batch command)
@@ -423,28 +443,20 @@ function createExplorerState() {
         const r = await deleteEntryPermanent(path);
         if (!r.ok) errors.push(r.error);
       }
-      result = errors.length > 0 ? { ok: false, error: errors.join("; ") } : { ok: true };
-    } else {
-      result = entries.length === 1
-        ? await deleteEntry(paths[0])
-        : await deleteMultipleEntries(paths);
-    }
-
-    if (result.ok) {
-      // Only push to undo for trash operations (permanent deletes can't be undone)
-      if (!isPermanent) {
-        undoStore.push({ type: "delete", paths, parentDir: coreState.currentPath });
-      }
-      const deletedPaths = new Set(paths);
-      coreState.entries = coreState.entries.filter((e) => !deletedPaths.has(e.path));
-      coreState.selectedPaths = new Set(
-        [...coreState.selectedPaths].filter((p) => !deletedPaths.has(p))
-      );
+      if (errors.length > 0) return errors.join("; ");
       dialogStore.cancelDelete();
-      await navigateAwayIfNeeded(deletedPaths);
+      await removeDeletedEntries(new Set(paths));
       return null;
     }
-    return result.error;
+
+    // Keep the dialog open with the error if any path was left in place
+    const { trashed, gone, error } = await trashPaths(paths);
+    if (trashed.length > 0) {
+      undoStore.push({ type: "delete", paths: trashed, parentDir: coreState.currentPath });
+    }
+    if (!error) dialogStore.cancelDelete();
+    if (gone.length > 0) await removeDeletedEntries(new Set(gone));
+    return error;
   }
 
   // ===================
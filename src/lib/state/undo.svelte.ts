@@ -7,7 +7,13 @@
  * The stack is global to provide a unified undo experience.
  */
 
-import { renameEntry, moveEntry, restoreFromTrash, deleteMultipleEntries } from "$lib/api/files";
+import {
+  renameEntry,
+  moveEntry,
+  restoreFromTrash,
+  deleteMultipleEntries,
+  trashFailureMessage,
+} from "$lib/api/files";
 import type { UndoAction } from "./types";
 
 function createUndoStore() {
@@ -113,7 +119,10 @@ async function executeRedo(
     }
     case "delete": {
       // Undo restored from trash, so redo re-deletes
-      return deleteMultipleEntries(action.paths);
+      const result = await deleteMultipleEntries(action.paths);
+      if (!result.ok) return result;
+      const error = trashFailureMessage(result.data);
+      return error ? { ok: false, error } : { ok: true };
     }
     default: {
       const _exhaustive: never = action;